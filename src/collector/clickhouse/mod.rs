@@ -0,0 +1,140 @@
+//! ClickHouse collector, over the HTTP interface.
+//!
+//! Collects from every non-system database on the server:
+//! - Query statistics from `system.query_log`, grouped by
+//!   `normalized_query_hash`
+//! - Rows, sizes, part counts and merge pressure per table from
+//!   `system.parts`
+//! - Replication state and lag from `system.replicas`
+//! - Session settings, MergeTree defaults and server settings
+//! - Schema metadata (engines, sorting, primary and partition keys, data
+//!   skipping indexes) from `system.tables` and `system.columns`
+//!
+//! ClickHouse Cloud, Altinity.Cloud, Aiven and self-managed servers are told
+//! apart in [`providers`]. The user needs `SELECT` on the `system` tables
+//! above; `readonly = 1` is enough. On a cluster, the collector sees the
+//! server it connects to; point one agent at each replica to cover them all.
+
+mod client;
+pub mod providers;
+mod queries;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{ClickHouseMetadata, DatabaseInfo, Payload};
+use async_trait::async_trait;
+use client::{text, ClickHouseClient};
+use tracing::{info, warn};
+
+/// ClickHouse metrics collector
+pub struct ClickHouseCollector {
+    client: ClickHouseClient,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+    host_name: Option<String>,
+}
+
+impl ClickHouseCollector {
+    /// Check the connection, read the server version and detect the
+    /// provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to ClickHouse");
+
+        let client = ClickHouseClient::from_url(database_url)?;
+        let row = client
+            .query(queries::SERVER_INFO)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CollectorError::QueryError("No server info returned".into()))?;
+        let version = text(&row, "version");
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            database = text(&row, "database_name").as_deref().unwrap_or("unknown"),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+            host_name: text(&row, "host_name"),
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for ClickHouseCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let client = &self.client;
+        let query_stats = stats::query_stats(client).await?;
+        let (settings, setting_details) = stats::settings(client).await?;
+        let parts = stats::parts(client, stats::parts_to_throw_insert(&setting_details)).await?;
+        let schema = schema::collect(client).await?;
+        // Optional like the other engine-specific sections
+        let replicas = stats::replicas(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect replication state");
+            Vec::new()
+        });
+
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Clickhouse.to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                client.host(),
+                self.host_name.as_deref(),
+            ),
+        };
+
+        info!(
+            tables = schema.tables.len(),
+            views = schema.views.len(),
+            queries = query_stats.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_query_stats(query_stats)
+            .with_table_stats(stats::table_stats(&parts))
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema)
+            .with_clickhouse(ClickHouseMetadata { parts, replicas }))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        self.client
+            .query("SELECT 1")
+            .await
+            .map(|_| ())
+            .map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Clickhouse
+    }
+}
@@ -0,0 +1,93 @@
+//! ClickHouse provider detection.
+//!
+//! Returns one of:
+//! - `"clickhouse_cloud"` — ClickHouse Cloud
+//! - `"altinity"` — Altinity.Cloud
+//! - `"aiven"` — Aiven for ClickHouse
+//! - `"generic"` — self-managed
+//!
+//! The managed services don't identify themselves through the server, so
+//! detection goes by hostname.
+
+use std::collections::HashMap;
+
+pub const PROVIDER_CLICKHOUSE_CLOUD: &str = "clickhouse_cloud";
+pub const PROVIDER_ALTINITY: &str = "altinity";
+pub const PROVIDER_AIVEN: &str = "aiven";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// Detect the provider from the server hostname.
+pub fn detect_provider(host: &str) -> &'static str {
+    let lower = host.to_lowercase();
+    if lower.ends_with(".clickhouse.cloud") {
+        PROVIDER_CLICKHOUSE_CLOUD
+    } else if lower.ends_with(".altinity.cloud") {
+        PROVIDER_ALTINITY
+    } else if lower.ends_with(".aivencloud.com") {
+        PROVIDER_AIVEN
+    } else {
+        PROVIDER_GENERIC
+    }
+}
+
+/// Provider metadata derivable from the hostname.
+pub fn provider_metadata(
+    provider: &str,
+    host: &str,
+    host_name: Option<&str>,
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    match provider {
+        PROVIDER_CLICKHOUSE_CLOUD => {
+            // `abc123xyz.eu-west-1.aws.clickhouse.cloud`
+            let parts: Vec<&str> = host.split('.').collect();
+            if parts.len() == 5 {
+                out.insert("service_id".to_string(), parts[0].to_string());
+                out.insert("region".to_string(), parts[1].to_string());
+                out.insert("cloud".to_string(), parts[2].to_string());
+            }
+        }
+        PROVIDER_ALTINITY | PROVIDER_AIVEN => {
+            if let Some(service) = host.split('.').next().filter(|s| !s.is_empty()) {
+                out.insert("service".to_string(), service.to_string());
+            }
+        }
+        _ => {
+            if let Some(host_name) = host_name {
+                out.insert("host_name".to_string(), host_name.to_string());
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_managed_services() {
+        assert_eq!(
+            detect_provider("abc123.eu-west-1.aws.clickhouse.cloud"),
+            PROVIDER_CLICKHOUSE_CLOUD
+        );
+        assert_eq!(
+            detect_provider("analytics.acme.altinity.cloud"),
+            PROVIDER_ALTINITY
+        );
+        assert_eq!(detect_provider("ch-acme.a.aivencloud.com"), PROVIDER_AIVEN);
+        assert_eq!(detect_provider("clickhouse.internal"), PROVIDER_GENERIC);
+    }
+
+    #[test]
+    fn cloud_metadata_extracts_service_and_region() {
+        let meta = provider_metadata(
+            PROVIDER_CLICKHOUSE_CLOUD,
+            "abc123.eu-west-1.aws.clickhouse.cloud",
+            None,
+        );
+        assert_eq!(meta.get("service_id").unwrap(), "abc123");
+        assert_eq!(meta.get("region").unwrap(), "eu-west-1");
+        assert_eq!(meta.get("cloud").unwrap(), "aws");
+    }
+}
@@ -0,0 +1,157 @@
+//! ClickHouse system table queries.
+//!
+//! All queries leave out the system databases (`system`,
+//! `information_schema`, `INFORMATION_SCHEMA`). Columns used here exist in
+//! ClickHouse 22.3 and later.
+
+/// Server version and the database the connection defaults to
+pub const SERVER_INFO: &str = r#"
+SELECT
+    version() AS version,
+    currentDatabase() AS database_name,
+    hostName() AS host_name
+"#;
+
+/// Finished queries of the last 24 hours grouped by normalized query hash,
+/// top 100 by total duration. The agent's own queries carry its user agent
+/// and are left out.
+pub const QUERY_LOG_STATS: &str = r#"
+SELECT
+    toString(normalized_query_hash) AS query_hash,
+    normalizeQuery(any(query)) AS query_text,
+    any(current_database) AS database_name,
+    count() AS calls,
+    sum(query_duration_ms) AS total_time_ms,
+    sum(result_rows) AS result_rows,
+    sum(read_rows) AS read_rows,
+    sum(read_bytes) AS read_bytes
+FROM system.query_log
+WHERE type = 'QueryFinish'
+  AND is_initial_query
+  AND event_date >= yesterday()
+  AND event_time >= now() - INTERVAL 1 DAY
+  AND http_user_agent NOT LIKE 'datapace-agent/%'
+GROUP BY normalized_query_hash
+ORDER BY total_time_ms DESC
+LIMIT 100
+"#;
+
+/// Active parts per table, with the part count of the busiest partition
+pub const PARTS: &str = r#"
+SELECT
+    database,
+    table,
+    sum(parts) AS active_parts,
+    count() AS partitions,
+    max(parts) AS max_parts_in_partition,
+    sum(rows) AS rows,
+    sum(bytes_on_disk) AS bytes_on_disk,
+    sum(data_compressed_bytes) AS data_compressed_bytes,
+    sum(data_uncompressed_bytes) AS data_uncompressed_bytes
+FROM (
+    SELECT
+        database,
+        table,
+        partition_id,
+        count() AS parts,
+        sum(rows) AS rows,
+        sum(bytes_on_disk) AS bytes_on_disk,
+        sum(data_compressed_bytes) AS data_compressed_bytes,
+        sum(data_uncompressed_bytes) AS data_uncompressed_bytes
+    FROM system.parts
+    WHERE active
+      AND database NOT IN ('system', 'information_schema', 'INFORMATION_SCHEMA')
+    GROUP BY database, table, partition_id
+)
+GROUP BY database, table
+ORDER BY database, table
+"#;
+
+/// Merges running right now, per table
+pub const MERGES: &str = r#"
+SELECT database, table, count() AS merges
+FROM system.merges
+GROUP BY database, table
+"#;
+
+/// Replication state of replicated tables
+pub const REPLICAS: &str = r#"
+SELECT
+    database,
+    table,
+    is_leader,
+    is_readonly,
+    absolute_delay,
+    toInt64(log_max_index) - toInt64(log_pointer) AS log_lag,
+    queue_size,
+    inserts_in_queue,
+    merges_in_queue,
+    total_replicas,
+    active_replicas
+FROM system.replicas
+ORDER BY database, table
+"#;
+
+/// Session settings of the agent's user
+pub const SETTINGS: &str = r#"
+SELECT name, value, changed, type
+FROM system.settings
+ORDER BY name
+"#;
+
+/// Server-wide MergeTree defaults, including the part count thresholds
+pub const MERGE_TREE_SETTINGS: &str = r#"
+SELECT name, value, changed, type
+FROM system.merge_tree_settings
+ORDER BY name
+"#;
+
+/// Server configuration (ClickHouse 23.4 and later)
+pub const SERVER_SETTINGS: &str = r#"
+SELECT name, value, changed, type
+FROM system.server_settings
+ORDER BY name
+"#;
+
+/// Tables and views with their engine and keys
+pub const TABLES: &str = r#"
+SELECT
+    database,
+    name,
+    engine,
+    sorting_key,
+    primary_key,
+    partition_key,
+    total_rows,
+    total_bytes,
+    as_select,
+    comment
+FROM system.tables
+WHERE database NOT IN ('system', 'information_schema', 'INFORMATION_SCHEMA')
+  AND NOT is_temporary
+ORDER BY database, name
+"#;
+
+/// Columns in table order
+pub const COLUMNS: &str = r#"
+SELECT
+    database,
+    table,
+    name,
+    type,
+    position,
+    default_kind,
+    default_expression,
+    comment
+FROM system.columns
+WHERE database NOT IN ('system', 'information_schema', 'INFORMATION_SCHEMA')
+ORDER BY database, table, position
+"#;
+
+/// Data skipping indexes
+pub const DATA_SKIPPING_INDICES: &str = r#"
+SELECT database, table, name, type, expr
+FROM system.data_skipping_indices
+WHERE database NOT IN ('system', 'information_schema', 'INFORMATION_SCHEMA')
+ORDER BY database, table, name
+"#;
@@ -0,0 +1,207 @@
+//! ClickHouse schema metadata from `system.tables`, `system.columns` and
+//! `system.data_skipping_indices`.
+//!
+//! ClickHouse databases map to schemas. Tables carry their engine, sorting
+//! key (as `sort_key`) and partition key expression; the primary key, where
+//! set, and data skipping indexes become indexes. `View` and
+//! `MaterializedView` engines are reported as views.
+
+use super::client::{int, text, ClickHouseClient, Row};
+use super::queries;
+use crate::collector::CollectorError;
+use crate::payload::{ColumnMetadata, IndexMetadata, SchemaMetadata, TableMetadata, ViewMetadata};
+use std::collections::HashMap;
+use tracing::debug;
+
+type TableKey = (String, String);
+
+pub(super) async fn collect(client: &ClickHouseClient) -> Result<SchemaMetadata, CollectorError> {
+    debug!("Collecting ClickHouse schema metadata");
+
+    let table_rows = client.query(queries::TABLES).await?;
+    let column_rows = client.query(queries::COLUMNS).await?;
+    let index_rows = client.query(queries::DATA_SKIPPING_INDICES).await?;
+
+    let mut columns: HashMap<TableKey, Vec<ColumnMetadata>> = HashMap::new();
+    for row in &column_rows {
+        let key = (
+            text(row, "database").unwrap_or_default(),
+            text(row, "table").unwrap_or_default(),
+        );
+        columns.entry(key).or_default().push(column(row));
+    }
+
+    let mut schema = SchemaMetadata::default();
+    for row in &table_rows {
+        let database = text(row, "database").unwrap_or_default();
+        let name = text(row, "name").unwrap_or_default();
+        let engine = text(row, "engine").unwrap_or_default();
+        let table_columns = columns
+            .remove(&(database.clone(), name.clone()))
+            .unwrap_or_default();
+
+        if matches!(engine.as_str(), "View" | "MaterializedView") {
+            schema.views.push(ViewMetadata {
+                schema: database,
+                name,
+                materialized: engine == "MaterializedView",
+                definition: text(row, "as_select").filter(|s| !s.is_empty()),
+                columns: table_columns,
+                ..Default::default()
+            });
+            continue;
+        }
+
+        let primary_key = key_columns(&text(row, "primary_key").unwrap_or_default());
+        if !primary_key.is_empty() {
+            schema.indexes.push(IndexMetadata {
+                schema: database.clone(),
+                table: name.clone(),
+                name: "primary_key".to_string(),
+                columns: primary_key,
+                // The sparse primary index doesn't enforce uniqueness
+                is_primary: true,
+                ..Default::default()
+            });
+        }
+
+        schema.tables.push(TableMetadata {
+            columns: table_columns,
+            row_count_estimate: int(row, "total_rows"),
+            size_bytes: int(row, "total_bytes"),
+            partition_key: text(row, "partition_key").filter(|k| !k.is_empty()),
+            sort_key: key_columns(&text(row, "sorting_key").unwrap_or_default()),
+            engine: Some(engine),
+            schema: database,
+            name,
+            ..Default::default()
+        });
+    }
+
+    schema
+        .indexes
+        .extend(index_rows.iter().map(|row| IndexMetadata {
+            schema: text(row, "database").unwrap_or_default(),
+            table: text(row, "table").unwrap_or_default(),
+            name: text(row, "name").unwrap_or_default(),
+            columns: key_columns(&text(row, "expr").unwrap_or_default()),
+            index_type: text(row, "type"),
+            ..Default::default()
+        }));
+
+    Ok(schema)
+}
+
+fn column(row: &Row) -> ColumnMetadata {
+    let data_type = text(row, "type").unwrap_or_default();
+    let default_kind = text(row, "default_kind").unwrap_or_default();
+    let expression = text(row, "default_expression").filter(|e| !e.is_empty());
+
+    ColumnMetadata {
+        name: text(row, "name").unwrap_or_default(),
+        nullable: is_nullable(&data_type),
+        // `MATERIALIZED` and `ALIAS` columns are computed from others
+        default: matches!(default_kind.as_str(), "DEFAULT" | "EPHEMERAL")
+            .then(|| expression.clone())
+            .flatten(),
+        generation_expression: matches!(default_kind.as_str(), "MATERIALIZED" | "ALIAS")
+            .then_some(expression)
+            .flatten(),
+        position: int(row, "position").unwrap_or_default() as i32,
+        comment: text(row, "comment").filter(|c| !c.is_empty()),
+        native_type: Some(data_type.clone()),
+        data_type,
+        ..Default::default()
+    }
+}
+
+/// `Nullable(T)`, also inside `LowCardinality(Nullable(T))`
+fn is_nullable(data_type: &str) -> bool {
+    data_type.starts_with("Nullable(") || data_type.starts_with("LowCardinality(Nullable(")
+}
+
+/// Split a key expression such as `tenant_id, toStartOfHour(ts)` into its
+/// top-level elements. A tuple key `(a, b)` is unwrapped first.
+fn key_columns(expression: &str) -> Vec<String> {
+    let expression = unwrap_tuple(expression.trim());
+
+    let mut out = Vec::new();
+    let mut depth = 0usize;
+    let mut quoted = None;
+    let mut start = 0;
+    for (i, c) in expression.char_indices() {
+        match (quoted, c) {
+            (Some(q), c) if c == q => quoted = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quoted = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                out.push(expression[start..i].trim().to_string());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    out.push(expression[start..].trim().to_string());
+    out.retain(|c| !c.is_empty());
+    out
+}
+
+/// The elements of `tuple(...)` or `(...)`, or the expression itself when it
+/// isn't a single tuple
+fn unwrap_tuple(expression: &str) -> &str {
+    let Some(inner) = expression
+        .strip_prefix("tuple(")
+        .or_else(|| expression.strip_prefix('('))
+    else {
+        return expression;
+    };
+    // The opening parenthesis must close at the very end, unlike `(a) + (b)`
+    let mut depth = 1usize;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return if i == inner.len() - 1 {
+                        &inner[..i]
+                    } else {
+                        expression
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+    expression
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_columns() {
+        assert_eq!(
+            key_columns("tenant_id, toStartOfHour(ts), cityHash64(user_id, 'a,b')"),
+            vec![
+                "tenant_id",
+                "toStartOfHour(ts)",
+                "cityHash64(user_id, 'a,b')"
+            ]
+        );
+        assert_eq!(key_columns("(a, b)"), vec!["a", "b"]);
+        assert_eq!(key_columns("tuple()"), Vec::<String>::new());
+        assert_eq!(key_columns("(a) + (b)"), vec!["(a) + (b)"]);
+        assert!(key_columns("").is_empty());
+    }
+
+    #[test]
+    fn test_is_nullable() {
+        assert!(is_nullable("Nullable(String)"));
+        assert!(is_nullable("LowCardinality(Nullable(String))"));
+        assert!(!is_nullable("Array(Nullable(String))"));
+    }
+}
@@ -0,0 +1,298 @@
+//! ClickHouse statistics and configuration.
+//!
+//! - query statistics from `system.query_log`, grouped by normalized query
+//!   hash over the last 24 hours
+//! - per-table rows, sizes and part counts from `system.parts`, with running
+//!   merges from `system.merges`
+//! - replication state from `system.replicas`
+//! - session settings, MergeTree defaults and (on 23.4 and later) server
+//!   settings
+//!
+//! `system.query_log` only exists once the server has flushed its first
+//! entries, and not at all with `log_queries = 0`; query statistics are then
+//! empty.
+
+use super::client::{flag, float, int, text, ClickHouseClient, Row};
+use super::queries;
+use crate::collector::CollectorError;
+use crate::payload::{QueryStats, ReplicaStatus, Setting, TableParts, TableStats};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Default of `parts_to_throw_insert` in current releases, used when
+/// `system.merge_tree_settings` doesn't say
+const DEFAULT_PARTS_TO_THROW_INSERT: i64 = 3000;
+
+/// Top queries by total duration.
+pub(super) async fn query_stats(
+    client: &ClickHouseClient,
+) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics from system.query_log");
+
+    let rows = match client.query(queries::QUERY_LOG_STATS).await {
+        Ok(rows) => rows,
+        Err(CollectorError::QueryError(e)) => {
+            debug!(error = %e, "system.query_log not available");
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e),
+    };
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let calls = int(row, "calls");
+            let total_time_ms = float(row, "total_time_ms");
+            QueryStats {
+                query_hash: text(row, "query_hash"),
+                query: text(row, "query_text"),
+                mean_time_ms: match (total_time_ms, calls) {
+                    (Some(total), Some(calls)) if calls > 0 => Some(total / calls as f64),
+                    _ => None,
+                },
+                calls,
+                total_time_ms,
+                rows: int(row, "result_rows"),
+                read_rows: int(row, "read_rows"),
+                read_bytes: int(row, "read_bytes"),
+                database: text(row, "database_name"),
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+/// Active parts per MergeTree table. `parts_to_throw_insert` scales the
+/// merge pressure.
+pub(super) async fn parts(
+    client: &ClickHouseClient,
+    parts_to_throw_insert: Option<i64>,
+) -> Result<Vec<TableParts>, CollectorError> {
+    debug!("Collecting part statistics");
+
+    let merges: HashMap<(String, String), i64> = client
+        .query(queries::MERGES)
+        .await?
+        .iter()
+        .filter_map(|row| {
+            Some((
+                (text(row, "database")?, text(row, "table")?),
+                int(row, "merges").unwrap_or(0),
+            ))
+        })
+        .collect();
+    let limit = parts_to_throw_insert
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_PARTS_TO_THROW_INSERT);
+
+    Ok(client
+        .query(queries::PARTS)
+        .await?
+        .iter()
+        .filter_map(|row| {
+            let database = text(row, "database")?;
+            let table = text(row, "table")?;
+            let max_parts_in_partition = int(row, "max_parts_in_partition").unwrap_or(0);
+            Some(TableParts {
+                merges_in_progress: merges
+                    .get(&(database.clone(), table.clone()))
+                    .copied()
+                    .unwrap_or(0),
+                merge_pressure: Some(max_parts_in_partition as f64 / limit as f64),
+                active_parts: int(row, "active_parts").unwrap_or(0),
+                partitions: int(row, "partitions").unwrap_or(0),
+                max_parts_in_partition,
+                rows: int(row, "rows").unwrap_or(0),
+                bytes_on_disk: int(row, "bytes_on_disk").unwrap_or(0),
+                data_compressed_bytes: int(row, "data_compressed_bytes").unwrap_or(0),
+                data_uncompressed_bytes: int(row, "data_uncompressed_bytes").unwrap_or(0),
+                database,
+                table,
+            })
+        })
+        .collect())
+}
+
+/// Table statistics derived from the part counts. ClickHouse keeps no
+/// access counters per table, so only the row count is set.
+pub(super) fn table_stats(parts: &[TableParts]) -> Vec<TableStats> {
+    parts
+        .iter()
+        .map(|p| TableStats {
+            schema: p.database.clone(),
+            table: p.table.clone(),
+            n_live_tup: Some(p.rows),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Replication state of the replicated tables on this server.
+pub(super) async fn replicas(
+    client: &ClickHouseClient,
+) -> Result<Vec<ReplicaStatus>, CollectorError> {
+    debug!("Collecting replication state");
+
+    Ok(client
+        .query(queries::REPLICAS)
+        .await?
+        .iter()
+        .filter_map(|row| {
+            Some(ReplicaStatus {
+                database: text(row, "database")?,
+                table: text(row, "table")?,
+                is_leader: flag(row, "is_leader"),
+                is_readonly: flag(row, "is_readonly"),
+                absolute_delay_s: int(row, "absolute_delay"),
+                log_lag: int(row, "log_lag").map(|lag| lag.max(0)),
+                queue_size: int(row, "queue_size"),
+                inserts_in_queue: int(row, "inserts_in_queue"),
+                merges_in_queue: int(row, "merges_in_queue"),
+                total_replicas: int(row, "total_replicas"),
+                active_replicas: int(row, "active_replicas"),
+            })
+        })
+        .collect())
+}
+
+/// Settings as a flat map and as structured [`Setting`]s.
+///
+/// Session settings come first, then MergeTree defaults (category
+/// `merge_tree`) and server settings (category `server`). The latter two are
+/// prefixed with their category in the flat map, as their names can clash
+/// with session settings.
+pub(super) async fn settings(
+    client: &ClickHouseClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting ClickHouse settings");
+
+    let mut details: Vec<Setting> = client
+        .query(queries::SETTINGS)
+        .await?
+        .iter()
+        .filter_map(|row| setting(row, None))
+        .collect();
+    details.extend(
+        client
+            .query(queries::MERGE_TREE_SETTINGS)
+            .await?
+            .iter()
+            .filter_map(|row| setting(row, Some("merge_tree"))),
+    );
+    match client.query(queries::SERVER_SETTINGS).await {
+        Ok(rows) => details.extend(rows.iter().filter_map(|row| setting(row, Some("server")))),
+        Err(e) => debug!(error = %e, "system.server_settings not available"),
+    }
+
+    let flat = details
+        .iter()
+        .map(|s| {
+            let name = match s.category.as_deref() {
+                Some(category) => format!("{category}.{}", s.name),
+                _ => s.name.clone(),
+            };
+            (name, s.value.clone())
+        })
+        .collect();
+    Ok((flat, details))
+}
+
+/// `parts_to_throw_insert` among the settings collected by [`settings`]
+pub(super) fn parts_to_throw_insert(details: &[Setting]) -> Option<i64> {
+    details
+        .iter()
+        .find(|s| s.category.as_deref() == Some("merge_tree") && s.name == "parts_to_throw_insert")
+        .and_then(|s| s.value.parse().ok())
+}
+
+fn setting(row: &Row, category: Option<&str>) -> Option<Setting> {
+    let name = text(row, "name")?;
+    let value = text(row, "value").unwrap_or_default();
+    let value_type = text(row, "type");
+    let (unit, value_bytes, value_ms) = normalize(&name, value_type.as_deref(), &value);
+
+    Some(Setting {
+        unit: unit.map(str::to_string),
+        value_bytes,
+        value_ms,
+        category: category.map(str::to_string),
+        value_type,
+        is_default: !flag(row, "changed"),
+        name,
+        value,
+        ..Default::default()
+    })
+}
+
+/// Unit and normalized value of a setting. Durations are typed (`Seconds`,
+/// `Milliseconds`); sizes are plain integers with `bytes` in the name, and
+/// the `max_memory_usage*` limits.
+fn normalize(
+    name: &str,
+    value_type: Option<&str>,
+    value: &str,
+) -> (Option<&'static str>, Option<i64>, Option<f64>) {
+    match value_type {
+        Some("Seconds") => (
+            Some("s"),
+            None,
+            value.parse::<f64>().ok().map(|s| s * 1_000.0),
+        ),
+        Some("Milliseconds") => (Some("ms"), None, value.parse::<f64>().ok()),
+        _ if name.split('_').any(|word| word == "bytes") || name.contains("memory_usage") => {
+            match value.parse::<i64>() {
+                Ok(n) => (Some("B"), Some(n), None),
+                Err(_) => (None, None, None),
+            }
+        }
+        _ => (None, None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("max_execution_time", Some("Seconds"), "30"),
+            (Some("s"), None, Some(30_000.0))
+        );
+        assert_eq!(
+            normalize(
+                "max_bytes_before_external_sort",
+                Some("UInt64"),
+                "10737418240"
+            ),
+            (Some("B"), Some(10_737_418_240), None)
+        );
+        assert_eq!(
+            normalize("max_memory_usage", Some("UInt64"), "0"),
+            (Some("B"), Some(0), None)
+        );
+        assert_eq!(
+            normalize("max_threads", Some("MaxThreads"), "'auto(8)'"),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn test_parts_to_throw_insert() {
+        let details = vec![
+            Setting {
+                name: "parts_to_throw_insert".to_string(),
+                value: "300".to_string(),
+                category: Some("merge_tree".to_string()),
+                ..Default::default()
+            },
+            Setting {
+                name: "parts_to_throw_insert".to_string(),
+                value: "0".to_string(),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(parts_to_throw_insert(&details), Some(300));
+        assert_eq!(parts_to_throw_insert(&details[1..]), None);
+    }
+}
@@ -0,0 +1,248 @@
+//! Couchbase collector, over the cluster manager and query service REST
+//! APIs.
+//!
+//! Collects:
+//! - Buckets with their memory quota, usage, disk usage and active
+//!   resident ratio
+//! - Collections as tables (bucket as `database`, scope as `schema`), with
+//!   item counts, memory and data size, and fields inferred from a sample
+//!   of documents as for MongoDB
+//! - GSI index definitions, state and last scan time (`system:indexes`)
+//! - Slow statement shapes (`system:completed_requests`)
+//! - Service memory quotas, query and index service settings
+//!
+//! The user needs the `ro_admin` role for the cluster manager, plus
+//! `query_system_catalog` for the indexes and completed requests and
+//! `query_select` on the buckets for the document samples. Anything read
+//! over N1QL is skipped when no node runs the query service.
+
+mod client;
+pub mod providers;
+mod queries;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{
+    CouchbaseBucket, CouchbaseMetadata, DatabaseInfo, Payload, SchemaMetadata, TableMetadata,
+    TableStats,
+};
+use async_trait::async_trait;
+use client::CouchbaseClient;
+use tracing::{info, warn};
+
+/// Couchbase metrics collector
+pub struct CouchbaseCollector {
+    client: CouchbaseClient,
+    database_url: String,
+    detected_provider: String,
+}
+
+impl CouchbaseCollector {
+    /// Read the cluster version, find a query service and detect the
+    /// provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to Couchbase");
+
+        let client = CouchbaseClient::connect(database_url).await?;
+        info!(
+            version = client.version().unwrap_or("unknown"),
+            edition = client.edition().unwrap_or("unknown"),
+            query_service = client.has_query_service(),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            database_url: database_url.to_string(),
+            detected_provider,
+        })
+    }
+
+    /// Collections of one bucket as tables, with their statistics; fills
+    /// in the bucket's resident ratio
+    async fn collect_bucket(
+        &self,
+        bucket: &mut CouchbaseBucket,
+    ) -> (Vec<TableMetadata>, Vec<TableStats>) {
+        let client = &self.client;
+        let mut tables = Vec::new();
+        let mut table_stats = Vec::new();
+
+        // Memcached buckets hold no documents the agent can read
+        if bucket.bucket_type.as_deref() == Some("memcached") {
+            return (tables, table_stats);
+        }
+        let name = bucket.name.clone();
+        bucket.resident_ratio = stats::resident_ratio(client, &name)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(bucket = %name, error = %e, "Failed to collect resident ratio");
+                None
+            });
+        let collections = schema::collections(client, &name)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(bucket = %name, error = %e, "Failed to list collections");
+                Vec::new()
+            });
+        let collection_stats = stats::collection_stats(client, &name)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(bucket = %name, error = %e, "Failed to collect collection statistics");
+                Default::default()
+            });
+
+        for collection in &collections {
+            let mut counts = collection_stats
+                .get(&(collection.scope.clone(), collection.name.clone()))
+                .copied()
+                .unwrap_or_default();
+            // Before 7.0 the default collection is the whole bucket
+            if collection_stats.is_empty() && collections.len() == 1 {
+                counts.items = bucket.item_count;
+                counts.mem_used_bytes = bucket.mem_used_bytes;
+                counts.data_size_bytes = bucket.data_used_bytes;
+            }
+
+            let (columns, sampled) = if client.has_query_service() {
+                match schema::sample(client, &name, collection, counts.items).await {
+                    Ok(walker) => {
+                        let sampled = walker.docs_sampled();
+                        (walker.into_columns(), Some(sampled))
+                    }
+                    Err(e) => {
+                        warn!(
+                            bucket = %name,
+                            scope = %collection.scope,
+                            collection = %collection.name,
+                            error = %e,
+                            "Failed to sample documents"
+                        );
+                        (Vec::new(), None)
+                    }
+                }
+            } else {
+                (Vec::new(), None)
+            };
+
+            tables.push(TableMetadata {
+                database: Some(name.clone()),
+                schema: collection.scope.clone(),
+                name: collection.name.clone(),
+                columns,
+                row_count_estimate: counts.items,
+                size_bytes: counts.data_size_bytes,
+                document_count_sampled: sampled,
+                ..Default::default()
+            });
+            table_stats.push(TableStats {
+                database: Some(name.clone()),
+                schema: collection.scope.clone(),
+                table: collection.name.clone(),
+                n_live_tup: counts.items,
+                mem_used_bytes: counts.mem_used_bytes,
+                ..Default::default()
+            });
+        }
+        (tables, table_stats)
+    }
+}
+
+#[async_trait]
+impl Collector for CouchbaseCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let client = &self.client;
+        let mut buckets = stats::buckets(client).await?;
+        let mut tables = Vec::new();
+        let mut table_stats = Vec::new();
+
+        for bucket in &mut buckets {
+            let (bucket_tables, bucket_stats) = self.collect_bucket(bucket).await;
+            tables.extend(bucket_tables);
+            table_stats.extend(bucket_stats);
+        }
+
+        let (indexes, index_states, query_stats) = if client.has_query_service() {
+            let (indexes, index_states) = schema::indexes(client).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect indexes");
+                Default::default()
+            });
+            let query_stats = stats::query_stats(client).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect query statistics");
+                Vec::new()
+            });
+            (indexes, index_states, query_stats)
+        } else {
+            Default::default()
+        };
+        let (settings, setting_details) = stats::settings(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect settings");
+            Default::default()
+        });
+
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Couchbase.to_string(),
+            version: client.version().map(str::to_string),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                client.edition(),
+                client.has_query_service(),
+            ),
+        };
+
+        info!(
+            buckets = buckets.len(),
+            collections = tables.len(),
+            indexes = indexes.len(),
+            queries = query_stats.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_query_stats(query_stats)
+            .with_table_stats(table_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(SchemaMetadata {
+                tables,
+                indexes,
+                ..Default::default()
+            })
+            .with_couchbase(CouchbaseMetadata {
+                buckets,
+                indexes: index_states,
+            }))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        self.client
+            .get("pools/default")
+            .await
+            .map(|_| ())
+            .map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.client.version().map(str::to_string)
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Couchbase
+    }
+}
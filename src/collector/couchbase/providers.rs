@@ -0,0 +1,50 @@
+//! Couchbase provider detection.
+//!
+//! Returns one of:
+//! - `"couchbase_capella"` — Couchbase Capella (`*.cloud.couchbase.com`)
+//! - `"generic"` — self-managed Couchbase Server
+
+use std::collections::HashMap;
+
+pub const PROVIDER_COUCHBASE_CAPELLA: &str = "couchbase_capella";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// Detect the provider from the hostname.
+pub fn detect_provider(host: &str) -> &'static str {
+    if host.to_lowercase().ends_with(".cloud.couchbase.com") {
+        PROVIDER_COUCHBASE_CAPELLA
+    } else {
+        PROVIDER_GENERIC
+    }
+}
+
+/// Provider metadata derivable from the server.
+pub fn provider_metadata(edition: Option<&str>, query_service: bool) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    if let Some(edition) = edition {
+        out.insert("edition".to_string(), edition.to_string());
+    }
+    out.insert("query_service".to_string(), query_service.to_string());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(
+            detect_provider("cb.a1b2c3d4.cloud.couchbase.com"),
+            PROVIDER_COUCHBASE_CAPELLA
+        );
+        assert_eq!(detect_provider("cb.internal"), PROVIDER_GENERIC);
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let meta = provider_metadata(Some("enterprise"), true);
+        assert_eq!(meta.get("edition").unwrap(), "enterprise");
+        assert_eq!(meta.get("query_service").unwrap(), "true");
+    }
+}
@@ -0,0 +1,65 @@
+//! N1QL statements used by the Couchbase collector.
+
+use super::client::CLIENT_CONTEXT_ID;
+
+/// Slowest statements kept from `system:completed_requests`
+pub const MAX_QUERY_SHAPES: usize = 500;
+
+/// GSI and search index definitions. Indexes created before collections
+/// (or on the default collection with the bucket as keyspace) have no
+/// `bucket_id`, and `keyspace_id` is the bucket.
+pub const INDEXES: &str = "SELECT RAW i FROM system:indexes AS i \
+     WHERE i.namespace_id = \"default\"";
+
+/// Completed requests grouped by statement. The query service keeps the
+/// requests over `completed-threshold` (1 s by default), up to
+/// `completed-limit` of them; prepared statements are grouped by their
+/// text rather than their `EXECUTE` name.
+pub fn completed_requests() -> String {
+    format!(
+        "SELECT IFMISSINGORNULL(r.preparedText, r.statement) AS statement, \
+                COUNT(*) AS calls, \
+                SUM(STR_TO_DURATION(r.elapsedTime)) / 1000000 AS total_ms, \
+                SUM(r.resultCount) AS `rows` \
+         FROM system:completed_requests AS r \
+         WHERE IFMISSINGORNULL(r.preparedText, r.statement) IS VALUED \
+           AND IFMISSINGORNULL(r.clientContextID, \"\") != \"{CLIENT_CONTEXT_ID}\" \
+         GROUP BY IFMISSINGORNULL(r.preparedText, r.statement) \
+         ORDER BY total_ms DESC \
+         LIMIT {MAX_QUERY_SHAPES}"
+    )
+}
+
+/// Up to `limit` documents of a collection, for schema inference. Needs a
+/// primary index on the collection, or Couchbase 7.6+ (sequential scans).
+pub fn sample(bucket: &str, scope: &str, collection: &str, limit: i64) -> String {
+    format!(
+        "SELECT RAW d FROM {}.{}.{} AS d LIMIT {limit}",
+        identifier(bucket),
+        identifier(scope),
+        identifier(collection)
+    )
+}
+
+/// A backquoted N1QL identifier
+fn identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(
+            sample("travel-sample", "inventory", "air`port", 100),
+            "SELECT RAW d FROM `travel-sample`.`inventory`.`air``port` AS d LIMIT 100"
+        );
+    }
+
+    #[test]
+    fn test_completed_requests_excludes_agent() {
+        assert!(completed_requests().contains("!= \"datapace-agent\""));
+    }
+}
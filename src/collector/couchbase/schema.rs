@@ -0,0 +1,235 @@
+//! Scopes, collections, sampled document structure and indexes.
+//!
+//! Collections come from each bucket's manifest
+//! (`/pools/default/buckets/<bucket>/scopes`); before Couchbase 7.0 a
+//! bucket has only the `_default` collection. Their fields are inferred
+//! with the shared [`SchemaWalker`] from a sample of documents read over
+//! N1QL.
+//! Index definitions come from `system:indexes`.
+
+use super::client::{encode, int, text, CouchbaseClient};
+use super::queries;
+use crate::collector::schema::{pick_sample_size, SchemaWalker};
+use crate::collector::CollectorError;
+use crate::payload::{CouchbaseIndex, IndexMetadata};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tracing::debug;
+
+const DEFAULT: &str = "_default";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Collection {
+    pub scope: String,
+    pub name: String,
+}
+
+/// Collections of a bucket, without the `_system` scope (7.6+) that holds
+/// the server's own transaction and query metadata.
+pub(super) async fn collections(
+    client: &CouchbaseClient,
+    bucket: &str,
+) -> Result<Vec<Collection>, CollectorError> {
+    let path = format!("pools/default/buckets/{}/scopes", encode(bucket));
+    let Some(manifest) = client.get_opt(&path).await? else {
+        return Ok(vec![Collection {
+            scope: DEFAULT.to_string(),
+            name: DEFAULT.to_string(),
+        }]);
+    };
+    Ok(manifest_collections(&manifest))
+}
+
+fn manifest_collections(manifest: &Value) -> Vec<Collection> {
+    manifest["scopes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|scope| Some((text(&scope["name"])?, scope["collections"].as_array()?)))
+        .filter(|(scope, _)| scope == DEFAULT || !scope.starts_with('_'))
+        .flat_map(|(scope, collections)| {
+            collections.iter().filter_map(move |c| {
+                Some(Collection {
+                    scope: scope.clone(),
+                    name: text(&c["name"])?,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Sample a collection's documents into a [`SchemaWalker`], sized from its
+/// item count as for MongoDB. The query service returns documents in
+/// primary index order, so this is the first documents rather than a
+/// random sample.
+pub(super) async fn sample(
+    client: &CouchbaseClient,
+    bucket: &str,
+    collection: &Collection,
+    item_count: Option<i64>,
+) -> Result<SchemaWalker, CollectorError> {
+    debug!(bucket, scope = %collection.scope, collection = %collection.name, "Sampling documents");
+
+    let limit = pick_sample_size(item_count.unwrap_or(0));
+    let documents = client
+        .query(&queries::sample(
+            bucket,
+            &collection.scope,
+            &collection.name,
+            limit,
+        ))
+        .await?;
+
+    let mut walker = SchemaWalker::new();
+    for document in &documents {
+        // Binary and scalar values have no fields to walk
+        if let Value::Object(document) = document {
+            walker.observe_document(document);
+        }
+    }
+    Ok(walker)
+}
+
+/// Index definitions for the schema, and their state and last use
+pub(super) async fn indexes(
+    client: &CouchbaseClient,
+) -> Result<(Vec<IndexMetadata>, Vec<CouchbaseIndex>), CollectorError> {
+    debug!("Collecting indexes");
+
+    Ok(client
+        .query(queries::INDEXES)
+        .await?
+        .iter()
+        .filter_map(index)
+        .filter(|(definition, _)| {
+            client
+                .bucket()
+                .is_none_or(|only| definition.database.as_deref() == Some(only))
+        })
+        .unzip())
+}
+
+fn index(row: &Value) -> Option<(IndexMetadata, CouchbaseIndex)> {
+    let name = text(&row["name"])?;
+    let keyspace = text(&row["keyspace_id"])?;
+    // Without `bucket_id` the keyspace is a bucket's default collection
+    let (bucket, scope, collection) = match text(&row["bucket_id"]) {
+        Some(bucket) => (
+            bucket,
+            text(&row["scope_id"]).unwrap_or_else(|| DEFAULT.to_string()),
+            keyspace,
+        ),
+        None => (keyspace, DEFAULT.to_string(), DEFAULT.to_string()),
+    };
+    let metadata = &row["metadata"];
+
+    let definition = IndexMetadata {
+        database: Some(bucket.clone()),
+        schema: scope.clone(),
+        table: collection.clone(),
+        name: name.clone(),
+        columns: row["index_key"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|key| key.replace('`', ""))
+            .collect(),
+        is_unique: false,
+        is_primary: row["is_primary"].as_bool().unwrap_or(false),
+        size_bytes: None,
+        index_type: text(&row["using"]),
+        ..Default::default()
+    };
+    let state = CouchbaseIndex {
+        bucket,
+        scope,
+        collection,
+        name,
+        state: text(&row["state"]),
+        condition: text(&row["condition"]).filter(|c| !c.is_empty()),
+        partition: text(&row["partition"]),
+        replicas: int(&metadata["num_replica"]),
+        last_scan_time: scan_time(&metadata["last_scan_time"])
+            .or_else(|| scan_time(&metadata["stats"]["last_known_scan_time"])),
+    };
+    Some((definition, state))
+}
+
+/// An RFC 3339 timestamp, or nanoseconds since the epoch; zero is never
+fn scan_time(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        Value::Number(_) => int(value)
+            .filter(|ns| *ns > 0)
+            .map(DateTime::from_timestamp_nanos),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_manifest_collections() {
+        let manifest = json!({"uid": "2", "scopes": [
+            {"name": "_default", "collections": [{"name": "_default"}]},
+            {"name": "inventory", "collections": [{"name": "airline"}, {"name": "route"}]},
+            {"name": "_system", "collections": [{"name": "_query"}]}
+        ]});
+        let names: Vec<_> = manifest_collections(&manifest)
+            .into_iter()
+            .map(|c| format!("{}.{}", c.scope, c.name))
+            .collect();
+        assert_eq!(
+            names,
+            ["_default._default", "inventory.airline", "inventory.route"]
+        );
+    }
+
+    #[test]
+    fn test_index() {
+        let (definition, state) = index(&json!({
+            "bucket_id": "travel-sample",
+            "scope_id": "inventory",
+            "keyspace_id": "airport",
+            "namespace_id": "default",
+            "name": "def_inventory_airport_city",
+            "index_key": ["`city`", "`geo`.`alt` DESC"],
+            "condition": "(`type` = \"airport\")",
+            "state": "online",
+            "using": "gsi",
+            "metadata": {"num_replica": 1, "last_scan_time": "2024-05-01T10:00:00.000Z"}
+        }))
+        .unwrap();
+        assert_eq!(definition.database.as_deref(), Some("travel-sample"));
+        assert_eq!(
+            (definition.schema.as_str(), definition.table.as_str()),
+            ("inventory", "airport")
+        );
+        assert_eq!(definition.columns, ["city", "geo.alt DESC"]);
+        assert_eq!(definition.index_type.as_deref(), Some("gsi"));
+        assert_eq!(state.replicas, Some(1));
+        assert_eq!(
+            state.last_scan_time.map(|t| t.to_rfc3339()).as_deref(),
+            Some("2024-05-01T10:00:00+00:00")
+        );
+
+        // A bucket-level index from before collections
+        let (definition, state) = index(&json!({
+            "keyspace_id": "beer-sample",
+            "name": "#primary",
+            "is_primary": true,
+            "metadata": {"stats": {"last_known_scan_time": 0}}
+        }))
+        .unwrap();
+        assert_eq!(definition.database.as_deref(), Some("beer-sample"));
+        assert_eq!(definition.schema, "_default");
+        assert!(definition.is_primary);
+        assert_eq!(state.last_scan_time, None);
+    }
+}
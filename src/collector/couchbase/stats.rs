@@ -0,0 +1,336 @@
+//! Bucket and collection statistics, query statistics and settings.
+//!
+//! - buckets with their quota and `basicStats` from `/pools/default/buckets`
+//! - item count, memory and data size per collection, and each bucket's
+//!   active resident ratio, from the statistics range API
+//!   (`/pools/default/stats/range`, Couchbase 7.0+; the resident ratio
+//!   falls back to the older per-bucket stats endpoint)
+//! - slow statement shapes from `system:completed_requests`
+//! - service memory quotas, query and index service settings
+
+use super::client::{encode, float, int, text, CouchbaseClient};
+use super::queries;
+use crate::collector::CollectorError;
+use crate::payload::{CouchbaseBucket, QueryStats, Setting};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Item count, memory and data size of one collection
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(super) struct CollectionStats {
+    pub items: Option<i64>,
+    pub mem_used_bytes: Option<i64>,
+    pub data_size_bytes: Option<i64>,
+}
+
+pub(super) async fn buckets(
+    client: &CouchbaseClient,
+) -> Result<Vec<CouchbaseBucket>, CollectorError> {
+    debug!("Listing buckets");
+
+    let response = client.get("pools/default/buckets?skipMap=true").await?;
+    Ok(response
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(bucket)
+        .filter(|b| client.bucket().is_none_or(|only| only == b.name))
+        .collect())
+}
+
+fn bucket(value: &Value) -> CouchbaseBucket {
+    let stats = &value["basicStats"];
+    CouchbaseBucket {
+        name: text(&value["name"]).unwrap_or_default(),
+        // The REST API still calls Couchbase buckets by their old name
+        bucket_type: text(&value["bucketType"]).map(|t| match t.as_str() {
+            "membase" => "couchbase".to_string(),
+            _ => t,
+        }),
+        storage_backend: text(&value["storageBackend"]),
+        eviction_policy: text(&value["evictionPolicy"]),
+        replicas: int(&value["replicaNumber"]),
+        ram_quota_bytes: int(&value["quota"]["ram"]),
+        item_count: int(&stats["itemCount"]),
+        mem_used_bytes: int(&stats["memUsed"]),
+        quota_percent_used: float(&stats["quotaPercentUsed"]),
+        disk_used_bytes: int(&stats["diskUsed"]),
+        data_used_bytes: int(&stats["dataUsed"]),
+        ops_per_sec: float(&stats["opsPerSec"]),
+        resident_ratio: None,
+    }
+}
+
+/// Per-collection statistics of a bucket, keyed by scope and collection.
+/// Empty before Couchbase 7.0, which has neither collections nor the range
+/// API.
+pub(super) async fn collection_stats(
+    client: &CouchbaseClient,
+    bucket: &str,
+) -> Result<HashMap<(String, String), CollectionStats>, CollectorError> {
+    debug!(bucket, "Collecting collection statistics");
+
+    let mut out: HashMap<(String, String), CollectionStats> = HashMap::new();
+    for metric in [
+        "kv_collection_item_count",
+        "kv_collection_mem_used_bytes",
+        "kv_collection_data_size_bytes",
+    ] {
+        let path = range_path(metric, &[("bucket", bucket)], "sum");
+        let Some(response) = client.get_opt(&path).await? else {
+            return Ok(HashMap::new());
+        };
+        for series in response["data"].as_array().into_iter().flatten() {
+            let labels = &series["metric"];
+            let (Some(scope), Some(collection), Some(value)) = (
+                text(&labels["scope"]),
+                text(&labels["collection"]),
+                last_value(series),
+            ) else {
+                continue;
+            };
+            let stats = out.entry((scope, collection)).or_default();
+            let value = Some(value as i64);
+            match metric {
+                "kv_collection_item_count" => stats.items = value,
+                "kv_collection_mem_used_bytes" => stats.mem_used_bytes = value,
+                _ => stats.data_size_bytes = value,
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Share of active items resident in memory, averaged over the nodes
+pub(super) async fn resident_ratio(
+    client: &CouchbaseClient,
+    bucket: &str,
+) -> Result<Option<f64>, CollectorError> {
+    let path = range_path(
+        "kv_vb_perc_mem_resident_ratio",
+        &[("bucket", bucket), ("state", "active")],
+        "avg",
+    );
+    if let Some(response) = client.get_opt(&path).await? {
+        return Ok(response["data"]
+            .as_array()
+            .and_then(|data| data.first())
+            .and_then(last_value));
+    }
+
+    // Before 7.0: one sample per second over the last minute
+    let path = format!("pools/default/buckets/{}/stats", encode(bucket));
+    Ok(client.get_opt(&path).await?.and_then(|response| {
+        response["op"]["samples"]["vb_active_resident_items_ratio"]
+            .as_array()?
+            .last()
+            .and_then(float)
+    }))
+}
+
+/// The last minute of a metric, one point, aggregated over the nodes
+fn range_path(metric: &str, labels: &[(&str, &str)], aggregation: &str) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, value) in labels {
+        query.append_pair(key, value);
+    }
+    query
+        .append_pair("start", "-60")
+        .append_pair("step", "60")
+        .append_pair("nodesAggregation", aggregation);
+    format!("pools/default/stats/range/{metric}?{}", query.finish())
+}
+
+/// Latest `[timestamp, "value"]` point of a range API series
+fn last_value(series: &Value) -> Option<f64> {
+    float(series["values"].as_array()?.last()?.get(1)?)
+}
+
+pub(super) async fn query_stats(
+    client: &CouchbaseClient,
+) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics");
+
+    Ok(client
+        .query(&queries::completed_requests())
+        .await?
+        .iter()
+        .filter_map(query_stat)
+        .collect())
+}
+
+fn query_stat(row: &Value) -> Option<QueryStats> {
+    let statement = row["statement"].as_str()?;
+    let calls = int(&row["calls"]);
+    let total_ms = float(&row["total_ms"]);
+    Some(QueryStats {
+        query_hash: Some(hex::encode(&Sha256::digest(statement.as_bytes())[..8])),
+        query: Some(statement.to_string()),
+        calls,
+        total_time_ms: total_ms,
+        mean_time_ms: total_ms
+            .zip(calls.filter(|c| *c > 0))
+            .map(|(total, calls)| total / calls as f64),
+        rows: int(&row["rows"]),
+        ..Default::default()
+    })
+}
+
+/// Settings read from each endpoint, with their category
+const SETTINGS: &[(&str, &str)] = &[
+    ("pools/default", "cluster"),
+    ("settings/querySettings", "query"),
+    ("settings/indexes", "index"),
+];
+
+/// Service memory quotas of `/pools/default`; the rest of it is cluster
+/// state rather than configuration
+const QUOTAS: &[&str] = &[
+    "memoryQuota",
+    "indexMemoryQuota",
+    "ftsMemoryQuota",
+    "cbasMemoryQuota",
+    "eventingMemoryQuota",
+];
+
+pub(super) async fn settings(
+    client: &CouchbaseClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting settings");
+
+    let mut details = Vec::new();
+    for (path, category) in SETTINGS {
+        // `settings/querySettings` is new in 6.5
+        let Some(response) = client.get_opt(path).await? else {
+            continue;
+        };
+        let Some(settings) = response.as_object() else {
+            continue;
+        };
+        details.extend(
+            settings
+                .iter()
+                .filter(|(name, _)| *category != "cluster" || QUOTAS.contains(&name.as_str()))
+                .map(|(name, value)| {
+                    let (unit, value_bytes, value_ms) = normalize(name, value);
+                    Setting {
+                        name: name.clone(),
+                        value: match value {
+                            Value::String(s) => s.clone(),
+                            other => other.to_string(),
+                        },
+                        unit: unit.map(str::to_string),
+                        value_bytes,
+                        value_ms,
+                        category: Some(category.to_string()),
+                        source: Some(path.to_string()),
+                        ..Default::default()
+                    }
+                }),
+        );
+    }
+    details.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+/// Quotas are in MiB, `queryTimeout` in nanoseconds and the other
+/// intervals and thresholds in milliseconds.
+fn normalize(name: &str, value: &Value) -> (Option<&'static str>, Option<i64>, Option<f64>) {
+    let Some(n) = value.as_i64() else {
+        return (None, None, None);
+    };
+    match name {
+        _ if name.ends_with("MemoryQuota") || name == "memoryQuota" => {
+            (Some("MB"), Some(n * 1024 * 1024), None)
+        }
+        "queryTimeout" => (Some("ns"), None, Some(n as f64 / 1e6)),
+        "queryCompletedThreshold" | "memorySnapshotInterval" | "stableSnapshotInterval" => {
+            (Some("ms"), None, Some(n as f64))
+        }
+        _ => (None, None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_bucket() {
+        let bucket = bucket(&json!({
+            "name": "travel-sample",
+            "bucketType": "membase",
+            "storageBackend": "couchstore",
+            "evictionPolicy": "valueOnly",
+            "replicaNumber": 1,
+            "quota": {"ram": 209715200, "rawRAM": 209715200},
+            "basicStats": {
+                "quotaPercentUsed": 61.5,
+                "opsPerSec": 12.0,
+                "diskUsed": 120000000,
+                "dataUsed": 90000000,
+                "memUsed": 129000000,
+                "itemCount": 63288
+            }
+        }));
+        assert_eq!(bucket.bucket_type.as_deref(), Some("couchbase"));
+        assert_eq!(bucket.ram_quota_bytes, Some(209_715_200));
+        assert_eq!(bucket.item_count, Some(63_288));
+        assert_eq!(bucket.quota_percent_used, Some(61.5));
+    }
+
+    #[test]
+    fn test_range_path() {
+        assert_eq!(
+            range_path("kv_vb_perc_mem_resident_ratio", &[("bucket", "a b"), ("state", "active")], "avg"),
+            "pools/default/stats/range/kv_vb_perc_mem_resident_ratio?bucket=a+b&state=active&start=-60&step=60&nodesAggregation=avg"
+        );
+    }
+
+    #[test]
+    fn test_last_value() {
+        let series = json!({"metric": {}, "values": [[1700000000, "98.5"], [1700000060, "97"]]});
+        assert_eq!(last_value(&series), Some(97.0));
+        assert_eq!(last_value(&json!({"values": []})), None);
+    }
+
+    #[test]
+    fn test_query_stat() {
+        let stat = query_stat(&json!({
+            "statement": "SELECT * FROM `travel-sample` WHERE city = $1",
+            "calls": 4,
+            "total_ms": 6000.0,
+            "rows": 40
+        }))
+        .unwrap();
+        assert_eq!(stat.calls, Some(4));
+        assert_eq!(stat.mean_time_ms, Some(1500.0));
+        assert_eq!(stat.query_hash.as_ref().map(String::len), Some(16));
+        assert!(query_stat(&json!({"calls": 1})).is_none());
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("indexMemoryQuota", &json!(512)),
+            (Some("MB"), Some(536_870_912), None)
+        );
+        assert_eq!(
+            normalize("queryTimeout", &json!(75_000_000_000i64)),
+            (Some("ns"), None, Some(75_000.0))
+        );
+        assert_eq!(
+            normalize("queryCompletedThreshold", &json!(1000)),
+            (Some("ms"), None, Some(1000.0))
+        );
+        assert_eq!(normalize("logLevel", &json!("info")), (None, None, None));
+    }
+}
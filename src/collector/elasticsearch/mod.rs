@@ -0,0 +1,166 @@
+//! Elasticsearch / OpenSearch collector, over the REST API.
+//!
+//! Collects:
+//! - Cluster health (`_cluster/health`)
+//! - Per-index documents, sizes and search/indexing counters
+//!   (`_cat/indices`), turned into rates between collections
+//! - Shard layout and unassigned shards (`_cat/shards`)
+//! - Node heap, GC, CPU, disk and thread pool rejections (`_nodes/stats`)
+//! - Cluster settings, including defaults (`_cluster/settings`)
+//! - Index mappings as schema metadata (`_mapping`)
+//!
+//! Elastic Cloud, Amazon OpenSearch Service and self-managed Elasticsearch
+//! and OpenSearch are told apart in [`providers`]. The user needs the
+//! `monitor` cluster privilege and `monitor` and `view_index_metadata` on
+//! the indices.
+
+mod client;
+pub mod providers;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, ElasticsearchMetadata, Payload};
+use async_trait::async_trait;
+use client::{text, ElasticsearchClient};
+use providers::ClusterInfo;
+use stats::CounterSnapshot;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+/// Elasticsearch / OpenSearch metrics collector
+pub struct ElasticsearchCollector {
+    client: ElasticsearchClient,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+    cluster_info: ClusterInfo,
+    /// Counters of the previous collection, for search and indexing rates
+    previous: Mutex<Option<CounterSnapshot>>,
+}
+
+impl ElasticsearchCollector {
+    /// Read the cluster info from the root endpoint and detect the provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to Elasticsearch");
+
+        let client = ElasticsearchClient::from_url(database_url)?;
+        let root = client.get("").await?;
+        let version = text(&root["version"]["number"]);
+        let cluster_info = ClusterInfo {
+            cluster_name: text(&root["cluster_name"]),
+            cluster_uuid: text(&root["cluster_uuid"]),
+            distribution: text(&root["version"]["distribution"]),
+        };
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            cluster = cluster_info.cluster_name.as_deref().unwrap_or("unknown"),
+            distribution = cluster_info
+                .distribution
+                .as_deref()
+                .unwrap_or("elasticsearch"),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(&cluster_info, client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+            cluster_info,
+            previous: Mutex::new(None),
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for ElasticsearchCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let client = &self.client;
+        let health = stats::cluster_health(client).await?;
+        let indices = stats::indices(client).await?;
+        let (settings, setting_details) = stats::settings(client).await?;
+        let schema = schema::collect(client, &health.cluster_name, &indices).await?;
+        // Optional like the other engine-specific sections
+        let (index_health, unassigned_shards) =
+            stats::shards(client, &indices).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect shard allocation");
+                Default::default()
+            });
+        let nodes = stats::nodes(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect node statistics");
+            Vec::new()
+        });
+
+        let table_stats = {
+            let mut previous = self
+                .previous
+                .lock()
+                .expect("counter snapshot lock poisoned");
+            let table_stats = stats::table_stats(&health.cluster_name, &indices, previous.as_ref());
+            *previous = Some(CounterSnapshot::take(&indices));
+            table_stats
+        };
+
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Elasticsearch.to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                &self.cluster_info,
+                client.host(),
+            ),
+        };
+
+        info!(
+            indices = indices.len(),
+            status = %health.status,
+            nodes = nodes.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_table_stats(table_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema)
+            .with_elasticsearch(ElasticsearchMetadata {
+                health,
+                indices: index_health,
+                unassigned_shards,
+                nodes,
+            }))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        self.client
+            .get("")
+            .await
+            .map(|_| ())
+            .map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Elasticsearch
+    }
+}
@@ -0,0 +1,145 @@
+//! Elasticsearch / OpenSearch provider detection.
+//!
+//! Returns one of:
+//! - `"elastic_cloud"` — Elastic Cloud (hosted and serverless)
+//! - `"aws_opensearch"` — Amazon OpenSearch Service
+//! - `"opensearch"` — self-managed OpenSearch
+//! - `"generic"` — self-managed Elasticsearch
+//!
+//! The managed services are recognized by hostname; OpenSearch reports
+//! `version.distribution = "opensearch"` from the root endpoint.
+
+use std::collections::HashMap;
+
+pub const PROVIDER_ELASTIC_CLOUD: &str = "elastic_cloud";
+pub const PROVIDER_AWS_OPENSEARCH: &str = "aws_opensearch";
+pub const PROVIDER_OPENSEARCH: &str = "opensearch";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// Hostname suffixes of Elastic Cloud deployments, current and legacy
+const ELASTIC_CLOUD_SUFFIXES: &[&str] = &[".cloud.es.io", ".elastic.cloud", ".found.io"];
+
+/// What the root endpoint (`GET /`) says about the cluster
+#[derive(Debug, Default)]
+pub struct ClusterInfo {
+    pub cluster_name: Option<String>,
+    pub cluster_uuid: Option<String>,
+    /// `opensearch` for OpenSearch; absent on Elasticsearch
+    pub distribution: Option<String>,
+}
+
+impl ClusterInfo {
+    pub fn is_opensearch(&self) -> bool {
+        self.distribution.as_deref() == Some("opensearch")
+    }
+}
+
+/// Detect the provider from the hostname, then the distribution.
+pub fn detect_provider(info: &ClusterInfo, host: &str) -> &'static str {
+    let lower = host.to_lowercase();
+    if ELASTIC_CLOUD_SUFFIXES.iter().any(|s| lower.ends_with(s)) {
+        PROVIDER_ELASTIC_CLOUD
+    } else if lower.ends_with(".es.amazonaws.com") || lower.ends_with(".aoss.amazonaws.com") {
+        PROVIDER_AWS_OPENSEARCH
+    } else if info.is_opensearch() {
+        PROVIDER_OPENSEARCH
+    } else {
+        PROVIDER_GENERIC
+    }
+}
+
+/// Provider metadata derivable from the cluster info and hostname.
+pub fn provider_metadata(
+    provider: &str,
+    info: &ClusterInfo,
+    host: &str,
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    out.insert(
+        "distribution".to_string(),
+        if info.is_opensearch() {
+            "opensearch"
+        } else {
+            "elasticsearch"
+        }
+        .to_string(),
+    );
+    if let Some(uuid) = &info.cluster_uuid {
+        out.insert("cluster_uuid".to_string(), uuid.clone());
+    }
+
+    let parts: Vec<&str> = host.split('.').collect();
+    match provider {
+        PROVIDER_ELASTIC_CLOUD => {
+            // `<deployment>.<region>.<cloud>.cloud.es.io`, or with an extra
+            // `es` label in 8.x endpoints
+            let labels: Vec<&str> = parts.iter().copied().filter(|l| *l != "es").collect();
+            if labels.len() >= 5 {
+                out.insert("deployment_id".to_string(), labels[0].to_string());
+                out.insert("region".to_string(), labels[1].to_string());
+                out.insert("cloud".to_string(), labels[2].to_string());
+            }
+        }
+        // `search-<domain>-<id>.<region>.es.amazonaws.com`
+        PROVIDER_AWS_OPENSEARCH if parts.len() >= 5 => {
+            let domain = parts[0]
+                .strip_prefix("search-")
+                .or_else(|| parts[0].strip_prefix("vpc-"))
+                .and_then(|d| d.rsplit_once('-'))
+                .map(|(domain, _)| domain);
+            if let Some(domain) = domain {
+                out.insert("domain".to_string(), domain.to_string());
+            }
+            out.insert("region".to_string(), parts[1].to_string());
+        }
+        _ => {}
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_managed_services() {
+        let info = ClusterInfo::default();
+        assert_eq!(
+            detect_provider(&info, "logs.es.us-central1.gcp.cloud.es.io"),
+            PROVIDER_ELASTIC_CLOUD
+        );
+        assert_eq!(
+            detect_provider(&info, "search-logs-abc123.us-east-1.es.amazonaws.com"),
+            PROVIDER_AWS_OPENSEARCH
+        );
+        assert_eq!(detect_provider(&info, "es.internal"), PROVIDER_GENERIC);
+
+        let info = ClusterInfo {
+            distribution: Some("opensearch".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(detect_provider(&info, "os.internal"), PROVIDER_OPENSEARCH);
+    }
+
+    #[test]
+    fn extracts_cloud_metadata() {
+        let info = ClusterInfo::default();
+        let meta = provider_metadata(
+            PROVIDER_ELASTIC_CLOUD,
+            &info,
+            "abc123.es.us-central1.gcp.cloud.es.io",
+        );
+        assert_eq!(meta.get("deployment_id").unwrap(), "abc123");
+        assert_eq!(meta.get("region").unwrap(), "us-central1");
+        assert_eq!(meta.get("cloud").unwrap(), "gcp");
+
+        let meta = provider_metadata(
+            PROVIDER_AWS_OPENSEARCH,
+            &info,
+            "search-app-logs-abc123xyz.eu-west-1.es.amazonaws.com",
+        );
+        assert_eq!(meta.get("domain").unwrap(), "app-logs");
+        assert_eq!(meta.get("region").unwrap(), "eu-west-1");
+        assert_eq!(meta.get("distribution").unwrap(), "elasticsearch");
+    }
+}
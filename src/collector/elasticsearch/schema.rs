@@ -0,0 +1,158 @@
+//! Index mappings as schema metadata.
+//!
+//! Each index becomes a table in a schema named after the cluster, with its
+//! mapped fields as columns. Object and nested fields are flattened to dot
+//! paths (`address.city`), as are multi-fields (`title.keyword`); the object
+//! itself is listed with type `object` or `nested`. Fields are optional in
+//! a document, so every column is nullable.
+
+use super::client::{text, ElasticsearchClient};
+use super::stats::IndexRow;
+use crate::collector::CollectorError;
+use crate::payload::{ColumnMetadata, SchemaMetadata, TableMetadata};
+use serde_json::{Map, Value};
+use tracing::debug;
+
+const MAPPINGS: &str = "_mapping?expand_wildcards=all";
+
+pub(super) async fn collect(
+    client: &ElasticsearchClient,
+    schema: &str,
+    indices: &[IndexRow],
+) -> Result<SchemaMetadata, CollectorError> {
+    debug!("Collecting index mappings");
+
+    let mappings = client.get(MAPPINGS).await?;
+    let tables = indices
+        .iter()
+        .map(|index| {
+            let properties = mappings[&index.name]["mappings"]["properties"].as_object();
+            let mut columns = Vec::new();
+            if let Some(properties) = properties {
+                flatten(properties, "", &mut columns);
+            }
+            TableMetadata {
+                schema: schema.to_string(),
+                name: index.name.clone(),
+                columns,
+                row_count_estimate: index.docs,
+                size_bytes: index.primary_store_size,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Ok(SchemaMetadata {
+        tables,
+        ..Default::default()
+    })
+}
+
+/// Append the fields of a `properties` object, depth first in mapping order.
+fn flatten(properties: &Map<String, Value>, prefix: &str, columns: &mut Vec<ColumnMetadata>) {
+    for (name, field) in properties {
+        let path = format!("{prefix}{name}");
+        let children = field["properties"].as_object();
+        let data_type = text(&field["type"]).unwrap_or_else(|| {
+            // Objects don't state their type unless they are `nested`
+            if children.is_some() {
+                "object"
+            } else {
+                "unknown"
+            }
+            .to_string()
+        });
+
+        columns.push(ColumnMetadata {
+            name: path.clone(),
+            native_type: Some(native_type(&data_type, field)),
+            data_type,
+            nullable: true,
+            position: columns.len() as i32 + 1,
+            ..Default::default()
+        });
+
+        if let Some(children) = children {
+            flatten(children, &format!("{path}."), columns);
+        }
+        if let Some(fields) = field["fields"].as_object() {
+            flatten(fields, &format!("{path}."), columns);
+        }
+    }
+}
+
+/// The field type with the mapping parameters that change how it is stored
+/// or searched, e.g. `text(analyzer=english)` or `dense_vector(dims=384)`
+fn native_type(data_type: &str, field: &Value) -> String {
+    const PARAMETERS: &[&str] = &[
+        "analyzer",
+        "normalizer",
+        "format",
+        "dims",
+        "similarity",
+        "index",
+        "doc_values",
+        "enabled",
+    ];
+    let parameters: Vec<String> = PARAMETERS
+        .iter()
+        .filter_map(|p| {
+            let value = match &field[*p] {
+                Value::Null => return None,
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            Some(format!("{p}={value}"))
+        })
+        .collect();
+
+    if parameters.is_empty() {
+        data_type.to_string()
+    } else {
+        format!("{data_type}({})", parameters.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten() {
+        let mapping = json!({
+            "title": {"type": "text", "analyzer": "english",
+                      "fields": {"keyword": {"type": "keyword"}}},
+            "address": {"properties": {"city": {"type": "keyword"}}},
+            "comments": {"type": "nested", "properties": {"body": {"type": "text"}}},
+            "embedding": {"type": "dense_vector", "dims": 384, "similarity": "cosine"}
+        });
+        let mut columns = Vec::new();
+        flatten(mapping.as_object().unwrap(), "", &mut columns);
+
+        let names: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "title",
+                "title.keyword",
+                "address",
+                "address.city",
+                "comments",
+                "comments.body",
+                "embedding"
+            ]
+        );
+        assert_eq!(
+            columns[0].native_type.as_deref(),
+            Some("text(analyzer=english)")
+        );
+        assert_eq!(columns[2].data_type, "object");
+        assert_eq!(columns[4].data_type, "nested");
+        assert_eq!(
+            columns[6].native_type.as_deref(),
+            Some("dense_vector(dims=384, similarity=cosine)")
+        );
+        assert_eq!(columns[6].position, 7);
+    }
+}
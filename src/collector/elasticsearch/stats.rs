@@ -0,0 +1,424 @@
+//! Elasticsearch / OpenSearch statistics and settings.
+//!
+//! - cluster health from `_cluster/health`
+//! - per-index document counts, sizes and search/indexing counters from
+//!   `_cat/indices`, shard layout and unassigned shards from `_cat/shards`
+//! - heap, GC, CPU, disk and thread pool rejections from `_nodes/stats`
+//! - cluster settings, including defaults, from `_cluster/settings`
+//!
+//! Dot-prefixed system indices are left out; data stream backing indices
+//! (`.ds-*`) are kept.
+
+use super::client::{float, int, text, ElasticsearchClient};
+use crate::collector::CollectorError;
+use crate::payload::{ClusterHealth, IndexHealth, NodeStats, Setting, TableStats, UnassignedShard};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::debug;
+
+const CAT_INDICES: &str = "_cat/indices?format=json&bytes=b&time=ms&expand_wildcards=all\
+    &h=health,status,index,pri,rep,docs.count,docs.deleted,store.size,pri.store.size,\
+    search.query_total,search.query_time,indexing.index_total,indexing.index_time,\
+    indexing.delete_total";
+const CAT_SHARDS: &str =
+    "_cat/shards?format=json&bytes=b&h=index,shard,prirep,state,store,unassigned.reason";
+const NODES_STATS: &str = "_nodes/stats/jvm,os,fs,thread_pool";
+const CLUSTER_SETTINGS: &str = "_cluster/settings?include_defaults=true&flat_settings=true";
+
+/// Whether an index holds user data rather than cluster internals
+pub(super) fn is_user_index(name: &str) -> bool {
+    !name.starts_with('.') || name.starts_with(".ds-")
+}
+
+pub(super) async fn cluster_health(
+    client: &ElasticsearchClient,
+) -> Result<ClusterHealth, CollectorError> {
+    debug!("Collecting cluster health");
+
+    let health = client.get("_cluster/health").await?;
+    let count = |field: &str| int(&health[field]).unwrap_or(0);
+    Ok(ClusterHealth {
+        cluster_name: text(&health["cluster_name"]).unwrap_or_default(),
+        status: text(&health["status"]).unwrap_or_default(),
+        number_of_nodes: count("number_of_nodes"),
+        number_of_data_nodes: count("number_of_data_nodes"),
+        active_primary_shards: count("active_primary_shards"),
+        active_shards: count("active_shards"),
+        relocating_shards: count("relocating_shards"),
+        initializing_shards: count("initializing_shards"),
+        unassigned_shards: count("unassigned_shards"),
+        delayed_unassigned_shards: count("delayed_unassigned_shards"),
+        number_of_pending_tasks: count("number_of_pending_tasks"),
+        active_shards_percent: float(&health["active_shards_percent_as_number"]),
+    })
+}
+
+/// One row of `_cat/indices`
+#[derive(Debug, Default)]
+pub(super) struct IndexRow {
+    pub name: String,
+    pub health: Option<String>,
+    pub status: Option<String>,
+    pub primary_shards: Option<i64>,
+    pub replicas: Option<i64>,
+    pub docs: Option<i64>,
+    pub deleted_docs: Option<i64>,
+    pub store_size: Option<i64>,
+    pub primary_store_size: Option<i64>,
+    pub search_count: Option<i64>,
+    pub search_time_ms: Option<f64>,
+    pub indexing_count: Option<i64>,
+    pub indexing_time_ms: Option<f64>,
+    pub delete_count: Option<i64>,
+}
+
+pub(super) async fn indices(client: &ElasticsearchClient) -> Result<Vec<IndexRow>, CollectorError> {
+    debug!("Collecting index statistics");
+
+    let rows = client.get(CAT_INDICES).await?;
+    let mut indices: Vec<IndexRow> = rows
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|row| {
+            let name = text(&row["index"]).filter(|n| is_user_index(n))?;
+            Some(IndexRow {
+                name,
+                health: text(&row["health"]),
+                status: text(&row["status"]),
+                primary_shards: int(&row["pri"]),
+                replicas: int(&row["rep"]),
+                docs: int(&row["docs.count"]),
+                deleted_docs: int(&row["docs.deleted"]),
+                store_size: int(&row["store.size"]),
+                primary_store_size: int(&row["pri.store.size"]),
+                search_count: int(&row["search.query_total"]),
+                search_time_ms: float(&row["search.query_time"]),
+                indexing_count: int(&row["indexing.index_total"]),
+                indexing_time_ms: float(&row["indexing.index_time"]),
+                delete_count: int(&row["indexing.delete_total"]),
+            })
+        })
+        .collect();
+    indices.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(indices)
+}
+
+/// Search and indexing counters of the previous collection, to turn the
+/// cumulative counters into rates
+#[derive(Debug)]
+pub(super) struct CounterSnapshot {
+    at: Instant,
+    counters: HashMap<String, Counters>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    search_count: i64,
+    search_time_ms: f64,
+    indexing_count: i64,
+    indexing_time_ms: f64,
+}
+
+impl CounterSnapshot {
+    pub(super) fn take(indices: &[IndexRow]) -> Self {
+        Self {
+            at: Instant::now(),
+            counters: indices
+                .iter()
+                .map(|i| (i.name.clone(), counters(i)))
+                .collect(),
+        }
+    }
+}
+
+fn counters(index: &IndexRow) -> Counters {
+    Counters {
+        search_count: index.search_count.unwrap_or(0),
+        search_time_ms: index.search_time_ms.unwrap_or(0.0),
+        indexing_count: index.indexing_count.unwrap_or(0),
+        indexing_time_ms: index.indexing_time_ms.unwrap_or(0.0),
+    }
+}
+
+/// Per-index statistics. Rates and latencies cover the time since
+/// `previous`; without it, or for an index whose counters went backwards
+/// (recreated, or a shard moved), latencies cover the index's lifetime and
+/// rates are left out.
+pub(super) fn table_stats(
+    schema: &str,
+    indices: &[IndexRow],
+    previous: Option<&CounterSnapshot>,
+) -> Vec<TableStats> {
+    indices
+        .iter()
+        .map(|index| {
+            let current = counters(index);
+            let interval = previous.and_then(|p| {
+                let before = p.counters.get(&index.name)?;
+                let elapsed = p.at.elapsed().as_secs_f64();
+                let delta = Counters {
+                    search_count: current.search_count - before.search_count,
+                    search_time_ms: current.search_time_ms - before.search_time_ms,
+                    indexing_count: current.indexing_count - before.indexing_count,
+                    indexing_time_ms: current.indexing_time_ms - before.indexing_time_ms,
+                };
+                (elapsed > 0.0 && delta.search_count >= 0 && delta.indexing_count >= 0)
+                    .then_some((delta, elapsed))
+            });
+            let window = interval.map_or(current, |(delta, _)| delta);
+
+            TableStats {
+                schema: schema.to_string(),
+                table: index.name.clone(),
+                n_live_tup: index.docs,
+                // Deleted documents stay in their segments until merged away
+                n_dead_tup: index.deleted_docs,
+                n_tup_ins: index.indexing_count,
+                n_tup_del: index.delete_count,
+                search_count: index.search_count,
+                search_time_ms: index.search_time_ms,
+                search_rate: interval.map(|(d, secs)| d.search_count as f64 / secs),
+                search_latency_ms: mean(window.search_time_ms, window.search_count),
+                indexing_count: index.indexing_count,
+                indexing_time_ms: index.indexing_time_ms,
+                indexing_rate: interval.map(|(d, secs)| d.indexing_count as f64 / secs),
+                indexing_latency_ms: mean(window.indexing_time_ms, window.indexing_count),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn mean(total_ms: f64, count: i64) -> Option<f64> {
+    (count > 0).then(|| total_ms / count as f64)
+}
+
+/// Index health with the shard layout from `_cat/shards`, and the shards
+/// that are unassigned.
+pub(super) async fn shards(
+    client: &ElasticsearchClient,
+    indices: &[IndexRow],
+) -> Result<(Vec<IndexHealth>, Vec<UnassignedShard>), CollectorError> {
+    debug!("Collecting shard allocation");
+
+    let rows = client.get(CAT_SHARDS).await?;
+    let mut max_shard_size: HashMap<&str, i64> = HashMap::new();
+    let mut unassigned_count: HashMap<&str, i64> = HashMap::new();
+    let mut unassigned = Vec::new();
+    for row in rows.as_array().into_iter().flatten() {
+        let Some(index) = row["index"].as_str().filter(|n| is_user_index(n)) else {
+            continue;
+        };
+        let primary = row["prirep"].as_str() == Some("p");
+        if row["state"].as_str() == Some("UNASSIGNED") {
+            *unassigned_count.entry(index).or_default() += 1;
+            unassigned.push(UnassignedShard {
+                index: index.to_string(),
+                shard: int(&row["shard"]).unwrap_or_default(),
+                primary,
+                reason: text(&row["unassigned.reason"]),
+            });
+        } else if let (true, Some(size)) = (primary, int(&row["store"])) {
+            let max = max_shard_size.entry(index).or_default();
+            *max = (*max).max(size);
+        }
+    }
+
+    let health = indices
+        .iter()
+        .map(|index| IndexHealth {
+            index: index.name.clone(),
+            health: index.health.clone(),
+            status: index.status.clone(),
+            primary_shards: index.primary_shards,
+            replicas: index.replicas,
+            deleted_docs: index.deleted_docs,
+            store_size_bytes: index.store_size,
+            max_shard_size_bytes: max_shard_size.get(index.name.as_str()).copied(),
+            unassigned_shards: unassigned_count
+                .get(index.name.as_str())
+                .copied()
+                .unwrap_or(0),
+        })
+        .collect();
+    Ok((health, unassigned))
+}
+
+pub(super) async fn nodes(client: &ElasticsearchClient) -> Result<Vec<NodeStats>, CollectorError> {
+    debug!("Collecting node statistics");
+
+    let stats = client.get(NODES_STATS).await?;
+    let mut nodes: Vec<NodeStats> = stats["nodes"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(_, node)| {
+            let old_gc = &node["jvm"]["gc"]["collectors"]["old"];
+            NodeStats {
+                name: text(&node["name"]).unwrap_or_default(),
+                host: text(&node["host"]),
+                roles: node["roles"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(text)
+                    .collect(),
+                heap_used_percent: float(&node["jvm"]["mem"]["heap_used_percent"]),
+                heap_max_bytes: int(&node["jvm"]["mem"]["heap_max_in_bytes"]),
+                gc_old_count: int(&old_gc["collection_count"]),
+                gc_old_time_ms: float(&old_gc["collection_time_in_millis"]),
+                cpu_percent: float(&node["os"]["cpu"]["percent"]),
+                load_average_1m: float(&node["os"]["cpu"]["load_average"]["1m"]),
+                disk_total_bytes: int(&node["fs"]["total"]["total_in_bytes"]),
+                disk_available_bytes: int(&node["fs"]["total"]["available_in_bytes"]),
+                search_rejected: int(&node["thread_pool"]["search"]["rejected"]),
+                write_rejected: int(&node["thread_pool"]["write"]["rejected"]),
+            }
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(nodes)
+}
+
+/// Cluster settings as a flat map of effective values and as structured
+/// [`Setting`]s. `source` is `transient`, `persistent` or `default`, in that
+/// order of precedence.
+pub(super) async fn settings(
+    client: &ElasticsearchClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting cluster settings");
+
+    let response = client.get(CLUSTER_SETTINGS).await?;
+    let mut effective: HashMap<String, (String, &str)> = HashMap::new();
+    for (key, source) in [
+        ("defaults", "default"),
+        ("persistent", "persistent"),
+        ("transient", "transient"),
+    ] {
+        for (name, value) in response[key].as_object().into_iter().flatten() {
+            effective.insert(name.clone(), (setting_value(value), source));
+        }
+    }
+
+    let mut details: Vec<Setting> = effective
+        .iter()
+        .map(|(name, (value, source))| {
+            let (unit, value_bytes, value_ms) = normalize(value);
+            Setting {
+                name: name.clone(),
+                value: value.clone(),
+                unit: unit.map(str::to_string),
+                value_bytes,
+                value_ms,
+                category: name.split('.').next().map(str::to_string),
+                source: Some(source.to_string()),
+                is_default: *source == "default",
+                ..Default::default()
+            }
+        })
+        .collect();
+    details.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let flat = effective
+        .into_iter()
+        .map(|(name, (value, _))| (name, value))
+        .collect();
+    Ok((flat, details))
+}
+
+/// Settings are strings, or arrays of strings for list settings
+fn setting_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|v| v.as_str().map_or_else(|| v.to_string(), str::to_string))
+            .collect::<Vec<_>>()
+            .join(","),
+        other => other.to_string(),
+    }
+}
+
+/// Unit and normalized value of a time (`30s`, `500ms`) or byte size
+/// (`512mb`) setting, or of a percentage (`85%`).
+fn normalize(value: &str) -> (Option<&'static str>, Option<i64>, Option<f64>) {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+    let Ok(n) = number.parse::<f64>() else {
+        return (None, None, None);
+    };
+
+    let bytes = |unit, factor: f64| (Some(unit), Some((n * factor) as i64), None);
+    let millis = |unit, factor: f64| (Some(unit), None, Some(n * factor));
+    match suffix.to_ascii_lowercase().as_str() {
+        "b" => bytes("B", 1.0),
+        "kb" => bytes("kB", 1024.0),
+        "mb" => bytes("MB", 1024.0 * 1024.0),
+        "gb" => bytes("GB", 1024.0 * 1024.0 * 1024.0),
+        "tb" => bytes("TB", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        "nanos" => millis("ns", 1e-6),
+        "micros" => millis("us", 1e-3),
+        "ms" => millis("ms", 1.0),
+        "s" => millis("s", 1_000.0),
+        "m" => millis("min", 60_000.0),
+        "h" => millis("h", 3_600_000.0),
+        "d" => millis("d", 86_400_000.0),
+        "%" => (Some("%"), None, None),
+        _ => (None, None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("30s"), (Some("s"), None, Some(30_000.0)));
+        assert_eq!(normalize("1m"), (Some("min"), None, Some(60_000.0)));
+        assert_eq!(
+            normalize("512mb"),
+            (Some("MB"), Some(512 * 1024 * 1024), None)
+        );
+        assert_eq!(normalize("85%"), (Some("%"), None, None));
+        assert_eq!(normalize("-1"), (None, None, None));
+        assert_eq!(normalize("true"), (None, None, None));
+    }
+
+    #[test]
+    fn test_is_user_index() {
+        assert!(is_user_index("orders"));
+        assert!(is_user_index(".ds-logs-app-2024.06.01-000001"));
+        assert!(!is_user_index(".kibana_1"));
+    }
+
+    #[test]
+    fn test_table_stats_rates() {
+        let mut index = IndexRow {
+            name: "orders".to_string(),
+            search_count: Some(1_000),
+            search_time_ms: Some(5_000.0),
+            indexing_count: Some(200),
+            indexing_time_ms: Some(400.0),
+            ..Default::default()
+        };
+        let first = table_stats("cluster", std::slice::from_ref(&index), None);
+        assert_eq!(first[0].search_latency_ms, Some(5.0));
+        assert_eq!(first[0].search_rate, None);
+
+        let mut previous = CounterSnapshot::take(std::slice::from_ref(&index));
+        previous.at -= Duration::from_secs(10);
+        index.search_count = Some(1_100);
+        index.search_time_ms = Some(5_200.0);
+        let second = table_stats("cluster", std::slice::from_ref(&index), Some(&previous));
+        assert_eq!(second[0].search_latency_ms, Some(2.0));
+        assert!((second[0].search_rate.unwrap() - 10.0).abs() < 0.1);
+        assert_eq!(second[0].indexing_rate.map(|r| r.round()), Some(0.0));
+        assert_eq!(second[0].indexing_latency_ms, None);
+    }
+}
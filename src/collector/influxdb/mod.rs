@@ -0,0 +1,148 @@
+//! InfluxDB collector, over the v2 HTTP API or the v1 InfluxQL API.
+//!
+//! Collects:
+//! - Buckets (databases on 1.x) with their retention and shard group
+//!   duration, as schemas
+//! - Measurements as tables, with their tag and field keys as columns
+//! - Series cardinality per bucket and per measurement
+//!   (`influxdb.cardinality()` in Flux, `SHOW SERIES EXACT CARDINALITY` in
+//!   InfluxQL), as table stats
+//! - Server configuration (`/api/v2/config`)
+//! - Storage, write and query metrics from the Prometheus `/metrics`
+//!   endpoint
+//!
+//! The API is chosen from the server version, see [`client`]. On 2.x a
+//! read token for the buckets is enough for the schema and cardinality;
+//! the configuration needs an operator token and is skipped otherwise. On
+//! 1.x the user needs `READ` on each database.
+
+mod client;
+pub mod providers;
+mod queries;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, InfluxdbMetadata, Payload};
+use async_trait::async_trait;
+use client::{Api, InfluxClient};
+use tracing::{info, warn};
+
+/// InfluxDB metrics collector
+pub struct InfluxdbCollector {
+    client: InfluxClient,
+    database_url: String,
+    detected_provider: String,
+}
+
+impl InfluxdbCollector {
+    /// Ping the server, pick the API and detect the provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to InfluxDB");
+
+        let client = InfluxClient::connect(database_url).await?;
+        info!(
+            version = client.version().unwrap_or("unknown"),
+            build = client.build().unwrap_or("unknown"),
+            api = client.api().as_str(),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(client.build(), client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            database_url: database_url.to_string(),
+            detected_provider,
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for InfluxdbCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let client = &self.client;
+        let buckets = schema::collect(client).await?;
+        let (schema, table_stats) = schema::schema(&buckets);
+        // Optional: the configuration needs an operator token, and 1.x has
+        // neither endpoint
+        let (settings, setting_details) = match client.api() {
+            Api::V2 => stats::settings(client).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect server configuration");
+                Default::default()
+            }),
+            Api::V1 => Default::default(),
+        };
+        let metrics = stats::metrics(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect runtime metrics");
+            Vec::new()
+        });
+
+        let mut buckets: Vec<_> = buckets.into_iter().map(|b| b.info).collect();
+        stats::fill_series_from_metrics(&mut buckets, &metrics);
+
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Influxdb.to_string(),
+            version: client.version().map(str::to_string),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                client.api().as_str(),
+                client.build(),
+                client.org(),
+                client.host(),
+            ),
+        };
+
+        info!(
+            buckets = buckets.len(),
+            measurements = schema.tables.len(),
+            metrics = metrics.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_table_stats(table_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema)
+            .with_influxdb(InfluxdbMetadata {
+                api: client.api().as_str().to_string(),
+                buckets,
+                metrics,
+            }))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        let result = match self.client.api() {
+            Api::V2 => self.client.get("api/v2/buckets?limit=1").await.map(|_| ()),
+            Api::V1 => self
+                .client
+                .influxql(None, queries::SHOW_DATABASES)
+                .await
+                .map(|_| ()),
+        };
+        result.map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.client.version().map(str::to_string)
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Influxdb
+    }
+}
@@ -0,0 +1,88 @@
+//! InfluxDB provider detection.
+//!
+//! Returns one of:
+//! - `"influxdb_cloud"` — InfluxDB Cloud (TSM-based, `cloud2.influxdata.com`)
+//! - `"influxdb_enterprise"` — InfluxDB Enterprise
+//! - `"generic"` — self-hosted InfluxDB OSS
+//!
+//! The build comes from the `X-Influxdb-Build` header of `/ping`.
+
+use std::collections::HashMap;
+
+pub const PROVIDER_INFLUXDB_CLOUD: &str = "influxdb_cloud";
+pub const PROVIDER_INFLUXDB_ENTERPRISE: &str = "influxdb_enterprise";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// Detect the provider from the hostname, then the build.
+pub fn detect_provider(build: Option<&str>, host: &str) -> &'static str {
+    let host = host.to_lowercase();
+    match build.map(str::to_ascii_lowercase).as_deref() {
+        _ if host.ends_with(".influxdata.com") => PROVIDER_INFLUXDB_CLOUD,
+        Some("cloud") => PROVIDER_INFLUXDB_CLOUD,
+        Some("ent") | Some("enterprise") => PROVIDER_INFLUXDB_ENTERPRISE,
+        _ => PROVIDER_GENERIC,
+    }
+}
+
+/// Provider metadata derivable from the server and hostname.
+pub fn provider_metadata(
+    provider: &str,
+    api: &str,
+    build: Option<&str>,
+    org: Option<&str>,
+    host: &str,
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    out.insert("api".to_string(), api.to_string());
+    if let Some(build) = build {
+        out.insert("build".to_string(), build.to_string());
+    }
+    if let Some(org) = org {
+        out.insert("org".to_string(), org.to_string());
+    }
+
+    // `<region>.<cloud>.cloud2.influxdata.com`
+    let labels: Vec<&str> = host.split('.').collect();
+    if provider == PROVIDER_INFLUXDB_CLOUD && labels.len() == 5 && labels[2] == "cloud2" {
+        out.insert("region".to_string(), labels[0].to_string());
+        out.insert("cloud".to_string(), labels[1].to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(
+            detect_provider(None, "us-east-1-1.aws.cloud2.influxdata.com"),
+            PROVIDER_INFLUXDB_CLOUD
+        );
+        assert_eq!(
+            detect_provider(Some("Cloud"), "influx.internal"),
+            PROVIDER_INFLUXDB_CLOUD
+        );
+        assert_eq!(
+            detect_provider(Some("ENT"), "influx.internal"),
+            PROVIDER_INFLUXDB_ENTERPRISE
+        );
+        assert_eq!(detect_provider(Some("OSS"), "localhost"), PROVIDER_GENERIC);
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let meta = provider_metadata(
+            PROVIDER_INFLUXDB_CLOUD,
+            "v2",
+            Some("Cloud"),
+            Some("acme"),
+            "eu-central-1-1.aws.cloud2.influxdata.com",
+        );
+        assert_eq!(meta.get("region").unwrap(), "eu-central-1-1");
+        assert_eq!(meta.get("cloud").unwrap(), "aws");
+        assert_eq!(meta.get("org").unwrap(), "acme");
+        assert_eq!(meta.get("api").unwrap(), "v2");
+    }
+}
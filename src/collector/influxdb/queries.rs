@@ -0,0 +1,114 @@
+//! Flux and InfluxQL statements used by the InfluxDB collector.
+//!
+//! Flux schema functions default to the last 30 days; every query here
+//! starts at the epoch instead, so that measurements and series still held
+//! by the retention period but no longer written are counted too.
+
+/// Start of every Flux range: everything the bucket holds
+const ALL_TIME: &str = "time(v: 0)";
+
+// ---------- Flux (v2) ----------
+
+pub fn measurements(bucket: &str) -> String {
+    format!(
+        "import \"influxdata/influxdb/schema\"\n\
+         schema.measurements(bucket: {}, start: {ALL_TIME})",
+        string(bucket)
+    )
+}
+
+pub fn tag_keys(bucket: &str, measurement: &str) -> String {
+    format!(
+        "import \"influxdata/influxdb/schema\"\n\
+         schema.measurementTagKeys(bucket: {}, measurement: {}, start: {ALL_TIME})",
+        string(bucket),
+        string(measurement)
+    )
+}
+
+pub fn field_keys(bucket: &str, measurement: &str) -> String {
+    format!(
+        "import \"influxdata/influxdb/schema\"\n\
+         schema.measurementFieldKeys(bucket: {}, measurement: {}, start: {ALL_TIME})",
+        string(bucket),
+        string(measurement)
+    )
+}
+
+/// Series cardinality of a bucket, or of one measurement in it
+pub fn cardinality(bucket: &str, measurement: Option<&str>) -> String {
+    let predicate = measurement
+        .map(|m| format!(", predicate: (r) => r._measurement == {}", string(m)))
+        .unwrap_or_default();
+    format!(
+        "import \"influxdata/influxdb\"\n\
+         influxdb.cardinality(bucket: {}, start: {ALL_TIME}{predicate})",
+        string(bucket)
+    )
+}
+
+/// A Flux string literal
+fn string(value: &str) -> String {
+    format!(
+        "\"{}\"",
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace("${", "\\${")
+    )
+}
+
+// ---------- InfluxQL (v1) ----------
+
+pub const SHOW_DATABASES: &str = "SHOW DATABASES";
+
+pub fn retention_policies(database: &str) -> String {
+    format!("SHOW RETENTION POLICIES ON {}", identifier(database))
+}
+
+pub fn show_measurements(database: &str) -> String {
+    format!("SHOW MEASUREMENTS ON {}", identifier(database))
+}
+
+/// Tag keys of every measurement, one series per measurement
+pub fn show_tag_keys(database: &str) -> String {
+    format!("SHOW TAG KEYS ON {}", identifier(database))
+}
+
+/// Field keys and types of every measurement, one series per measurement
+pub fn show_field_keys(database: &str) -> String {
+    format!("SHOW FIELD KEYS ON {}", identifier(database))
+}
+
+/// Exact series count, one series per measurement
+pub fn series_cardinality(database: &str) -> String {
+    format!("SHOW SERIES EXACT CARDINALITY ON {}", identifier(database))
+}
+
+/// A double-quoted InfluxQL identifier
+fn identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flux_escaping() {
+        assert_eq!(
+            cardinality("tele\"graf", Some("cpu${x}")),
+            "import \"influxdata/influxdb\"\n\
+             influxdb.cardinality(bucket: \"tele\\\"graf\", start: time(v: 0), \
+             predicate: (r) => r._measurement == \"cpu\\${x}\")"
+        );
+    }
+
+    #[test]
+    fn test_influxql_identifier() {
+        assert_eq!(
+            show_field_keys("my \"db\""),
+            "SHOW FIELD KEYS ON \"my \\\"db\\\"\""
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! Buckets, measurements and series cardinality.
+//!
+//! On the v2 API buckets come from `/api/v2/buckets` and everything else
+//! from the Flux `schema` and `influxdb.cardinality` functions, one query
+//! per measurement. On the v1 API databases and their default retention
+//! policy stand in for buckets, and one `SHOW` statement per database
+//! covers all of its measurements.
+//!
+//! Each bucket becomes a schema and each measurement a table, with `time`,
+//! its tag keys and its field keys as columns. Field types are only known
+//! on v1 (`SHOW FIELD KEYS`); Flux reports field names alone.
+
+use super::client::{int, text, Api, InfluxClient, Row, Series};
+use super::queries;
+use super::stats::duration_ms;
+use crate::collector::CollectorError;
+use crate::payload::{ColumnMetadata, InfluxBucket, SchemaMetadata, TableMetadata, TableStats};
+use std::collections::HashMap;
+use tracing::{debug, warn};
+
+/// Buckets fetched per `/api/v2/buckets` call
+const PAGE_SIZE: usize = 100;
+
+/// Measurements per bucket whose keys and cardinality are queried one by
+/// one on v2; the rest are counted but not described
+pub const MAX_MEASUREMENTS_PER_BUCKET: usize = 500;
+
+/// InfluxDB 1.x's own monitoring database
+const INTERNAL_DATABASE: &str = "_internal";
+
+#[derive(Debug, Default)]
+pub(super) struct Bucket {
+    pub info: InfluxBucket,
+    pub measurements: Vec<Measurement>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct Measurement {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// Field key and, on v1, its type
+    pub fields: Vec<(String, Option<String>)>,
+    pub series: Option<i64>,
+}
+
+pub(super) async fn collect(client: &InfluxClient) -> Result<Vec<Bucket>, CollectorError> {
+    match client.api() {
+        Api::V2 => collect_v2(client).await,
+        Api::V1 => collect_v1(client).await,
+    }
+}
+
+// ---------- v2 ----------
+
+async fn collect_v2(client: &InfluxClient) -> Result<Vec<Bucket>, CollectorError> {
+    let mut buckets = Vec::new();
+    for info in list_buckets(client).await? {
+        if client.database().is_some_and(|only| only != info.name) {
+            continue;
+        }
+        let mut bucket = Bucket {
+            info,
+            measurements: Vec::new(),
+        };
+        // System buckets hold the server's own monitoring and task logs
+        if bucket.info.bucket_type.as_deref() != Some("system") {
+            if let Err(e) = describe_bucket(client, &mut bucket).await {
+                warn!(bucket = %bucket.info.name, error = %e, "Failed to describe bucket");
+            }
+        }
+        buckets.push(bucket);
+    }
+    Ok(buckets)
+}
+
+async fn list_buckets(client: &InfluxClient) -> Result<Vec<InfluxBucket>, CollectorError> {
+    let mut out = Vec::new();
+    loop {
+        let query = {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            query
+                .append_pair("limit", &PAGE_SIZE.to_string())
+                .append_pair("offset", &out.len().to_string());
+            if let Some(org) = client.org() {
+                // Organization IDs are 16 hex digits; anything else is a name
+                let key = if is_id(org) { "orgID" } else { "org" };
+                query.append_pair(key, org);
+            }
+            query.finish()
+        };
+        let response = client.get(&format!("api/v2/buckets?{query}")).await?;
+        let page: Vec<InfluxBucket> = response["buckets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|bucket| {
+                let rule = bucket["retentionRules"]
+                    .as_array()
+                    .and_then(|rules| rules.iter().find(|r| r["type"] == "expire"));
+                InfluxBucket {
+                    name: text(&bucket["name"]).unwrap_or_default(),
+                    id: text(&bucket["id"]),
+                    org_id: text(&bucket["orgID"]),
+                    bucket_type: text(&bucket["type"]),
+                    // `everySeconds: 0` keeps data forever
+                    retention_seconds: rule
+                        .and_then(|r| int(&r["everySeconds"]))
+                        .filter(|s| *s > 0),
+                    shard_group_duration_seconds: rule
+                        .and_then(|r| int(&r["shardGroupDurationSeconds"]))
+                        .filter(|s| *s > 0),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let done = page.len() < PAGE_SIZE;
+        out.extend(page);
+        if done {
+            return Ok(out);
+        }
+    }
+}
+
+async fn describe_bucket(client: &InfluxClient, bucket: &mut Bucket) -> Result<(), CollectorError> {
+    let name = bucket.info.name.clone();
+    let org_id = bucket.info.org_id.clone().unwrap_or_default();
+    debug!(bucket = %name, "Describing bucket");
+
+    let names = values(client.flux(&org_id, &queries::measurements(&name)).await?);
+    bucket.info.measurement_count = Some(names.len() as i64);
+    if names.len() > MAX_MEASUREMENTS_PER_BUCKET {
+        warn!(
+            bucket = %name,
+            measurements = names.len(),
+            limit = MAX_MEASUREMENTS_PER_BUCKET,
+            "Bucket has too many measurements; describing the first ones only"
+        );
+    }
+
+    // Cardinality is optional: an error skips it for the whole bucket
+    let mut with_cardinality = true;
+    match client
+        .flux(&org_id, &queries::cardinality(&name, None))
+        .await
+    {
+        Ok(rows) => bucket.info.series_cardinality = first_int(&rows),
+        Err(e) => {
+            warn!(bucket = %name, error = %e, "Failed to collect series cardinality");
+            with_cardinality = false;
+        }
+    }
+
+    for measurement in names.into_iter().take(MAX_MEASUREMENTS_PER_BUCKET) {
+        let tags = values(
+            client
+                .flux(&org_id, &queries::tag_keys(&name, &measurement))
+                .await?,
+        )
+        .into_iter()
+        // `_start`, `_stop`, `_measurement` and `_field` are not tags
+        .filter(|key| !key.starts_with('_'))
+        .collect();
+        let fields = values(
+            client
+                .flux(&org_id, &queries::field_keys(&name, &measurement))
+                .await?,
+        )
+        .into_iter()
+        .map(|key| (key, None))
+        .collect();
+        let series = if with_cardinality {
+            client
+                .flux(&org_id, &queries::cardinality(&name, Some(&measurement)))
+                .await
+                .ok()
+                .and_then(|rows| first_int(&rows))
+        } else {
+            None
+        };
+        bucket.measurements.push(Measurement {
+            name: measurement,
+            tags,
+            fields,
+            series,
+        });
+    }
+    Ok(())
+}
+
+fn is_id(value: &str) -> bool {
+    value.len() == 16 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// The `_value` column of Flux rows
+fn values(rows: Vec<Row>) -> Vec<String> {
+    rows.into_iter()
+        .filter_map(|mut row| row.remove("_value"))
+        .collect()
+}
+
+fn first_int(rows: &[Row]) -> Option<i64> {
+    rows.first()?.get("_value")?.parse().ok()
+}
+
+// ---------- v1 ----------
+
+async fn collect_v1(client: &InfluxClient) -> Result<Vec<Bucket>, CollectorError> {
+    let databases = match client.database() {
+        Some(database) => vec![database.to_string()],
+        None => client
+            .influxql(None, queries::SHOW_DATABASES)
+            .await?
+            .into_iter()
+            .flat_map(|series| series.rows)
+            .filter_map(|mut row| row.remove("name"))
+            .filter(|name| name != INTERNAL_DATABASE)
+            .collect(),
+    };
+
+    let mut buckets = Vec::new();
+    for database in databases {
+        debug!(database = %database, "Describing database");
+        let mut bucket = Bucket {
+            info: InfluxBucket {
+                name: database.clone(),
+                ..Default::default()
+            },
+            measurements: Vec::new(),
+        };
+
+        let policies = client
+            .influxql(None, &queries::retention_policies(&database))
+            .await?;
+        if let Some(policy) = default_policy(&policies) {
+            bucket.info.retention_policy = policy.get("name").cloned();
+            bucket.info.retention_seconds = policy
+                .get("duration")
+                .and_then(|d| duration_ms(d))
+                .map(|ms| (ms / 1000.0) as i64)
+                .filter(|s| *s > 0);
+            bucket.info.shard_group_duration_seconds = policy
+                .get("shardGroupDuration")
+                .and_then(|d| duration_ms(d))
+                .map(|ms| (ms / 1000.0) as i64)
+                .filter(|s| *s > 0);
+        }
+
+        let measurements = client
+            .influxql(None, &queries::show_measurements(&database))
+            .await?;
+        let tags = client
+            .influxql(None, &queries::show_tag_keys(&database))
+            .await?;
+        let fields = client
+            .influxql(None, &queries::show_field_keys(&database))
+            .await?;
+        // Optional: not available through the 2.x compatibility API
+        let cardinality = client
+            .influxql(None, &queries::series_cardinality(&database))
+            .await
+            .unwrap_or_else(|e| {
+                warn!(database = %database, error = %e, "Failed to collect series cardinality");
+                Vec::new()
+            });
+
+        bucket.measurements = v1_measurements(&measurements, &tags, &fields, &cardinality);
+        bucket.info.measurement_count = Some(bucket.measurements.len() as i64);
+        bucket.info.series_cardinality = (!cardinality.is_empty())
+            .then(|| bucket.measurements.iter().filter_map(|m| m.series).sum());
+        buckets.push(bucket);
+    }
+    Ok(buckets)
+}
+
+/// The policy marked `default`, or the only one
+fn default_policy(series: &[Series]) -> Option<&Row> {
+    let rows = &series.first()?.rows;
+    rows.iter()
+        .find(|row| row.get("default").map(String::as_str) == Some("true"))
+        .or_else(|| rows.first())
+}
+
+fn v1_measurements(
+    measurements: &[Series],
+    tags: &[Series],
+    fields: &[Series],
+    cardinality: &[Series],
+) -> Vec<Measurement> {
+    let by_measurement = |series: &[Series], column: &str| -> HashMap<String, Vec<Row>> {
+        series
+            .iter()
+            .map(|s| (s.name.clone(), s.rows.clone()))
+            .filter(|(_, rows)| rows.iter().any(|row| row.contains_key(column)))
+            .collect()
+    };
+    let tags = by_measurement(tags, "tagKey");
+    let fields = by_measurement(fields, "fieldKey");
+    let counts = by_measurement(cardinality, "count");
+
+    measurements
+        .iter()
+        .flat_map(|series| series.rows.iter())
+        .filter_map(|row| row.get("name"))
+        .map(|name| Measurement {
+            name: name.clone(),
+            tags: tags
+                .get(name)
+                .into_iter()
+                .flatten()
+                .filter_map(|row| row.get("tagKey").cloned())
+                .collect(),
+            fields: fields
+                .get(name)
+                .into_iter()
+                .flatten()
+                .filter_map(|row| {
+                    Some((row.get("fieldKey")?.clone(), row.get("fieldType").cloned()))
+                })
+                .collect(),
+            series: counts
+                .get(name)
+                .and_then(|rows| rows.first())
+                .and_then(|row| row.get("count")?.parse().ok()),
+        })
+        .collect()
+}
+
+// ---------- Mapping ----------
+
+/// Measurements as tables and their series counts as table stats
+pub(super) fn schema(buckets: &[Bucket]) -> (SchemaMetadata, Vec<TableStats>) {
+    let mut schema = SchemaMetadata::default();
+    let mut table_stats = Vec::new();
+
+    for bucket in buckets {
+        for measurement in &bucket.measurements {
+            let mut columns = vec![ColumnMetadata {
+                name: "time".to_string(),
+                data_type: "timestamp".to_string(),
+                nullable: false,
+                ..Default::default()
+            }];
+            columns.extend(measurement.tags.iter().map(|tag| ColumnMetadata {
+                name: tag.clone(),
+                data_type: "string".to_string(),
+                native_type: Some("tag".to_string()),
+                nullable: true,
+                ..Default::default()
+            }));
+            columns.extend(
+                measurement
+                    .fields
+                    .iter()
+                    .map(|(field, field_type)| ColumnMetadata {
+                        name: field.clone(),
+                        data_type: field_type.clone().unwrap_or_else(|| "unknown".to_string()),
+                        native_type: Some("field".to_string()),
+                        nullable: true,
+                        ..Default::default()
+                    }),
+            );
+            for (i, column) in columns.iter_mut().enumerate() {
+                column.position = i as i32 + 1;
+            }
+
+            schema.tables.push(TableMetadata {
+                schema: bucket.info.name.clone(),
+                name: measurement.name.clone(),
+                columns,
+                ..Default::default()
+            });
+            table_stats.push(TableStats {
+                schema: bucket.info.name.clone(),
+                table: measurement.name.clone(),
+                series_cardinality: measurement.series,
+                ..Default::default()
+            });
+        }
+    }
+    (schema, table_stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(name: &str, rows: &[&[(&str, &str)]]) -> Series {
+        Series {
+            name: name.to_string(),
+            rows: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_v1_measurements() {
+        let measurements = [series(
+            "measurements",
+            &[&[("name", "cpu")], &[("name", "mem")]],
+        )];
+        let tags = [series(
+            "cpu",
+            &[&[("tagKey", "host")], &[("tagKey", "region")]],
+        )];
+        let fields = [
+            series(
+                "cpu",
+                &[&[("fieldKey", "usage_idle"), ("fieldType", "float")]],
+            ),
+            series("mem", &[&[("fieldKey", "used"), ("fieldType", "integer")]]),
+        ];
+        let cardinality = [series("cpu", &[&[("count", "1200")]])];
+
+        let out = v1_measurements(&measurements, &tags, &fields, &cardinality);
+        assert_eq!(
+            out[0],
+            Measurement {
+                name: "cpu".to_string(),
+                tags: vec!["host".to_string(), "region".to_string()],
+                fields: vec![("usage_idle".to_string(), Some("float".to_string()))],
+                series: Some(1200),
+            }
+        );
+        assert!(out[1].tags.is_empty());
+        assert_eq!(out[1].series, None);
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policies = [series(
+            "",
+            &[
+                &[
+                    ("name", "autogen"),
+                    ("duration", "0s"),
+                    ("default", "false"),
+                ],
+                &[
+                    ("name", "two_weeks"),
+                    ("duration", "336h0m0s"),
+                    ("default", "true"),
+                ],
+            ],
+        )];
+        assert_eq!(default_policy(&policies).unwrap()["name"], "two_weeks");
+    }
+
+    #[test]
+    fn test_schema() {
+        let buckets = vec![Bucket {
+            info: InfluxBucket {
+                name: "telegraf".to_string(),
+                ..Default::default()
+            },
+            measurements: vec![Measurement {
+                name: "cpu".to_string(),
+                tags: vec!["host".to_string()],
+                fields: vec![("usage_idle".to_string(), None)],
+                series: Some(42),
+            }],
+        }];
+        let (schema, stats) = schema(&buckets);
+        let table = &schema.tables[0];
+        assert_eq!(
+            (table.schema.as_str(), table.name.as_str()),
+            ("telegraf", "cpu")
+        );
+        let columns: Vec<(&str, &str, i32)> = table
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str(), c.position))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("time", "timestamp", 1),
+                ("host", "string", 2),
+                ("usage_idle", "unknown", 3)
+            ]
+        );
+        assert_eq!(stats[0].series_cardinality, Some(42));
+    }
+
+    #[test]
+    fn test_is_id() {
+        assert!(is_id("0123456789abcdef"));
+        assert!(!is_id("acme"));
+    }
+}
@@ -0,0 +1,315 @@
+//! Server settings and runtime metrics.
+//!
+//! Settings come from `/api/v2/config` (InfluxDB 2.x, operator token
+//! only). Runtime metrics come from the Prometheus `/metrics` endpoint,
+//! limited to [`METRICS`] and summed per bucket, since most storage metrics
+//! carry one series per shard.
+
+use super::client::InfluxClient;
+use crate::collector::CollectorError;
+use crate::payload::{InfluxBucket, InfluxMetric, Setting};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use tracing::debug;
+
+const CONFIG: &str = "api/v2/config";
+const METRICS_PATH: &str = "metrics";
+
+/// Metrics reported from `/metrics`
+pub const METRICS: &[&str] = &[
+    // Cardinality
+    "storage_bucket_series_num",
+    "storage_bucket_measurement_num",
+    // Storage
+    "storage_shard_disk_size",
+    "storage_tsm_files_disk_bytes",
+    "storage_wal_size",
+    "storage_cache_inuse_bytes",
+    "storage_compactions_queued",
+    "storage_compactions_failed",
+    // Writes
+    "storage_writer_ok_points",
+    "storage_writer_dropped_points",
+    "storage_writer_err_points",
+    "storage_writer_timeouts",
+    // Queries
+    "qc_executing_active",
+    "qc_queueing_active",
+    "qc_memory_unused_bytes",
+    "qc_requests_total",
+    // Process
+    "influxdb_uptime_seconds",
+    "go_memstats_heap_inuse_bytes",
+    "go_goroutines",
+];
+
+/// Series per bucket, keyed by bucket ID
+const BUCKET_SERIES: &str = "storage_bucket_series_num";
+
+pub(super) async fn settings(
+    client: &InfluxClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting server configuration");
+
+    let Some(response) = client.get_opt(CONFIG).await? else {
+        return Ok(Default::default());
+    };
+    let mut details: Vec<Setting> = response["config"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        // Never report credentials, should a build include any
+        .filter(|(name, _)| !(name.contains("token") || name.contains("password")))
+        .map(|(name, value)| {
+            let (unit, value_bytes, value_ms) = normalize(name, value);
+            Setting {
+                name: name.clone(),
+                value: match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                },
+                unit: unit.map(str::to_string),
+                value_bytes,
+                value_ms,
+                category: name.split('-').next().map(str::to_string),
+                source: Some("config".to_string()),
+                ..Default::default()
+            }
+        })
+        .collect();
+    details.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+/// Durations are Go duration strings (`10m0s`) or integer nanoseconds;
+/// sizes are integer bytes.
+fn normalize(name: &str, value: &Value) -> (Option<&'static str>, Option<i64>, Option<f64>) {
+    match value {
+        Value::String(s) => match duration_ms(s) {
+            Some(ms) => (Some("ms"), None, Some(ms)),
+            None => (None, None, None),
+        },
+        Value::Number(n) => {
+            let Some(n) = n.as_i64() else {
+                return (None, None, None);
+            };
+            if ["timeout", "delay", "duration", "period", "interval"]
+                .iter()
+                .any(|s| name.ends_with(s))
+            {
+                (Some("ms"), None, Some(n as f64 / 1e6))
+            } else if ["-size", "-bytes", "-burst"]
+                .iter()
+                .any(|s| name.ends_with(s))
+            {
+                (Some("B"), Some(n), None)
+            } else {
+                (None, None, None)
+            }
+        }
+        _ => (None, None, None),
+    }
+}
+
+/// Milliseconds in a Go duration such as `168h0m0s`, `1m30s` or `500ms`
+pub(super) fn duration_ms(value: &str) -> Option<f64> {
+    let mut rest = value.trim();
+    match rest {
+        "" => return None,
+        "0" => return Some(0.0),
+        _ => {}
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|i| *i > 0)?;
+        let (number, tail) = rest.split_at(split);
+        let number: f64 = number.parse().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        total += number
+            * match unit {
+                "ns" => 1e-6,
+                "us" | "µs" | "μs" => 1e-3,
+                "ms" => 1.0,
+                "s" => 1_000.0,
+                "m" => 60_000.0,
+                "h" => 3_600_000.0,
+                _ => return None,
+            };
+        rest = tail;
+    }
+    Some(total)
+}
+
+pub(super) async fn metrics(client: &InfluxClient) -> Result<Vec<InfluxMetric>, CollectorError> {
+    debug!("Collecting runtime metrics");
+    Ok(client
+        .get_text(METRICS_PATH)
+        .await?
+        .map(|text| parse_metrics(&text))
+        .unwrap_or_default())
+}
+
+/// [`METRICS`] from the Prometheus text format, summed per bucket
+fn parse_metrics(text: &str) -> Vec<InfluxMetric> {
+    let mut sums: BTreeMap<(String, Option<String>), f64> = BTreeMap::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+        let name = &line[..name_end];
+        if !METRICS.contains(&name) {
+            continue;
+        }
+        let (labels, rest) = match line[name_end..].strip_prefix('{') {
+            Some(labelled) => match labelled.rfind('}') {
+                Some(end) => (&labelled[..end], &labelled[end + 1..]),
+                None => continue,
+            },
+            None => ("", &line[name_end..]),
+        };
+        let Some(value) = rest
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+        else {
+            continue;
+        };
+        *sums
+            .entry((name.to_string(), label(labels, "bucket")))
+            .or_default() += value;
+    }
+    sums.into_iter()
+        .map(|((name, bucket), value)| InfluxMetric {
+            name,
+            bucket,
+            value,
+        })
+        .collect()
+}
+
+/// Value of one label in a `key="value",...` label set
+fn label(labels: &str, key: &str) -> Option<String> {
+    let start = labels
+        .match_indices(&format!("{key}=\""))
+        .find(|(i, _)| *i == 0 || labels.as_bytes()[i - 1] == b',')
+        .map(|(i, m)| i + m.len())?;
+    let mut value = String::new();
+    let mut chars = labels[start..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.extend(chars.next()),
+            '"' => return Some(value),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// Fill in bucket series counts Flux could not provide from
+/// `storage_bucket_series_num`
+pub(super) fn fill_series_from_metrics(buckets: &mut [InfluxBucket], metrics: &[InfluxMetric]) {
+    for bucket in buckets
+        .iter_mut()
+        .filter(|b| b.series_cardinality.is_none())
+    {
+        bucket.series_cardinality = metrics
+            .iter()
+            .find(|m| m.name == BUCKET_SERIES && m.bucket.is_some() && m.bucket == bucket.id)
+            .map(|m| m.value as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_duration_ms() {
+        assert_eq!(duration_ms("0s"), Some(0.0));
+        assert_eq!(duration_ms("168h0m0s"), Some(604_800_000.0));
+        assert_eq!(duration_ms("1m30s"), Some(90_000.0));
+        assert_eq!(duration_ms("500ms"), Some(500.0));
+        assert_eq!(duration_ms("bolt"), None);
+        assert_eq!(duration_ms(""), None);
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("storage-cache-max-memory-size", &json!(1073741824)),
+            (Some("B"), Some(1_073_741_824), None)
+        );
+        assert_eq!(
+            normalize("http-idle-timeout", &json!(180_000_000_000i64)),
+            (Some("ms"), None, Some(180_000.0))
+        );
+        assert_eq!(
+            normalize("storage-retention-check-interval", &json!("30m0s")),
+            (Some("ms"), None, Some(1_800_000.0))
+        );
+        assert_eq!(
+            normalize("query-concurrency", &json!(1024)),
+            (None, None, None)
+        );
+        assert_eq!(normalize("log-level", &json!("info")), (None, None, None));
+    }
+
+    #[test]
+    fn test_parse_metrics() {
+        let text = r#"
+# HELP storage_bucket_series_num Gauge of series cardinality per bucket
+# TYPE storage_bucket_series_num gauge
+storage_bucket_series_num{bucket="0a1b2c3d4e5f6071"} 120000
+storage_shard_disk_size{bucket="0a1b2c3d4e5f6071",engine="tsm1",id="1",path="/a",walPath="/w"} 1000
+storage_shard_disk_size{bucket="0a1b2c3d4e5f6071",engine="tsm1",id="2",path="/b",walPath="/w"} 500
+qc_requests_total{org="o",result="success"} 10
+qc_requests_total{org="o",result="compile_error"} 2
+go_goroutines 42
+http_api_requests_total{path="/api/v2/write"} 99
+"#;
+        let metrics = parse_metrics(text);
+        let find = |name: &str| metrics.iter().find(|m| m.name == name).unwrap();
+        assert_eq!(find("storage_shard_disk_size").value, 1500.0);
+        assert_eq!(
+            find("storage_shard_disk_size").bucket.as_deref(),
+            Some("0a1b2c3d4e5f6071")
+        );
+        assert_eq!(find("qc_requests_total").value, 12.0);
+        assert_eq!(find("go_goroutines").bucket, None);
+        assert!(metrics.iter().all(|m| m.name != "http_api_requests_total"));
+    }
+
+    #[test]
+    fn test_label() {
+        assert_eq!(
+            label(r#"engine="tsm1",bucket="a\"b""#, "bucket").as_deref(),
+            Some("a\"b")
+        );
+        assert_eq!(label(r#"subbucket="x""#, "bucket"), None);
+    }
+
+    #[test]
+    fn test_fill_series_from_metrics() {
+        let mut buckets = vec![InfluxBucket {
+            name: "telegraf".to_string(),
+            id: Some("0a1b2c3d4e5f6071".to_string()),
+            ..Default::default()
+        }];
+        let metrics = parse_metrics("storage_bucket_series_num{bucket=\"0a1b2c3d4e5f6071\"} 7\n");
+        fill_series_from_metrics(&mut buckets, &metrics);
+        assert_eq!(buckets[0].series_cardinality, Some(7));
+    }
+}
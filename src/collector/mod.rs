@@ -5,19 +5,43 @@
 //!
 //! # Supported Databases
 //!
-//! - **PostgreSQL** - Full support (pg_stat_statements, pg_stat_user_tables, etc.)
+//! - **PostgreSQL** - Full support (pg_stat_statements, pg_stat_user_tables, etc.),
+//!   including TimescaleDB, pgvector, CockroachDB, YugabyteDB and Redshift
 //! - **MySQL** - Planned (performance_schema, information_schema)
-//! - **MongoDB** - Planned (serverStatus, dbStats)
+//! - **MongoDB** - sampled collection schemas, indexes and collection stats
+//! - **SQL Server** - Query Store / DMVs, `sys.*` catalog views
+//! - **Oracle** - `V$SQLSTATS`, `DBA_*` dictionary views, parameters and
+//!   wait events over ODBC (`oracle` feature)
+//! - **ClickHouse** - `system.*` tables over the HTTP interface
+//! - **Couchbase** - buckets, collections, GSI indexes and completed requests
+//!   over REST and N1QL
+//! - **Elasticsearch / OpenSearch** - cluster, index and node stats, mappings
+//! - **Neo4j / Memgraph** - labels and relationship types as tables, indexes,
+//!   constraints and settings over Bolt
+//! - **InfluxDB** - buckets, measurements and series cardinality over the
+//!   v2 API or InfluxQL
+//! - **Qdrant / Weaviate / Milvus / Chroma** - collections, vector fields and
+//!   ANN index parameters over their HTTP APIs
 //!
 //! # Adding a New Database
 //!
 //! See `docs/EXTENDING.md` for a complete guide on adding support for new databases.
 
+pub mod clickhouse;
+pub mod couchbase;
+pub mod elasticsearch;
+pub mod influxdb;
 pub mod mongodb;
+pub mod neo4j;
+#[cfg(feature = "oracle")]
+pub mod oracle;
 pub mod postgres;
+pub mod schema;
+pub mod sqlserver;
+pub mod vector;
 // pub mod mysql;    // Coming soon
 
-use crate::config::DatabaseType;
+use crate::config::{CollectionConfig, DatabaseType};
 use crate::payload::Payload;
 use async_trait::async_trait;
 use thiserror::Error;
@@ -64,6 +88,82 @@ impl From<sqlx::Error> for CollectorError {
     }
 }
 
+impl From<tiberius::error::Error> for CollectorError {
+    fn from(err: tiberius::error::Error) -> Self {
+        use tiberius::error::Error;
+        match err {
+            Error::Server(token) => match token.code() {
+                // Login failed; permission denied on object, database or
+                // server state
+                18456 | 229 | 262 | 297 | 300 => CollectorError::PermissionError(token.to_string()),
+                _ => CollectorError::QueryError(token.to_string()),
+            },
+            Error::Io { .. } | Error::Tls(_) | Error::Routing { .. } => {
+                CollectorError::ConnectionError(err.to_string())
+            }
+            _ => CollectorError::InternalError(err.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for CollectorError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_connect() || err.is_timeout() || err.is_request() {
+            CollectorError::ConnectionError(err.to_string())
+        } else if err.is_decode() {
+            CollectorError::QueryError(err.to_string())
+        } else {
+            CollectorError::InternalError(err.to_string())
+        }
+    }
+}
+
+impl From<neo4rs::Error> for CollectorError {
+    fn from(err: neo4rs::Error) -> Self {
+        use neo4rs::{Error, Neo4jClientErrorKind, Neo4jErrorKind};
+        match err {
+            Error::Neo4j(e) => match e.kind() {
+                Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(_)) => {
+                    CollectorError::PermissionError(e.message().to_string())
+                }
+                _ => CollectorError::QueryError(format!("{}: {}", e.code(), e.message())),
+            },
+            Error::AuthenticationError(msg) => CollectorError::PermissionError(msg),
+            Error::IOError { .. }
+            | Error::ConnectionError
+            | Error::UrlParseError(_)
+            | Error::UnsupportedScheme(_)
+            | Error::InvalidDnsName(_) => CollectorError::ConnectionError(err.to_string()),
+            Error::DeserializationError(_) => CollectorError::QueryError(err.to_string()),
+            _ => CollectorError::InternalError(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "oracle")]
+impl From<odbc_api::Error> for CollectorError {
+    fn from(err: odbc_api::Error) -> Self {
+        let odbc_api::Error::Diagnostics { record, .. } = &err else {
+            return CollectorError::InternalError(err.to_string());
+        };
+        match record.native_error {
+            // Table or view does not exist (no grant on a dictionary view),
+            // insufficient privileges, invalid login, no CREATE SESSION,
+            // account locked
+            942 | 1031 | 1017 | 1045 | 28000 => CollectorError::PermissionError(record.to_string()),
+            // End-of-file on communication channel, not connected, broken
+            // connection, and the TNS errors
+            3113 | 3114 | 3135 | 12150..=12699 => {
+                CollectorError::ConnectionError(record.to_string())
+            }
+            _ if record.state.as_str().starts_with("08") => {
+                CollectorError::ConnectionError(record.to_string())
+            }
+            _ => CollectorError::QueryError(record.to_string()),
+        }
+    }
+}
+
 /// Trait for database metrics collectors
 ///
 /// Implementations of this trait collect metrics from specific database types
@@ -135,9 +235,23 @@ pub trait Collector: Send + Sync {
 ///
 /// # Supported Database Types
 ///
-/// - `postgres://` or `postgresql://` - PostgreSQL
+/// - `postgres://` or `postgresql://` - PostgreSQL, and the compatible
+///   TimescaleDB, CockroachDB, YugabyteDB and Redshift
 /// - `mysql://` or `mariadb://` - MySQL (coming soon)
-/// - `mongodb://` or `mongodb+srv://` - MongoDB (coming soon)
+/// - `mongodb://` or `mongodb+srv://` - MongoDB, including Atlas and
+///   DocumentDB
+/// - `sqlserver://` or `mssql://` - SQL Server, including Azure SQL
+/// - `oracle://` - Oracle, including Autonomous Database (`oracle` feature)
+/// - `clickhouse://` - ClickHouse, including ClickHouse Cloud
+/// - `elasticsearch://` or `opensearch://` - Elasticsearch and OpenSearch,
+///   including Elastic Cloud and Amazon OpenSearch Service
+/// - `couchbase://` or `couchbases://` - Couchbase, including Capella
+/// - `influxdb://` - InfluxDB, including InfluxDB Cloud, over the v2 API or
+///   InfluxQL
+/// - `qdrant://`, `weaviate://`, `milvus://` or `chroma://` - vector stores,
+///   also detected from their hostnames over `http(s)://`
+/// - `bolt://` or `neo4j://` (and their `+s` variants) - Neo4j, including
+///   AuraDB, and Memgraph
 ///
 /// # Example
 ///
@@ -152,6 +266,16 @@ pub trait Collector: Send + Sync {
 pub async fn create_collector(
     database_url: &str,
     provider: crate::config::Provider,
+) -> Result<Box<dyn Collector>, CollectorError> {
+    create_collector_with_config(database_url, provider, &CollectionConfig::default()).await
+}
+
+/// Like [`create_collector`], but applies the per-engine options from the
+/// `collection` section of the agent configuration.
+pub async fn create_collector_with_config(
+    database_url: &str,
+    provider: crate::config::Provider,
+    collection: &CollectionConfig,
 ) -> Result<Box<dyn Collector>, CollectorError> {
     // Detect database type from URL
     let db_type = DatabaseType::from_url(database_url)
@@ -160,36 +284,51 @@ pub async fn create_collector(
     match db_type {
         // Implemented
         DatabaseType::Postgres => {
-            let collector = postgres::PostgresCollector::new(database_url, provider).await?;
+            let collector = postgres::PostgresCollector::new(database_url, provider)
+                .await?
+                .with_options(collection.postgres.clone());
             Ok(Box::new(collector))
         }
 
         // PostgreSQL-compatible (can use PostgreSQL collector)
         DatabaseType::Timescaledb | DatabaseType::Cockroachdb | DatabaseType::Yugabytedb => {
-            // These are PostgreSQL-compatible, use the Postgres collector
-            let collector = postgres::PostgresCollector::new(database_url, provider).await?;
+            // The Postgres collector detects CockroachDB and YugabyteDB from
+            // `version()` and switches to their statistics tables
+            let collector = postgres::PostgresCollector::new(database_url, provider)
+                .await?
+                .with_options(collection.postgres.clone());
             Ok(Box::new(collector))
         }
 
         DatabaseType::Redshift => {
-            // Redshift is PostgreSQL-compatible with some limitations
-            let collector = postgres::PostgresCollector::new(database_url, provider).await?;
+            // Redshift speaks the Postgres protocol; the collector detects it
+            // and reads the Redshift system views instead
+            let collector = postgres::PostgresCollector::new(database_url, provider)
+                .await?
+                .with_options(collection.postgres.clone());
             Ok(Box::new(collector))
         }
 
-        // Coming soon - Relational
-        DatabaseType::Mysql | DatabaseType::Tidb => Err(CollectorError::UnsupportedDatabase(
-            "MySQL/TiDB support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        DatabaseType::Sqlserver => {
+            let collector = sqlserver::SqlServerCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
-        DatabaseType::Sqlserver => Err(CollectorError::UnsupportedDatabase(
-            "SQL Server support is coming soon. See docs/EXTENDING.md for contribution guide."
+        #[cfg(feature = "oracle")]
+        DatabaseType::Oracle => {
+            let collector = oracle::OracleCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
+
+        #[cfg(not(feature = "oracle"))]
+        DatabaseType::Oracle => Err(CollectorError::UnsupportedDatabase(
+            "Oracle support is not included in this build; build with `--features oracle`."
                 .to_string(),
         )),
 
-        DatabaseType::Oracle => Err(CollectorError::UnsupportedDatabase(
-            "Oracle support is coming soon. See docs/EXTENDING.md for contribution guide."
+        // Coming soon - Relational
+        DatabaseType::Mysql | DatabaseType::Tidb => Err(CollectorError::UnsupportedDatabase(
+            "MySQL/TiDB support is coming soon. See docs/EXTENDING.md for contribution guide."
                 .to_string(),
         )),
 
@@ -204,27 +343,29 @@ pub async fn create_collector(
             Ok(Box::new(collector))
         }
 
-        DatabaseType::Couchbase => Err(CollectorError::UnsupportedDatabase(
-            "Couchbase support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        DatabaseType::Couchbase => {
+            let collector = couchbase::CouchbaseCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
         DatabaseType::Cosmosdb => Err(CollectorError::UnsupportedDatabase(
             "Azure Cosmos DB support is coming soon. See docs/EXTENDING.md for contribution guide."
                 .to_string(),
         )),
 
-        // Coming soon - Analytics
-        DatabaseType::Elasticsearch => Err(CollectorError::UnsupportedDatabase(
-            "Elasticsearch support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        // Analytics & search
+        DatabaseType::Elasticsearch => {
+            let collector =
+                elasticsearch::ElasticsearchCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
-        DatabaseType::Clickhouse => Err(CollectorError::UnsupportedDatabase(
-            "ClickHouse support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        DatabaseType::Clickhouse => {
+            let collector = clickhouse::ClickHouseCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
+        // Coming soon - Analytics
         DatabaseType::Snowflake => Err(CollectorError::UnsupportedDatabase(
             "Snowflake support is coming soon. See docs/EXTENDING.md for contribution guide."
                 .to_string(),
@@ -246,49 +387,41 @@ pub async fn create_collector(
                 .to_string(),
         )),
 
-        // Coming soon - Time-series
-        DatabaseType::Influxdb => Err(CollectorError::UnsupportedDatabase(
-            "InfluxDB support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        // Time-series
+        DatabaseType::Influxdb => {
+            let collector = influxdb::InfluxdbCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
-        // Coming soon - Vector databases
+        // Vector databases
         DatabaseType::Pinecone => Err(CollectorError::UnsupportedDatabase(
             "Pinecone support is coming soon. See docs/EXTENDING.md for contribution guide."
                 .to_string(),
         )),
 
-        DatabaseType::Milvus => Err(CollectorError::UnsupportedDatabase(
-            "Milvus support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
-
-        DatabaseType::Weaviate => Err(CollectorError::UnsupportedDatabase(
-            "Weaviate support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
-
-        DatabaseType::Qdrant => Err(CollectorError::UnsupportedDatabase(
-            "Qdrant support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
-
-        DatabaseType::Chroma => Err(CollectorError::UnsupportedDatabase(
-            "Chroma support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        DatabaseType::Qdrant
+        | DatabaseType::Weaviate
+        | DatabaseType::Milvus
+        | DatabaseType::Chroma => {
+            let collector = vector::VectorCollector::new(database_url, db_type, provider).await?;
+            Ok(Box::new(collector))
+        }
 
         DatabaseType::Pgvector => {
-            // pgvector is a PostgreSQL extension, use PostgreSQL collector
-            let collector = postgres::PostgresCollector::new(database_url, provider).await?;
+            // pgvector is a PostgreSQL extension; the Postgres collector picks
+            // it up wherever it is installed
+            let collector = postgres::PostgresCollector::new(database_url, provider)
+                .await?
+                .with_options(collection.postgres.clone());
             Ok(Box::new(collector))
         }
 
-        // Coming soon - Graph databases
-        DatabaseType::Neo4j => Err(CollectorError::UnsupportedDatabase(
-            "Neo4j support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
+        // Graph databases
+        DatabaseType::Neo4j | DatabaseType::Memgraph => {
+            // Both speak Bolt; the collector tells them apart at runtime
+            let collector = neo4j::Neo4jCollector::new(database_url, provider).await?;
+            Ok(Box::new(collector))
+        }
 
         DatabaseType::Neptune => Err(CollectorError::UnsupportedDatabase(
             "AWS Neptune support is coming soon. See docs/EXTENDING.md for contribution guide."
@@ -314,11 +447,6 @@ pub async fn create_collector(
             "Dgraph support is coming soon. See docs/EXTENDING.md for contribution guide."
                 .to_string(),
         )),
-
-        DatabaseType::Memgraph => Err(CollectorError::UnsupportedDatabase(
-            "Memgraph support is coming soon. See docs/EXTENDING.md for contribution guide."
-                .to_string(),
-        )),
     }
 }
 
@@ -2,15 +2,16 @@
 //!
 //! Unlike the Postgres collector, this one is built around document sampling
 //! rather than catalog queries. The MongoDB world is schemaless, so the
-//! `SchemaWalker` (in [`schema`]) infers per-field type variance, presence
-//! rates, and value distributions from a bounded random sample of each
-//! collection.
+//! shared `SchemaWalker` (in [`crate::collector::schema`], walking BSON
+//! through [`schema`]) infers per-field type variance, presence rates, and
+//! value distributions from a bounded random sample of each collection.
 
 pub mod bson_type;
 pub mod providers;
 pub mod schema;
 pub mod stats;
 
+use crate::collector::schema::{pick_sample_size, SchemaWalker};
 use crate::collector::{Collector, CollectorError};
 use crate::config::{DatabaseType, Provider};
 use crate::payload::{DatabaseInfo, IndexMetadata, Payload, SchemaMetadata, TableMetadata};
@@ -19,7 +20,6 @@ use futures::StreamExt;
 use mongodb::bson::{doc, Document};
 use mongodb::options::ClientOptions;
 use mongodb::{Client, Database};
-use schema::SchemaWalker;
 
 /// MongoDB metadata collector — connects to a single database extracted from
 /// the connection URL, samples each collection, and emits a [`Payload`].
@@ -126,7 +126,7 @@ impl MongoCollector {
             });
             let is_capped = stats_doc.as_ref().and_then(|s| s.get_bool("capped").ok());
 
-            let sample_size = schema::pick_sample_size(count.unwrap_or(0));
+            let sample_size = pick_sample_size(count.unwrap_or(0));
             let coll = self.database.collection::<Document>(&coll_name);
 
             let mut walker = SchemaWalker::new();
@@ -162,6 +162,7 @@ impl MongoCollector {
                 is_capped,
                 is_view: None,
                 is_timeseries: None,
+                ..Default::default()
             });
 
             // Indexes — pull names + size from stats_doc.indexSizes
@@ -205,6 +206,7 @@ impl MongoCollector {
                                     is_unique,
                                     is_primary,
                                     size_bytes,
+                                    ..Default::default()
                                 });
                             }
                             Err(err) => {
@@ -219,7 +221,11 @@ impl MongoCollector {
             }
         }
 
-        Ok(SchemaMetadata { tables, indexes })
+        Ok(SchemaMetadata {
+            tables,
+            indexes,
+            ..Default::default()
+        })
     }
 }
 
@@ -31,22 +31,11 @@ pub async fn collect_table_stats(
                 out.push(TableStats {
                     schema: db_name.clone(),
                     table: name.clone(),
-                    seq_scan: None,
-                    seq_tup_read: None,
-                    idx_scan: None,
-                    idx_tup_fetch: None,
-                    n_tup_ins: None,
-                    n_tup_upd: None,
-                    n_tup_del: None,
                     n_live_tup: stats
                         .get_i64("count")
                         .ok()
                         .or_else(|| stats.get_i32("count").ok().map(i64::from)),
-                    n_dead_tup: None,
-                    last_vacuum: None,
-                    last_autovacuum: None,
-                    last_analyze: None,
-                    last_autoanalyze: None,
+                    ..Default::default()
                 });
             }
             Err(err) => {
@@ -93,8 +82,7 @@ pub async fn collect_index_stats(
                 table: coll_name.clone(),
                 index: name,
                 idx_scan: ops,
-                idx_tup_read: None,
-                idx_tup_fetch: None,
+                ..Default::default()
             });
         }
     }
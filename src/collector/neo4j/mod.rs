@@ -0,0 +1,162 @@
+//! Neo4j / Memgraph collector, over Bolt with `neo4rs`.
+//!
+//! Collects:
+//! - Node labels and relationship types as tables, with their properties as
+//!   columns (`db.schema.nodeTypeProperties()` / `relTypeProperties()`, or
+//!   Memgraph's `schema` module)
+//! - Indexes and constraints (`SHOW INDEXES` / `SHOW CONSTRAINTS`, or
+//!   `SHOW INDEX INFO` / `SHOW CONSTRAINT INFO` on Memgraph), with index
+//!   read counts on Neo4j 5
+//! - Node and relationship counts per label and type (Neo4j)
+//! - Query statistics from `db.stats` where a collection is running (Neo4j)
+//! - Server settings
+//!
+//! The engine is detected at runtime, since both accept the same URLs; see
+//! [`providers`]. The Neo4j user needs the `reader` role on the database
+//! and `SHOW INDEX`, `SHOW CONSTRAINT` and `SHOW SETTING` privileges (all
+//! granted to `PUBLIC` by default except `SHOW SETTING`). Query statistics
+//! additionally need an admin to run `CALL db.stats.collect('QUERIES')`.
+
+mod connection;
+pub mod providers;
+mod queries;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, Payload};
+use async_trait::async_trait;
+use connection::{text, GraphClient};
+use providers::{Engine, ServerInfo};
+use tracing::{info, warn};
+
+/// Neo4j / Memgraph metrics collector
+pub struct Neo4jCollector {
+    client: GraphClient,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+    server_info: ServerInfo,
+}
+
+impl Neo4jCollector {
+    /// Connect, tell Neo4j from Memgraph and detect the provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to Neo4j / Memgraph");
+
+        let client = GraphClient::connect(database_url).await?;
+        let (server_info, version) = match client.fetch_all(queries::NEO4J_COMPONENTS).await {
+            Ok(rows) => {
+                let kernel = rows.first();
+                (
+                    ServerInfo {
+                        engine: Engine::Neo4j,
+                        edition: kernel.and_then(|row| text(row, "edition")),
+                    },
+                    kernel.and_then(|row| text(row, "version")),
+                )
+            }
+            // Memgraph has no `dbms` procedures
+            Err(CollectorError::QueryError(_)) => {
+                let rows = client.fetch_all(queries::MEMGRAPH_VERSION).await?;
+                (
+                    ServerInfo {
+                        engine: Engine::Memgraph,
+                        edition: None,
+                    },
+                    rows.first().and_then(|row| text(row, "version")),
+                )
+            }
+            Err(e) => return Err(e),
+        };
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            engine = %server_info.engine.database_type(),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(&server_info, client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+            server_info,
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for Neo4jCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let client = &self.client;
+        let engine = self.server_info.engine;
+        let (schema, index_stats) = schema::collect(client, engine).await?;
+        let (settings, setting_details) = stats::settings(client, engine).await?;
+        let table_stats = stats::table_stats(&schema.tables);
+        // Optional: `db.stats` is Neo4j only and needs the admin role
+        let query_stats = match engine {
+            Engine::Neo4j => stats::query_stats(client).await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect query statistics");
+                Vec::new()
+            }),
+            Engine::Memgraph => Vec::new(),
+        };
+
+        let database_info = DatabaseInfo {
+            database_type: engine.database_type().to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                &self.server_info,
+                client.host(),
+            ),
+        };
+
+        info!(
+            tables = schema.tables.len(),
+            indexes = schema.indexes.len(),
+            queries = query_stats.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_query_stats(query_stats)
+            .with_table_stats(table_stats)
+            .with_index_stats(index_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        self.client
+            .fetch_all("RETURN 1")
+            .await
+            .map(|_| ())
+            .map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        self.server_info.engine.database_type()
+    }
+}
@@ -0,0 +1,110 @@
+//! Neo4j / Memgraph engine and provider detection.
+//!
+//! Returns one of:
+//! - `"neo4j_aura"` — Neo4j AuraDB
+//! - `"memgraph"` — self-managed Memgraph
+//! - `"generic"` — self-managed Neo4j
+//!
+//! Both engines speak Bolt and accept the same URLs, so the engine is told
+//! apart at runtime: Neo4j answers `dbms.components()`, Memgraph only
+//! `SHOW VERSION`. Aura is recognized by hostname.
+
+use crate::config::DatabaseType;
+use std::collections::HashMap;
+
+pub const PROVIDER_NEO4J_AURA: &str = "neo4j_aura";
+pub const PROVIDER_MEMGRAPH: &str = "memgraph";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+const AURA_SUFFIX: &str = ".databases.neo4j.io";
+
+/// Graph engine behind a Bolt endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Engine {
+    #[default]
+    Neo4j,
+    Memgraph,
+}
+
+impl Engine {
+    pub fn database_type(self) -> DatabaseType {
+        match self {
+            Engine::Neo4j => DatabaseType::Neo4j,
+            Engine::Memgraph => DatabaseType::Memgraph,
+        }
+    }
+}
+
+/// What the server reports about itself
+#[derive(Debug, Default)]
+pub struct ServerInfo {
+    pub engine: Engine,
+    /// `community` or `enterprise` (Neo4j only)
+    pub edition: Option<String>,
+}
+
+/// Detect the provider from the hostname, then the engine.
+pub fn detect_provider(info: &ServerInfo, host: &str) -> &'static str {
+    if host.to_lowercase().ends_with(AURA_SUFFIX) {
+        PROVIDER_NEO4J_AURA
+    } else if info.engine == Engine::Memgraph {
+        PROVIDER_MEMGRAPH
+    } else {
+        PROVIDER_GENERIC
+    }
+}
+
+/// Provider metadata derivable from the server info and hostname.
+pub fn provider_metadata(provider: &str, info: &ServerInfo, host: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    out.insert(
+        "engine".to_string(),
+        info.engine.database_type().to_string(),
+    );
+    if let Some(edition) = &info.edition {
+        out.insert("edition".to_string(), edition.clone());
+    }
+    // `<instance id>.databases.neo4j.io`
+    if provider == PROVIDER_NEO4J_AURA {
+        if let Some((instance_id, _)) = host.split_once('.') {
+            out.insert("instance_id".to_string(), instance_id.to_string());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_provider() {
+        let neo4j = ServerInfo::default();
+        assert_eq!(
+            detect_provider(&neo4j, "abcd1234.databases.neo4j.io"),
+            PROVIDER_NEO4J_AURA
+        );
+        assert_eq!(detect_provider(&neo4j, "graph.internal"), PROVIDER_GENERIC);
+
+        let memgraph = ServerInfo {
+            engine: Engine::Memgraph,
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&memgraph, "graph.internal"),
+            PROVIDER_MEMGRAPH
+        );
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let info = ServerInfo {
+            engine: Engine::Neo4j,
+            edition: Some("enterprise".to_string()),
+        };
+        let meta = provider_metadata(PROVIDER_NEO4J_AURA, &info, "abcd1234.databases.neo4j.io");
+        assert_eq!(meta.get("instance_id").unwrap(), "abcd1234");
+        assert_eq!(meta.get("engine").unwrap(), "neo4j");
+        assert_eq!(meta.get("edition").unwrap(), "enterprise");
+    }
+}
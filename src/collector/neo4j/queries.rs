@@ -0,0 +1,61 @@
+//! Cypher statements for Neo4j and Memgraph.
+//!
+//! Neo4j statements target 4.4 and 5.x; `SHOW ... YIELD *` returns whichever
+//! columns the server version has, and the readers treat all of them as
+//! optional. Memgraph statements target 2.x and later.
+
+// ---------- Server ----------
+
+pub const NEO4J_COMPONENTS: &str = "CALL dbms.components() YIELD name, versions, edition \
+     RETURN name, versions[0] AS version, edition";
+
+pub const MEMGRAPH_VERSION: &str = "SHOW VERSION";
+
+// ---------- Schema ----------
+
+pub const NEO4J_LABELS: &str = "CALL db.labels() YIELD label RETURN label";
+
+pub const NEO4J_RELATIONSHIP_TYPES: &str =
+    "CALL db.relationshipTypes() YIELD relationshipType RETURN relationshipType";
+
+/// One row per label combination and property; labels without properties
+/// come back with a `NULL` property name
+pub const NEO4J_NODE_PROPERTIES: &str = "CALL db.schema.nodeTypeProperties() \
+     YIELD nodeType, nodeLabels, propertyName, propertyTypes, mandatory \
+     RETURN nodeType, nodeLabels, propertyName, propertyTypes, mandatory";
+
+pub const NEO4J_RELATIONSHIP_PROPERTIES: &str = "CALL db.schema.relTypeProperties() \
+     YIELD relType, propertyName, propertyTypes, mandatory \
+     RETURN relType, propertyName, propertyTypes, mandatory";
+
+/// Memgraph's built-in `schema` module mirrors the Neo4j procedures
+pub const MEMGRAPH_NODE_PROPERTIES: &str = "CALL schema.node_type_properties() \
+     YIELD nodeType, nodeLabels, propertyName, propertyTypes, mandatory \
+     RETURN nodeType, nodeLabels, propertyName, propertyTypes, mandatory";
+
+pub const MEMGRAPH_RELATIONSHIP_PROPERTIES: &str = "CALL schema.rel_type_properties() \
+     YIELD relType, propertyName, propertyTypes, mandatory \
+     RETURN relType, propertyName, propertyTypes, mandatory";
+
+pub const NEO4J_INDEXES: &str = "SHOW INDEXES YIELD *";
+
+pub const NEO4J_CONSTRAINTS: &str = "SHOW CONSTRAINTS YIELD *";
+
+pub const MEMGRAPH_INDEXES: &str = "SHOW INDEX INFO";
+
+pub const MEMGRAPH_CONSTRAINTS: &str = "SHOW CONSTRAINT INFO";
+
+// ---------- Statistics ----------
+
+/// Neo4j 5 settings, with defaults and whether each was set explicitly
+pub const NEO4J_SETTINGS: &str = "SHOW SETTINGS YIELD *";
+
+/// Neo4j 4.4 settings
+pub const NEO4J_LIST_CONFIG: &str =
+    "CALL dbms.listConfig() YIELD name, value, dynamic RETURN name, value, dynamic";
+
+pub const MEMGRAPH_CONFIG: &str = "SHOW CONFIG";
+
+/// Queries recorded by `db.stats.collect('QUERIES')`; empty unless a
+/// collection has been started on the server
+pub const NEO4J_QUERY_STATS: &str = "CALL db.stats.retrieve('QUERIES') YIELD data RETURN data";
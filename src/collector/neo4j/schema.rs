@@ -0,0 +1,633 @@
+//! Graph schema as relational metadata.
+//!
+//! Node labels become tables in the `nodes` schema and relationship types
+//! tables in the `relationships` schema, with the properties seen on them
+//! as columns. A node with several labels contributes its properties to
+//! each of them; a property is only `NOT NULL` for a label if every node
+//! carrying the label has it. Indexes and constraints map onto the usual
+//! index and constraint metadata:
+//!
+//! | Graph constraint              | `kind`          |
+//! |-------------------------------|-----------------|
+//! | uniqueness                    | `unique`        |
+//! | node / relationship key       | `primary_key`   |
+//! | property existence            | `not_null`      |
+//! | property type                 | `property_type` |
+//!
+//! Row counts come from Neo4j's count store, which answers a count by label
+//! or relationship type without a scan. Memgraph has no such store, so its
+//! tables carry no row count.
+
+use super::connection::{flag, int, strings, text, GraphClient, Row};
+use super::providers::Engine;
+use super::queries;
+use crate::collector::CollectorError;
+use crate::payload::{
+    ColumnMetadata, ConstraintMetadata, IndexMetadata, IndexStats, SchemaMetadata, TableMetadata,
+};
+use std::collections::BTreeSet;
+use tracing::debug;
+
+pub(super) const NODES: &str = "nodes";
+pub(super) const RELATIONSHIPS: &str = "relationships";
+
+pub(super) async fn collect(
+    client: &GraphClient,
+    engine: Engine,
+) -> Result<(SchemaMetadata, Vec<IndexStats>), CollectorError> {
+    debug!("Collecting graph schema");
+
+    let (node_rows, relationship_rows, labels, relationship_types) = match engine {
+        Engine::Neo4j => (
+            client.fetch_all(queries::NEO4J_NODE_PROPERTIES).await?,
+            client
+                .fetch_all(queries::NEO4J_RELATIONSHIP_PROPERTIES)
+                .await?,
+            column(client, queries::NEO4J_LABELS, "label").await?,
+            column(
+                client,
+                queries::NEO4J_RELATIONSHIP_TYPES,
+                "relationshipType",
+            )
+            .await?,
+        ),
+        Engine::Memgraph => (
+            client.fetch_all(queries::MEMGRAPH_NODE_PROPERTIES).await?,
+            client
+                .fetch_all(queries::MEMGRAPH_RELATIONSHIP_PROPERTIES)
+                .await?,
+            Vec::new(),
+            Vec::new(),
+        ),
+    };
+    let mut tables = property_tables(
+        NODES,
+        &labels,
+        node_rows.iter().map(PropertyRow::node).collect(),
+    );
+    tables.extend(property_tables(
+        RELATIONSHIPS,
+        &relationship_types,
+        relationship_rows
+            .iter()
+            .map(PropertyRow::relationship)
+            .collect(),
+    ));
+
+    let (mut indexes, index_stats, constraints) = match engine {
+        Engine::Neo4j => {
+            let mut indexes = Vec::new();
+            let mut index_stats = Vec::new();
+            for row in client.fetch_all(queries::NEO4J_INDEXES).await? {
+                let (index, stats) = neo4j_index(&row);
+                indexes.extend(index);
+                index_stats.extend(stats);
+            }
+            let constraints = client
+                .fetch_all(queries::NEO4J_CONSTRAINTS)
+                .await?
+                .iter()
+                .filter_map(neo4j_constraint)
+                .collect::<Vec<_>>();
+            (indexes, index_stats, constraints)
+        }
+        Engine::Memgraph => (
+            client
+                .fetch_all(queries::MEMGRAPH_INDEXES)
+                .await?
+                .iter()
+                .filter_map(memgraph_index)
+                .collect(),
+            Vec::new(),
+            client
+                .fetch_all(queries::MEMGRAPH_CONSTRAINTS)
+                .await?
+                .iter()
+                .filter_map(memgraph_constraint)
+                .collect(),
+        ),
+    };
+    for constraint in constraints {
+        apply_constraint(&mut tables, &mut indexes, constraint);
+    }
+
+    if engine == Engine::Neo4j {
+        for table in &mut tables {
+            let cypher = if table.schema == NODES {
+                format!("MATCH (n:{}) RETURN count(n) AS count", quote(&table.name))
+            } else {
+                format!(
+                    "MATCH ()-[r:{}]->() RETURN count(r) AS count",
+                    quote(&table.name)
+                )
+            };
+            table.row_count_estimate = client
+                .fetch_all(&cypher)
+                .await?
+                .first()
+                .and_then(|row| int(row, "count"));
+        }
+    }
+
+    Ok((
+        SchemaMetadata {
+            tables,
+            indexes,
+            ..Default::default()
+        },
+        index_stats,
+    ))
+}
+
+/// The single string column of a procedure's rows
+async fn column(
+    client: &GraphClient,
+    cypher: &str,
+    name: &str,
+) -> Result<Vec<String>, CollectorError> {
+    Ok(client
+        .fetch_all(cypher)
+        .await?
+        .iter()
+        .filter_map(|row| text(row, name))
+        .collect())
+}
+
+/// Quote a label or relationship type for use in a pattern
+fn quote(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// `:`KNOWS`` as reported by `relTypeProperties`, to `KNOWS`
+fn type_name(reported: &str) -> String {
+    let name = reported.strip_prefix(':').unwrap_or(reported);
+    name.strip_prefix('`')
+        .and_then(|n| n.strip_suffix('`'))
+        .map_or_else(|| name.to_string(), |n| n.replace("``", "`"))
+}
+
+/// One row of `nodeTypeProperties` or `relTypeProperties`
+struct PropertyRow {
+    /// Labels of the node type, or the relationship type
+    owners: Vec<String>,
+    /// The label combination (or relationship type) the row describes
+    node_type: String,
+    property: Option<String>,
+    types: Vec<String>,
+    mandatory: bool,
+}
+
+impl PropertyRow {
+    fn node(row: &Row) -> Self {
+        let owners = strings(row, "nodeLabels");
+        Self {
+            node_type: text(row, "nodeType").unwrap_or_else(|| owners.join(":")),
+            owners,
+            property: text(row, "propertyName"),
+            types: strings(row, "propertyTypes"),
+            mandatory: flag(row, "mandatory"),
+        }
+    }
+
+    fn relationship(row: &Row) -> Self {
+        let name = text(row, "relType")
+            .map(|t| type_name(&t))
+            .unwrap_or_default();
+        Self {
+            owners: vec![name.clone()],
+            node_type: name,
+            property: text(row, "propertyName"),
+            types: strings(row, "propertyTypes"),
+            mandatory: flag(row, "mandatory"),
+        }
+    }
+}
+
+/// A label or relationship type and its properties, merged over the node
+/// types it is part of
+struct Owner {
+    name: String,
+    node_types: BTreeSet<String>,
+    properties: Vec<Property>,
+}
+
+struct Property {
+    name: String,
+    types: BTreeSet<String>,
+    /// Node types on which every node has the property
+    mandatory_in: BTreeSet<String>,
+}
+
+/// Tables for the given labels (or relationship types) and the ones found
+/// in the property rows, in that order.
+fn property_tables(schema: &str, names: &[String], rows: Vec<PropertyRow>) -> Vec<TableMetadata> {
+    let mut owners: Vec<Owner> = Vec::new();
+    let owner = |owners: &mut Vec<Owner>, name: &str| -> usize {
+        owners
+            .iter()
+            .position(|o| o.name == name)
+            .unwrap_or_else(|| {
+                owners.push(Owner {
+                    name: name.to_string(),
+                    node_types: BTreeSet::new(),
+                    properties: Vec::new(),
+                });
+                owners.len() - 1
+            })
+    };
+    for name in names {
+        owner(&mut owners, name);
+    }
+
+    for row in rows {
+        for name in row.owners.iter().filter(|n| !n.is_empty()) {
+            let i = owner(&mut owners, name);
+            let owner = &mut owners[i];
+            owner.node_types.insert(row.node_type.clone());
+            let Some(property) = &row.property else {
+                continue;
+            };
+            let p = match owner.properties.iter().position(|p| &p.name == property) {
+                Some(p) => p,
+                None => {
+                    owner.properties.push(Property {
+                        name: property.clone(),
+                        types: BTreeSet::new(),
+                        mandatory_in: BTreeSet::new(),
+                    });
+                    owner.properties.len() - 1
+                }
+            };
+            let property = &mut owner.properties[p];
+            property.types.extend(row.types.iter().cloned());
+            if row.mandatory {
+                property.mandatory_in.insert(row.node_type.clone());
+            }
+        }
+    }
+
+    owners
+        .into_iter()
+        .map(|owner| TableMetadata {
+            schema: schema.to_string(),
+            name: owner.name,
+            columns: owner
+                .properties
+                .into_iter()
+                .enumerate()
+                .map(|(i, property)| ColumnMetadata {
+                    name: property.name,
+                    data_type: if property.types.is_empty() {
+                        "unknown".to_string()
+                    } else {
+                        property.types.into_iter().collect::<Vec<_>>().join("|")
+                    },
+                    nullable: property.mandatory_in != owner.node_types,
+                    position: i as i32 + 1,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// One row of Neo4j's `SHOW INDEXES`, as one index per label or type it
+/// covers. Token lookup indexes cover no particular label and are skipped.
+fn neo4j_index(row: &Row) -> (Vec<IndexMetadata>, Vec<IndexStats>) {
+    let Some(name) = text(row, "name") else {
+        return Default::default();
+    };
+    let schema = match text(row, "entityType").as_deref() {
+        Some("RELATIONSHIP") => RELATIONSHIPS,
+        _ => NODES,
+    };
+    // `uniqueness` is 4.4 only; in 5.x uniqueness comes from a constraint
+    let is_unique = text(row, "owningConstraint").is_some()
+        || text(row, "uniqueness").as_deref() == Some("UNIQUE");
+    let index_type = text(row, "type");
+    let columns = strings(row, "properties");
+    let read_count = int(row, "readCount");
+
+    let mut indexes = Vec::new();
+    let mut stats = Vec::new();
+    for table in strings(row, "labelsOrTypes") {
+        if let Some(reads) = read_count {
+            stats.push(IndexStats {
+                schema: schema.to_string(),
+                table: table.clone(),
+                index: name.clone(),
+                idx_scan: Some(reads),
+                ..Default::default()
+            });
+        }
+        indexes.push(IndexMetadata {
+            schema: schema.to_string(),
+            table,
+            name: name.clone(),
+            columns: columns.clone(),
+            is_unique,
+            index_type: index_type.clone(),
+            ..Default::default()
+        });
+    }
+    (indexes, stats)
+}
+
+/// One row of Memgraph's `SHOW INDEX INFO`. Memgraph indexes are unnamed, so
+/// the name is made up from the label and properties.
+fn memgraph_index(row: &Row) -> Option<IndexMetadata> {
+    let table = text(row, "label")?;
+    let index_type = text(row, "index type")?;
+    let columns = strings(row, "property");
+    let schema = if index_type.starts_with("edge") {
+        RELATIONSHIPS
+    } else {
+        NODES
+    };
+    let name = if columns.is_empty() {
+        table.clone()
+    } else {
+        format!("{table}({})", columns.join(", "))
+    };
+    Some(IndexMetadata {
+        schema: schema.to_string(),
+        table,
+        name,
+        columns,
+        index_type: Some(index_type),
+        ..Default::default()
+    })
+}
+
+/// A constraint and the label or relationship type it belongs to
+struct GraphConstraint {
+    schema: &'static str,
+    table: String,
+    constraint: ConstraintMetadata,
+    /// Index backing a uniqueness or key constraint (Neo4j 5)
+    owned_index: Option<String>,
+}
+
+fn neo4j_constraint(row: &Row) -> Option<GraphConstraint> {
+    let constraint_type = text(row, "type")?;
+    let kind = if constraint_type.ends_with("UNIQUENESS") {
+        "unique"
+    } else if constraint_type.ends_with("_KEY") {
+        "primary_key"
+    } else if constraint_type.contains("EXISTENCE") {
+        "not_null"
+    } else if constraint_type.contains("PROPERTY_TYPE") {
+        "property_type"
+    } else {
+        return None;
+    };
+    let schema = match text(row, "entityType").as_deref() {
+        Some("RELATIONSHIP") => RELATIONSHIPS,
+        _ => NODES,
+    };
+    Some(GraphConstraint {
+        schema,
+        table: strings(row, "labelsOrTypes").into_iter().next()?,
+        constraint: ConstraintMetadata {
+            name: text(row, "name")?,
+            kind: kind.to_string(),
+            columns: strings(row, "properties"),
+            // `createStatement` is 5.x, `description` 4.4
+            definition: text(row, "createStatement")
+                .or_else(|| text(row, "description"))
+                .unwrap_or_default(),
+            validated: true,
+            deferrable: false,
+        },
+        owned_index: text(row, "ownedIndex"),
+    })
+}
+
+/// One row of Memgraph's `SHOW CONSTRAINT INFO`. Memgraph constraints are
+/// on node labels only and unnamed; the definition is rebuilt in Memgraph's
+/// `CREATE CONSTRAINT` syntax.
+fn memgraph_constraint(row: &Row) -> Option<GraphConstraint> {
+    let label = text(row, "label")?;
+    let columns = strings(row, "properties");
+    let properties = columns
+        .iter()
+        .map(|p| format!("n.{p}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (kind, assertion) = match text(row, "constraint type")?.as_str() {
+        "unique" => ("unique", format!("{properties} IS UNIQUE")),
+        "exists" => ("not_null", format!("EXISTS ({properties})")),
+        "data_type" => (
+            "property_type",
+            format!(
+                "{properties} IS TYPED {}",
+                text(row, "data_type").unwrap_or_default()
+            ),
+        ),
+        _ => return None,
+    };
+    Some(GraphConstraint {
+        schema: NODES,
+        constraint: ConstraintMetadata {
+            name: format!("{label}_{}_{kind}", columns.join("_")),
+            kind: kind.to_string(),
+            columns,
+            definition: format!("CREATE CONSTRAINT ON (n:{label}) ASSERT {assertion}"),
+            validated: true,
+            deferrable: false,
+        },
+        table: label,
+        owned_index: None,
+    })
+}
+
+/// Attach a constraint to its table, which is created if no node or
+/// relationship carries the label yet. Key and existence constraints make
+/// their properties `NOT NULL`; a key constraint makes its backing index
+/// the primary key.
+fn apply_constraint(
+    tables: &mut Vec<TableMetadata>,
+    indexes: &mut [IndexMetadata],
+    graph_constraint: GraphConstraint,
+) {
+    let GraphConstraint {
+        schema,
+        table,
+        constraint,
+        owned_index,
+    } = graph_constraint;
+
+    let i = match tables
+        .iter()
+        .position(|t| t.schema == schema && t.name == table)
+    {
+        Some(i) => i,
+        None => {
+            tables.push(TableMetadata {
+                schema: schema.to_string(),
+                name: table.clone(),
+                ..Default::default()
+            });
+            tables.len() - 1
+        }
+    };
+    let table = &mut tables[i];
+
+    if constraint.kind == "primary_key" || constraint.kind == "not_null" {
+        for column in &mut table.columns {
+            if constraint.columns.contains(&column.name) {
+                column.nullable = false;
+            }
+        }
+    }
+    if constraint.kind == "primary_key" {
+        if let Some(owned_index) = &owned_index {
+            for index in indexes.iter_mut().filter(|i| &i.name == owned_index) {
+                index.is_primary = true;
+            }
+        }
+    }
+    table.constraints.push(constraint);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn row(value: Value) -> Row {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name(":`KNOWS`"), "KNOWS");
+        assert_eq!(type_name(":`ACTED IN`"), "ACTED IN");
+        assert_eq!(type_name("LIKES"), "LIKES");
+        assert_eq!(quote("my`label"), "`my``label`");
+    }
+
+    #[test]
+    fn test_property_tables() {
+        let rows = [
+            json!({"nodeType": ":`Person`", "nodeLabels": ["Person"], "propertyName": "name",
+                   "propertyTypes": ["String"], "mandatory": true}),
+            json!({"nodeType": ":`Person`", "nodeLabels": ["Person"], "propertyName": "born",
+                   "propertyTypes": ["Long"], "mandatory": false}),
+            json!({"nodeType": ":`Person`:`Actor`", "nodeLabels": ["Person", "Actor"],
+                   "propertyName": "name", "propertyTypes": ["String"], "mandatory": true}),
+            json!({"nodeType": ":`Person`:`Actor`", "nodeLabels": ["Person", "Actor"],
+                   "propertyName": "born", "propertyTypes": ["String"], "mandatory": true}),
+            json!({"nodeType": ":`Tag`", "nodeLabels": ["Tag"], "propertyName": null,
+                   "propertyTypes": null, "mandatory": false}),
+        ];
+        let rows = rows
+            .iter()
+            .map(|r| PropertyRow::node(&row(r.clone())))
+            .collect();
+        let tables = property_tables(NODES, &["Movie".to_string()], rows);
+
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Movie", "Person", "Actor", "Tag"]);
+        assert!(tables[0].columns.is_empty());
+
+        let person = &tables[1];
+        assert_eq!(person.schema, "nodes");
+        assert_eq!(person.columns[0].name, "name");
+        assert!(!person.columns[0].nullable);
+        // Optional on plain `Person` nodes, and typed differently
+        assert!(person.columns[1].nullable);
+        assert_eq!(person.columns[1].data_type, "Long|String");
+        assert_eq!(person.columns[1].position, 2);
+
+        let actor = &tables[2];
+        assert!(!actor.columns[1].nullable);
+        assert!(tables[3].columns.is_empty());
+    }
+
+    #[test]
+    fn test_neo4j_indexes_and_constraints() {
+        let (indexes, stats) = neo4j_index(&row(json!({
+            "name": "person_email", "type": "RANGE", "entityType": "NODE",
+            "labelsOrTypes": ["Person"], "properties": ["email"],
+            "owningConstraint": "person_email_key", "readCount": 1500
+        })));
+        assert_eq!(indexes.len(), 1);
+        assert!(indexes[0].is_unique);
+        assert_eq!(indexes[0].index_type.as_deref(), Some("RANGE"));
+        assert_eq!(stats[0].idx_scan, Some(1500));
+
+        let (lookup, _) = neo4j_index(&row(json!({
+            "name": "index_343aff4e", "type": "LOOKUP", "entityType": "NODE",
+            "labelsOrTypes": null, "properties": null
+        })));
+        assert!(lookup.is_empty());
+
+        let (fulltext, _) = neo4j_index(&row(json!({
+            "name": "titles", "type": "FULLTEXT", "entityType": "NODE",
+            "labelsOrTypes": ["Movie", "Book"], "properties": ["title"]
+        })));
+        assert_eq!(fulltext.len(), 2);
+
+        let constraint = neo4j_constraint(&row(json!({
+            "name": "person_email_key", "type": "NODE_KEY", "entityType": "NODE",
+            "labelsOrTypes": ["Person"], "properties": ["email"], "ownedIndex": "person_email",
+            "createStatement": "CREATE CONSTRAINT `person_email_key` FOR (n:`Person`) REQUIRE (n.`email`) IS NODE KEY"
+        })))
+        .unwrap();
+        assert_eq!(constraint.constraint.kind, "primary_key");
+
+        let mut tables = vec![TableMetadata {
+            schema: NODES.to_string(),
+            name: "Person".to_string(),
+            columns: vec![ColumnMetadata {
+                name: "email".to_string(),
+                nullable: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }];
+        let mut indexes = indexes;
+        apply_constraint(&mut tables, &mut indexes, constraint);
+        assert!(!tables[0].columns[0].nullable);
+        assert!(indexes[0].is_primary);
+        assert!(tables[0].constraints[0].definition.contains("IS NODE KEY"));
+
+        let existence = neo4j_constraint(&row(json!({
+            "name": "acted_in_role", "type": "RELATIONSHIP_PROPERTY_EXISTENCE",
+            "entityType": "RELATIONSHIP", "labelsOrTypes": ["ACTED_IN"], "properties": ["role"]
+        })))
+        .unwrap();
+        apply_constraint(&mut tables, &mut indexes, existence);
+        assert_eq!(tables[1].schema, "relationships");
+        assert_eq!(tables[1].constraints[0].kind, "not_null");
+    }
+
+    #[test]
+    fn test_memgraph_indexes_and_constraints() {
+        let index = memgraph_index(&row(json!({
+            "index type": "label+property", "label": "Person", "property": "email", "count": 10
+        })))
+        .unwrap();
+        assert_eq!(index.name, "Person(email)");
+        assert_eq!(index.schema, "nodes");
+
+        let edge = memgraph_index(&row(json!({
+            "index type": "edge-type", "label": "KNOWS", "property": null, "count": 3
+        })))
+        .unwrap();
+        assert_eq!(edge.schema, "relationships");
+        assert!(edge.columns.is_empty());
+
+        let constraint = memgraph_constraint(&row(json!({
+            "constraint type": "unique", "label": "Person", "properties": ["email", "tenant"]
+        })))
+        .unwrap();
+        assert_eq!(constraint.constraint.name, "Person_email_tenant_unique");
+        assert_eq!(
+            constraint.constraint.definition,
+            "CREATE CONSTRAINT ON (n:Person) ASSERT n.email, n.tenant IS UNIQUE"
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! Neo4j / Memgraph statistics and settings.
+//!
+//! - query statistics from `db.stats.retrieve('QUERIES')` (Neo4j), which has
+//!   data only while a `db.stats.collect('QUERIES')` run is active; the
+//!   agent never starts one itself. Memgraph keeps no query statistics.
+//! - node and relationship counts per label and type, as table statistics
+//! - server settings from `SHOW SETTINGS` (Neo4j 5), `dbms.listConfig()`
+//!   (Neo4j 4.4) or `SHOW CONFIG` (Memgraph)
+
+use super::connection::{flag, text, GraphClient, Row};
+use super::providers::Engine;
+use super::queries;
+use crate::collector::CollectorError;
+use crate::payload::{QueryStats, Setting, TableMetadata, TableStats};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tracing::debug;
+
+pub(super) async fn query_stats(client: &GraphClient) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics");
+
+    let mut stats: Vec<QueryStats> = client
+        .fetch_all(queries::NEO4J_QUERY_STATS)
+        .await?
+        .iter()
+        .filter_map(|row| query_stat(row.get("data")?))
+        .collect();
+    stats.sort_by(|a, b| {
+        b.total_time_ms
+            .unwrap_or(0.0)
+            .total_cmp(&a.total_time_ms.unwrap_or(0.0))
+    });
+    Ok(stats)
+}
+
+/// One query of the `QUERIES` section. Times are in microseconds; the
+/// summary is present on all 4.4 and 5.x servers, the individual
+/// invocations only until the collection buffer wraps.
+fn query_stat(data: &Value) -> Option<QueryStats> {
+    let query = data["query"].as_str()?;
+    let summary = &data["invocationSummary"];
+    let invocations = data["invocations"].as_array();
+    let calls = summary["invocationCount"]
+        .as_i64()
+        .or_else(|| invocations.map(|i| i.len() as i64))?;
+
+    let mean_us = summary["executionInUs"]["avg"].as_f64();
+    let total_us = mean_us.map(|mean| mean * calls as f64).or_else(|| {
+        invocations.map(|i| {
+            i.iter()
+                .filter_map(|i| i["elapsedExecutionInUs"].as_f64())
+                .sum()
+        })
+    });
+    let plan = &data["queryExecutionPlan"];
+
+    Some(QueryStats {
+        query_hash: Some(hex::encode(&Sha256::digest(query.as_bytes())[..8])),
+        query: Some(query.to_string()),
+        calls: Some(calls),
+        total_time_ms: total_us.map(|us| us / 1_000.0),
+        mean_time_ms: mean_us.map(|us| us / 1_000.0).or_else(|| {
+            total_us
+                .filter(|_| calls > 0)
+                .map(|us| us / 1_000.0 / calls as f64)
+        }),
+        plan: (!plan.is_null()).then(|| plan.clone()),
+        plan_source: (!plan.is_null()).then(|| "db_stats".to_string()),
+        ..Default::default()
+    })
+}
+
+/// Node and relationship counts, from the schema's row counts
+pub(super) fn table_stats(tables: &[TableMetadata]) -> Vec<TableStats> {
+    tables
+        .iter()
+        .filter(|t| t.row_count_estimate.is_some())
+        .map(|t| TableStats {
+            schema: t.schema.clone(),
+            table: t.name.clone(),
+            n_live_tup: t.row_count_estimate,
+            ..Default::default()
+        })
+        .collect()
+}
+
+pub(super) async fn settings(
+    client: &GraphClient,
+    engine: Engine,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting settings");
+
+    let mut details: Vec<Setting> = match engine {
+        Engine::Neo4j => match client.fetch_all(queries::NEO4J_SETTINGS).await {
+            Ok(rows) => rows.iter().filter_map(neo4j_setting).collect(),
+            // `SHOW SETTINGS` is new in Neo4j 5
+            Err(CollectorError::QueryError(_)) => client
+                .fetch_all(queries::NEO4J_LIST_CONFIG)
+                .await?
+                .iter()
+                .filter_map(neo4j_setting)
+                .collect(),
+            Err(e) => return Err(e),
+        },
+        Engine::Memgraph => client
+            .fetch_all(queries::MEMGRAPH_CONFIG)
+            .await?
+            .iter()
+            .filter_map(memgraph_setting)
+            .collect(),
+    };
+    details.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+/// A row of `SHOW SETTINGS` or `dbms.listConfig()`; only the former says
+/// whether a setting was set explicitly and what it started with.
+fn neo4j_setting(row: &Row) -> Option<Setting> {
+    let name = text(row, "name")?;
+    let value = text(row, "value").unwrap_or_default();
+    let (unit, value_bytes, value_ms) = normalize(&name, &value);
+    let explicit = row
+        .contains_key("isExplicitlySet")
+        .then(|| flag(row, "isExplicitlySet"));
+    Some(Setting {
+        category: name.split('.').next().map(str::to_string),
+        source: explicit.map(|e| if e { "configured" } else { "default" }.to_string()),
+        is_default: explicit == Some(false),
+        boot_value: text(row, "startupValue"),
+        unit: unit.map(str::to_string),
+        value_bytes,
+        value_ms,
+        name,
+        value,
+        ..Default::default()
+    })
+}
+
+/// A row of Memgraph's `SHOW CONFIG`
+fn memgraph_setting(row: &Row) -> Option<Setting> {
+    let name = text(row, "name")?;
+    let value = text(row, "current_value").unwrap_or_default();
+    let default = text(row, "default_value");
+    let (unit, value_bytes, value_ms) = normalize(&name, &value);
+    Some(Setting {
+        is_default: default.as_deref() == Some(value.as_str()),
+        reset_value: default,
+        unit: unit.map(str::to_string),
+        value_bytes,
+        value_ms,
+        name,
+        value,
+        ..Default::default()
+    })
+}
+
+/// Unit and normalized value of a setting. Neo4j spells the unit out in the
+/// value (`512.00MiB`, `10s`); Memgraph puts it in the name
+/// (`query_execution_timeout_sec`, `memory_limit` in MiB).
+fn normalize(name: &str, value: &str) -> (Option<&'static str>, Option<i64>, Option<f64>) {
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+    let Ok(n) = number.parse::<f64>() else {
+        return (None, None, None);
+    };
+
+    let bytes = |unit, factor: f64| (Some(unit), Some((n * factor) as i64), None);
+    let millis = |unit, factor: f64| (Some(unit), None, Some(n * factor));
+    const KIB: f64 = 1024.0;
+    match suffix.to_ascii_lowercase().as_str() {
+        "b" => bytes("B", 1.0),
+        "k" | "kb" | "kib" => bytes("kB", KIB),
+        "mb" | "mib" => bytes("MB", KIB * KIB),
+        "g" | "gb" | "gib" => bytes("GB", KIB * KIB * KIB),
+        "tb" | "tib" => bytes("TB", KIB * KIB * KIB * KIB),
+        "ns" => millis("ns", 1e-6),
+        "us" | "μs" => millis("us", 1e-3),
+        "ms" => millis("ms", 1.0),
+        "s" => millis("s", 1_000.0),
+        // `512m` is a memory size in Neo4j 4.4 configs
+        "m" if name.contains("memory") || name.ends_with("size") => bytes("MB", KIB * KIB),
+        "m" => millis("min", 60_000.0),
+        "h" => millis("h", 3_600_000.0),
+        "d" => millis("d", 86_400_000.0),
+        "" if name.ends_with("_sec") => millis("s", 1_000.0),
+        "" if name.ends_with("_ms") => millis("ms", 1.0),
+        "" if name.ends_with("_mb") || name == "memory_limit" => bytes("MB", KIB * KIB),
+        _ => (None, None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("server.memory.pagecache.size", "512.00MiB"),
+            (Some("MB"), Some(512 * 1024 * 1024), None)
+        );
+        assert_eq!(
+            normalize("db.transaction.timeout", "10s"),
+            (Some("s"), None, Some(10_000.0))
+        );
+        assert_eq!(
+            normalize("query_execution_timeout_sec", "600"),
+            (Some("s"), None, Some(600_000.0))
+        );
+        assert_eq!(
+            normalize("memory_limit", "2048"),
+            (Some("MB"), Some(2048 * 1024 * 1024), None)
+        );
+        assert_eq!(
+            normalize("dbms.memory.pagecache.size", "512m"),
+            (Some("MB"), Some(512 * 1024 * 1024), None)
+        );
+        assert_eq!(normalize("bolt_port", "7687"), (None, None, None));
+        assert_eq!(normalize("db.format", "block"), (None, None, None));
+    }
+
+    #[test]
+    fn test_settings_rows() {
+        let row = json!({"name": "db.transaction.timeout", "value": "30s",
+                         "isExplicitlySet": true, "startupValue": "0s", "isDynamic": true});
+        let setting = neo4j_setting(row.as_object().unwrap()).unwrap();
+        assert_eq!(setting.category.as_deref(), Some("db"));
+        assert_eq!(setting.source.as_deref(), Some("configured"));
+        assert!(!setting.is_default);
+        assert_eq!(setting.boot_value.as_deref(), Some("0s"));
+        assert_eq!(setting.value_ms, Some(30_000.0));
+
+        // `dbms.listConfig()` doesn't say where a value came from
+        let row = json!({"name": "dbms.memory.heap.max_size", "value": "1g", "dynamic": false});
+        let setting = neo4j_setting(row.as_object().unwrap()).unwrap();
+        assert_eq!(setting.source, None);
+        assert_eq!(setting.value_bytes, Some(1024 * 1024 * 1024));
+
+        let row = json!({"name": "storage_gc_cycle_sec", "default_value": "30",
+                         "current_value": "30", "description": "..."});
+        let setting = memgraph_setting(row.as_object().unwrap()).unwrap();
+        assert!(setting.is_default);
+        assert_eq!(setting.value_ms, Some(30_000.0));
+    }
+
+    #[test]
+    fn test_query_stat() {
+        let data = json!({
+            "query": "MATCH (p:Person {email: $email}) RETURN p",
+            "queryExecutionPlan": {"operator": "ProduceResults"},
+            "invocationSummary": {
+                "invocationCount": 4,
+                "executionInUs": {"min": 500, "max": 4000, "avg": 2500.0}
+            }
+        });
+        let stat = query_stat(&data).unwrap();
+        assert_eq!(stat.calls, Some(4));
+        assert_eq!(stat.mean_time_ms, Some(2.5));
+        assert_eq!(stat.total_time_ms, Some(10.0));
+        assert_eq!(stat.query_hash.as_deref().map(str::len), Some(16));
+        assert_eq!(stat.plan_source.as_deref(), Some("db_stats"));
+
+        let data = json!({
+            "query": "MATCH (n) RETURN count(n)",
+            "invocations": [{"elapsedExecutionInUs": 1000}, {"elapsedExecutionInUs": 3000}]
+        });
+        let stat = query_stat(&data).unwrap();
+        assert_eq!(stat.calls, Some(2));
+        assert_eq!(stat.total_time_ms, Some(4.0));
+        assert_eq!(stat.mean_time_ms, Some(2.0));
+    }
+}
@@ -0,0 +1,205 @@
+//! Oracle collector, over ODBC with `odbc-api` and the Oracle ODBC driver.
+//!
+//! Built with the `oracle` cargo feature only: it links against an ODBC
+//! driver manager (unixODBC), and needs the Oracle Instant Client ODBC
+//! package at run time.
+//!
+//! Collects from all schemas of users not maintained by Oracle:
+//! - Query statistics from the shared pool (`V$SQLSTATS`)
+//! - Tables with row counts, segment sizes and partitioning (`DBA_TABLES`,
+//!   `DBA_SEGMENTS`), with DML since the last statistics gathering
+//! - Columns with optimizer statistics (`DBA_TAB_COLUMNS`), indexes
+//!   (`DBA_INDEXES`), constraints and views
+//! - Index usage (`DBA_INDEX_USAGE`)
+//! - Initialization parameters (`V$PARAMETER`)
+//! - Non-idle wait events (`V$SYSTEM_EVENT`)
+//!
+//! Autonomous Database, Amazon RDS and self-managed databases are told
+//! apart in [`providers`]. The user needs `CREATE SESSION` and
+//! `SELECT_CATALOG_ROLE` (or `SELECT ANY DICTIONARY`).
+//!
+//! The collector holds a single connection and queries it sequentially. A
+//! connection that fails mid-cycle is dropped and reopened on the next one.
+
+mod connection;
+pub mod providers;
+mod queries;
+mod rows;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, OracleMetadata, Payload};
+use async_trait::async_trait;
+use connection::OracleClient;
+use providers::ServerInfo;
+use rows::{int, text};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Oracle metrics collector
+pub struct OracleCollector {
+    connection_string: String,
+    client: Mutex<Option<OracleClient>>,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+    server_info: ServerInfo,
+}
+
+impl OracleCollector {
+    /// Connect, read the instance properties and detect the provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to Oracle");
+
+        let connection_string = connection::connection_string(database_url)?;
+        let client = OracleClient::connect(&connection_string).await?;
+
+        let row = client
+            .fetch_all(queries::SERVER_INFO)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CollectorError::QueryError("No instance properties returned".into()))?;
+        // `version` stops at the release (`19.0.0.0.0`) from 18c on
+        let version = match client.fetch_all(queries::VERSION_FULL).await {
+            Ok(rows) => rows.first().and_then(|r| text(r, "version_full")),
+            Err(e) => {
+                debug!(error = %e, "V$INSTANCE.VERSION_FULL not available");
+                None
+            }
+        }
+        .or_else(|| text(&row, "version"));
+        let cloud_service = match client.fetch_all(queries::CLOUD_SERVICE).await {
+            Ok(rows) => rows.first().and_then(|r| text(r, "cloud_service")),
+            Err(e) => {
+                debug!(error = %e, "CLOUD_SERVICE context not available");
+                None
+            }
+        };
+        let server_info = ServerInfo {
+            cloud_service,
+            has_rdsadmin: int(&row, "has_rdsadmin").unwrap_or(0) > 0,
+            instance_name: text(&row, "instance_name"),
+            database_name: text(&row, "database_name"),
+        };
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            database = server_info.database_name.as_deref().unwrap_or("unknown"),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            let host = connection::host(database_url);
+            providers::detect_provider(&server_info, host.as_deref()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            connection_string,
+            client: Mutex::new(Some(client)),
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+            server_info,
+        })
+    }
+
+    async fn collect_with(&self, client: &OracleClient) -> Result<Payload, CollectorError> {
+        let query_stats = stats::query_stats(client).await?;
+        let table_stats = stats::table_stats(client).await?;
+        let (settings, setting_details) = stats::settings(client).await?;
+        let schema = schema::collect(client).await?;
+        // Optional like the other engine-specific sections
+        let index_stats = stats::index_stats(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect index usage");
+            Vec::new()
+        });
+        let wait_events = stats::wait_events(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect wait events");
+            Vec::new()
+        });
+
+        let host = connection::host(&self.database_url);
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Oracle.to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                &self.server_info,
+                host.as_deref(),
+            ),
+        };
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_query_stats(query_stats)
+            .with_table_stats(table_stats)
+            .with_index_stats(index_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema)
+            .with_oracle(OracleMetadata { wait_events }))
+    }
+}
+
+#[async_trait]
+impl Collector for OracleCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let mut guard = self.client.lock().await;
+        if guard.is_none() {
+            *guard = Some(OracleClient::connect(&self.connection_string).await?);
+        }
+        let client = guard.as_ref().expect("connected above");
+
+        let result = self.collect_with(client).await;
+        if let Err(CollectorError::ConnectionError(_)) = &result {
+            *guard = None;
+        }
+        let payload = result?;
+
+        info!(
+            tables = payload.schema.as_ref().map(|s| s.tables.len()).unwrap_or(0),
+            indexes = payload
+                .schema
+                .as_ref()
+                .map(|s| s.indexes.len())
+                .unwrap_or(0),
+            queries = payload.query_stats.as_ref().map(|q| q.len()).unwrap_or(0),
+            "Metrics collection complete"
+        );
+
+        Ok(payload)
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        let mut guard = self.client.lock().await;
+        if guard.is_none() {
+            *guard = Some(OracleClient::connect(&self.connection_string).await?);
+        }
+        let client = guard.as_ref().expect("connected above");
+        if let Err(e) = client.fetch_all("SELECT 1 FROM dual").await {
+            *guard = None;
+            return Err(CollectorError::ConnectionError(e.to_string()));
+        }
+        Ok(())
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Oracle
+    }
+}
@@ -0,0 +1,150 @@
+//! Oracle provider detection.
+//!
+//! Returns one of:
+//! - `"oracle_cloud"` — Autonomous Database on Oracle Cloud Infrastructure
+//! - `"rds"` — Amazon RDS for Oracle
+//! - `"generic"` — self-managed (on-premises, on a VM or in a container)
+//!
+//! Autonomous Database answers `SYS_CONTEXT('USERENV', 'CLOUD_SERVICE')`
+//! whatever the hostname used to connect (private endpoints). RDS instances
+//! carry an `RDSADMIN` user.
+
+use std::collections::HashMap;
+
+pub const PROVIDER_ORACLE_CLOUD: &str = "oracle_cloud";
+pub const PROVIDER_RDS: &str = "rds";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// What the database says about itself, from `SERVER_INFO` and
+/// `CLOUD_SERVICE`
+#[derive(Debug, Default)]
+pub struct ServerInfo {
+    pub cloud_service: Option<String>,
+    pub has_rdsadmin: bool,
+    pub instance_name: Option<String>,
+    pub database_name: Option<String>,
+}
+
+/// Detect the provider from the server properties, falling back to the
+/// host.
+pub fn detect_provider(info: &ServerInfo, host: Option<&str>) -> &'static str {
+    if info.cloud_service.is_some() {
+        return PROVIDER_ORACLE_CLOUD;
+    }
+    if info.has_rdsadmin {
+        return PROVIDER_RDS;
+    }
+
+    match host {
+        Some(host) if host.ends_with(".oraclecloud.com") => PROVIDER_ORACLE_CLOUD,
+        Some(host) if host.ends_with(".rds.amazonaws.com") => PROVIDER_RDS,
+        _ => PROVIDER_GENERIC,
+    }
+}
+
+/// Provider metadata derivable from the server properties and hostname.
+pub fn provider_metadata(
+    provider: &str,
+    info: &ServerInfo,
+    host: Option<&str>,
+) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    if let Some(database_name) = &info.database_name {
+        out.insert("database_name".to_string(), database_name.clone());
+    }
+    match provider {
+        PROVIDER_ORACLE_CLOUD => {
+            if let Some(workload) = &info.cloud_service {
+                out.insert("workload".to_string(), workload.clone());
+            }
+            // `adb.eu-frankfurt-1.oraclecloud.com`
+            if let Some(region) = host.and_then(|h| h.split('.').nth(1)) {
+                out.insert("region".to_string(), region.to_string());
+            }
+        }
+        PROVIDER_RDS => {
+            // `orders.abc123xyz.us-east-1.rds.amazonaws.com`
+            let parts: Vec<&str> = host.unwrap_or_default().split('.').collect();
+            if parts.len() >= 6 {
+                out.insert("instance_identifier".to_string(), parts[0].to_string());
+                out.insert("region".to_string(), parts[2].to_string());
+            }
+        }
+        _ => {
+            if let Some(instance_name) = &info.instance_name {
+                out.insert("instance_name".to_string(), instance_name.clone());
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_autonomous_database() {
+        let info = ServerInfo {
+            cloud_service: Some("OLTP".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&info, Some("10.0.0.12")),
+            PROVIDER_ORACLE_CLOUD
+        );
+        assert_eq!(
+            detect_provider(
+                &ServerInfo::default(),
+                Some("adb.eu-frankfurt-1.oraclecloud.com")
+            ),
+            PROVIDER_ORACLE_CLOUD
+        );
+    }
+
+    #[test]
+    fn detects_rds() {
+        let info = ServerInfo {
+            has_rdsadmin: true,
+            ..Default::default()
+        };
+        assert_eq!(detect_provider(&info, Some("db.internal")), PROVIDER_RDS);
+    }
+
+    #[test]
+    fn falls_back_to_generic() {
+        assert_eq!(
+            detect_provider(&ServerInfo::default(), Some("localhost")),
+            PROVIDER_GENERIC
+        );
+        assert_eq!(
+            detect_provider(&ServerInfo::default(), None),
+            PROVIDER_GENERIC
+        );
+    }
+
+    #[test]
+    fn metadata_per_provider() {
+        let info = ServerInfo {
+            cloud_service: Some("DWCS".to_string()),
+            database_name: Some("ABC".to_string()),
+            ..Default::default()
+        };
+        let meta = provider_metadata(
+            PROVIDER_ORACLE_CLOUD,
+            &info,
+            Some("adb.eu-frankfurt-1.oraclecloud.com"),
+        );
+        assert_eq!(meta.get("workload").unwrap(), "DWCS");
+        assert_eq!(meta.get("region").unwrap(), "eu-frankfurt-1");
+        assert_eq!(meta.get("database_name").unwrap(), "ABC");
+
+        let meta = provider_metadata(
+            PROVIDER_RDS,
+            &ServerInfo::default(),
+            Some("orders.abc123xyz.eu-west-1.rds.amazonaws.com"),
+        );
+        assert_eq!(meta.get("instance_identifier").unwrap(), "orders");
+        assert_eq!(meta.get("region").unwrap(), "eu-west-1");
+    }
+}
@@ -0,0 +1,290 @@
+//! Oracle dynamic performance view and data dictionary queries.
+//!
+//! Everything targets Oracle Database 12.2 and later (`oracle_maintained`,
+//! `*_vc` columns, `DBA_INDEX_USAGE`), including Autonomous Database and
+//! Amazon RDS. Schemas are the users not maintained by Oracle, without
+//! `RDSADMIN`. `LONG` columns are avoided; dates are converted to UTC and
+//! formatted as RFC 3339 for [`super::rows::timestamp`].
+
+/// Version, instance and database names, and whether this is RDS
+pub const SERVER_INFO: &str = r#"
+SELECT
+    i.version,
+    i.instance_name,
+    i.host_name,
+    d.name AS database_name,
+    (SELECT COUNT(*) FROM dba_users WHERE username = 'RDSADMIN') AS has_rdsadmin
+FROM v$instance i
+CROSS JOIN v$database d
+"#;
+
+/// Full version including the release update, e.g. `19.22.0.0.0`. Oracle
+/// 18c and later.
+pub const VERSION_FULL: &str = "SELECT version_full FROM v$instance";
+
+/// Autonomous Database workload type (`OLTP`, `DWCS`, `JDCS`, ...); `NULL`
+/// elsewhere. Fails on releases that don't know the parameter.
+pub const CLOUD_SERVICE: &str =
+    "SELECT SYS_CONTEXT('USERENV', 'CLOUD_SERVICE') AS cloud_service FROM dual";
+
+/// Top statements by total elapsed time from the shared pool, summed over
+/// the plans of each `sql_id`. Only statements parsed by user schemas
+/// count, and not the agent's own (its session module is
+/// [`super::connection::MODULE`]). Times are in microseconds.
+pub const QUERY_STATS: &str = r#"
+SELECT
+    s.sql_id,
+    MAX(s.sql_text) AS sql_text,
+    SUM(s.executions) AS calls,
+    SUM(s.elapsed_time) / 1000 AS total_time_ms,
+    SUM(s.rows_processed) AS rows_processed,
+    SUM(s.buffer_gets) AS buffer_gets,
+    SUM(s.disk_reads) AS disk_reads
+FROM v$sqlstats s
+WHERE s.executions > 0
+  AND EXISTS (
+    SELECT 1
+    FROM v$sql q
+    JOIN dba_users u ON u.username = q.parsing_schema_name
+    WHERE q.sql_id = s.sql_id
+      AND u.oracle_maintained = 'N'
+      AND u.username <> 'RDSADMIN'
+      AND NVL(q.module, '-') <> 'datapace-agent'
+  )
+GROUP BY s.sql_id
+ORDER BY total_time_ms DESC
+FETCH FIRST 100 ROWS ONLY
+"#;
+
+/// Tables with their optimizer statistics, segment sizes and partitioning.
+/// Sizes cover the table, partition and subpartition segments; LOB and
+/// index segments are not included.
+pub const TABLES: &str = r#"
+SELECT
+    t.owner AS schema_name,
+    t.table_name,
+    t.num_rows,
+    TO_CHAR(
+        SYS_EXTRACT_UTC(FROM_TZ(CAST(t.last_analyzed AS TIMESTAMP), TO_CHAR(SYSTIMESTAMP, 'TZH:TZM'))),
+        'YYYY-MM-DD"T"HH24:MI:SS"Z"'
+    ) AS last_analyzed,
+    seg.bytes AS size_bytes,
+    LOWER(p.partitioning_type) AS partition_strategy,
+    tp.partition_count
+FROM dba_tables t
+JOIN dba_users u ON u.username = t.owner
+LEFT JOIN (
+    SELECT owner, segment_name, SUM(bytes) AS bytes
+    FROM dba_segments
+    WHERE segment_type IN ('TABLE', 'TABLE PARTITION', 'TABLE SUBPARTITION')
+    GROUP BY owner, segment_name
+) seg ON seg.owner = t.owner AND seg.segment_name = t.table_name
+LEFT JOIN dba_part_tables p ON p.owner = t.owner AND p.table_name = t.table_name
+LEFT JOIN (
+    SELECT table_owner, table_name, COUNT(*) AS partition_count
+    FROM dba_tab_partitions
+    GROUP BY table_owner, table_name
+) tp ON tp.table_owner = t.owner AND tp.table_name = t.table_name
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+  AND t.dropped = 'NO'
+  AND t.secondary = 'N'
+  AND t.nested = 'NO'
+ORDER BY t.owner, t.table_name
+"#;
+
+/// Columns of the tables and views in [`TABLES`] and [`VIEWS`], with their
+/// optimizer statistics. `null_rate` is relative to the table's `num_rows`
+/// from the same statistics gathering.
+pub const COLUMNS: &str = r#"
+SELECT
+    c.owner AS schema_name,
+    c.table_name,
+    c.column_name,
+    c.column_id AS position,
+    c.data_type,
+    c.char_length,
+    c.char_used,
+    c.data_precision,
+    c.data_scale,
+    c.nullable,
+    c.data_default_vc AS column_default,
+    c.num_distinct,
+    CASE WHEN t.num_rows > 0 THEN c.num_nulls / t.num_rows END AS null_rate,
+    c.avg_col_len,
+    c.collation,
+    i.generation_type AS identity,
+    cc.comments AS column_comment
+FROM dba_tab_columns c
+JOIN dba_users u ON u.username = c.owner
+LEFT JOIN dba_tables t ON t.owner = c.owner AND t.table_name = c.table_name
+LEFT JOIN dba_tab_identity_cols i
+    ON i.owner = c.owner AND i.table_name = c.table_name AND i.column_name = c.column_name
+LEFT JOIN dba_col_comments cc
+    ON cc.owner = c.owner AND cc.table_name = c.table_name AND cc.column_name = c.column_name
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+ORDER BY c.owner, c.table_name, c.column_id
+"#;
+
+/// One row per index column, ordered by index then column position, with
+/// the index segment size and whether the index backs the primary key.
+/// LOB indexes are left out.
+pub const INDEX_COLUMNS: &str = r#"
+SELECT
+    i.table_owner AS schema_name,
+    i.table_name,
+    i.index_name,
+    LOWER(i.index_type) AS index_type,
+    CASE WHEN i.uniqueness = 'UNIQUE' THEN 1 ELSE 0 END AS is_unique,
+    CASE WHEN EXISTS (
+        SELECT 1 FROM dba_constraints k
+        WHERE k.constraint_type = 'P'
+          AND k.owner = i.table_owner
+          AND k.table_name = i.table_name
+          AND k.index_owner = i.owner
+          AND k.index_name = i.index_name
+    ) THEN 1 ELSE 0 END AS is_primary,
+    seg.bytes AS size_bytes,
+    ic.column_name
+FROM dba_indexes i
+JOIN dba_users u ON u.username = i.table_owner
+JOIN dba_ind_columns ic ON ic.index_owner = i.owner AND ic.index_name = i.index_name
+LEFT JOIN (
+    SELECT owner, segment_name, SUM(bytes) AS bytes
+    FROM dba_segments
+    WHERE segment_type IN ('INDEX', 'INDEX PARTITION', 'INDEX SUBPARTITION')
+    GROUP BY owner, segment_name
+) seg ON seg.owner = i.owner AND seg.segment_name = i.index_name
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+  AND i.index_type <> 'LOB'
+  AND i.dropped = 'NO'
+ORDER BY i.table_owner, i.table_name, i.index_name, ic.column_position
+"#;
+
+/// One row per constraint column of primary key, unique, foreign key and
+/// check constraints, ordered by constraint then position. The `NOT NULL`
+/// checks Oracle generates for nullability are left out.
+pub const CONSTRAINTS: &str = r#"
+SELECT
+    k.owner AS schema_name,
+    k.table_name,
+    k.constraint_name,
+    CASE k.constraint_type
+        WHEN 'P' THEN 'primary_key'
+        WHEN 'U' THEN 'unique'
+        WHEN 'R' THEN 'foreign_key'
+        ELSE 'check'
+    END AS kind,
+    k.search_condition_vc AS definition,
+    CASE WHEN k.validated = 'VALIDATED' THEN 1 ELSE 0 END AS is_validated,
+    CASE WHEN k.deferrable = 'DEFERRABLE' THEN 1 ELSE 0 END AS is_deferrable,
+    kc.column_name,
+    r.owner AS referenced_schema,
+    r.table_name AS referenced_table,
+    rc.column_name AS referenced_column
+FROM dba_constraints k
+JOIN dba_users u ON u.username = k.owner
+LEFT JOIN dba_cons_columns kc
+    ON kc.owner = k.owner AND kc.constraint_name = k.constraint_name
+LEFT JOIN dba_constraints r
+    ON r.owner = k.r_owner AND r.constraint_name = k.r_constraint_name
+LEFT JOIN dba_cons_columns rc
+    ON rc.owner = r.owner AND rc.constraint_name = r.constraint_name
+   AND rc.position = kc.position
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+  AND k.constraint_type IN ('P', 'U', 'R', 'C')
+  AND k.table_name NOT LIKE 'BIN$%'
+  AND NOT (
+    k.constraint_type = 'C'
+    AND k.generated = 'GENERATED NAME'
+    AND k.search_condition_vc LIKE '% IS NOT NULL'
+  )
+ORDER BY k.owner, k.table_name, k.constraint_name, kc.position
+"#;
+
+/// Views with their query text (first 4000 characters)
+pub const VIEWS: &str = r#"
+SELECT v.owner AS schema_name, v.view_name, v.text_vc AS definition
+FROM dba_views v
+JOIN dba_users u ON u.username = v.owner
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+ORDER BY v.owner, v.view_name
+"#;
+
+/// Row counts from the optimizer statistics and DML since they were
+/// gathered. `DBA_TAB_MODIFICATIONS` is flushed from memory every few
+/// minutes, so it lags behind.
+pub const TABLE_STATS: &str = r#"
+SELECT
+    t.owner AS schema_name,
+    t.table_name,
+    t.num_rows,
+    TO_CHAR(
+        SYS_EXTRACT_UTC(FROM_TZ(CAST(t.last_analyzed AS TIMESTAMP), TO_CHAR(SYSTIMESTAMP, 'TZH:TZM'))),
+        'YYYY-MM-DD"T"HH24:MI:SS"Z"'
+    ) AS last_analyzed,
+    m.inserts + m.updates + m.deletes AS n_mod_since_analyze
+FROM dba_tables t
+JOIN dba_users u ON u.username = t.owner
+LEFT JOIN dba_tab_modifications m
+    ON m.table_owner = t.owner AND m.table_name = t.table_name AND m.partition_name IS NULL
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+  AND t.dropped = 'NO'
+  AND t.secondary = 'N'
+  AND t.nested = 'NO'
+ORDER BY t.owner, t.table_name
+"#;
+
+/// Index usage tracked since the index was created or the database
+/// started tracking it. By default Oracle samples accesses, so counts are
+/// estimates.
+pub const INDEX_USAGE: &str = r#"
+SELECT
+    i.table_owner AS schema_name,
+    i.table_name,
+    i.index_name,
+    iu.total_access_count AS idx_scan,
+    iu.total_rows_returned AS idx_tup_read
+FROM dba_index_usage iu
+JOIN dba_indexes i ON i.owner = iu.owner AND i.index_name = iu.name
+JOIN dba_users u ON u.username = i.table_owner
+WHERE u.oracle_maintained = 'N'
+  AND u.username <> 'RDSADMIN'
+ORDER BY i.table_owner, i.table_name, i.index_name
+"#;
+
+/// Initialization parameters of the instance, without hidden ones.
+/// `type` is 1 for booleans, 2 strings, 3 integers, 4 parameter files and
+/// 6 big integers (sizes in bytes).
+pub const PARAMETERS: &str = r#"
+SELECT
+    name,
+    value,
+    type,
+    isdefault,
+    isbasic,
+    ismodified
+FROM v$parameter
+ORDER BY name
+"#;
+
+/// Non-idle wait events since instance startup, by total time waited.
+/// `time_waited_micro` is in microseconds.
+pub const SYSTEM_EVENTS: &str = r#"
+SELECT
+    event,
+    wait_class,
+    total_waits,
+    total_timeouts,
+    time_waited_micro
+FROM v$system_event
+WHERE wait_class <> 'Idle'
+  AND total_waits > 0
+ORDER BY time_waited_micro DESC
+FETCH FIRST 100 ROWS ONLY
+"#;
@@ -0,0 +1,90 @@
+//! Column access for rows fetched as text.
+//!
+//! Every value comes back from the driver as text (numbers with `.` as the
+//! decimal separator, see [`super::connection`]), keyed by the lowercased
+//! column name. `NULL` columns are absent from the row.
+
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// One row, column name to value
+pub(super) type Row = HashMap<String, String>;
+
+pub(super) fn text(row: &Row, column: &str) -> Option<String> {
+    row.get(column).cloned()
+}
+
+/// An integer column; `NUMBER` sums and averages may carry a fraction,
+/// which is dropped
+pub(super) fn int(row: &Row, column: &str) -> Option<i64> {
+    let value = row.get(column)?.trim();
+    value
+        .parse::<i64>()
+        .ok()
+        .or_else(|| value.parse::<f64>().ok().map(|v| v as i64))
+}
+
+pub(super) fn float(row: &Row, column: &str) -> Option<f64> {
+    row.get(column)?.trim().parse().ok()
+}
+
+/// A `YES`/`NO`, `Y`/`N`, `TRUE`/`FALSE` or `1`/`0` column, `false` when
+/// `NULL`
+pub(super) fn flag(row: &Row, column: &str) -> bool {
+    row.get(column).is_some_and(|value| {
+        matches!(
+            value.trim().to_ascii_uppercase().as_str(),
+            "YES" | "Y" | "TRUE" | "1"
+        )
+    })
+}
+
+/// A timestamp the query formatted as RFC 3339
+pub(super) fn timestamp(row: &Row, column: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(row.get(column)?.trim())
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> Row {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_numbers() {
+        let row = row(&[
+            ("calls", "42"),
+            ("avg", "2.5"),
+            ("small", ".25"),
+            ("bad", "n/a"),
+        ]);
+        assert_eq!(int(&row, "calls"), Some(42));
+        assert_eq!(int(&row, "avg"), Some(2));
+        assert_eq!(float(&row, "small"), Some(0.25));
+        assert_eq!(int(&row, "bad"), None);
+        assert_eq!(int(&row, "missing"), None);
+    }
+
+    #[test]
+    fn test_flag_and_timestamp() {
+        let row = row(&[
+            ("nullable", "Y"),
+            ("isdefault", "FALSE"),
+            ("last_analyzed", "2024-05-01T10:00:00Z"),
+        ]);
+        assert!(flag(&row, "nullable"));
+        assert!(!flag(&row, "isdefault"));
+        assert!(!flag(&row, "missing"));
+        assert_eq!(
+            timestamp(&row, "last_analyzed").map(|t| t.to_rfc3339()),
+            Some("2024-05-01T10:00:00+00:00".to_string())
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Oracle schema metadata from the data dictionary (`DBA_*` views) for all
+//! user schemas: tables with segment sizes and partitioning, columns with
+//! optimizer statistics, indexes, primary key, unique, foreign key and check
+//! constraints, and views.
+
+use super::connection::OracleClient;
+use super::queries;
+use super::rows::{flag, float, int, text, Row};
+use crate::collector::CollectorError;
+use crate::payload::{
+    ColumnMetadata, ConstraintMetadata, IndexMetadata, SchemaMetadata, TableMetadata, ViewMetadata,
+};
+use std::collections::HashMap;
+use tracing::debug;
+
+type TableKey = (String, String);
+
+pub(super) async fn collect(client: &OracleClient) -> Result<SchemaMetadata, CollectorError> {
+    debug!("Collecting Oracle schema metadata");
+
+    let table_rows = client.fetch_all(queries::TABLES).await?;
+    let column_rows = client.fetch_all(queries::COLUMNS).await?;
+    let index_rows = client.fetch_all(queries::INDEX_COLUMNS).await?;
+    let constraint_rows = client.fetch_all(queries::CONSTRAINTS).await?;
+    let view_rows = client.fetch_all(queries::VIEWS).await?;
+
+    let mut columns: HashMap<TableKey, Vec<ColumnMetadata>> = HashMap::new();
+    for row in &column_rows {
+        columns.entry(key(row)).or_default().push(column(row));
+    }
+
+    let mut constraints: HashMap<TableKey, Vec<ConstraintMetadata>> = HashMap::new();
+    for (table, constraint) in group_constraints(&constraint_rows) {
+        constraints.entry(table).or_default().push(constraint);
+    }
+
+    let tables = table_rows
+        .iter()
+        .map(|row| {
+            let table = key(row);
+            TableMetadata {
+                columns: columns.remove(&table).unwrap_or_default(),
+                constraints: constraints.remove(&table).unwrap_or_default(),
+                schema: table.0,
+                name: table.1,
+                row_count_estimate: int(row, "num_rows"),
+                size_bytes: int(row, "size_bytes"),
+                partition_strategy: text(row, "partition_strategy"),
+                partition_count: int(row, "partition_count"),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let views = view_rows
+        .iter()
+        .map(|row| {
+            let view = key_of(row, "view_name");
+            ViewMetadata {
+                columns: columns.remove(&view).unwrap_or_default(),
+                schema: view.0,
+                name: view.1,
+                definition: text(row, "definition"),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Ok(SchemaMetadata {
+        tables,
+        indexes: group_indexes(&index_rows),
+        views,
+        ..Default::default()
+    })
+}
+
+fn key(row: &Row) -> TableKey {
+    key_of(row, "table_name")
+}
+
+fn key_of(row: &Row, name: &str) -> TableKey {
+    (
+        text(row, "schema_name").unwrap_or_default(),
+        text(row, name).unwrap_or_default(),
+    )
+}
+
+fn column(row: &Row) -> ColumnMetadata {
+    let data_type = text(row, "data_type").unwrap_or_default();
+    let char_length = int(row, "char_length").filter(|l| *l > 0);
+    let precision = int(row, "data_precision");
+    let scale = int(row, "data_scale");
+    let is_number = data_type == "NUMBER" && precision.is_some();
+
+    ColumnMetadata {
+        name: text(row, "column_name").unwrap_or_default(),
+        native_type: Some(native_type(
+            &data_type,
+            char_length,
+            text(row, "char_used").as_deref() == Some("C"),
+            precision,
+            scale,
+        )),
+        data_type,
+        nullable: flag(row, "nullable"),
+        // Stored as written, often with a trailing newline
+        default: text(row, "column_default").map(|d| d.trim().to_string()),
+        position: int(row, "position").unwrap_or_default() as i32,
+        max_length: char_length.map(|l| l as i32),
+        numeric_precision: is_number.then(|| precision.unwrap_or_default() as i32),
+        numeric_scale: is_number.then(|| scale.unwrap_or_default() as i32),
+        null_rate: float(row, "null_rate"),
+        distinct_count: int(row, "num_distinct"),
+        avg_width: int(row, "avg_col_len").map(|w| w as i32),
+        // `ALWAYS` or `BY DEFAULT`, as in Postgres
+        identity: text(row, "identity"),
+        collation: text(row, "collation"),
+        comment: text(row, "column_comment"),
+        ..Default::default()
+    }
+}
+
+/// Full type as written in DDL, e.g. `VARCHAR2(100 CHAR)`, `NUMBER(10,2)`.
+/// `TIMESTAMP(6)` and the like already carry their precision.
+fn native_type(
+    data_type: &str,
+    char_length: Option<i64>,
+    char_semantics: bool,
+    precision: Option<i64>,
+    scale: Option<i64>,
+) -> String {
+    match (data_type, char_length, precision) {
+        ("VARCHAR2" | "CHAR", Some(length), _) => format!(
+            "{data_type}({length}{})",
+            if char_semantics { " CHAR" } else { " BYTE" }
+        ),
+        ("NVARCHAR2" | "NCHAR", Some(length), _) => format!("{data_type}({length})"),
+        ("NUMBER", _, Some(precision)) => {
+            format!("NUMBER({precision},{})", scale.unwrap_or_default())
+        }
+        // Scale 0 without precision is how `INTEGER` is stored
+        ("NUMBER", _, None) if scale == Some(0) => "NUMBER(*,0)".to_string(),
+        _ => data_type.to_string(),
+    }
+}
+
+/// Fold the per-column index rows (ordered by index, then position) into
+/// indexes.
+fn group_indexes(rows: &[Row]) -> Vec<IndexMetadata> {
+    let mut indexes: Vec<IndexMetadata> = Vec::new();
+    for row in rows {
+        let (schema, table) = key(row);
+        let name = text(row, "index_name").unwrap_or_default();
+
+        let index = match indexes.last_mut() {
+            Some(last) if last.schema == schema && last.table == table && last.name == name => last,
+            _ => {
+                indexes.push(IndexMetadata {
+                    schema,
+                    table,
+                    name,
+                    is_unique: flag(row, "is_unique"),
+                    is_primary: flag(row, "is_primary"),
+                    size_bytes: int(row, "size_bytes"),
+                    index_type: text(row, "index_type"),
+                    ..Default::default()
+                });
+                indexes.last_mut().expect("just pushed")
+            }
+        };
+        if let Some(column) = text(row, "column_name") {
+            index.columns.push(column);
+        }
+    }
+    indexes
+}
+
+/// Fold the per-column constraint rows into constraints with a definition
+/// in Oracle syntax.
+fn group_constraints(rows: &[Row]) -> Vec<(TableKey, ConstraintMetadata)> {
+    let mut out: Vec<(TableKey, ConstraintMetadata)> = Vec::new();
+    // Referenced table and columns of each foreign key, in column order
+    let mut references: Vec<(String, Vec<String>)> = Vec::new();
+
+    for row in rows {
+        let table = key(row);
+        let name = text(row, "constraint_name").unwrap_or_default();
+        let kind = text(row, "kind").unwrap_or_default();
+
+        let same = matches!(out.last(), Some((t, c)) if *t == table && c.name == name);
+        if !same {
+            out.push((
+                table,
+                ConstraintMetadata {
+                    name,
+                    definition: text(row, "definition").unwrap_or_default(),
+                    validated: flag(row, "is_validated"),
+                    deferrable: flag(row, "is_deferrable"),
+                    columns: Vec::new(),
+                    kind: kind.clone(),
+                },
+            ));
+            references.push((
+                format!(
+                    "{}.{}",
+                    quote(&text(row, "referenced_schema").unwrap_or_default()),
+                    quote(&text(row, "referenced_table").unwrap_or_default())
+                ),
+                Vec::new(),
+            ));
+        }
+        let (_, constraint) = out.last_mut().expect("pushed above");
+        if let Some(column) = text(row, "column_name") {
+            constraint.columns.push(column);
+        }
+        if kind == "foreign_key" {
+            if let Some(referenced) = text(row, "referenced_column") {
+                references
+                    .last_mut()
+                    .expect("pushed above")
+                    .1
+                    .push(referenced);
+            }
+        }
+    }
+
+    for ((_, constraint), (table, referenced)) in out.iter_mut().zip(references) {
+        let columns = |columns: &[String]| {
+            columns
+                .iter()
+                .map(|c| quote(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        constraint.definition = match constraint.kind.as_str() {
+            "primary_key" => format!("PRIMARY KEY ({})", columns(&constraint.columns)),
+            "unique" => format!("UNIQUE ({})", columns(&constraint.columns)),
+            "check" => format!("CHECK ({})", constraint.definition),
+            "foreign_key" => format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                columns(&constraint.columns),
+                table,
+                columns(&referenced)
+            ),
+            _ => std::mem::take(&mut constraint.definition),
+        };
+    }
+    out
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> Row {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_native_type() {
+        assert_eq!(
+            native_type("VARCHAR2", Some(100), true, None, None),
+            "VARCHAR2(100 CHAR)"
+        );
+        assert_eq!(
+            native_type("CHAR", Some(1), false, None, None),
+            "CHAR(1 BYTE)"
+        );
+        assert_eq!(
+            native_type("NVARCHAR2", Some(50), true, None, None),
+            "NVARCHAR2(50)"
+        );
+        assert_eq!(
+            native_type("NUMBER", None, false, Some(10), Some(2)),
+            "NUMBER(10,2)"
+        );
+        assert_eq!(
+            native_type("NUMBER", None, false, None, Some(0)),
+            "NUMBER(*,0)"
+        );
+        assert_eq!(native_type("NUMBER", None, false, None, None), "NUMBER");
+        assert_eq!(
+            native_type("TIMESTAMP(6)", None, false, None, Some(6)),
+            "TIMESTAMP(6)"
+        );
+    }
+
+    #[test]
+    fn test_column() {
+        let column = column(&row(&[
+            ("column_name", "EMAIL"),
+            ("position", "3"),
+            ("data_type", "VARCHAR2"),
+            ("char_length", "255"),
+            ("char_used", "C"),
+            ("nullable", "N"),
+            ("column_default", "'none'\n"),
+            ("num_distinct", "980"),
+            ("null_rate", ".02"),
+            ("avg_col_len", "24"),
+        ]));
+        assert_eq!(column.native_type.as_deref(), Some("VARCHAR2(255 CHAR)"));
+        assert_eq!(column.max_length, Some(255));
+        assert!(!column.nullable);
+        assert_eq!(column.default.as_deref(), Some("'none'"));
+        assert_eq!(column.null_rate, Some(0.02));
+        assert_eq!(column.distinct_count, Some(980));
+        assert_eq!(column.numeric_precision, None);
+    }
+
+    #[test]
+    fn test_group_constraints() {
+        let fk = |column: &str, referenced: &str| {
+            row(&[
+                ("schema_name", "APP"),
+                ("table_name", "ORDERS"),
+                ("constraint_name", "ORDERS_CUSTOMER_FK"),
+                ("kind", "foreign_key"),
+                ("is_validated", "1"),
+                ("is_deferrable", "0"),
+                ("column_name", column),
+                ("referenced_schema", "APP"),
+                ("referenced_table", "CUSTOMERS"),
+                ("referenced_column", referenced),
+            ])
+        };
+        let rows = vec![
+            row(&[
+                ("schema_name", "APP"),
+                ("table_name", "ORDERS"),
+                ("constraint_name", "ORDERS_AMOUNT_CK"),
+                ("kind", "check"),
+                ("definition", "amount > 0"),
+                ("is_validated", "0"),
+                ("is_deferrable", "0"),
+            ]),
+            fk("REGION", "REGION"),
+            fk("CUSTOMER_ID", "ID"),
+        ];
+        let constraints = group_constraints(&rows);
+        assert_eq!(constraints.len(), 2);
+        let check = &constraints[0].1;
+        assert_eq!(check.definition, "CHECK (amount > 0)");
+        assert!(check.columns.is_empty());
+        assert!(!check.validated);
+        let fk = &constraints[1].1;
+        assert_eq!(
+            fk.definition,
+            r#"FOREIGN KEY ("REGION", "CUSTOMER_ID") REFERENCES "APP"."CUSTOMERS" ("REGION", "ID")"#
+        );
+        assert!(fk.validated);
+    }
+
+    #[test]
+    fn test_group_indexes() {
+        let index_row = |name: &str, column: &str| {
+            row(&[
+                ("schema_name", "APP"),
+                ("table_name", "ORDERS"),
+                ("index_name", name),
+                ("index_type", "normal"),
+                ("is_unique", "1"),
+                ("is_primary", if name == "ORDERS_PK" { "1" } else { "0" }),
+                ("size_bytes", "65536"),
+                ("column_name", column),
+            ])
+        };
+        let indexes = group_indexes(&[
+            index_row("ORDERS_PK", "ID"),
+            index_row("ORDERS_UK", "REGION"),
+            index_row("ORDERS_UK", "NUMBER"),
+        ]);
+        assert_eq!(indexes.len(), 2);
+        assert!(indexes[0].is_primary);
+        assert_eq!(indexes[1].columns, ["REGION", "NUMBER"]);
+        assert_eq!(indexes[1].size_bytes, Some(65_536));
+    }
+}
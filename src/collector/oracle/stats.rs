@@ -0,0 +1,270 @@
+//! Oracle statistics and configuration.
+//!
+//! - query statistics from the shared pool (`V$SQLSTATS`), limited to
+//!   statements parsed by user schemas through `V$SQL`
+//! - table row counts from the optimizer statistics and DML since they were
+//!   gathered (`DBA_TAB_MODIFICATIONS`)
+//! - index usage from `DBA_INDEX_USAGE`
+//! - initialization parameters from `V$PARAMETER`
+//! - non-idle wait events from `V$SYSTEM_EVENT`
+//!
+//! The shared pool and `V$SYSTEM_EVENT` are cleared on restart, and
+//! statements age out of the shared pool, so their counters cover the time
+//! since then.
+
+use super::connection::OracleClient;
+use super::queries;
+use super::rows::{flag, float, int, text, timestamp, Row};
+use crate::collector::CollectorError;
+use crate::payload::{IndexStats, OracleWaitEvent, QueryStats, Setting, TableStats};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Top statements by total elapsed time.
+pub(super) async fn query_stats(client: &OracleClient) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics from V$SQLSTATS");
+
+    Ok(client
+        .fetch_all(queries::QUERY_STATS)
+        .await?
+        .iter()
+        .map(query_stat)
+        .collect())
+}
+
+fn query_stat(row: &Row) -> QueryStats {
+    let calls = int(row, "calls");
+    let total_time_ms = float(row, "total_time_ms");
+    let buffer_gets = int(row, "buffer_gets");
+    let disk_reads = int(row, "disk_reads");
+    QueryStats {
+        // `sql_id` is already a hash of the statement text
+        query_hash: text(row, "sql_id"),
+        query: text(row, "sql_text"),
+        calls,
+        total_time_ms,
+        mean_time_ms: total_time_ms
+            .zip(calls.filter(|c| *c > 0))
+            .map(|(total, calls)| total / calls as f64),
+        rows: int(row, "rows_processed"),
+        // Buffer gets are logical reads, including the blocks read from disk
+        shared_blks_hit: buffer_gets
+            .zip(disk_reads)
+            .map(|(gets, reads)| (gets - reads).max(0)),
+        shared_blks_read: disk_reads,
+        ..Default::default()
+    }
+}
+
+/// Per-table row counts and modifications of the user schemas.
+pub(super) async fn table_stats(client: &OracleClient) -> Result<Vec<TableStats>, CollectorError> {
+    debug!("Collecting table statistics");
+
+    Ok(client
+        .fetch_all(queries::TABLE_STATS)
+        .await?
+        .iter()
+        .map(|row| TableStats {
+            schema: text(row, "schema_name").unwrap_or_default(),
+            table: text(row, "table_name").unwrap_or_default(),
+            n_live_tup: int(row, "num_rows"),
+            last_analyze: timestamp(row, "last_analyzed"),
+            n_mod_since_analyze: int(row, "n_mod_since_analyze"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Per-index usage of the user schemas.
+pub(super) async fn index_stats(client: &OracleClient) -> Result<Vec<IndexStats>, CollectorError> {
+    debug!("Collecting index statistics");
+
+    Ok(client
+        .fetch_all(queries::INDEX_USAGE)
+        .await?
+        .iter()
+        .map(|row| IndexStats {
+            schema: text(row, "schema_name").unwrap_or_default(),
+            table: text(row, "table_name").unwrap_or_default(),
+            index: text(row, "index_name").unwrap_or_default(),
+            idx_scan: int(row, "idx_scan"),
+            idx_tup_read: int(row, "idx_tup_read"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+pub(super) async fn settings(
+    client: &OracleClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting Oracle parameters");
+
+    let details: Vec<Setting> = client
+        .fetch_all(queries::PARAMETERS)
+        .await?
+        .iter()
+        .filter_map(parameter)
+        .collect();
+
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+fn parameter(row: &Row) -> Option<Setting> {
+    let name = text(row, "name")?;
+    let value = text(row, "value").unwrap_or_default();
+    let value_type = int(row, "type");
+    // Big integers are sizes in bytes; `display_value` shows them as `2G`
+    let value_bytes = (value_type == Some(6))
+        .then(|| value.parse::<i64>().ok())
+        .flatten();
+
+    Some(Setting {
+        unit: value_bytes.map(|_| "B".to_string()),
+        value_bytes,
+        category: Some(
+            if flag(row, "isbasic") {
+                "basic"
+            } else {
+                "advanced"
+            }
+            .to_string(),
+        ),
+        value_type: match value_type {
+            Some(1) => Some("bool"),
+            Some(2) | Some(4) => Some("string"),
+            Some(3) | Some(6) => Some("integer"),
+            _ => None,
+        }
+        .map(str::to_string),
+        source: Some(parameter_source(row).to_string()),
+        is_default: flag(row, "isdefault"),
+        name,
+        value,
+        ..Default::default()
+    })
+}
+
+/// `ALTER SYSTEM` since startup, else the parameter file unless the value
+/// is the default
+fn parameter_source(row: &Row) -> &'static str {
+    if text(row, "ismodified").as_deref() == Some("SYSTEM_MOD") {
+        "alter system"
+    } else if flag(row, "isdefault") {
+        "default"
+    } else {
+        "parameter file"
+    }
+}
+
+/// Non-idle wait events, by total time waited.
+pub(super) async fn wait_events(
+    client: &OracleClient,
+) -> Result<Vec<OracleWaitEvent>, CollectorError> {
+    debug!("Collecting wait events");
+
+    Ok(client
+        .fetch_all(queries::SYSTEM_EVENTS)
+        .await?
+        .iter()
+        .filter_map(wait_event)
+        .collect())
+}
+
+fn wait_event(row: &Row) -> Option<OracleWaitEvent> {
+    let total_waits = int(row, "total_waits");
+    let time_waited_ms = float(row, "time_waited_micro").map(|us| us / 1000.0);
+    Some(OracleWaitEvent {
+        event: text(row, "event")?,
+        wait_class: text(row, "wait_class"),
+        total_waits,
+        total_timeouts: int(row, "total_timeouts"),
+        time_waited_ms,
+        average_wait_ms: time_waited_ms
+            .zip(total_waits.filter(|w| *w > 0))
+            .map(|(time, waits)| time / waits as f64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, &str)]) -> Row {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_query_stat() {
+        let stat = query_stat(&row(&[
+            ("sql_id", "7b2twsn8vgfsc"),
+            ("sql_text", "SELECT * FROM orders WHERE id = :1"),
+            ("calls", "4"),
+            ("total_time_ms", "6000.5"),
+            ("rows_processed", "4"),
+            ("buffer_gets", "120"),
+            ("disk_reads", "20"),
+        ]));
+        assert_eq!(stat.query_hash.as_deref(), Some("7b2twsn8vgfsc"));
+        assert_eq!(stat.mean_time_ms, Some(1500.125));
+        assert_eq!(stat.shared_blks_hit, Some(100));
+        assert_eq!(stat.shared_blks_read, Some(20));
+    }
+
+    #[test]
+    fn test_parameter() {
+        let sga = parameter(&row(&[
+            ("name", "sga_target"),
+            ("value", "1610612736"),
+            ("type", "6"),
+            ("isdefault", "FALSE"),
+            ("isbasic", "TRUE"),
+            ("ismodified", "SYSTEM_MOD"),
+        ]))
+        .unwrap();
+        assert_eq!(sga.value_bytes, Some(1_610_612_736));
+        assert_eq!(sga.value_type.as_deref(), Some("integer"));
+        assert_eq!(sga.category.as_deref(), Some("basic"));
+        assert_eq!(sga.source.as_deref(), Some("alter system"));
+        assert!(!sga.is_default);
+
+        let cursor_sharing = parameter(&row(&[
+            ("name", "cursor_sharing"),
+            ("value", "EXACT"),
+            ("type", "2"),
+            ("isdefault", "TRUE"),
+            ("isbasic", "FALSE"),
+            ("ismodified", "FALSE"),
+        ]))
+        .unwrap();
+        assert_eq!(cursor_sharing.value_bytes, None);
+        assert_eq!(cursor_sharing.source.as_deref(), Some("default"));
+        assert!(cursor_sharing.is_default);
+    }
+
+    #[test]
+    fn test_wait_event() {
+        let event = wait_event(&row(&[
+            ("event", "db file sequential read"),
+            ("wait_class", "User I/O"),
+            ("total_waits", "1000"),
+            ("total_timeouts", "0"),
+            ("time_waited_micro", "2500000"),
+        ]))
+        .unwrap();
+        assert_eq!(event.time_waited_ms, Some(2500.0));
+        assert_eq!(event.average_wait_ms, Some(2.5));
+        assert!(wait_event(&row(&[("total_waits", "1")])).is_none());
+    }
+
+    #[test]
+    fn test_query_stats_excludes_agent_module() {
+        assert!(queries::QUERY_STATS.contains(&format!("'{}'", super::super::connection::MODULE)));
+    }
+}
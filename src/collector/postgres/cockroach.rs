@@ -0,0 +1,342 @@
+//! CockroachDB support.
+//!
+//! CockroachDB speaks the Postgres protocol and emulates much of
+//! `pg_catalog`, but none of the statistics views the generic collection
+//! relies on: there is no `pg_stat_statements`, `pg_stat_user_tables` is
+//! empty, relation sizes are per range rather than per relation, and
+//! `pg_settings` only lists session variables. Instead this module reads:
+//!
+//! - statement statistics from `crdb_internal.statement_statistics`
+//! - cluster settings from `SHOW CLUSTER SETTINGS`, with durations and byte
+//!   sizes normalized like Postgres settings
+//! - tables, columns, indexes and views from `information_schema`, with row
+//!   estimates from table statistics and index reads from
+//!   `crdb_internal.index_usage_statistics`
+//! - range replicas and leaseholders per node from `crdb_internal.ranges`
+
+use super::queries;
+use super::DatabaseSlice;
+use crate::collector::CollectorError;
+use crate::config::SchemasConfig;
+use crate::payload::{
+    ColumnMetadata, Distribution, DistributionNode, IndexMetadata, IndexStats, QueryStats,
+    SchemaMetadata, Setting, TableMetadata, TableStats, ViewMetadata,
+};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Top statements by total service latency.
+pub(super) async fn query_stats(pool: &PgPool) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics from crdb_internal.statement_statistics");
+
+    let rows = sqlx::query_as::<_, queries::CrdbStatementRow>(queries::CRDB_STATEMENT_STATISTICS)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| QueryStats {
+            query_hash: Some(row.fingerprint_id),
+            query: row.query,
+            mean_time_ms: match (row.total_time_ms, row.calls) {
+                (Some(total), Some(calls)) if calls > 0 => Some(total / calls as f64),
+                _ => None,
+            },
+            calls: row.calls,
+            total_time_ms: row.total_time_ms,
+            rows: row.rows_returned,
+            database: row.datname,
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Cluster settings as a flat map and as structured [`Setting`]s.
+pub(super) async fn settings(
+    pool: &PgPool,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting cluster settings");
+
+    let rows = sqlx::query(queries::CRDB_CLUSTER_SETTINGS)
+        .fetch_all(pool)
+        .await?;
+
+    let details: Vec<Setting> = rows.iter().filter_map(setting_from_row).collect();
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+fn setting_from_row(row: &PgRow) -> Option<Setting> {
+    let name: String = row.try_get("variable").ok()?;
+    let value: String = row.try_get("value").ok()?;
+    let setting_type: Option<String> = row.try_get("setting_type").ok();
+    // Only reported by recent releases
+    let origin: Option<String> = row.try_get("origin").ok();
+    let default_value: Option<String> = row.try_get("default_value").ok();
+
+    let (value_bytes, value_ms) = match setting_type.as_deref() {
+        Some("z") => (parse_byte_size(&value), None),
+        Some("d") => (None, parse_duration_ms(&value)),
+        _ => (None, None),
+    };
+    let is_default = match (&origin, &default_value) {
+        (Some(origin), _) => origin == "default",
+        (None, Some(default)) => *default == value,
+        (None, None) => false,
+    };
+
+    Some(Setting {
+        name,
+        value,
+        value_bytes,
+        value_ms,
+        value_type: setting_type.map(|t| setting_type_name(&t).to_string()),
+        source: origin,
+        boot_value: default_value,
+        is_default,
+        ..Default::default()
+    })
+}
+
+/// Spell out the one-letter `setting_type` codes
+fn setting_type_name(code: &str) -> &str {
+    match code {
+        "b" => "bool",
+        "i" => "integer",
+        "f" => "real",
+        "s" => "string",
+        "e" => "enum",
+        "d" => "duration",
+        "z" => "byte_size",
+        "m" => "version",
+        "p" => "protobuf",
+        other => other,
+    }
+}
+
+/// Parse a Go duration such as `1h30m0s`, `500ms` or `2.5s` into
+/// milliseconds.
+fn parse_duration_ms(value: &str) -> Option<f64> {
+    let mut rest = value.trim();
+    if rest == "0" {
+        return Some(0.0);
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let n: f64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let ms_per = match &rest[..unit] {
+            "h" => 3_600_000.0,
+            "m" => 60_000.0,
+            "s" => 1_000.0,
+            "ms" => 1.0,
+            "us" | "µs" => 0.001,
+            "ns" => 0.000_001,
+            _ => return None,
+        };
+        total += n * ms_per;
+        rest = &rest[unit..];
+    }
+    Some(total)
+}
+
+/// Parse a humanized byte size such as `64 MiB` or `0 B` into bytes.
+fn parse_byte_size(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let digits = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let n: f64 = value[..digits].parse().ok()?;
+    let multiplier: f64 = match value[digits..].trim() {
+        "" | "B" => 1.0,
+        "KiB" | "KB" => 1024.0,
+        "MiB" | "MB" => 1024.0 * 1024.0,
+        "GiB" | "GB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" | "TB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "PiB" | "PB" => 1024.0 * 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((n * multiplier) as i64)
+}
+
+/// Range placement per node.
+pub(super) async fn ranges(pool: &PgPool) -> Result<Distribution, CollectorError> {
+    debug!("Collecting range distribution from crdb_internal.ranges");
+
+    let rows = sqlx::query_as::<_, queries::CrdbNodeRangesRow>(queries::CRDB_RANGES_BY_NODE)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(Distribution {
+        unit: "range".to_string(),
+        total_units: rows.first().and_then(|row| row.total_ranges),
+        nodes: rows
+            .into_iter()
+            .map(|row| DistributionNode {
+                node: row.node_id.to_string(),
+                address: row.address,
+                locality: row.locality,
+                replicas: row.replicas,
+                leaders: row.leaseholders,
+                size_bytes: row.size_bytes,
+                ..Default::default()
+            })
+            .collect(),
+        tables: Vec::new(),
+    })
+}
+
+/// Schema, table and index data of the database `pool` is connected to.
+pub(super) async fn collect_database(
+    pool: &PgPool,
+    filter: &SchemasConfig,
+) -> Result<DatabaseSlice, CollectorError> {
+    debug!("Collecting CockroachDB schema metadata");
+
+    let (table_rows, column_rows, index_rows, view_rows, usage_rows) = tokio::try_join!(
+        sqlx::query_as::<_, queries::CrdbTableRow>(queries::CRDB_TABLES).fetch_all(pool),
+        sqlx::query_as::<_, queries::CrdbColumnRow>(queries::CRDB_COLUMNS).fetch_all(pool),
+        sqlx::query_as::<_, queries::CrdbIndexRow>(queries::CRDB_INDEXES).fetch_all(pool),
+        sqlx::query_as::<_, queries::CrdbViewRow>(queries::CRDB_VIEWS).fetch_all(pool),
+        sqlx::query_as::<_, queries::CrdbIndexUsageRow>(queries::CRDB_INDEX_USAGE).fetch_all(pool),
+    )?;
+
+    let mut columns: HashMap<(String, String), Vec<ColumnMetadata>> = HashMap::new();
+    for row in column_rows {
+        if !filter.matches(&row.table_schema) {
+            continue;
+        }
+        columns
+            .entry((row.table_schema, row.table_name))
+            .or_default()
+            .push(ColumnMetadata {
+                name: row.column_name,
+                data_type: row.data_type,
+                nullable: row.nullable,
+                default: row.column_default,
+                position: row.ordinal_position,
+                native_type: row.native_type,
+                max_length: row.max_length,
+                numeric_precision: row.numeric_precision,
+                numeric_scale: row.numeric_scale,
+                generation_expression: row.generation_expression,
+                collation: row.collation_name,
+                ..Default::default()
+            });
+    }
+
+    let table_rows: Vec<_> = table_rows
+        .into_iter()
+        .filter(|row| filter.matches(&row.table_schema))
+        .collect();
+    let table_stats = table_rows
+        .iter()
+        .map(|row| TableStats {
+            schema: row.table_schema.clone(),
+            table: row.table_name.clone(),
+            n_live_tup: row.row_estimate,
+            ..Default::default()
+        })
+        .collect();
+    let tables = table_rows
+        .into_iter()
+        .map(|row| TableMetadata {
+            columns: columns
+                .remove(&(row.table_schema.clone(), row.table_name.clone()))
+                .unwrap_or_default(),
+            schema: row.table_schema,
+            name: row.table_name,
+            row_count_estimate: row.row_estimate,
+            ..Default::default()
+        })
+        .collect();
+
+    let views = view_rows
+        .into_iter()
+        .filter(|row| filter.matches(&row.table_schema))
+        .map(|row| ViewMetadata {
+            columns: columns
+                .remove(&(row.table_schema.clone(), row.table_name.clone()))
+                .unwrap_or_default(),
+            schema: row.table_schema,
+            name: row.table_name,
+            definition: row.view_definition,
+            ..Default::default()
+        })
+        .collect();
+
+    let indexes = index_rows
+        .into_iter()
+        .filter(|row| filter.matches(&row.table_schema))
+        .map(|row| IndexMetadata {
+            schema: row.table_schema,
+            table: row.table_name,
+            name: row.index_name,
+            columns: row.columns.unwrap_or_default(),
+            is_unique: row.is_unique,
+            is_primary: row.is_primary,
+            ..Default::default()
+        })
+        .collect();
+
+    let index_stats = usage_rows
+        .into_iter()
+        .filter(|row| filter.matches(&row.schema_name))
+        .map(|row| IndexStats {
+            schema: row.schema_name,
+            table: row.table_name,
+            index: row.index_name,
+            idx_scan: row.total_reads,
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(DatabaseSlice {
+        table_stats,
+        index_stats,
+        schema: SchemaMetadata {
+            tables,
+            indexes,
+            views,
+            ..Default::default()
+        },
+        timescaledb: None,
+        pgvector: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_ms() {
+        assert_eq!(parse_duration_ms("0s"), Some(0.0));
+        assert_eq!(parse_duration_ms("500ms"), Some(500.0));
+        assert_eq!(parse_duration_ms("1m30s"), Some(90_000.0));
+        assert_eq!(parse_duration_ms("168h0m0s"), Some(604_800_000.0));
+        assert_eq!(parse_duration_ms("2.5s"), Some(2_500.0));
+        assert_eq!(parse_duration_ms("250µs"), Some(0.25));
+        assert_eq!(parse_duration_ms("forever"), None);
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("0 B"), Some(0));
+        assert_eq!(parse_byte_size("512 KiB"), Some(512 * 1024));
+        assert_eq!(parse_byte_size("64 MiB"), Some(64 * 1024 * 1024));
+        assert_eq!(parse_byte_size("1.5 GiB"), Some(3 * 512 * 1024 * 1024));
+        assert_eq!(parse_byte_size("lots"), None);
+    }
+}
@@ -0,0 +1,84 @@
+//! Engines that speak the PostgreSQL wire protocol but differ in catalogs
+//! and statistics views.
+//!
+//! The dialect is detected from `version()` on connect rather than trusted
+//! from the connection URL: a CockroachDB or YugabyteDB cluster behind a
+//! plain hostname looks like any other Postgres URL.
+
+use crate::config::DatabaseType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    /// No `pg_stat_statements`; statement statistics, ranges and cluster
+    /// settings come from `crdb_internal` and `SHOW CLUSTER SETTINGS`
+    Cockroach,
+    /// A PostgreSQL 11 (or 15) fork that keeps the Postgres catalogs and
+    /// adds tablet placement
+    Yugabyte,
+    /// A columnar warehouse forked from PostgreSQL 8.0, collected from its
+    /// `STL`/`SVL`/`SVV`/`STV` system views
+    Redshift,
+}
+
+impl Dialect {
+    /// Identify the engine from its `version()` string, e.g.
+    /// `CockroachDB CCL v23.2.4 (x86_64-pc-linux-gnu, ...)` or
+    /// `PostgreSQL 11.2-YB-2.20.1.0-b0 on x86_64-pc-linux-gnu, ...` or
+    /// `PostgreSQL 8.0.2 on i686-pc-linux-gnu, ..., Redshift 1.0.77467`.
+    pub fn detect(version: &str) -> Self {
+        if version.starts_with("CockroachDB") {
+            Dialect::Cockroach
+        } else if version.contains("-YB-") {
+            Dialect::Yugabyte
+        } else if version.contains("Redshift") {
+            Dialect::Redshift
+        } else {
+            Dialect::Postgres
+        }
+    }
+
+    /// Engine reported in the payload's `database.type`
+    pub fn database_type(self) -> DatabaseType {
+        match self {
+            Dialect::Postgres => DatabaseType::Postgres,
+            Dialect::Cockroach => DatabaseType::Cockroachdb,
+            Dialect::Yugabyte => DatabaseType::Yugabytedb,
+            Dialect::Redshift => DatabaseType::Redshift,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            Dialect::detect("PostgreSQL 16.2 on x86_64-pc-linux-gnu, compiled by gcc"),
+            Dialect::Postgres
+        );
+        assert_eq!(
+            Dialect::detect("CockroachDB CCL v23.2.4 (x86_64-pc-linux-gnu, built 2024/04/08)"),
+            Dialect::Cockroach
+        );
+        assert_eq!(
+            Dialect::detect(
+                "PostgreSQL 11.2-YB-2.20.1.0-b0 on x86_64-pc-linux-gnu, compiled by clang"
+            ),
+            Dialect::Yugabyte
+        );
+        assert_eq!(
+            Dialect::detect(
+                "PostgreSQL 8.0.2 on i686-pc-linux-gnu, compiled by GCC gcc (GCC) 3.4.2 \
+                 20041017 (Red Hat 3.4.2-6.fc3), Redshift 1.0.77467"
+            ),
+            Dialect::Redshift
+        );
+        assert_eq!(
+            Dialect::Cockroach.database_type().to_string(),
+            "cockroachdb"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Structure inside `jsonb` columns.
+//!
+//! Enabled with `collection.postgres.jsonb_sampling`. Each table with
+//! `jsonb` columns is sampled with `TABLESAMPLE SYSTEM`, and the values are
+//! walked with the shared [`SchemaWalker`] as if each row were a document
+//! holding only those columns. The paths below each column are appended to
+//! the table's columns (`payload.customer.id`, `payload.items[]`), and the
+//! number of rows read becomes the table's `document_count_sampled`.
+//!
+//! Each sample runs in its own read-only transaction under a
+//! `statement_timeout`. A table that cannot be sampled is logged and keeps
+//! its declared columns only.
+
+use crate::collector::schema::{pick_sample_size, SchemaWalker};
+use crate::collector::CollectorError;
+use crate::config::JsonbSamplingConfig;
+use crate::payload::{ColumnMetadata, TableMetadata};
+use serde_json::Map;
+use sqlx::{PgPool, Row};
+use tracing::{debug, warn};
+
+/// `TABLESAMPLE SYSTEM` picks whole pages, so the sampled share of rows
+/// varies; read twice the wanted share and cut at the `LIMIT`.
+const OVERSAMPLE: f64 = 2.0;
+
+/// Sample the `jsonb` columns of `tables` and append their inner paths.
+pub(super) async fn sample(
+    pool: &PgPool,
+    tables: &mut [TableMetadata],
+    config: &JsonbSamplingConfig,
+) {
+    for table in tables.iter_mut() {
+        // Partitions hold the same columns as their parent, which covers them
+        if table.parent_table.is_some() {
+            continue;
+        }
+        let columns: Vec<String> = table
+            .columns
+            .iter()
+            .filter(|c| c.data_type == "jsonb")
+            .map(|c| c.name.clone())
+            .collect();
+        if columns.is_empty() {
+            continue;
+        }
+
+        match sample_table(pool, table, &columns, config.statement_timeout_ms).await {
+            Ok(walker) => {
+                table.document_count_sampled = Some(walker.docs_sampled());
+                let nested = nested_columns(walker, &table.columns);
+                table.columns.extend(nested);
+            }
+            Err(e) => {
+                warn!(
+                    schema = %table.schema,
+                    table = %table.name,
+                    error = %e,
+                    "Failed to sample jsonb columns"
+                );
+            }
+        }
+    }
+}
+
+async fn sample_table(
+    pool: &PgPool,
+    table: &TableMetadata,
+    columns: &[String],
+    statement_timeout_ms: u64,
+) -> Result<SchemaWalker, CollectorError> {
+    let limit = pick_sample_size(table.row_count_estimate.unwrap_or(0));
+    let sql = sample_query(
+        &table.schema,
+        &table.name,
+        columns,
+        sample_percent(limit, table.row_count_estimate),
+        limit,
+    );
+    debug!(schema = %table.schema, table = %table.name, limit, "Sampling jsonb columns");
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!(
+        "SET LOCAL statement_timeout = {statement_timeout_ms}"
+    ))
+    .execute(&mut *tx)
+    .await?;
+    let rows = sqlx::query(&sql).fetch_all(&mut *tx).await;
+    tx.rollback().await?;
+
+    let mut walker = SchemaWalker::new();
+    for row in rows? {
+        // SQL NULLs are left out, so they count as absent rather than null
+        let mut document = Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            if let Some(text) = row.try_get::<Option<String>, _>(i)? {
+                let value = serde_json::from_str(&text)
+                    .map_err(|e| CollectorError::InternalError(e.to_string()))?;
+                document.insert(column.clone(), value);
+            }
+        }
+        walker.observe_document(&document);
+    }
+    Ok(walker)
+}
+
+/// Share of the table's pages to read for about `limit` rows, in percent
+fn sample_percent(limit: i64, row_estimate: Option<i64>) -> f64 {
+    // Never analyzed (-1) or no larger than the sample: read everything
+    match row_estimate {
+        Some(rows) if rows > limit => (100.0 * OVERSAMPLE * limit as f64 / rows as f64).min(100.0),
+        _ => 100.0,
+    }
+}
+
+fn sample_query(schema: &str, table: &str, columns: &[String], percent: f64, limit: i64) -> String {
+    let select = columns
+        .iter()
+        .map(|c| format!("{}::text", quote_ident(c)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT {select} FROM {}.{} TABLESAMPLE SYSTEM ({percent}) LIMIT {limit}",
+        quote_ident(schema),
+        quote_ident(table)
+    )
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Paths below the sampled columns, sorted by path and numbered after the
+/// declared columns, so a path keeps its position whatever order a sample
+/// saw it in. The columns themselves are already declared and are left out.
+fn nested_columns(walker: SchemaWalker, declared: &[ColumnMetadata]) -> Vec<ColumnMetadata> {
+    let last_position = declared.iter().map(|c| c.position).max().unwrap_or(0);
+    let mut nested: Vec<ColumnMetadata> = walker
+        .into_columns()
+        .into_iter()
+        .filter(|c| !declared.iter().any(|d| d.name == c.name))
+        .collect();
+    nested.sort_by(|a, b| a.name.cmp(&b.name));
+    nested
+        .into_iter()
+        .zip(last_position + 1..)
+        .map(|(c, position)| ColumnMetadata { position, ..c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sample_percent() {
+        assert_eq!(sample_percent(100, Some(50)), 100.0);
+        assert_eq!(sample_percent(100, Some(-1)), 100.0);
+        assert_eq!(sample_percent(100, None), 100.0);
+        assert_eq!(sample_percent(500, Some(50_000)), 2.0);
+        assert_eq!(sample_percent(1_000, Some(1_500)), 100.0);
+    }
+
+    #[test]
+    fn test_sample_query() {
+        assert_eq!(
+            sample_query(
+                "public",
+                "Events",
+                &["payload".to_string(), "odd\"name".to_string()],
+                2.0,
+                500
+            ),
+            r#"SELECT "payload"::text, "odd""name"::text FROM "public"."Events" TABLESAMPLE SYSTEM (2) LIMIT 500"#
+        );
+    }
+
+    #[test]
+    fn test_nested_columns() {
+        let declared = vec![
+            ColumnMetadata {
+                name: "id".to_string(),
+                data_type: "bigint".to_string(),
+                position: 1,
+                ..Default::default()
+            },
+            ColumnMetadata {
+                name: "payload".to_string(),
+                data_type: "jsonb".to_string(),
+                position: 2,
+                ..Default::default()
+            },
+        ];
+        let mut walker = SchemaWalker::new();
+        // `items` is seen before `customer`
+        for row in [
+            json!({"payload": {"items": [{"sku": "A1"}]}}),
+            json!({"payload": {"customer": {"id": 7}}}),
+            json!({"payload": {"customer": null}}),
+        ] {
+            walker.observe_document(row.as_object().unwrap());
+        }
+
+        let columns = nested_columns(walker, &declared);
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "payload.customer",
+                "payload.customer.id",
+                "payload.items",
+                "payload.items[]",
+                "payload.items[].sku"
+            ]
+        );
+        assert_eq!(columns[0].position, 3);
+        assert_eq!(columns[0].data_type, "mixed");
+        assert!((columns[1].presence_rate.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(columns[4].is_array_element, Some(true));
+    }
+}
@@ -1,27 +1,153 @@
 //! PostgreSQL metrics collector.
 //!
 //! Collects metrics from PostgreSQL databases including:
-//! - Query statistics (pg_stat_statements)
+//! - Query statistics (pg_stat_statements), optionally with EXPLAIN plans
 //! - Table statistics (pg_stat_user_tables)
 //! - Index statistics (pg_stat_user_indexes)
 //! - Configuration settings (pg_settings)
-//! - Schema metadata
-
+//! - Vacuum health and wraparound ages (pg_class, pg_database)
+//! - Database activity, cache hit ratios and I/O (pg_stat_database,
+//!   pg_statio_*, pg_stat_bgwriter/pg_stat_checkpointer, pg_stat_io)
+//! - Extension, role and database privilege inventory (never password hashes)
+//! - Schema metadata: tables, columns, indexes, views, functions, triggers
+//!   and sequences
+//! - TimescaleDB hypertables, continuous aggregates and jobs, with chunks
+//!   folded into their hypertables
+//! - pgvector columns, HNSW/IVFFlat indexes and their settings
+//! - Optionally, the structure inside `jsonb` columns, from sampled rows
+//!
+//! CockroachDB, YugabyteDB and Redshift connect through this collector too.
+//! The engine is detected from `version()` (see `dialect.rs`): CockroachDB
+//! and Redshift are collected from their own system tables instead of the
+//! Postgres views, CockroachDB and YugabyteDB report how their data is
+//! spread over the cluster's nodes, and Redshift reports its WLM queues.
+//!
+//! Table, index and schema data live in each database's own catalog, while
+//! `pg_stat_statements` and `pg_settings` are cluster-wide. With
+//! `collection.postgres.databases` enabled the per-database part is repeated
+//! for every matching database in the cluster over a short-lived connection,
+//! and the cluster-wide part is read once.
+
+mod cockroach;
+mod dialect;
+mod jsonb;
+mod partitions;
+mod pgvector;
+mod plans;
 mod providers;
 mod queries;
+mod redshift;
+mod schema;
+mod settings;
+mod timescaledb;
+mod yugabyte;
 
 use crate::collector::{Collector, CollectorError};
-use crate::config::{DatabaseType, Provider};
+use crate::config::{DatabaseType, PostgresCollectionConfig, Provider, SettingsScope};
 use crate::payload::{
-    ColumnMetadata, DatabaseInfo, IndexMetadata, IndexStats, Payload, QueryStats, SchemaMetadata,
-    TableMetadata, TableStats,
+    CheckpointerStats, DatabaseInfo, DatabasePrivilege, DatabaseStats, Distribution, Extension,
+    IndexStats, Inventory, IoStats, Payload, PgvectorMetadata, QueryStats, RedshiftMetadata, Role,
+    SchemaMetadata, Setting, TableStats, TimescaleMetadata,
 };
 use async_trait::async_trait;
-use sqlx::postgres::{PgPool, PgPoolOptions};
+use dialect::Dialect;
+use sqlx::postgres::types::Oid;
+use sqlx::postgres::{PgConnectOptions, PgPool, PgPoolOptions};
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::time::Duration;
 use tracing::{debug, info, warn};
 
+/// Per-database portion of a collection cycle.
+#[derive(Default)]
+struct DatabaseSlice {
+    table_stats: Vec<TableStats>,
+    index_stats: Vec<IndexStats>,
+    schema: SchemaMetadata,
+    timescaledb: Option<TimescaleMetadata>,
+    pgvector: Option<PgvectorMetadata>,
+}
+
+impl DatabaseSlice {
+    /// Stamp every record with the database it was collected from.
+    fn tag(&mut self, database: &str) {
+        let name = Some(database.to_string());
+        for t in &mut self.table_stats {
+            t.database = name.clone();
+        }
+        for i in &mut self.index_stats {
+            i.database = name.clone();
+        }
+        for t in &mut self.schema.tables {
+            t.database = name.clone();
+        }
+        for i in &mut self.schema.indexes {
+            i.database = name.clone();
+        }
+        for v in &mut self.schema.views {
+            v.database = name.clone();
+        }
+        for f in &mut self.schema.functions {
+            f.database = name.clone();
+        }
+        for t in &mut self.schema.triggers {
+            t.database = name.clone();
+        }
+        for s in &mut self.schema.sequences {
+            s.database = name.clone();
+        }
+        if let Some(timescaledb) = &mut self.timescaledb {
+            for h in &mut timescaledb.hypertables {
+                h.database = name.clone();
+            }
+            for c in &mut timescaledb.continuous_aggregates {
+                c.database = name.clone();
+            }
+            for j in &mut timescaledb.jobs {
+                j.database = name.clone();
+            }
+        }
+        if let Some(pgvector) = &mut self.pgvector {
+            for c in &mut pgvector.columns {
+                c.database = name.clone();
+            }
+            for i in &mut pgvector.indexes {
+                i.database = name.clone();
+            }
+        }
+    }
+
+    fn extend(&mut self, other: DatabaseSlice) {
+        self.table_stats.extend(other.table_stats);
+        self.index_stats.extend(other.index_stats);
+        self.schema.tables.extend(other.schema.tables);
+        self.schema.indexes.extend(other.schema.indexes);
+        self.schema.views.extend(other.schema.views);
+        self.schema.functions.extend(other.schema.functions);
+        self.schema.triggers.extend(other.schema.triggers);
+        self.schema.sequences.extend(other.schema.sequences);
+        match (&mut self.timescaledb, other.timescaledb) {
+            (Some(ours), Some(theirs)) => {
+                ours.hypertables.extend(theirs.hypertables);
+                ours.continuous_aggregates
+                    .extend(theirs.continuous_aggregates);
+                ours.jobs.extend(theirs.jobs);
+            }
+            (ours @ None, theirs) => *ours = theirs,
+            (Some(_), None) => {}
+        }
+        // Settings are per cluster, so the first database's are kept
+        match (&mut self.pgvector, other.pgvector) {
+            (Some(ours), Some(theirs)) => {
+                ours.columns.extend(theirs.columns);
+                ours.indexes.extend(theirs.indexes);
+            }
+            (ours @ None, theirs) => *ours = theirs,
+            (Some(_), None) => {}
+        }
+    }
+}
+
 /// PostgreSQL metrics collector
 pub struct PostgresCollector {
     pool: PgPool,
@@ -29,7 +155,13 @@ pub struct PostgresCollector {
     provider: Provider,
     detected_provider: String,
     version: Option<String>,
+    /// `server_version_num` (e.g. `160002`), used to pick version-specific queries
+    server_version_num: i32,
+    /// Engine behind the connection, detected from `version()`
+    dialect: Dialect,
     database_url: String,
+    options: PostgresCollectionConfig,
+    plan_cache: plans::PlanCache,
 }
 
 impl PostgresCollector {
@@ -81,7 +213,13 @@ impl PostgresCollector {
 
         // Get database version
         let version = Self::get_version(&pool).await?;
-        info!(version = %version, "Connected to PostgreSQL");
+        let dialect = Dialect::detect(&version);
+        // Redshift has no `server_version_num`; it reports PostgreSQL 8.0.2
+        let server_version_num = match dialect {
+            Dialect::Redshift => 80_002,
+            _ => Self::get_server_version_num(&pool).await?,
+        };
+        info!(version = %version, database_type = %dialect.database_type(), "Connected to database");
 
         // Detect provider if set to auto
         let detected_provider = if provider == Provider::Auto {
@@ -97,16 +235,49 @@ impl PostgresCollector {
             provider,
             detected_provider,
             version: Some(version),
+            server_version_num,
+            dialect,
             database_url: database_url.to_string(),
+            options: PostgresCollectionConfig::default(),
+            plan_cache: plans::PlanCache::default(),
         })
     }
 
+    /// Apply PostgreSQL-specific collection options.
+    pub fn with_options(mut self, options: PostgresCollectionConfig) -> Self {
+        self.options = options;
+        self
+    }
+
     async fn get_version(pool: &PgPool) -> Result<String, CollectorError> {
         let row: (String,) = sqlx::query_as("SELECT version()").fetch_one(pool).await?;
         Ok(row.0)
     }
 
+    async fn get_server_version_num(pool: &PgPool) -> Result<i32, CollectorError> {
+        let row: (i32,) = sqlx::query_as("SELECT current_setting('server_version_num')::int")
+            .fetch_one(pool)
+            .await?;
+        Ok(row.0)
+    }
+
     async fn collect_query_stats(&self) -> Result<Vec<QueryStats>, CollectorError> {
+        let mut stats = match self.dialect {
+            Dialect::Cockroach => cockroach::query_stats(&self.pool).await?,
+            Dialect::Redshift => redshift::query_stats(&self.pool).await?,
+            Dialect::Postgres | Dialect::Yugabyte => self.collect_pg_stat_statements().await?,
+        };
+
+        if !self.options.databases.enabled {
+            for stat in &mut stats {
+                stat.database = None;
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn collect_pg_stat_statements(&self) -> Result<Vec<QueryStats>, CollectorError> {
         debug!("Collecting query statistics from pg_stat_statements");
 
         // Check if pg_stat_statements is available
@@ -121,11 +292,12 @@ impl PostgresCollector {
             return Ok(vec![]);
         }
 
-        let rows = sqlx::query_as::<_, queries::PgStatStatementsRow>(queries::PG_STAT_STATEMENTS)
+        let sql = queries::pg_stat_statements(self.server_version_num);
+        let rows = sqlx::query_as::<_, queries::PgStatStatementsRow>(&sql)
             .fetch_all(&self.pool)
             .await?;
 
-        Ok(rows
+        let mut stats: Vec<QueryStats> = rows
             .into_iter()
             .map(|row| QueryStats {
                 query_hash: row.queryid.map(|id| format!("{:x}", id)),
@@ -136,15 +308,45 @@ impl PostgresCollector {
                 rows: row.rows,
                 shared_blks_hit: row.shared_blks_hit,
                 shared_blks_read: row.shared_blks_read,
+                database: row.datname,
+                ..Default::default()
             })
-            .collect())
+            .collect();
+
+        if self.options.plans.enabled {
+            // Statements can only be planned in the database they ran in
+            let current: (String,) = sqlx::query_as("SELECT current_database()")
+                .fetch_one(&self.pool)
+                .await?;
+            let top = stats
+                .iter_mut()
+                .filter(|s| s.database.as_deref() == Some(current.0.as_str()))
+                .take(self.options.plans.top_n)
+                .collect();
+            plans::capture(
+                &self.pool,
+                self.server_version_num,
+                &self.options.plans,
+                &self.plan_cache,
+                top,
+            )
+            .await;
+        }
+
+        Ok(stats)
     }
 
-    async fn collect_table_stats(&self) -> Result<Vec<TableStats>, CollectorError> {
+    async fn collect_table_stats(
+        &self,
+        pool: &PgPool,
+        namespaces: &[Oid],
+    ) -> Result<Vec<TableStats>, CollectorError> {
         debug!("Collecting table statistics from pg_stat_user_tables");
 
-        let rows = sqlx::query_as::<_, queries::PgStatUserTablesRow>(queries::PG_STAT_USER_TABLES)
-            .fetch_all(&self.pool)
+        let sql = queries::pg_stat_user_tables(self.server_version_num);
+        let rows = sqlx::query_as::<_, queries::PgStatUserTablesRow>(&sql)
+            .bind(namespaces)
+            .fetch_all(pool)
             .await?;
 
         Ok(rows
@@ -152,6 +354,7 @@ impl PostgresCollector {
             .map(|row| TableStats {
                 schema: row.schemaname,
                 table: row.relname,
+                database: None,
                 seq_scan: row.seq_scan,
                 seq_tup_read: row.seq_tup_read,
                 idx_scan: row.idx_scan,
@@ -165,16 +368,177 @@ impl PostgresCollector {
                 last_autovacuum: row.last_autovacuum,
                 last_analyze: row.last_analyze,
                 last_autoanalyze: row.last_autoanalyze,
+                n_mod_since_analyze: row.n_mod_since_analyze,
+                n_ins_since_vacuum: row.n_ins_since_vacuum,
+                vacuum_count: row.vacuum_count,
+                autovacuum_count: row.autovacuum_count,
+                analyze_count: row.analyze_count,
+                autoanalyze_count: row.autoanalyze_count,
+                frozen_xid_age: row.frozen_xid_age,
+                min_mxid_age: row.min_mxid_age,
+                bloat_ratio: row.bloat_ratio,
+                toast_size_bytes: row.toast_size_bytes,
+                reloptions: row.reloptions,
+                heap_blks_read: row.heap_blks_read,
+                heap_blks_hit: row.heap_blks_hit,
+                idx_blks_read: row.idx_blks_read,
+                idx_blks_hit: row.idx_blks_hit,
+                toast_blks_read: row.toast_blks_read,
+                toast_blks_hit: row.toast_blks_hit,
+                ..Default::default()
             })
             .collect())
     }
 
-    async fn collect_index_stats(&self) -> Result<Vec<IndexStats>, CollectorError> {
+    /// `None` on CockroachDB and Redshift, which have no `pg_stat_database`
+    /// counters.
+    async fn collect_database_stats(&self) -> Result<Option<DatabaseStats>, CollectorError> {
+        if matches!(self.dialect, Dialect::Cockroach | Dialect::Redshift) {
+            return Ok(None);
+        }
+        debug!("Collecting database-level statistics from pg_stat_database");
+
+        let (row, checkpointer) = tokio::try_join!(
+            sqlx::query_as::<_, queries::DatabaseStatsRow>(queries::DATABASE_STATS)
+                .fetch_one(&self.pool),
+            sqlx::query_as::<_, queries::CheckpointerRow>(queries::checkpointer_stats(
+                self.server_version_num
+            ))
+            .fetch_one(&self.pool),
+        )?;
+        let io = self.collect_io_stats().await?;
+
+        Ok(Some(DatabaseStats {
+            frozen_xid_age: row.frozen_xid_age,
+            min_mxid_age: row.min_mxid_age,
+            xact_commit: row.xact_commit,
+            xact_rollback: row.xact_rollback,
+            blks_read: row.blks_read,
+            blks_hit: row.blks_hit,
+            temp_files: row.temp_files,
+            temp_bytes: row.temp_bytes,
+            deadlocks: row.deadlocks,
+            conflicts: row.conflicts,
+            heap_hit_ratio: hit_ratio(row.heap_hit, row.heap_read),
+            index_hit_ratio: hit_ratio(row.idx_hit, row.idx_read),
+            toast_hit_ratio: hit_ratio(row.toast_hit, row.toast_read),
+            checkpointer: Some(CheckpointerStats {
+                checkpoints_timed: checkpointer.checkpoints_timed,
+                checkpoints_requested: checkpointer.checkpoints_requested,
+                checkpoint_write_time_ms: checkpointer.checkpoint_write_time,
+                checkpoint_sync_time_ms: checkpointer.checkpoint_sync_time,
+                buffers_checkpoint: checkpointer.buffers_checkpoint,
+                buffers_clean: checkpointer.buffers_clean,
+                maxwritten_clean: checkpointer.maxwritten_clean,
+                buffers_backend: checkpointer.buffers_backend,
+                buffers_backend_fsync: checkpointer.buffers_backend_fsync,
+                buffers_alloc: checkpointer.buffers_alloc,
+                stats_reset: checkpointer.stats_reset,
+            }),
+            io,
+        }))
+    }
+
+    /// `pg_stat_io` rows; `None` before PostgreSQL 16.
+    async fn collect_io_stats(&self) -> Result<Option<Vec<IoStats>>, CollectorError> {
+        let Some(sql) = queries::pg_stat_io(self.server_version_num) else {
+            return Ok(None);
+        };
+
+        let rows = sqlx::query_as::<_, queries::PgStatIoRow>(sql)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(Some(
+            rows.into_iter()
+                .map(|row| IoStats {
+                    backend_type: row.backend_type,
+                    object: row.object,
+                    context: row.context,
+                    reads: row.reads,
+                    read_time_ms: row.read_time,
+                    writes: row.writes,
+                    write_time_ms: row.write_time,
+                    extends: row.extends,
+                    hits: row.hits,
+                    evictions: row.evictions,
+                    reuses: row.reuses,
+                    fsyncs: row.fsyncs,
+                })
+                .collect(),
+        ))
+    }
+
+    /// `None` on CockroachDB and Redshift, which have no extensions and
+    /// manage roles and grants through their own statements.
+    async fn collect_inventory(&self) -> Result<Option<Inventory>, CollectorError> {
+        if matches!(self.dialect, Dialect::Cockroach | Dialect::Redshift) {
+            return Ok(None);
+        }
+        debug!("Collecting extension, role and privilege inventory");
+
+        let (extensions, roles, privileges) = tokio::try_join!(
+            sqlx::query_as::<_, queries::ExtensionRow>(queries::EXTENSIONS).fetch_all(&self.pool),
+            sqlx::query_as::<_, queries::RoleRow>(queries::ROLES).fetch_all(&self.pool),
+            sqlx::query_as::<_, queries::DatabasePrivilegeRow>(queries::DATABASE_PRIVILEGES)
+                .fetch_all(&self.pool),
+        )?;
+
+        Ok(Some(Inventory {
+            extensions: extensions
+                .into_iter()
+                .map(|row| Extension {
+                    update_available: update_available(
+                        &row.extversion,
+                        row.default_version.as_deref(),
+                        row.update_targets.as_deref().unwrap_or_default(),
+                    ),
+                    name: row.extname,
+                    version: row.extversion,
+                    schema: Some(row.schema),
+                    default_version: row.default_version,
+                    available_versions: row.available_versions,
+                })
+                .collect(),
+            roles: roles
+                .into_iter()
+                .map(|row| Role {
+                    name: row.rolname,
+                    superuser: row.rolsuper,
+                    inherit: row.rolinherit,
+                    create_role: row.rolcreaterole,
+                    create_db: row.rolcreatedb,
+                    login: row.rolcanlogin,
+                    replication: row.rolreplication,
+                    bypass_rls: row.rolbypassrls,
+                    connection_limit: row.rolconnlimit,
+                    valid_until: row.rolvaliduntil,
+                    member_of: row.member_of,
+                })
+                .collect(),
+            database_privileges: privileges
+                .into_iter()
+                .map(|row| DatabasePrivilege {
+                    database: row.datname,
+                    grantee: row.grantee,
+                    privilege: row.privilege_type,
+                    is_grantable: row.is_grantable,
+                })
+                .collect(),
+        }))
+    }
+
+    async fn collect_index_stats(
+        &self,
+        pool: &PgPool,
+        namespaces: &[Oid],
+    ) -> Result<Vec<IndexStats>, CollectorError> {
         debug!("Collecting index statistics from pg_stat_user_indexes");
 
         let rows =
             sqlx::query_as::<_, queries::PgStatUserIndexesRow>(queries::PG_STAT_USER_INDEXES)
-                .fetch_all(&self.pool)
+                .bind(namespaces)
+                .fetch_all(pool)
                 .await?;
 
         Ok(rows
@@ -186,94 +550,168 @@ impl PostgresCollector {
                 idx_scan: row.idx_scan,
                 idx_tup_read: row.idx_tup_read,
                 idx_tup_fetch: row.idx_tup_fetch,
+                idx_blks_read: row.idx_blks_read,
+                idx_blks_hit: row.idx_blks_hit,
+                ..Default::default()
             })
             .collect())
     }
 
-    async fn collect_settings(&self) -> Result<HashMap<String, String>, CollectorError> {
+    async fn collect_settings(
+        &self,
+    ) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+        match self.dialect {
+            Dialect::Cockroach => return cockroach::settings(&self.pool).await,
+            Dialect::Redshift => return redshift::settings(&self.pool).await,
+            Dialect::Postgres | Dialect::Yugabyte => {}
+        }
         debug!("Collecting database settings from pg_settings");
 
+        let all = self.options.settings == SettingsScope::All;
         let rows = sqlx::query_as::<_, queries::PgSettingsRow>(queries::PG_SETTINGS)
+            .bind(all)
             .fetch_all(&self.pool)
             .await?;
 
-        Ok(rows
-            .into_iter()
-            .map(|row| (row.name, row.setting))
-            .collect())
+        let details: Vec<Setting> = rows.into_iter().map(settings::from_row).collect();
+        let flat = details
+            .iter()
+            .map(|s| (s.name.clone(), s.value.clone()))
+            .collect();
+        Ok((flat, details))
     }
 
-    async fn collect_schema_metadata(&self) -> Result<SchemaMetadata, CollectorError> {
-        debug!("Collecting schema metadata");
+    /// Collect the per-database part of a cycle from `pool`.
+    ///
+    /// Only the schemas passing `collection.postgres.schemas` are collected.
+    async fn collect_database(&self, pool: &PgPool) -> Result<DatabaseSlice, CollectorError> {
+        match self.dialect {
+            Dialect::Cockroach => {
+                return cockroach::collect_database(pool, &self.options.schemas).await
+            }
+            Dialect::Redshift => {
+                return redshift::collect_database(pool, &self.options.schemas).await
+            }
+            Dialect::Postgres | Dialect::Yugabyte => {}
+        }
 
-        // Collect tables.
-        let table_rows = sqlx::query_as::<_, queries::TableInfoRow>(queries::TABLE_INFO)
-            .fetch_all(&self.pool)
-            .await?;
+        let namespaces = schema::namespaces(pool, &self.options.schemas).await?;
+        let (mut table_stats, mut index_stats, mut schema) = tokio::try_join!(
+            self.collect_table_stats(pool, &namespaces),
+            self.collect_index_stats(pool, &namespaces),
+            schema::collect(pool, self.server_version_num, &self.options, &namespaces),
+        )?;
 
-        // Collect columns and group by `(schema, table)` so we can join into
-        // each table's `columns` field below. information_schema.columns is
-        // already ordered by `(table_schema, table_name, ordinal_position)`,
-        // so the per-table list arrives in column order.
-        let column_rows = sqlx::query_as::<_, queries::ColumnInfoRow>(queries::COLUMN_INFO)
-            .fetch_all(&self.pool)
-            .await?;
+        partitions::apply(
+            &mut schema,
+            &mut table_stats,
+            &mut index_stats,
+            &self.options.partitions,
+        );
 
-        let mut columns_by_table: HashMap<(String, String), Vec<ColumnMetadata>> = HashMap::new();
-        for row in column_rows {
-            let key = (row.table_schema.clone(), row.table_name.clone());
-            columns_by_table
-                .entry(key)
-                .or_default()
-                .push(ColumnMetadata {
-                    name: row.column_name,
-                    data_type: row.data_type,
-                    nullable: row.is_nullable.eq_ignore_ascii_case("YES"),
-                    default: row.column_default,
-                    position: row.ordinal_position,
-                    ..Default::default()
-                });
-        }
-
-        let tables: Vec<TableMetadata> = table_rows
-            .into_iter()
-            .map(|row| {
-                let columns = columns_by_table
-                    .remove(&(row.table_schema.clone(), row.table_name.clone()))
-                    .unwrap_or_default();
-                TableMetadata {
-                    schema: row.table_schema,
-                    name: row.table_name,
-                    columns,
-                    row_count_estimate: row.row_estimate,
-                    size_bytes: row.total_bytes,
-                    ..Default::default()
-                }
-            })
-            .collect();
+        if self.options.jsonb_sampling.enabled {
+            jsonb::sample(pool, &mut schema.tables, &self.options.jsonb_sampling).await;
+        }
+
+        // TimescaleDB data is optional: a failure here should not cost the
+        // rest of the cycle
+        let timescaledb = match timescaledb::collect(pool, &self.options.schemas).await {
+            Ok(Some((metadata, chunks))) => {
+                timescaledb::fold_chunks(&mut schema, &mut table_stats, &mut index_stats, &chunks);
+                Some(metadata)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!(error = %e, "Failed to collect TimescaleDB metadata");
+                None
+            }
+        };
+
+        let pgvector = pgvector::collect(pool, &namespaces)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to collect pgvector metadata");
+                None
+            });
+
+        Ok(DatabaseSlice {
+            table_stats,
+            index_stats,
+            schema,
+            timescaledb,
+            pgvector,
+        })
+    }
+
+    /// Range or tablet placement for distributed engines. Like TimescaleDB
+    /// data it is optional, so errors are logged rather than returned.
+    async fn collect_distribution(&self) -> Result<Option<Distribution>, CollectorError> {
+        let result = match self.dialect {
+            Dialect::Postgres | Dialect::Redshift => return Ok(None),
+            Dialect::Cockroach => cockroach::ranges(&self.pool).await,
+            Dialect::Yugabyte => yugabyte::tablets(&self.pool, &self.options.schemas).await,
+        };
+        match result {
+            Ok(distribution) => Ok(Some(distribution)),
+            Err(e) => {
+                warn!(error = %e, "Failed to collect data distribution");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Redshift WLM queues; optional in the same way as
+    /// [`Self::collect_distribution`].
+    async fn collect_redshift(&self) -> Result<Option<RedshiftMetadata>, CollectorError> {
+        if self.dialect != Dialect::Redshift {
+            return Ok(None);
+        }
+        match redshift::wlm(&self.pool).await {
+            Ok(metadata) => Ok(Some(metadata)),
+            Err(e) => {
+                warn!(error = %e, "Failed to collect WLM queues");
+                Ok(None)
+            }
+        }
+    }
 
-        // Collect indexes
-        let index_rows = sqlx::query_as::<_, queries::IndexInfoRow>(queries::INDEX_INFO)
+    /// Collect every other matching database in the cluster, one at a time
+    /// over a single short-lived connection each. A database that cannot be
+    /// reached (e.g. no `CONNECT` privilege) is logged and skipped.
+    async fn collect_other_databases(&self) -> Result<Vec<DatabaseSlice>, CollectorError> {
+        let names: Vec<(String,)> = sqlx::query_as(queries::DATABASE_LIST)
             .fetch_all(&self.pool)
             .await?;
 
-        let indexes: Vec<IndexMetadata> = index_rows
-            .into_iter()
-            .map(|row| IndexMetadata {
-                schema: row.schemaname,
-                table: row.tablename,
-                name: row.indexname,
-                columns: row
-                    .columns
-                    .map(|c| c.split(", ").map(String::from).collect())
-                    .unwrap_or_default(),
-                is_unique: row.is_unique.unwrap_or(false),
-                is_primary: row.is_primary.unwrap_or(false),
-                size_bytes: row.index_size,
-            })
-            .collect();
+        let mut slices = Vec::new();
+        for (name,) in names {
+            if !self.options.databases.matches(&name) {
+                continue;
+            }
+            debug!(database = %name, "Collecting from additional database");
+            match self.collect_other_database(&name).await {
+                Ok(mut slice) => {
+                    slice.tag(&name);
+                    slices.push(slice);
+                }
+                Err(e) => {
+                    warn!(database = %name, error = %e, "Skipping database");
+                }
+            }
+        }
+        Ok(slices)
+    }
 
-        Ok(SchemaMetadata { tables, indexes })
+    async fn collect_other_database(&self, name: &str) -> Result<DatabaseSlice, CollectorError> {
+        let options = PgConnectOptions::from_str(&self.database_url)?.database(name);
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(10))
+            .connect_with(options)
+            .await?;
+        let result = self.collect_database(&pool).await;
+        pool.close().await;
+        result
     }
 }
 
@@ -283,16 +721,41 @@ impl Collector for PostgresCollector {
         info!("Starting metrics collection");
 
         // Collect all metrics concurrently
-        let (query_stats, table_stats, index_stats, settings, schema) = tokio::try_join!(
+        let (
+            query_stats,
+            (settings, setting_details),
+            database_stats,
+            inventory,
+            mut slice,
+            distribution,
+            redshift,
+        ) = tokio::try_join!(
             self.collect_query_stats(),
-            self.collect_table_stats(),
-            self.collect_index_stats(),
             self.collect_settings(),
-            self.collect_schema_metadata(),
+            self.collect_database_stats(),
+            self.collect_inventory(),
+            self.collect_database(&self.pool),
+            self.collect_distribution(),
+            self.collect_redshift(),
         )?;
 
+        if self.options.databases.enabled {
+            let current: (String,) = sqlx::query_as("SELECT current_database()")
+                .fetch_one(&self.pool)
+                .await?;
+            if self.options.databases.matches(&current.0) {
+                slice.tag(&current.0);
+            } else {
+                debug!(database = %current.0, "Database in the URL is filtered out");
+                slice = DatabaseSlice::default();
+            }
+            for other in self.collect_other_databases().await? {
+                slice.extend(other);
+            }
+        }
+
         let database_info = DatabaseInfo {
-            database_type: "postgres".to_string(),
+            database_type: self.dialect.database_type().to_string(),
             version: self.version.clone(),
             provider: self.detected_provider.clone(),
             provider_metadata: providers::get_provider_metadata(
@@ -303,13 +766,32 @@ impl Collector for PostgresCollector {
             .unwrap_or_default(),
         };
 
-        let payload = Payload::new(database_info)
+        let mut payload = Payload::new(database_info)
             .with_instance_id(&self.database_url)
             .with_query_stats(query_stats)
-            .with_table_stats(table_stats)
-            .with_index_stats(index_stats)
+            .with_table_stats(slice.table_stats)
+            .with_index_stats(slice.index_stats)
             .with_settings(settings)
-            .with_schema(schema);
+            .with_setting_details(setting_details)
+            .with_schema(slice.schema);
+        if let Some(database_stats) = database_stats {
+            payload = payload.with_database_stats(database_stats);
+        }
+        if let Some(inventory) = inventory {
+            payload = payload.with_inventory(inventory);
+        }
+        if let Some(timescaledb) = slice.timescaledb {
+            payload = payload.with_timescaledb(timescaledb);
+        }
+        if let Some(pgvector) = slice.pgvector {
+            payload = payload.with_pgvector(pgvector);
+        }
+        if let Some(distribution) = distribution {
+            payload = payload.with_distribution(distribution);
+        }
+        if let Some(redshift) = redshift {
+            payload = payload.with_redshift(redshift);
+        }
 
         info!(
             tables = payload.schema.as_ref().map(|s| s.tables.len()).unwrap_or(0),
@@ -342,10 +824,27 @@ impl Collector for PostgresCollector {
     }
 
     fn database_type(&self) -> DatabaseType {
-        DatabaseType::Postgres
+        self.dialect.database_type()
     }
 }
 
+/// Whether `ALTER EXTENSION ... UPDATE` would move an extension from
+/// `installed` to a different `default_version`, i.e. an update script path
+/// leads there. An extension installed at a version newer than the default
+/// has none.
+fn update_available(installed: &str, default_version: Option<&str>, targets: &[String]) -> bool {
+    default_version
+        .is_some_and(|default| default != installed && targets.iter().any(|t| t == default))
+}
+
+/// Share of block requests served from shared buffers; `None` before any
+/// block was requested.
+fn hit_ratio(hit: Option<i64>, read: Option<i64>) -> Option<f64> {
+    let hit = hit.unwrap_or(0);
+    let total = hit + read.unwrap_or(0);
+    (total > 0).then(|| hit as f64 / total as f64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +854,33 @@ mod tests {
         assert_eq!(Provider::Auto.to_string(), "auto");
         assert_eq!(Provider::Rds.to_string(), "rds");
     }
+
+    #[test]
+    fn test_update_available() {
+        let targets =
+            |versions: &[&str]| -> Vec<String> { versions.iter().map(|v| v.to_string()).collect() };
+        assert!(update_available(
+            "1.4",
+            Some("1.6"),
+            &targets(&["1.5", "1.6"])
+        ));
+        // Already at the default
+        assert!(!update_available("1.6", Some("1.6"), &targets(&[])));
+        // Installed at a version newer than the control file's default
+        assert!(!update_available("1.7", Some("1.6"), &targets(&[])));
+        // No script leads to the default
+        assert!(!update_available("0.9", Some("1.6"), &targets(&["1.0"])));
+        // Control file gone
+        assert!(!update_available("1.4", None, &targets(&[])));
+    }
+
+    #[test]
+    fn test_hit_ratio() {
+        assert_eq!(hit_ratio(Some(90), Some(10)), Some(0.9));
+        assert_eq!(hit_ratio(Some(0), Some(10)), Some(0.0));
+        assert_eq!(hit_ratio(Some(10), None), Some(1.0));
+        // No blocks requested yet, or no user tables at all
+        assert_eq!(hit_ratio(Some(0), Some(0)), None);
+        assert_eq!(hit_ratio(None, None), None);
+    }
 }
@@ -0,0 +1,481 @@
+//! Declarative partitioning support.
+//!
+//! Postgres reports every partition as an independent relation, so a table
+//! with thousands of time-based partitions floods the payload. The helpers
+//! here rebuild the partition hierarchy from the parent links captured in
+//! [`TableMetadata`] and then, depending on [`PartitionConfig`]:
+//!
+//! - annotate each partitioned table with its leaf `partition_count`
+//! - roll leaf sizes and counters up into every partitioned ancestor
+//! - collapse partition trees larger than `collapse_threshold`, removing the
+//!   partitions (and their indexes and stats) and keeping only the root
+//!
+//! Only leaves contribute to a rollup: partitioned tables have no storage of
+//! their own, so summing intermediate levels would double-count.
+
+use crate::config::PartitionConfig;
+use crate::payload::{IndexStats, SchemaMetadata, TableMetadata, TableStats};
+use std::collections::{HashMap, HashSet};
+
+type TableKey = (String, String);
+type IndexKey = (String, String, String);
+
+/// Parent links between partitions and their partitioned tables.
+struct PartitionTree {
+    parent_of: HashMap<TableKey, TableKey>,
+    partitioned: HashSet<TableKey>,
+}
+
+impl PartitionTree {
+    fn from_tables(tables: &[TableMetadata]) -> Self {
+        let mut parent_of = HashMap::new();
+        let mut partitioned = HashSet::new();
+        for table in tables {
+            let key = (table.schema.clone(), table.name.clone());
+            if table.partition_strategy.is_some() {
+                partitioned.insert(key.clone());
+            }
+            if let (Some(schema), Some(name)) = (&table.parent_schema, &table.parent_table) {
+                parent_of.insert(key, (schema.clone(), name.clone()));
+            }
+        }
+        Self {
+            parent_of,
+            partitioned,
+        }
+    }
+
+    /// Ancestors of `key`, nearest first.
+    fn ancestors(&self, key: &TableKey) -> Vec<TableKey> {
+        let mut out = Vec::new();
+        let mut current = key;
+        while let Some(parent) = self.parent_of.get(current) {
+            // A cycle is impossible in the catalog; the bound just keeps a
+            // malformed input from looping forever.
+            if out.contains(parent) || out.len() > self.parent_of.len() {
+                break;
+            }
+            out.push(parent.clone());
+            current = parent;
+        }
+        out
+    }
+
+    fn root(&self, key: &TableKey) -> Option<TableKey> {
+        self.ancestors(key).pop()
+    }
+
+    fn is_leaf_partition(&self, key: &TableKey) -> bool {
+        self.parent_of.contains_key(key) && !self.partitioned.contains(key)
+    }
+}
+
+/// Apply partition counting, rollup and collapsing to one collection cycle.
+pub fn apply(
+    schema: &mut SchemaMetadata,
+    table_stats: &mut Vec<TableStats>,
+    index_stats: &mut Vec<IndexStats>,
+    config: &PartitionConfig,
+) {
+    let tree = PartitionTree::from_tables(&schema.tables);
+    if tree.partitioned.is_empty() {
+        return;
+    }
+
+    // Leaf counts per partitioned table.
+    let mut leaf_counts: HashMap<TableKey, i64> = HashMap::new();
+    for key in tree.parent_of.keys() {
+        if tree.is_leaf_partition(key) {
+            for ancestor in tree.ancestors(key) {
+                *leaf_counts.entry(ancestor).or_default() += 1;
+            }
+        }
+    }
+
+    // Roots whose trees are big enough to collapse.
+    let collapsed_roots: HashSet<TableKey> = match config.collapse_threshold {
+        Some(threshold) => tree
+            .partitioned
+            .iter()
+            .filter(|key| !tree.parent_of.contains_key(*key))
+            .filter(|key| leaf_counts.get(*key).copied().unwrap_or(0) > threshold as i64)
+            .cloned()
+            .collect(),
+        None => HashSet::new(),
+    };
+    let removed: HashSet<TableKey> = tree
+        .parent_of
+        .keys()
+        .filter(|key| {
+            tree.root(key)
+                .map(|root| collapsed_roots.contains(&root))
+                .unwrap_or(false)
+        })
+        .cloned()
+        .collect();
+
+    // Which partitioned tables receive rolled-up values.
+    let rolls_up = |key: &TableKey| -> bool {
+        config.rollup_stats
+            || tree
+                .root(key)
+                .map(|root| collapsed_roots.contains(&root))
+                .unwrap_or_else(|| collapsed_roots.contains(key))
+    };
+
+    rollup_table_metadata(&mut schema.tables, &tree, &rolls_up);
+    rollup_table_stats(table_stats, &tree, &rolls_up);
+
+    for table in schema.tables.iter_mut() {
+        let key = (table.schema.clone(), table.name.clone());
+        if let Some(count) = leaf_counts.get(&key) {
+            table.partition_count = Some(*count);
+        }
+        if collapsed_roots.contains(&key) {
+            table.partitions_collapsed = Some(true);
+        }
+    }
+
+    if removed.is_empty() {
+        return;
+    }
+
+    // Map each removed partition index onto the nearest surviving parent
+    // index so its scan counters are not lost.
+    let parent_index: HashMap<IndexKey, IndexKey> = schema
+        .indexes
+        .iter()
+        .filter_map(|idx| {
+            let table_key = (idx.schema.clone(), idx.table.clone());
+            let (parent_schema, parent_table) = tree.parent_of.get(&table_key)?;
+            let parent = idx.parent_index.clone()?;
+            Some((
+                (idx.schema.clone(), idx.table.clone(), idx.name.clone()),
+                (parent_schema.clone(), parent_table.clone(), parent),
+            ))
+        })
+        .collect();
+
+    let mut rolled: HashMap<IndexKey, IndexStats> = HashMap::new();
+    index_stats.retain(|stats| {
+        let table_key = (stats.schema.clone(), stats.table.clone());
+        if !removed.contains(&table_key) {
+            return true;
+        }
+        let mut key = (
+            stats.schema.clone(),
+            stats.table.clone(),
+            stats.index.clone(),
+        );
+        while let Some(parent) = parent_index.get(&key) {
+            key = parent.clone();
+            if !removed.contains(&(key.0.clone(), key.1.clone())) {
+                let target = rolled.entry(key.clone()).or_insert_with(|| IndexStats {
+                    schema: key.0.clone(),
+                    table: key.1.clone(),
+                    index: key.2.clone(),
+                    ..Default::default()
+                });
+                merge_index_stats(target, stats);
+                break;
+            }
+        }
+        false
+    });
+    for stats in index_stats.iter_mut() {
+        let key = (
+            stats.schema.clone(),
+            stats.table.clone(),
+            stats.index.clone(),
+        );
+        if let Some(child) = rolled.remove(&key) {
+            merge_index_stats(stats, &child);
+        }
+    }
+    index_stats.extend(rolled.into_values());
+
+    schema
+        .tables
+        .retain(|t| !removed.contains(&(t.schema.clone(), t.name.clone())));
+    schema
+        .indexes
+        .retain(|i| !removed.contains(&(i.schema.clone(), i.table.clone())));
+    table_stats.retain(|t| !removed.contains(&(t.schema.clone(), t.table.clone())));
+}
+
+fn rollup_table_metadata(
+    tables: &mut [TableMetadata],
+    tree: &PartitionTree,
+    rolls_up: &dyn Fn(&TableKey) -> bool,
+) {
+    let mut sums: HashMap<TableKey, (Option<i64>, Option<i64>)> = HashMap::new();
+    for table in tables.iter() {
+        let key = (table.schema.clone(), table.name.clone());
+        if !tree.is_leaf_partition(&key) {
+            continue;
+        }
+        for ancestor in tree.ancestors(&key) {
+            if rolls_up(&ancestor) {
+                let entry = sums.entry(ancestor).or_default();
+                add(&mut entry.0, table.row_count_estimate);
+                add(&mut entry.1, table.size_bytes);
+            }
+        }
+    }
+    for table in tables.iter_mut() {
+        let key = (table.schema.clone(), table.name.clone());
+        if let Some((rows, bytes)) = sums.remove(&key) {
+            table.row_count_estimate = rows;
+            table.size_bytes = bytes;
+        }
+    }
+}
+
+fn rollup_table_stats(
+    table_stats: &mut Vec<TableStats>,
+    tree: &PartitionTree,
+    rolls_up: &dyn Fn(&TableKey) -> bool,
+) {
+    let mut sums: HashMap<TableKey, TableStats> = HashMap::new();
+    for stats in table_stats.iter() {
+        let key = (stats.schema.clone(), stats.table.clone());
+        if !tree.is_leaf_partition(&key) {
+            continue;
+        }
+        for ancestor in tree.ancestors(&key) {
+            if rolls_up(&ancestor) {
+                let target = sums.entry(ancestor.clone()).or_insert_with(|| TableStats {
+                    schema: ancestor.0.clone(),
+                    table: ancestor.1.clone(),
+                    ..Default::default()
+                });
+                merge_table_stats(target, stats);
+            }
+        }
+    }
+
+    // Partitioned parents appear in pg_stat_user_tables from PostgreSQL 15
+    // with all-zero counters; replace those rather than adding a duplicate.
+    for stats in table_stats.iter_mut() {
+        let key = (stats.schema.clone(), stats.table.clone());
+        if let Some(rolled) = sums.remove(&key) {
+            *stats = rolled;
+        }
+    }
+    table_stats.extend(sums.into_values());
+}
+
+/// Fold a partition's counters into its parent: counters are summed, last
+/// maintenance times and freeze ages take the maximum.
+pub(super) fn merge_table_stats(target: &mut TableStats, child: &TableStats) {
+    add(&mut target.seq_scan, child.seq_scan);
+    add(&mut target.seq_tup_read, child.seq_tup_read);
+    add(&mut target.idx_scan, child.idx_scan);
+    add(&mut target.idx_tup_fetch, child.idx_tup_fetch);
+    add(&mut target.n_tup_ins, child.n_tup_ins);
+    add(&mut target.n_tup_upd, child.n_tup_upd);
+    add(&mut target.n_tup_del, child.n_tup_del);
+    add(&mut target.n_live_tup, child.n_live_tup);
+    add(&mut target.n_dead_tup, child.n_dead_tup);
+    add(&mut target.n_mod_since_analyze, child.n_mod_since_analyze);
+    add(&mut target.n_ins_since_vacuum, child.n_ins_since_vacuum);
+    add(&mut target.vacuum_count, child.vacuum_count);
+    add(&mut target.autovacuum_count, child.autovacuum_count);
+    add(&mut target.analyze_count, child.analyze_count);
+    add(&mut target.autoanalyze_count, child.autoanalyze_count);
+    add(&mut target.toast_size_bytes, child.toast_size_bytes);
+    add(&mut target.heap_blks_read, child.heap_blks_read);
+    add(&mut target.heap_blks_hit, child.heap_blks_hit);
+    add(&mut target.idx_blks_read, child.idx_blks_read);
+    add(&mut target.idx_blks_hit, child.idx_blks_hit);
+    add(&mut target.toast_blks_read, child.toast_blks_read);
+    add(&mut target.toast_blks_hit, child.toast_blks_hit);
+    max(&mut target.last_vacuum, child.last_vacuum);
+    max(&mut target.last_autovacuum, child.last_autovacuum);
+    max(&mut target.last_analyze, child.last_analyze);
+    max(&mut target.last_autoanalyze, child.last_autoanalyze);
+    max(&mut target.frozen_xid_age, child.frozen_xid_age);
+    max(&mut target.min_mxid_age, child.min_mxid_age);
+}
+
+/// Fold a partition index's counters into its parent index.
+pub(super) fn merge_index_stats(target: &mut IndexStats, child: &IndexStats) {
+    add(&mut target.idx_scan, child.idx_scan);
+    add(&mut target.idx_tup_read, child.idx_tup_read);
+    add(&mut target.idx_tup_fetch, child.idx_tup_fetch);
+    add(&mut target.idx_blks_read, child.idx_blks_read);
+    add(&mut target.idx_blks_hit, child.idx_blks_hit);
+}
+
+pub(super) fn add(target: &mut Option<i64>, value: Option<i64>) {
+    if let Some(v) = value {
+        *target = Some(target.unwrap_or(0) + v);
+    }
+}
+
+fn max<T: Ord + Copy>(target: &mut Option<T>, value: Option<T>) {
+    if value > *target {
+        *target = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::IndexMetadata;
+
+    fn table(name: &str, parent: Option<&str>, partitioned: bool, size: i64) -> TableMetadata {
+        TableMetadata {
+            schema: "public".to_string(),
+            name: name.to_string(),
+            size_bytes: Some(size),
+            row_count_estimate: Some(size / 10),
+            partition_strategy: partitioned.then(|| "range".to_string()),
+            parent_schema: parent.map(|_| "public".to_string()),
+            parent_table: parent.map(String::from),
+            ..Default::default()
+        }
+    }
+
+    fn stats(name: &str, seq_scan: i64) -> TableStats {
+        TableStats {
+            schema: "public".to_string(),
+            table: name.to_string(),
+            seq_scan: Some(seq_scan),
+            n_live_tup: Some(seq_scan * 10),
+            frozen_xid_age: Some(seq_scan * 100),
+            ..Default::default()
+        }
+    }
+
+    /// events (range) -> events_a, events_b, events_c (hash) -> events_c0, events_c1
+    fn fixture() -> (SchemaMetadata, Vec<TableStats>, Vec<IndexStats>) {
+        let tables = vec![
+            table("events", None, true, 0),
+            table("events_a", Some("events"), false, 100),
+            table("events_b", Some("events"), false, 200),
+            table("events_c", Some("events"), true, 0),
+            table("events_c0", Some("events_c"), false, 300),
+            table("events_c1", Some("events_c"), false, 400),
+            table("users", None, false, 50),
+        ];
+        let indexes = vec![
+            IndexMetadata {
+                schema: "public".to_string(),
+                table: "events".to_string(),
+                name: "events_ts_idx".to_string(),
+                ..Default::default()
+            },
+            IndexMetadata {
+                schema: "public".to_string(),
+                table: "events_a".to_string(),
+                name: "events_a_ts_idx".to_string(),
+                parent_index: Some("events_ts_idx".to_string()),
+                ..Default::default()
+            },
+        ];
+        let table_stats = vec![
+            stats("events_a", 1),
+            stats("events_b", 2),
+            stats("events_c0", 3),
+            stats("events_c1", 4),
+            stats("users", 5),
+        ];
+        let index_stats = vec![IndexStats {
+            schema: "public".to_string(),
+            table: "events_a".to_string(),
+            index: "events_a_ts_idx".to_string(),
+            idx_scan: Some(7),
+            ..Default::default()
+        }];
+        (
+            SchemaMetadata {
+                tables,
+                indexes,
+                ..Default::default()
+            },
+            table_stats,
+            index_stats,
+        )
+    }
+
+    fn find<'a>(tables: &'a [TableMetadata], name: &str) -> &'a TableMetadata {
+        tables.iter().find(|t| t.name == name).unwrap()
+    }
+
+    #[test]
+    fn counts_leaves_without_rollup() {
+        let (mut schema, mut table_stats, mut index_stats) = fixture();
+        apply(
+            &mut schema,
+            &mut table_stats,
+            &mut index_stats,
+            &PartitionConfig::default(),
+        );
+
+        assert_eq!(schema.tables.len(), 7);
+        assert_eq!(find(&schema.tables, "events").partition_count, Some(4));
+        assert_eq!(find(&schema.tables, "events_c").partition_count, Some(2));
+        assert_eq!(find(&schema.tables, "events").size_bytes, Some(0));
+        assert_eq!(table_stats.len(), 5);
+    }
+
+    #[test]
+    fn rollup_sums_leaves_into_every_ancestor() {
+        let (mut schema, mut table_stats, mut index_stats) = fixture();
+        let config = PartitionConfig {
+            rollup_stats: true,
+            collapse_threshold: None,
+        };
+        apply(&mut schema, &mut table_stats, &mut index_stats, &config);
+
+        assert_eq!(find(&schema.tables, "events").size_bytes, Some(1000));
+        assert_eq!(find(&schema.tables, "events_c").size_bytes, Some(700));
+
+        let events = table_stats.iter().find(|s| s.table == "events").unwrap();
+        assert_eq!(events.seq_scan, Some(10));
+        assert_eq!(events.frozen_xid_age, Some(400));
+        let events_c = table_stats.iter().find(|s| s.table == "events_c").unwrap();
+        assert_eq!(events_c.seq_scan, Some(7));
+        // Partitions are still reported.
+        assert!(table_stats.iter().any(|s| s.table == "events_a"));
+    }
+
+    #[test]
+    fn collapse_removes_partitions_above_threshold() {
+        let (mut schema, mut table_stats, mut index_stats) = fixture();
+        let config = PartitionConfig {
+            rollup_stats: false,
+            collapse_threshold: Some(3),
+        };
+        apply(&mut schema, &mut table_stats, &mut index_stats, &config);
+
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["events", "users"]);
+        let events = find(&schema.tables, "events");
+        assert_eq!(events.partitions_collapsed, Some(true));
+        assert_eq!(events.partition_count, Some(4));
+        assert_eq!(events.size_bytes, Some(1000));
+
+        assert_eq!(schema.indexes.len(), 1);
+        assert_eq!(index_stats.len(), 1);
+        assert_eq!(index_stats[0].index, "events_ts_idx");
+        assert_eq!(index_stats[0].idx_scan, Some(7));
+
+        let tables: Vec<&str> = table_stats.iter().map(|s| s.table.as_str()).collect();
+        assert_eq!(tables.len(), 2);
+        assert!(tables.contains(&"events") && tables.contains(&"users"));
+    }
+
+    #[test]
+    fn collapse_leaves_small_trees_alone() {
+        let (mut schema, mut table_stats, mut index_stats) = fixture();
+        let config = PartitionConfig {
+            rollup_stats: false,
+            collapse_threshold: Some(10),
+        };
+        apply(&mut schema, &mut table_stats, &mut index_stats, &config);
+        assert_eq!(schema.tables.len(), 7);
+        assert_eq!(find(&schema.tables, "events").partitions_collapsed, None);
+    }
+}
@@ -0,0 +1,157 @@
+//! pgvector support.
+//!
+//! pgvector is an extension rather than an engine, so it is detected per
+//! database from `pg_extension`. Where it is installed this module reports
+//! the vector columns with their dimensions, the HNSW and IVFFlat indexes
+//! with the build options that decide their recall and size, and the
+//! `hnsw.*` / `ivfflat.*` settings that tune scans.
+
+use super::{queries, settings};
+use crate::collector::CollectorError;
+use crate::payload::{PgvectorMetadata, VectorColumn, VectorIndex};
+use sqlx::postgres::types::Oid;
+use sqlx::PgPool;
+use tracing::debug;
+
+/// pgvector's defaults for index options that were not set explicitly
+const HNSW_DEFAULT_M: i32 = 16;
+const HNSW_DEFAULT_EF_CONSTRUCTION: i32 = 64;
+const IVFFLAT_DEFAULT_LISTS: i32 = 100;
+
+/// Collect pgvector metadata, or `None` if the extension is not installed
+/// in this database.
+pub(super) async fn collect(
+    pool: &PgPool,
+    namespaces: &[Oid],
+) -> Result<Option<PgvectorMetadata>, CollectorError> {
+    let extension: Option<(String, String)> = sqlx::query_as(queries::PGVECTOR_EXTENSION)
+        .fetch_optional(pool)
+        .await?;
+    let Some((version, ext_schema)) = extension else {
+        return Ok(None);
+    };
+    debug!(version = %version, "Collecting pgvector metadata");
+
+    let (column_rows, index_rows) = tokio::try_join!(
+        sqlx::query_as::<_, queries::PgvectorColumnRow>(queries::PGVECTOR_COLUMNS)
+            .bind(namespaces)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::PgvectorIndexRow>(queries::PGVECTOR_INDEXES)
+            .bind(namespaces)
+            .fetch_all(pool),
+    )?;
+
+    // The extension's settings only exist once its library is loaded in the
+    // session, so load it and read them over the same connection
+    let mut conn = pool.acquire().await?;
+    sqlx::query(&format!("SELECT '[1]'::{ext_schema}.vector"))
+        .execute(&mut *conn)
+        .await?;
+    let setting_rows = sqlx::query_as::<_, queries::PgSettingsRow>(queries::PGVECTOR_SETTINGS)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    let indexes: Vec<VectorIndex> = index_rows
+        .into_iter()
+        .map(|row| {
+            let option = |name| reloption(row.reloptions.as_deref(), name);
+            let hnsw = row.method == "hnsw";
+            VectorIndex {
+                m: hnsw.then(|| option("m").unwrap_or(HNSW_DEFAULT_M)),
+                ef_construction: hnsw
+                    .then(|| option("ef_construction").unwrap_or(HNSW_DEFAULT_EF_CONSTRUCTION)),
+                lists: (row.method == "ivfflat")
+                    .then(|| option("lists").unwrap_or(IVFFLAT_DEFAULT_LISTS)),
+                distance: row.operator_class.as_deref().and_then(distance),
+                database: None,
+                schema: row.schema_name,
+                table: row.table_name,
+                name: row.index_name,
+                method: row.method,
+                column: row.key,
+                operator_class: row.operator_class,
+                size_bytes: row.size_bytes,
+                idx_scan: row.idx_scan,
+                table_rows: row.table_rows,
+                quantization: None,
+            }
+        })
+        .collect();
+
+    let columns = column_rows
+        .into_iter()
+        .map(|row| VectorColumn {
+            indexes: indexes
+                .iter()
+                .filter(|i| {
+                    i.schema == row.schema_name
+                        && i.table == row.table_name
+                        && i.column.as_deref() == Some(row.column_name.as_str())
+                })
+                .map(|i| i.name.clone())
+                .collect(),
+            database: None,
+            schema: row.schema_name,
+            table: row.table_name,
+            column: row.column_name,
+            vector_type: row.vector_type,
+            dimensions: row.dimensions,
+            nullable: !row.not_null,
+        })
+        .collect();
+
+    Ok(Some(PgvectorMetadata {
+        version,
+        columns,
+        indexes,
+        settings: setting_rows.into_iter().map(settings::from_row).collect(),
+    }))
+}
+
+/// Integer value of `name` in an index's `reloptions` (`{m=16,ef_construction=64}`)
+fn reloption(reloptions: Option<&[String]>, name: &str) -> Option<i32> {
+    reloptions?.iter().find_map(|option| {
+        let (key, value) = option.split_once('=')?;
+        (key == name).then(|| value.parse().ok()).flatten()
+    })
+}
+
+/// Distance served by an operator class, e.g. `vector_cosine_ops` → `cosine`
+fn distance(operator_class: &str) -> Option<String> {
+    let distance = match operator_class.strip_suffix("_ops")?.rsplit_once('_')?.1 {
+        "l2" => "l2",
+        "ip" => "inner_product",
+        "cosine" => "cosine",
+        "l1" => "l1",
+        "hamming" => "hamming",
+        "jaccard" => "jaccard",
+        _ => return None,
+    };
+    Some(distance.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloption() {
+        let options = vec!["m=32".to_string(), "ef_construction=128".to_string()];
+        assert_eq!(reloption(Some(&options), "m"), Some(32));
+        assert_eq!(reloption(Some(&options), "ef_construction"), Some(128));
+        assert_eq!(reloption(Some(&options), "lists"), None);
+        assert_eq!(reloption(None, "m"), None);
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("vector_l2_ops").as_deref(), Some("l2"));
+        assert_eq!(distance("halfvec_cosine_ops").as_deref(), Some("cosine"));
+        assert_eq!(
+            distance("sparsevec_ip_ops").as_deref(),
+            Some("inner_product")
+        );
+        assert_eq!(distance("bit_hamming_ops").as_deref(), Some("hamming"));
+        assert_eq!(distance("int4_ops"), None);
+    }
+}
@@ -0,0 +1,281 @@
+//! EXPLAIN plan capture for the most expensive statements.
+//!
+//! Plans come from two places. If `auto_explain` logs JSON plans to a
+//! `jsonlog` destination the agent can read, the most recent logged plan for
+//! each `query_id` is used: it is a real plan for real parameters. Otherwise,
+//! on PostgreSQL 16+, the normalized statement text is planned with
+//! `EXPLAIN (GENERIC_PLAN)`, which accepts `$n` placeholders. Each EXPLAIN
+//! runs in its own read-only transaction under a `statement_timeout` and is
+//! rolled back.
+//!
+//! Generic plans are cached by `query_hash` and only re-explained when the
+//! statement's mean execution time moves by more than [`REFRESH_RATIO`].
+
+use super::queries;
+use crate::collector::CollectorError;
+use crate::config::PlanCaptureConfig;
+use crate::payload::QueryStats;
+use serde_json::Value;
+use sqlx::{Executor, PgPool, Row};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tracing::debug;
+
+/// Relative change in mean execution time that triggers a fresh EXPLAIN.
+const REFRESH_RATIO: f64 = 0.5;
+
+/// How much of the server log tail to scan for `auto_explain` output.
+const AUTO_EXPLAIN_LOG_BYTES: i64 = 4 * 1024 * 1024;
+
+const GENERIC_PLAN: &str = "generic_plan";
+const AUTO_EXPLAIN: &str = "auto_explain";
+
+struct CachedPlan {
+    mean_time_ms: f64,
+    plan: Value,
+    source: &'static str,
+}
+
+/// Plans kept between collection cycles, keyed by `query_hash`.
+#[derive(Default)]
+pub struct PlanCache {
+    entries: Mutex<HashMap<String, CachedPlan>>,
+}
+
+impl PlanCache {
+    fn get(&self, key: &str, mean_time_ms: f64) -> Option<(Value, &'static str)> {
+        let entries = self.entries.lock().unwrap();
+        entries
+            .get(key)
+            .filter(|cached| !needs_refresh(cached.mean_time_ms, mean_time_ms))
+            .map(|cached| (cached.plan.clone(), cached.source))
+    }
+
+    fn insert(&self, key: String, mean_time_ms: f64, plan: Value, source: &'static str) {
+        self.entries.lock().unwrap().insert(
+            key,
+            CachedPlan {
+                mean_time_ms,
+                plan,
+                source,
+            },
+        );
+    }
+
+    /// Drop plans for statements that fell out of the top N.
+    fn retain(&self, keys: &HashSet<String>) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| keys.contains(key));
+    }
+}
+
+/// Attach plans to `stats`, which must already be limited to the statements
+/// to explain. Failures are logged and leave `plan` unset.
+pub async fn capture(
+    pool: &PgPool,
+    server_version_num: i32,
+    config: &PlanCaptureConfig,
+    cache: &PlanCache,
+    stats: Vec<&mut QueryStats>,
+) {
+    let logged = if server_version_num >= 150000 {
+        auto_explain_plans(pool).await.unwrap_or_else(|e| {
+            debug!(error = %e, "auto_explain output not readable");
+            HashMap::new()
+        })
+    } else {
+        HashMap::new()
+    };
+
+    let mut seen = HashSet::new();
+    for stat in stats {
+        let (Some(key), Some(query)) = (stat.query_hash.clone(), stat.query.as_deref()) else {
+            continue;
+        };
+        let mean_time_ms = stat.mean_time_ms.unwrap_or_default();
+        seen.insert(key.clone());
+
+        let plan = if let Some(plan) = logged.get(&key) {
+            cache.insert(key, mean_time_ms, plan.clone(), AUTO_EXPLAIN);
+            Some((plan.clone(), AUTO_EXPLAIN))
+        } else if let Some(cached) = cache.get(&key, mean_time_ms) {
+            Some(cached)
+        } else if server_version_num >= 160000 && is_explainable(query) {
+            match explain_generic(pool, query, config.statement_timeout_ms).await {
+                Ok(plan) => {
+                    cache.insert(key, mean_time_ms, plan.clone(), GENERIC_PLAN);
+                    Some((plan, GENERIC_PLAN))
+                }
+                Err(e) => {
+                    debug!(query_hash = %key, error = %e, "EXPLAIN failed");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if let Some((plan, source)) = plan {
+            stat.plan = Some(plan);
+            stat.plan_source = Some(source.to_string());
+        }
+    }
+    cache.retain(&seen);
+}
+
+/// Run `EXPLAIN (GENERIC_PLAN, FORMAT JSON)` in a read-only transaction.
+///
+/// The statement is sent as plain text with no arguments, which makes sqlx use
+/// the simple query protocol: the `$n` placeholders have no values to bind.
+async fn explain_generic(
+    pool: &PgPool,
+    query: &str,
+    statement_timeout_ms: u64,
+) -> Result<Value, CollectorError> {
+    let mut tx = pool.begin().await?;
+    sqlx::query("SET TRANSACTION READ ONLY")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query(&format!(
+        "SET LOCAL statement_timeout = {statement_timeout_ms}"
+    ))
+    .execute(&mut *tx)
+    .await?;
+    let sql = format!("EXPLAIN (GENERIC_PLAN, FORMAT JSON) {}", query.trim());
+    let row = (&mut *tx).fetch_one(sql.as_str()).await;
+    tx.rollback().await?;
+
+    let text: String = row?.try_get_unchecked(0)?;
+    serde_json::from_str(&text).map_err(|e| CollectorError::InternalError(e.to_string()))
+}
+
+/// Most recent `auto_explain` plan per `query_hash` from the server log tail.
+async fn auto_explain_plans(pool: &PgPool) -> Result<HashMap<String, Value>, CollectorError> {
+    let log: Option<(Option<String>,)> = sqlx::query_as(queries::AUTO_EXPLAIN_LOG_TAIL)
+        .bind(AUTO_EXPLAIN_LOG_BYTES)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(log
+        .and_then(|(log,)| log)
+        .map(|log| parse_auto_explain_log(&log))
+        .unwrap_or_default())
+}
+
+/// Parse `jsonlog` lines, keeping the last plan logged for each `query_id`.
+fn parse_auto_explain_log(log: &str) -> HashMap<String, Value> {
+    let mut plans = HashMap::new();
+    for line in log.lines() {
+        // The tail usually starts mid-line; unparsable lines are skipped
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let Some(query_id) = entry.get("query_id").and_then(Value::as_i64) else {
+            continue;
+        };
+        if query_id == 0 {
+            continue;
+        }
+        let Some(message) = entry.get("message").and_then(Value::as_str) else {
+            continue;
+        };
+        let Some((_, plan)) = message
+            .strip_prefix("duration:")
+            .and_then(|rest| rest.split_once("plan:"))
+        else {
+            continue;
+        };
+        if let Ok(mut plan) = serde_json::from_str::<Value>(plan.trim()) {
+            // The logged text carries literal parameter values; the
+            // normalized text is already in `QueryStats.query`
+            if let Some(plan) = plan.as_object_mut() {
+                plan.remove("Query Text");
+            }
+            plans.insert(format!("{:x}", query_id), plan);
+        }
+    }
+    plans
+}
+
+/// Only single plannable statements can be explained.
+fn is_explainable(query: &str) -> bool {
+    let query = query.trim().trim_end_matches(';');
+    if query.contains(';') {
+        return false;
+    }
+    let keyword = query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    matches!(
+        keyword.as_str(),
+        "select" | "with" | "insert" | "update" | "delete" | "values" | "merge" | "table"
+    )
+}
+
+fn needs_refresh(cached_mean_ms: f64, mean_ms: f64) -> bool {
+    if cached_mean_ms <= 0.0 {
+        return mean_ms > 0.0;
+    }
+    ((mean_ms - cached_mean_ms) / cached_mean_ms).abs() > REFRESH_RATIO
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_plannable_statements_are_explained() {
+        assert!(is_explainable("SELECT * FROM orders WHERE id = $1"));
+        assert!(is_explainable("  with x as (select 1) select * from x;"));
+        assert!(is_explainable(
+            "UPDATE orders SET status = $1 WHERE id = $2"
+        ));
+        assert!(!is_explainable("VACUUM orders"));
+        assert!(!is_explainable("CREATE INDEX ON orders (id)"));
+        assert!(!is_explainable("SELECT 1; DROP TABLE orders"));
+    }
+
+    #[test]
+    fn refresh_only_on_material_change() {
+        assert!(!needs_refresh(10.0, 12.0));
+        assert!(!needs_refresh(10.0, 6.0));
+        assert!(needs_refresh(10.0, 16.0));
+        assert!(needs_refresh(10.0, 4.0));
+        assert!(needs_refresh(0.0, 1.0));
+    }
+
+    #[test]
+    fn cache_reuses_until_mean_time_moves() {
+        let cache = PlanCache::default();
+        cache.insert("abc".to_string(), 10.0, json!({"Plan": {}}), GENERIC_PLAN);
+        assert!(cache.get("abc", 11.0).is_some());
+        assert!(cache.get("abc", 30.0).is_none());
+
+        cache.retain(&HashSet::new());
+        assert!(cache.get("abc", 10.0).is_none());
+    }
+
+    #[test]
+    fn parses_auto_explain_jsonlog_lines() {
+        let plan = json!({"Query Text": "select 1", "Plan": {"Node Type": "Result"}});
+        let message = format!("duration: 1.234 ms  plan:\n{}", plan);
+        let log = format!(
+            "{}\n{}\n{}\n",
+            r#"ial line"}"#,
+            json!({"query_id": -42, "message": message}),
+            json!({"query_id": 7, "message": "statement: select 1"}),
+        );
+
+        let plans = parse_auto_explain_log(&log);
+        assert_eq!(plans.len(), 1);
+        assert_eq!(
+            plans[&format!("{:x}", -42i64)],
+            json!({"Plan": {"Node Type": "Result"}})
+        );
+    }
+}
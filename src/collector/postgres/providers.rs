@@ -6,6 +6,7 @@
 //! - AWS Aurora
 //! - Supabase
 //! - Neon
+//! - Amazon Redshift (provisioned and serverless)
 
 use crate::collector::CollectorError;
 use sqlx::PgPool;
@@ -30,6 +31,14 @@ pub async fn detect_provider(
         return Ok("neon".to_string());
     }
 
+    if url_lower.contains("redshift-serverless.amazonaws.com") {
+        return Ok("redshift_serverless".to_string());
+    }
+
+    if url_lower.contains(".redshift.amazonaws.com") {
+        return Ok("redshift".to_string());
+    }
+
     if url_lower.contains(".rds.amazonaws.com") {
         // Could be RDS or Aurora, need to check further
         return detect_aws_provider(pool).await;
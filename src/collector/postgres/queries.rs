@@ -1,24 +1,42 @@
 //! SQL queries for PostgreSQL metrics collection.
 
 use chrono::{DateTime, Utc};
+use sqlx::postgres::types::Oid;
 use sqlx::FromRow;
 
-/// Query statistics from pg_stat_statements
-pub const PG_STAT_STATEMENTS: &str = r#"
-SELECT
-    queryid,
-    query,
-    calls,
-    total_exec_time,
-    mean_exec_time,
-    rows,
-    shared_blks_hit,
-    shared_blks_read
-FROM pg_stat_statements
-WHERE userid = (SELECT usesysid FROM pg_user WHERE usename = current_user)
-ORDER BY total_exec_time DESC
+/// Query statistics from pg_stat_statements.
+///
+/// The view is cluster-wide, so one read covers every database; `datname`
+/// attributes each statement to the database it ran in.
+///
+/// Before PostgreSQL 13 (and on YugabyteDB, which is based on 11) the timing
+/// columns are `total_time` and `mean_time`.
+pub fn pg_stat_statements(server_version_num: i32) -> String {
+    let (total, mean) = if server_version_num >= 130_000 {
+        ("total_exec_time", "mean_exec_time")
+    } else {
+        ("total_time", "mean_time")
+    };
+    format!(
+        r#"
+SELECT
+    s.queryid,
+    s.query,
+    s.calls,
+    s.{total} AS total_exec_time,
+    s.{mean} AS mean_exec_time,
+    s.rows,
+    s.shared_blks_hit,
+    s.shared_blks_read,
+    d.datname
+FROM pg_stat_statements s
+LEFT JOIN pg_database d ON d.oid = s.dbid
+WHERE s.userid = (SELECT usesysid FROM pg_user WHERE usename = current_user)
+ORDER BY s.{total} DESC
 LIMIT 100
-"#;
+"#
+    )
+}
 
 #[derive(Debug, FromRow)]
 pub struct PgStatStatementsRow {
@@ -30,30 +48,98 @@ pub struct PgStatStatementsRow {
     pub rows: Option<i64>,
     pub shared_blks_hit: Option<i64>,
     pub shared_blks_read: Option<i64>,
+    pub datname: Option<String>,
 }
 
-/// Table statistics from pg_stat_user_tables
-pub const PG_STAT_USER_TABLES: &str = r#"
-SELECT
-    schemaname,
-    relname,
-    seq_scan,
-    seq_tup_read,
-    idx_scan,
-    idx_tup_fetch,
-    n_tup_ins,
-    n_tup_upd,
-    n_tup_del,
-    n_live_tup,
-    n_dead_tup,
-    last_vacuum,
-    last_autovacuum,
-    last_analyze,
-    last_autoanalyze
-FROM pg_stat_user_tables
-ORDER BY n_live_tup DESC
+/// Other databases in the cluster that the agent can connect to
+pub const DATABASE_LIST: &str = r#"
+SELECT datname
+FROM pg_database
+WHERE datallowconn
+    AND NOT datistemplate
+    AND datname <> current_database()
+ORDER BY datname
 "#;
 
+/// Table statistics from pg_stat_user_tables, joined to `pg_class` by OID
+/// for freeze ages, TOAST size and storage-parameter overrides, and to
+/// `pg_statio_user_tables` for buffer cache hits and reads.
+///
+/// `bloat_ratio` is a heuristic: the expected heap page count is derived from
+/// `reltuples` and the per-column `avg_width` in `pg_stats` (plus tuple header
+/// and line pointer), then compared to the actual `relpages`. Only the
+/// statistics of each table's own rows count, not those including
+/// inheritance children. Tables without planner statistics report `NULL`.
+///
+/// `$1` is the namespace OIDs to include (see [`NAMESPACE_LIST`]).
+///
+/// `n_ins_since_vacuum` only exists on PostgreSQL 13+, so the column list is
+/// chosen by server version.
+pub fn pg_stat_user_tables(server_version_num: i32) -> String {
+    let n_ins_since_vacuum = if server_version_num >= 130_000 {
+        "s.n_ins_since_vacuum"
+    } else {
+        "NULL::bigint"
+    };
+    format!(
+        r#"
+WITH row_widths AS (
+    SELECT schemaname, tablename, SUM((1 - null_frac) * avg_width) AS data_width
+    FROM pg_stats
+    WHERE NOT inherited
+    GROUP BY schemaname, tablename
+)
+SELECT
+    s.schemaname,
+    s.relname,
+    s.seq_scan,
+    s.seq_tup_read,
+    s.idx_scan,
+    s.idx_tup_fetch,
+    s.n_tup_ins,
+    s.n_tup_upd,
+    s.n_tup_del,
+    s.n_live_tup,
+    s.n_dead_tup,
+    s.last_vacuum,
+    s.last_autovacuum,
+    s.last_analyze,
+    s.last_autoanalyze,
+    s.n_mod_since_analyze,
+    {n_ins_since_vacuum} AS n_ins_since_vacuum,
+    s.vacuum_count,
+    s.autovacuum_count,
+    s.analyze_count,
+    s.autoanalyze_count,
+    age(c.relfrozenxid)::bigint AS frozen_xid_age,
+    mxid_age(c.relminmxid)::bigint AS min_mxid_age,
+    CASE
+        WHEN c.relpages > 0 AND c.reltuples >= 0 AND w.data_width IS NOT NULL THEN
+            GREATEST(0, 1 - CEIL(
+                c.reltuples * (w.data_width + 28)
+                / (current_setting('block_size')::numeric - 24)
+            ) / c.relpages)::float8
+    END AS bloat_ratio,
+    CASE
+        WHEN c.reltoastrelid <> 0 THEN pg_total_relation_size(c.reltoastrelid)
+    END AS toast_size_bytes,
+    c.reloptions,
+    io.heap_blks_read,
+    io.heap_blks_hit,
+    io.idx_blks_read,
+    io.idx_blks_hit,
+    io.toast_blks_read,
+    io.toast_blks_hit
+FROM pg_stat_user_tables s
+JOIN pg_class c ON c.oid = s.relid
+LEFT JOIN pg_statio_user_tables io ON io.relid = s.relid
+LEFT JOIN row_widths w ON w.schemaname = s.schemaname AND w.tablename = s.relname
+WHERE c.relnamespace = ANY($1)
+ORDER BY s.n_live_tup DESC
+"#
+    )
+}
+
 #[derive(Debug, FromRow)]
 pub struct PgStatUserTablesRow {
     pub schemaname: String,
@@ -71,19 +157,203 @@ pub struct PgStatUserTablesRow {
     pub last_autovacuum: Option<DateTime<Utc>>,
     pub last_analyze: Option<DateTime<Utc>>,
     pub last_autoanalyze: Option<DateTime<Utc>>,
+    pub n_mod_since_analyze: Option<i64>,
+    pub n_ins_since_vacuum: Option<i64>,
+    pub vacuum_count: Option<i64>,
+    pub autovacuum_count: Option<i64>,
+    pub analyze_count: Option<i64>,
+    pub autoanalyze_count: Option<i64>,
+    pub frozen_xid_age: Option<i64>,
+    pub min_mxid_age: Option<i64>,
+    pub bloat_ratio: Option<f64>,
+    pub toast_size_bytes: Option<i64>,
+    pub reloptions: Option<Vec<String>>,
+    pub heap_blks_read: Option<i64>,
+    pub heap_blks_hit: Option<i64>,
+    pub idx_blks_read: Option<i64>,
+    pub idx_blks_hit: Option<i64>,
+    pub toast_blks_read: Option<i64>,
+    pub toast_blks_hit: Option<i64>,
+}
+
+/// Database-level counters for the current database: wraparound ages from
+/// `pg_database`, activity from `pg_stat_database`, and buffer cache hits and
+/// reads summed over `pg_statio_user_tables`, for the hit ratios.
+pub const DATABASE_STATS: &str = r#"
+WITH statio AS (
+    SELECT
+        SUM(heap_blks_hit)::bigint AS heap_hit,
+        SUM(heap_blks_read)::bigint AS heap_read,
+        SUM(idx_blks_hit)::bigint AS idx_hit,
+        SUM(idx_blks_read)::bigint AS idx_read,
+        SUM(toast_blks_hit)::bigint AS toast_hit,
+        SUM(toast_blks_read)::bigint AS toast_read
+    FROM pg_statio_user_tables
+)
+SELECT
+    age(d.datfrozenxid)::bigint AS frozen_xid_age,
+    mxid_age(d.datminmxid)::bigint AS min_mxid_age,
+    sd.xact_commit,
+    sd.xact_rollback,
+    sd.blks_read,
+    sd.blks_hit,
+    sd.temp_files,
+    sd.temp_bytes,
+    sd.deadlocks,
+    sd.conflicts,
+    io.heap_hit,
+    io.heap_read,
+    io.idx_hit,
+    io.idx_read,
+    io.toast_hit,
+    io.toast_read
+FROM pg_database d
+JOIN pg_stat_database sd ON sd.datid = d.oid
+CROSS JOIN statio io
+WHERE d.datname = current_database()
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct DatabaseStatsRow {
+    pub frozen_xid_age: Option<i64>,
+    pub min_mxid_age: Option<i64>,
+    pub xact_commit: Option<i64>,
+    pub xact_rollback: Option<i64>,
+    pub blks_read: Option<i64>,
+    pub blks_hit: Option<i64>,
+    pub temp_files: Option<i64>,
+    pub temp_bytes: Option<i64>,
+    pub deadlocks: Option<i64>,
+    pub conflicts: Option<i64>,
+    pub heap_hit: Option<i64>,
+    pub heap_read: Option<i64>,
+    pub idx_hit: Option<i64>,
+    pub idx_read: Option<i64>,
+    pub toast_hit: Option<i64>,
+    pub toast_read: Option<i64>,
 }
 
-/// Index statistics from pg_stat_user_indexes
+/// Checkpointer and background writer counters.
+///
+/// PostgreSQL 17 moved checkpoint counters to `pg_stat_checkpointer` and
+/// dropped the backend write counters from `pg_stat_bgwriter`; there they are
+/// summed from `pg_stat_io` for client backends instead.
+pub fn checkpointer_stats(server_version_num: i32) -> &'static str {
+    if server_version_num >= 170_000 {
+        r#"
+SELECT
+    c.num_timed AS checkpoints_timed,
+    c.num_requested AS checkpoints_requested,
+    c.write_time AS checkpoint_write_time,
+    c.sync_time AS checkpoint_sync_time,
+    c.buffers_written AS buffers_checkpoint,
+    b.buffers_clean,
+    b.maxwritten_clean,
+    io.writes AS buffers_backend,
+    io.fsyncs AS buffers_backend_fsync,
+    b.buffers_alloc,
+    c.stats_reset
+FROM pg_stat_checkpointer c
+CROSS JOIN pg_stat_bgwriter b
+CROSS JOIN (
+    SELECT SUM(writes)::bigint AS writes, SUM(fsyncs)::bigint AS fsyncs
+    FROM pg_stat_io
+    WHERE backend_type = 'client backend'
+) io
+"#
+    } else {
+        r#"
+SELECT
+    checkpoints_timed,
+    checkpoints_req AS checkpoints_requested,
+    checkpoint_write_time,
+    checkpoint_sync_time,
+    buffers_checkpoint,
+    buffers_clean,
+    maxwritten_clean,
+    buffers_backend,
+    buffers_backend_fsync,
+    buffers_alloc,
+    stats_reset
+FROM pg_stat_bgwriter
+"#
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct CheckpointerRow {
+    pub checkpoints_timed: Option<i64>,
+    pub checkpoints_requested: Option<i64>,
+    pub checkpoint_write_time: Option<f64>,
+    pub checkpoint_sync_time: Option<f64>,
+    pub buffers_checkpoint: Option<i64>,
+    pub buffers_clean: Option<i64>,
+    pub maxwritten_clean: Option<i64>,
+    pub buffers_backend: Option<i64>,
+    pub buffers_backend_fsync: Option<i64>,
+    pub buffers_alloc: Option<i64>,
+    pub stats_reset: Option<DateTime<Utc>>,
+}
+
+/// I/O by backend type, object and context from pg_stat_io, which exists
+/// from PostgreSQL 16.
+pub fn pg_stat_io(server_version_num: i32) -> Option<&'static str> {
+    (server_version_num >= 160_000).then_some(PG_STAT_IO)
+}
+
+/// Combinations that never saw any activity are skipped.
+const PG_STAT_IO: &str = r#"
+SELECT
+    backend_type,
+    object,
+    context,
+    reads,
+    read_time,
+    writes,
+    write_time,
+    extends,
+    hits,
+    evictions,
+    reuses,
+    fsyncs
+FROM pg_stat_io
+WHERE COALESCE(reads, 0) + COALESCE(writes, 0) + COALESCE(extends, 0)
+    + COALESCE(hits, 0) + COALESCE(evictions, 0) + COALESCE(fsyncs, 0) > 0
+ORDER BY backend_type, object, context
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct PgStatIoRow {
+    pub backend_type: String,
+    pub object: String,
+    pub context: String,
+    pub reads: Option<i64>,
+    pub read_time: Option<f64>,
+    pub writes: Option<i64>,
+    pub write_time: Option<f64>,
+    pub extends: Option<i64>,
+    pub hits: Option<i64>,
+    pub evictions: Option<i64>,
+    pub reuses: Option<i64>,
+    pub fsyncs: Option<i64>,
+}
+
+/// Index statistics from pg_stat_user_indexes, for the namespace OIDs in `$1`
 pub const PG_STAT_USER_INDEXES: &str = r#"
 SELECT
-    schemaname,
-    relname,
-    indexrelname,
-    idx_scan,
-    idx_tup_read,
-    idx_tup_fetch
-FROM pg_stat_user_indexes
-ORDER BY idx_scan DESC
+    s.schemaname,
+    s.relname,
+    s.indexrelname,
+    s.idx_scan,
+    s.idx_tup_read,
+    s.idx_tup_fetch,
+    io.idx_blks_read,
+    io.idx_blks_hit
+FROM pg_stat_user_indexes s
+JOIN pg_class c ON c.oid = s.indexrelid
+LEFT JOIN pg_statio_user_indexes io ON io.indexrelid = s.indexrelid
+WHERE c.relnamespace = ANY($1)
+ORDER BY s.idx_scan DESC
 "#;
 
 #[derive(Debug, FromRow)]
@@ -94,13 +364,28 @@ pub struct PgStatUserIndexesRow {
     pub idx_scan: Option<i64>,
     pub idx_tup_read: Option<i64>,
     pub idx_tup_fetch: Option<i64>,
+    pub idx_blks_read: Option<i64>,
+    pub idx_blks_hit: Option<i64>,
 }
 
-/// Relevant database settings from pg_settings
+/// Database settings from pg_settings.
+///
+/// `$1` selects every setting (including extension GUCs) when true;
+/// otherwise only the curated list below is returned.
 pub const PG_SETTINGS: &str = r#"
-SELECT name, setting
+SELECT
+    name,
+    setting,
+    unit,
+    category,
+    vartype,
+    source,
+    sourcefile,
+    boot_val,
+    reset_val,
+    pending_restart
 FROM pg_settings
-WHERE name IN (
+WHERE $1 OR name IN (
     'max_connections',
     'shared_buffers',
     'effective_cache_size',
@@ -117,6 +402,8 @@ WHERE name IN (
     'max_parallel_workers_per_gather',
     'max_parallel_workers',
     'max_parallel_maintenance_workers',
+    'autovacuum_freeze_max_age',
+    'autovacuum_multixact_freeze_max_age',
     'server_version',
     'server_encoding',
     'timezone'
@@ -128,84 +415,455 @@ ORDER BY name
 pub struct PgSettingsRow {
     pub name: String,
     pub setting: String,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+    pub vartype: Option<String>,
+    pub source: Option<String>,
+    pub sourcefile: Option<String>,
+    pub boot_val: Option<String>,
+    pub reset_val: Option<String>,
+    pub pending_restart: Option<bool>,
 }
 
-/// Table information for schema metadata
+/// Schemas whose objects are collected: everything except the system
+/// catalogs, TOAST and temporary schemas. Include/exclude patterns are
+/// applied to this list before the OIDs are passed to the queries below.
+pub const NAMESPACE_LIST: &str = r#"
+SELECT oid, nspname
+FROM pg_namespace
+WHERE nspname NOT IN ('pg_catalog', 'information_schema')
+    AND nspname !~ '^pg_(toast|temp_|toast_temp_)'
+"#;
+
+/// Table information for schema metadata, one page at a time.
+///
+/// `$1` is the namespace OIDs to include. Pages are keyed on the table OID:
+/// `$2` is the last OID of the previous page (0 for the first) and `$3` the
+/// page size.
+///
+/// Partitioned parents (`relkind = 'p'`) have no storage of their own, so
+/// their `total_bytes` is zero until partitions are rolled up. Partitions
+/// carry their parent and bound so the hierarchy can be rebuilt.
 pub const TABLE_INFO: &str = r#"
 SELECT
-    t.table_schema,
-    t.table_name,
-    c.reltuples::bigint as row_estimate,
-    pg_total_relation_size(quote_ident(t.table_schema) || '.' || quote_ident(t.table_name))::bigint as total_bytes
-FROM information_schema.tables t
-JOIN pg_class c ON c.relname = t.table_name
-JOIN pg_namespace n ON n.oid = c.relnamespace AND n.nspname = t.table_schema
-WHERE t.table_schema NOT IN ('pg_catalog', 'information_schema')
-    AND t.table_type = 'BASE TABLE'
-ORDER BY total_bytes DESC
+    c.oid,
+    n.nspname AS table_schema,
+    c.relname AS table_name,
+    c.reltuples::bigint AS row_estimate,
+    pg_total_relation_size(c.oid) AS total_bytes,
+    CASE pt.partstrat
+        WHEN 'r' THEN 'range'
+        WHEN 'l' THEN 'list'
+        WHEN 'h' THEN 'hash'
+    END AS partition_strategy,
+    CASE WHEN pt.partrelid IS NOT NULL THEN pg_get_partkeydef(c.oid) END AS partition_key,
+    pn.nspname AS parent_schema,
+    pc.relname AS parent_table,
+    CASE WHEN c.relispartition THEN pg_get_expr(c.relpartbound, c.oid) END AS partition_bound
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+LEFT JOIN pg_partitioned_table pt ON pt.partrelid = c.oid
+LEFT JOIN pg_inherits i ON i.inhrelid = c.oid AND c.relispartition
+LEFT JOIN pg_class pc ON pc.oid = i.inhparent
+LEFT JOIN pg_namespace pn ON pn.oid = pc.relnamespace
+WHERE c.relkind IN ('r', 'p')
+    AND c.relnamespace = ANY($1)
+    AND c.oid > $2
+ORDER BY c.oid
+LIMIT $3
 "#;
 
 #[derive(Debug, FromRow)]
 pub struct TableInfoRow {
+    pub oid: Oid,
     pub table_schema: String,
     pub table_name: String,
     pub row_estimate: Option<i64>,
     pub total_bytes: Option<i64>,
+    pub partition_strategy: Option<String>,
+    pub partition_key: Option<String>,
+    pub parent_schema: Option<String>,
+    pub parent_table: Option<String>,
+    pub partition_bound: Option<String>,
 }
 
-/// Column information for schema metadata.
+/// Column information for the relations in `$1` (table or view OIDs).
+///
+/// Reads `pg_attribute` directly. `data_type` reproduces the
+/// `information_schema.columns` spelling (`integer`, `ARRAY`,
+/// `USER-DEFINED`, domains reported by their base type) so payloads stay
+/// comparable with earlier agent versions.
 ///
-/// Joined per-table by `collect_schema_metadata` to populate
-/// `TableMetadata.columns`. Excludes `pg_catalog` / `information_schema`
-/// since those are server internals, not user-owned schemas.
-pub const COLUMN_INFO: &str = r#"
+/// Planner statistics come from `pg_stats`. A negative `n_distinct` is a
+/// fraction of the row count, scaled by `reltuples` in
+/// `schema::distinct_count`. `$2` enables the most-common-values lists,
+/// which contain actual column data.
+///
+/// Generated columns (`attgenerated`) exist from PostgreSQL 12.
+pub fn column_info(server_version_num: i32) -> String {
+    let generated = if server_version_num >= 120_000 {
+        "a.attgenerated"
+    } else {
+        "''::\"char\""
+    };
+    format!(
+        r#"
 SELECT
-    table_schema,
-    table_name,
-    column_name,
-    ordinal_position,
-    is_nullable,
-    data_type,
-    column_default
-FROM information_schema.columns
-WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
-ORDER BY table_schema, table_name, ordinal_position
-"#;
+    n.nspname AS table_schema,
+    c.relname AS table_name,
+    a.attname AS column_name,
+    a.attnum::int AS ordinal_position,
+    NOT a.attnotnull AS nullable,
+    CASE
+        WHEN bt.typelem <> 0 AND bt.typlen = -1 THEN 'ARRAY'
+        WHEN btn.nspname = 'pg_catalog' THEN format_type(bt.oid, NULL)
+        ELSE 'USER-DEFINED'
+    END AS data_type,
+    CASE WHEN {generated} = '' THEN pg_get_expr(ad.adbin, ad.adrelid) END AS column_default,
+    format_type(a.atttypid, a.atttypmod) AS native_type,
+    CASE
+        WHEN bt.oid IN (1042, 1043) AND btm.typmod > 4 THEN btm.typmod - 4
+    END AS max_length,
+    CASE bt.oid
+        WHEN 21 THEN 16
+        WHEN 23 THEN 32
+        WHEN 20 THEN 64
+        WHEN 700 THEN 24
+        WHEN 701 THEN 53
+        WHEN 1700 THEN CASE WHEN btm.typmod > 4 THEN ((btm.typmod - 4) >> 16) & 65535 END
+    END AS numeric_precision,
+    CASE
+        WHEN bt.oid IN (21, 23, 20) THEN 0
+        WHEN bt.oid = 1700 AND btm.typmod > 4 THEN (btm.typmod - 4) & 65535
+    END AS numeric_scale,
+    CASE WHEN bt.typelem <> 0 AND bt.typlen = -1 THEN format_type(bt.typelem, NULL) END
+        AS element_type,
+    CASE WHEN t.typtype = 'd' THEN tn.nspname || '.' || t.typname END AS domain,
+    (
+        SELECT array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+        FROM pg_enum e
+        WHERE e.enumtypid = CASE
+            WHEN bt.typelem <> 0 AND bt.typlen = -1 THEN bt.typelem
+            ELSE bt.oid
+        END
+    ) AS enum_labels,
+    CASE a.attidentity WHEN 'a' THEN 'ALWAYS' WHEN 'd' THEN 'BY DEFAULT' END AS identity,
+    CASE WHEN {generated} = 's' THEN pg_get_expr(ad.adbin, ad.adrelid) END
+        AS generation_expression,
+    CASE
+        WHEN (con.nspname, co.collname) <> ('pg_catalog', 'default') THEN co.collname::text
+    END AS collation,
+    col_description(c.oid, a.attnum) AS comment,
+    st.null_frac::float8 AS null_frac,
+    st.n_distinct::float8 AS n_distinct,
+    c.reltuples::float8 AS reltuples,
+    st.avg_width,
+    st.correlation::float8 AS correlation,
+    CASE
+        WHEN $2 AND NOT (bt.typelem <> 0 AND bt.typlen = -1)
+        THEN st.most_common_vals::text::text[]
+    END AS most_common_vals,
+    CASE
+        WHEN $2 AND NOT (bt.typelem <> 0 AND bt.typlen = -1)
+        THEN st.most_common_freqs::float8[]
+    END AS most_common_freqs
+FROM pg_attribute a
+JOIN pg_class c ON c.oid = a.attrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_type t ON t.oid = a.atttypid
+JOIN pg_namespace tn ON tn.oid = t.typnamespace
+-- Domains are described by their base type, as in information_schema
+JOIN pg_type bt ON bt.oid = CASE WHEN t.typtype = 'd' THEN t.typbasetype ELSE t.oid END
+JOIN pg_namespace btn ON btn.oid = bt.typnamespace
+CROSS JOIN LATERAL (
+    SELECT CASE WHEN t.typtype = 'd' THEN t.typtypmod ELSE a.atttypmod END AS typmod
+) btm
+LEFT JOIN pg_attrdef ad ON ad.adrelid = a.attrelid AND ad.adnum = a.attnum
+LEFT JOIN pg_collation co ON co.oid = a.attcollation
+LEFT JOIN pg_namespace con ON con.oid = co.collnamespace
+-- Partitioned parents only have inherited statistics
+LEFT JOIN pg_stats st
+    ON st.schemaname = n.nspname
+    AND st.tablename = c.relname
+    AND st.attname = a.attname
+    AND st.inherited = (c.relkind = 'p')
+WHERE a.attrelid = ANY($1)
+    AND a.attnum > 0
+    AND NOT a.attisdropped
+ORDER BY a.attrelid, a.attnum
+"#
+    )
+}
 
-/// Row shape for `COLUMN_INFO` — one row per column, joined into
-/// `TableMetadata.columns` by `collect_schema_metadata`.
+/// Row shape for `column_info` — one row per column, joined into
+/// `TableMetadata.columns` / `ViewMetadata.columns`.
 #[derive(Debug, FromRow)]
 pub struct ColumnInfoRow {
     pub table_schema: String,
     pub table_name: String,
     pub column_name: String,
     pub ordinal_position: i32,
-    pub is_nullable: String,
+    pub nullable: bool,
     pub data_type: String,
     pub column_default: Option<String>,
+    pub native_type: Option<String>,
+    pub max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub element_type: Option<String>,
+    pub domain: Option<String>,
+    pub enum_labels: Option<Vec<String>>,
+    pub identity: Option<String>,
+    pub generation_expression: Option<String>,
+    pub collation: Option<String>,
+    pub comment: Option<String>,
+    pub null_frac: Option<f64>,
+    pub n_distinct: Option<f64>,
+    pub reltuples: Option<f64>,
+    pub avg_width: Option<i32>,
+    pub correlation: Option<f64>,
+    pub most_common_vals: Option<Vec<String>>,
+    pub most_common_freqs: Option<Vec<f64>>,
+}
+
+/// Constraints on the tables in `$1`, with columns in key order and the
+/// clause as printed by `pg_get_constraintdef`. `contype` is mapped to a
+/// kind by `schema::constraint_kind`.
+pub const CONSTRAINT_INFO: &str = r#"
+SELECT
+    n.nspname AS schema_name,
+    c.relname AS table_name,
+    con.conname AS constraint_name,
+    con.contype::text AS contype,
+    COALESCE(
+        (
+            SELECT array_agg(a.attname::text ORDER BY k.ord)
+            FROM unnest(con.conkey) WITH ORDINALITY AS k(attnum, ord)
+            JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+        ),
+        '{}'
+    ) AS columns,
+    pg_get_constraintdef(con.oid) AS definition,
+    con.convalidated AS validated,
+    con.condeferrable AS deferrable
+FROM pg_constraint con
+JOIN pg_class c ON c.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE con.contype IN ('p', 'u', 'c', 'x', 'f')
+    AND con.conrelid = ANY($1)
+ORDER BY n.nspname, c.relname, con.conname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct ConstraintInfoRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub constraint_name: String,
+    pub contype: String,
+    pub columns: Vec<String>,
+    pub definition: String,
+    pub validated: bool,
+    pub deferrable: bool,
+}
+
+/// Views and materialized views in the namespaces `$1`, excluding
+/// extension-owned ones.
+pub const VIEW_INFO: &str = r#"
+SELECT
+    c.oid,
+    n.nspname AS schema_name,
+    c.relname AS view_name,
+    c.relkind = 'm' AS materialized,
+    pg_get_viewdef(c.oid) AS definition,
+    CASE WHEN c.relkind = 'm' THEN pg_total_relation_size(c.oid) END AS size_bytes,
+    CASE WHEN c.relkind = 'm' AND c.reltuples >= 0 THEN c.reltuples::bigint END AS row_estimate,
+    CASE WHEN c.relkind = 'm' THEN c.relispopulated END AS is_populated
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relkind IN ('v', 'm')
+    AND c.relnamespace = ANY($1)
+    AND NOT EXISTS (
+        SELECT 1 FROM pg_depend d
+        WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid AND d.deptype = 'e'
+    )
+ORDER BY n.nspname, c.relname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct ViewInfoRow {
+    pub oid: Oid,
+    pub schema_name: String,
+    pub view_name: String,
+    pub materialized: bool,
+    pub definition: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub row_estimate: Option<i64>,
+    pub is_populated: Option<bool>,
 }
 
-/// Index information for schema metadata
+/// Functions and procedures in the namespaces `$2`, excluding
+/// extension-owned ones.
+///
+/// `$1` includes the full definition from `pg_get_functiondef` (which does
+/// not accept aggregates). `prokind` replaced `proisagg`/`proiswindow` in
+/// PostgreSQL 11, so the kind expression is chosen by server version.
+pub fn function_info(server_version_num: i32) -> String {
+    let kind = if server_version_num >= 110_000 {
+        "p.prokind"
+    } else {
+        "CASE WHEN p.proisagg THEN 'a' WHEN p.proiswindow THEN 'w' ELSE 'f' END"
+    };
+    format!(
+        r#"
+SELECT
+    n.nspname AS schema_name,
+    p.proname AS function_name,
+    CASE {kind}
+        WHEN 'p' THEN 'procedure'
+        WHEN 'a' THEN 'aggregate'
+        WHEN 'w' THEN 'window'
+        ELSE 'function'
+    END AS kind,
+    l.lanname AS language,
+    pg_get_function_identity_arguments(p.oid) AS arguments,
+    CASE WHEN {kind} <> 'p' THEN pg_get_function_result(p.oid) END AS return_type,
+    CASE p.provolatile
+        WHEN 'i' THEN 'immutable'
+        WHEN 's' THEN 'stable'
+        ELSE 'volatile'
+    END AS volatility,
+    p.prosecdef AS security_definer,
+    CASE WHEN $1 AND {kind} <> 'a' THEN pg_get_functiondef(p.oid) END AS body
+FROM pg_proc p
+JOIN pg_namespace n ON n.oid = p.pronamespace
+LEFT JOIN pg_language l ON l.oid = p.prolang
+WHERE p.pronamespace = ANY($2)
+    AND NOT EXISTS (
+        SELECT 1 FROM pg_depend d
+        WHERE d.classid = 'pg_proc'::regclass AND d.objid = p.oid AND d.deptype = 'e'
+    )
+ORDER BY n.nspname, p.proname
+"#
+    )
+}
+
+#[derive(Debug, FromRow)]
+pub struct FunctionInfoRow {
+    pub schema_name: String,
+    pub function_name: String,
+    pub kind: String,
+    pub language: Option<String>,
+    pub arguments: String,
+    pub return_type: Option<String>,
+    pub volatility: Option<String>,
+    pub security_definer: bool,
+    pub body: Option<String>,
+}
+
+/// User-defined triggers on tables in the namespaces `$1`. Internal
+/// triggers (foreign key enforcement) are skipped. Timing, events and level
+/// are decoded from the `tgtype` bitmask.
+pub const TRIGGER_INFO: &str = r#"
+SELECT
+    n.nspname AS schema_name,
+    c.relname AS table_name,
+    t.tgname AS trigger_name,
+    CASE
+        WHEN t.tgtype & 2 <> 0 THEN 'BEFORE'
+        WHEN t.tgtype & 64 <> 0 THEN 'INSTEAD OF'
+        ELSE 'AFTER'
+    END AS timing,
+    array_remove(ARRAY[
+        CASE WHEN t.tgtype & 4 <> 0 THEN 'INSERT' END,
+        CASE WHEN t.tgtype & 16 <> 0 THEN 'UPDATE' END,
+        CASE WHEN t.tgtype & 8 <> 0 THEN 'DELETE' END,
+        CASE WHEN t.tgtype & 32 <> 0 THEN 'TRUNCATE' END
+    ], NULL) AS events,
+    CASE WHEN t.tgtype & 1 <> 0 THEN 'ROW' ELSE 'STATEMENT' END AS level,
+    fn.nspname || '.' || p.proname AS function_name,
+    t.tgenabled <> 'D' AS enabled,
+    pg_get_triggerdef(t.oid) AS definition
+FROM pg_trigger t
+JOIN pg_class c ON c.oid = t.tgrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_proc p ON p.oid = t.tgfoid
+JOIN pg_namespace fn ON fn.oid = p.pronamespace
+WHERE NOT t.tgisinternal
+    AND c.relnamespace = ANY($1)
+ORDER BY n.nspname, c.relname, t.tgname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct TriggerInfoRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub trigger_name: String,
+    pub timing: String,
+    pub events: Vec<String>,
+    pub level: String,
+    pub function_name: String,
+    pub enabled: bool,
+    pub definition: Option<String>,
+}
+
+/// Sequences in the namespaces `$1`. `last_value` is NULL when the sequence
+/// has never been used or the agent lacks `USAGE`/`SELECT` on it (the same
+/// rule as the `pg_sequences` view).
+pub const SEQUENCE_INFO: &str = r#"
+SELECT
+    n.nspname AS schemaname,
+    c.relname AS sequencename,
+    format_type(s.seqtypid, NULL) AS data_type,
+    s.seqmin AS min_value,
+    s.seqmax AS max_value,
+    s.seqincrement AS increment_by,
+    s.seqcycle AS cycle,
+    CASE
+        WHEN has_sequence_privilege(c.oid, 'SELECT,USAGE') THEN pg_sequence_last_value(c.oid)
+    END AS last_value
+FROM pg_sequence s
+JOIN pg_class c ON c.oid = s.seqrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relnamespace = ANY($1)
+ORDER BY n.nspname, c.relname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct SequenceInfoRow {
+    pub schemaname: String,
+    pub sequencename: String,
+    pub data_type: String,
+    pub min_value: i64,
+    pub max_value: i64,
+    pub increment_by: i64,
+    pub cycle: bool,
+    pub last_value: Option<i64>,
+}
+
+/// Indexes on the tables in `$1`, joined by OID.
 pub const INDEX_INFO: &str = r#"
 SELECT
-    schemaname,
-    tablename,
-    indexname,
-    indexdef,
-    pg_relation_size(quote_ident(schemaname) || '.' || quote_ident(indexname))::bigint as index_size,
-    idx.indisunique as is_unique,
-    idx.indisprimary as is_primary,
+    n.nspname AS schemaname,
+    tc.relname AS tablename,
+    ic.relname AS indexname,
+    pg_get_indexdef(ic.oid) AS indexdef,
+    pg_relation_size(ic.oid) AS index_size,
+    idx.indisunique AS is_unique,
+    idx.indisprimary AS is_primary,
     (
         SELECT string_agg(a.attname, ', ' ORDER BY array_position(idx.indkey, a.attnum))
         FROM pg_attribute a
         WHERE a.attrelid = idx.indrelid
         AND a.attnum = ANY(idx.indkey)
-    ) as columns
-FROM pg_indexes
-JOIN pg_class c ON c.relname = indexname
-JOIN pg_index idx ON idx.indexrelid = c.oid
-WHERE schemaname NOT IN ('pg_catalog', 'information_schema')
-ORDER BY index_size DESC
+    ) AS columns,
+    pi.relname AS parent_index
+FROM pg_index idx
+JOIN pg_class ic ON ic.oid = idx.indexrelid
+JOIN pg_class tc ON tc.oid = idx.indrelid
+JOIN pg_namespace n ON n.oid = tc.relnamespace
+LEFT JOIN pg_inherits ii ON ii.inhrelid = ic.oid
+LEFT JOIN pg_class pi ON pi.oid = ii.inhparent
+WHERE idx.indrelid = ANY($1)
 "#;
 
 #[derive(Debug, FromRow)]
@@ -223,44 +881,965 @@ pub struct IndexInfoRow {
     pub is_unique: Option<bool>,
     pub is_primary: Option<bool>,
     pub columns: Option<String>,
+    pub parent_index: Option<String>,
+}
+
+/// Installed extensions with the version the server would install by
+/// default, and the versions `ALTER EXTENSION ... UPDATE` can reach from the
+/// installed one. `pg_extension_update_paths` needs the control file, so it
+/// is only called for extensions that are still available.
+pub const EXTENSIONS: &str = r#"
+SELECT
+    e.extname,
+    e.extversion,
+    n.nspname AS schema,
+    a.default_version,
+    (
+        SELECT array_agg(v.version::text)
+        FROM pg_available_extension_versions v
+        WHERE v.name = e.extname
+    ) AS available_versions,
+    CASE WHEN a.name IS NOT NULL THEN (
+        SELECT array_agg(p.target::text)
+        FROM pg_extension_update_paths(e.extname) p
+        WHERE p.source = e.extversion AND p.path IS NOT NULL
+    ) END AS update_targets
+FROM pg_extension e
+JOIN pg_namespace n ON n.oid = e.extnamespace
+LEFT JOIN pg_available_extensions a ON a.name = e.extname
+ORDER BY e.extname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct ExtensionRow {
+    pub extname: String,
+    pub extversion: String,
+    pub schema: String,
+    pub default_version: Option<String>,
+    pub available_versions: Option<Vec<String>>,
+    pub update_targets: Option<Vec<String>>,
+}
+
+/// Role attributes from pg_roles.
+///
+/// Reads `pg_roles` rather than `pg_authid` so password hashes are never
+/// selected. Built-in `pg_*` roles are skipped but still appear in
+/// `member_of`.
+pub const ROLES: &str = r#"
+SELECT
+    r.rolname,
+    r.rolsuper,
+    r.rolinherit,
+    r.rolcreaterole,
+    r.rolcreatedb,
+    r.rolcanlogin,
+    r.rolreplication,
+    r.rolbypassrls,
+    r.rolconnlimit,
+    CASE WHEN isfinite(r.rolvaliduntil) THEN r.rolvaliduntil END AS rolvaliduntil,
+    COALESCE(
+        (
+            SELECT array_agg(g.rolname::text ORDER BY g.rolname)
+            FROM pg_auth_members m
+            JOIN pg_roles g ON g.oid = m.roleid
+            WHERE m.member = r.oid
+        ),
+        '{}'
+    ) AS member_of
+FROM pg_roles r
+WHERE r.rolname !~ '^pg_'
+ORDER BY r.rolname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RoleRow {
+    pub rolname: String,
+    pub rolsuper: bool,
+    pub rolinherit: bool,
+    pub rolcreaterole: bool,
+    pub rolcreatedb: bool,
+    pub rolcanlogin: bool,
+    pub rolreplication: bool,
+    pub rolbypassrls: bool,
+    pub rolconnlimit: i32,
+    pub rolvaliduntil: Option<DateTime<Utc>>,
+    pub member_of: Vec<String>,
 }
 
-/// Foreign key information.
+/// Database-level grants (CONNECT, CREATE, TEMPORARY).
 ///
-/// TODO(schema-introspection): scaffolded for FK-graph collection but not
-/// wired into `PostgresCollector::collect`. Either connect this to the
-/// payload (and drop the `dead_code` allow) or remove it.
-#[allow(dead_code)]
-pub const FOREIGN_KEY_INFO: &str = r#"
-SELECT
-    tc.table_schema,
-    tc.table_name,
-    kcu.column_name,
-    ccu.table_schema AS foreign_table_schema,
-    ccu.table_name AS foreign_table_name,
-    ccu.column_name AS foreign_column_name,
-    tc.constraint_name
-FROM information_schema.table_constraints AS tc
-JOIN information_schema.key_column_usage AS kcu
-    ON tc.constraint_name = kcu.constraint_name
-    AND tc.table_schema = kcu.table_schema
-JOIN information_schema.constraint_column_usage AS ccu
-    ON ccu.constraint_name = tc.constraint_name
-    AND ccu.table_schema = tc.table_schema
-WHERE tc.constraint_type = 'FOREIGN KEY'
-    AND tc.table_schema NOT IN ('pg_catalog', 'information_schema')
-"#;
-
-// TODO(schema-introspection): paired with `FOREIGN_KEY_INFO` above. Remove
-// or wire into the collector payload.
-#[derive(Debug, FromRow)]
-#[allow(dead_code)]
-pub struct ForeignKeyRow {
+/// A NULL `datacl` means the built-in defaults apply, so those are expanded
+/// with `acldefault`. Grantee OID 0 is `PUBLIC`.
+pub const DATABASE_PRIVILEGES: &str = r#"
+SELECT
+    d.datname,
+    COALESCE(g.rolname, 'PUBLIC') AS grantee,
+    a.privilege_type,
+    a.is_grantable
+FROM pg_database d
+CROSS JOIN LATERAL aclexplode(COALESCE(d.datacl, acldefault('d', d.datdba))) a
+LEFT JOIN pg_roles g ON g.oid = a.grantee
+WHERE NOT d.datistemplate
+ORDER BY d.datname, grantee, a.privilege_type
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct DatabasePrivilegeRow {
+    pub datname: String,
+    pub grantee: String,
+    pub privilege_type: String,
+    pub is_grantable: bool,
+}
+
+/// Tail of the current `jsonlog` server log, if `auto_explain` writes JSON
+/// plans there. `$1` is the number of bytes to read. Requires PostgreSQL 15+
+/// and `pg_read_server_files` (or superuser); callers treat errors as "not
+/// available".
+pub const AUTO_EXPLAIN_LOG_TAIL: &str = r#"
+SELECT pg_read_file(f, GREATEST(0, (pg_stat_file(f)).size - $1), $1) AS log
+FROM (SELECT pg_current_logfile('jsonlog') AS f) l
+WHERE f IS NOT NULL
+    AND current_setting('auto_explain.log_format', true) = 'json'
+"#;
+
+/// Installed TimescaleDB version and its schema, already quoted for use as
+/// a qualifier in the queries below.
+pub const TIMESCALEDB_EXTENSION: &str = r#"
+SELECT e.extversion, quote_ident(n.nspname) AS schema
+FROM pg_extension e
+JOIN pg_namespace n ON n.oid = e.extnamespace
+WHERE e.extname = 'timescaledb'
+"#;
+
+/// Hypertables with sizes and compression statistics. `ext` is the quoted
+/// extension schema, which holds the size and compression functions.
+pub fn timescaledb_hypertables(ext: &str) -> String {
+    format!(
+        r#"
+SELECT
+    h.hypertable_schema::text,
+    h.hypertable_name::text,
+    h.num_chunks::bigint,
+    h.compression_enabled,
+    s.table_bytes,
+    s.index_bytes,
+    s.toast_bytes,
+    s.total_bytes,
+    cs.number_compressed_chunks::bigint AS compressed_chunks,
+    cs.before_compression_total_bytes,
+    cs.after_compression_total_bytes,
+    {ext}.approximate_row_count(r.oid) AS row_estimate
+FROM timescaledb_information.hypertables h
+CROSS JOIN LATERAL (
+    SELECT format('%I.%I', h.hypertable_schema, h.hypertable_name)::regclass AS oid
+) r
+LEFT JOIN LATERAL {ext}.hypertable_detailed_size(r.oid) s ON true
+LEFT JOIN LATERAL {ext}.hypertable_compression_stats(r.oid) cs ON true
+ORDER BY h.hypertable_schema, h.hypertable_name
+"#
+    )
+}
+
+#[derive(Debug, FromRow)]
+pub struct HypertableRow {
+    pub hypertable_schema: String,
+    pub hypertable_name: String,
+    pub num_chunks: Option<i64>,
+    pub compression_enabled: Option<bool>,
+    pub table_bytes: Option<i64>,
+    pub index_bytes: Option<i64>,
+    pub toast_bytes: Option<i64>,
+    pub total_bytes: Option<i64>,
+    pub compressed_chunks: Option<i64>,
+    pub before_compression_total_bytes: Option<i64>,
+    pub after_compression_total_bytes: Option<i64>,
+    pub row_estimate: Option<i64>,
+}
+
+/// Partitioning dimensions of every hypertable. Time dimensions report the
+/// chunk interval as `time_interval` (or `integer_interval` for integer
+/// time columns); space dimensions report `num_partitions`.
+pub const TIMESCALEDB_DIMENSIONS: &str = r#"
+SELECT
+    hypertable_schema::text,
+    hypertable_name::text,
+    column_name::text,
+    column_type::text,
+    lower(dimension_type) AS dimension_type,
+    time_interval::text,
+    integer_interval,
+    num_partitions::int
+FROM timescaledb_information.dimensions
+ORDER BY hypertable_schema, hypertable_name, dimension_number
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct HypertableDimensionRow {
+    pub hypertable_schema: String,
+    pub hypertable_name: String,
+    pub column_name: String,
+    pub column_type: Option<String>,
+    pub dimension_type: Option<String>,
+    pub time_interval: Option<String>,
+    pub integer_interval: Option<i64>,
+    pub num_partitions: Option<i32>,
+}
+
+/// Chunks (and the internal compressed hypertables holding compressed
+/// chunk data), each mapped to the user-facing hypertable it belongs to.
+pub const TIMESCALEDB_CHUNKS: &str = r#"
+SELECT
+    c.schema_name::text AS relation_schema,
+    c.table_name::text AS relation_name,
+    COALESCE(uh.schema_name, h.schema_name)::text AS hypertable_schema,
+    COALESCE(uh.table_name, h.table_name)::text AS hypertable_name,
+    uh.id IS NOT NULL AS compressed
+FROM _timescaledb_catalog.chunk c
+JOIN _timescaledb_catalog.hypertable h ON h.id = c.hypertable_id
+LEFT JOIN _timescaledb_catalog.hypertable uh ON uh.compressed_hypertable_id = h.id
+WHERE NOT c.dropped
+UNION ALL
+SELECT h.schema_name::text, h.table_name::text, uh.schema_name::text, uh.table_name::text, true
+FROM _timescaledb_catalog.hypertable h
+JOIN _timescaledb_catalog.hypertable uh ON uh.compressed_hypertable_id = h.id
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct TimescaleChunkRow {
+    pub relation_schema: String,
+    pub relation_name: String,
+    pub hypertable_schema: String,
+    pub hypertable_name: String,
+    pub compressed: bool,
+}
+
+/// Chunk indexes mapped to the hypertable index they were created from.
+/// The `chunk_index` catalog does not exist in every release; callers check
+/// for it first.
+pub const TIMESCALEDB_CHUNK_INDEXES: &str = r#"
+SELECT
+    c.schema_name::text AS chunk_schema,
+    c.table_name::text AS chunk_name,
+    ci.index_name::text AS chunk_index,
+    h.schema_name::text AS hypertable_schema,
+    h.table_name::text AS hypertable_name,
+    ci.hypertable_index_name::text AS hypertable_index
+FROM _timescaledb_catalog.chunk_index ci
+JOIN _timescaledb_catalog.chunk c ON c.id = ci.chunk_id
+JOIN _timescaledb_catalog.hypertable h ON h.id = ci.hypertable_id
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct TimescaleChunkIndexRow {
+    pub chunk_schema: String,
+    pub chunk_name: String,
+    pub chunk_index: String,
+    pub hypertable_schema: String,
+    pub hypertable_name: String,
+    pub hypertable_index: String,
+}
+
+/// Continuous aggregates and the hypertable they are built on.
+pub const TIMESCALEDB_CONTINUOUS_AGGREGATES: &str = r#"
+SELECT
+    view_schema::text,
+    view_name::text,
+    hypertable_schema::text,
+    hypertable_name::text,
+    materialized_only,
+    compression_enabled,
+    materialization_hypertable_schema::text,
+    materialization_hypertable_name::text,
+    view_definition
+FROM timescaledb_information.continuous_aggregates
+ORDER BY view_schema, view_name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct ContinuousAggregateRow {
+    pub view_schema: String,
+    pub view_name: String,
+    pub hypertable_schema: String,
+    pub hypertable_name: String,
+    pub materialized_only: Option<bool>,
+    pub compression_enabled: Option<bool>,
+    pub materialization_hypertable_schema: Option<String>,
+    pub materialization_hypertable_name: Option<String>,
+    pub view_definition: Option<String>,
+}
+
+/// Background jobs (policies and user-defined actions) with their run
+/// history. `job_errors` exists from TimescaleDB 2.15; `with_errors` adds
+/// the most recent failure of each job from it. Timestamps that were never
+/// set are `-infinity` in `job_stats` and come back as NULL.
+pub fn timescaledb_jobs(with_errors: bool) -> String {
+    let (error_columns, error_join) = if with_errors {
+        (
+            "e.err_message AS last_error, e.finish_time AS last_error_at",
+            r#"LEFT JOIN LATERAL (
+    SELECT err_message, finish_time
+    FROM timescaledb_information.job_errors je
+    WHERE je.job_id = j.job_id
+    ORDER BY finish_time DESC
+    LIMIT 1
+) e ON true"#,
+        )
+    } else {
+        (
+            "NULL::text AS last_error, NULL::timestamptz AS last_error_at",
+            "",
+        )
+    };
+    format!(
+        r#"
+SELECT
+    j.job_id,
+    j.application_name::text,
+    j.proc_schema::text || '.' || j.proc_name::text AS procedure,
+    j.schedule_interval::text,
+    j.scheduled,
+    j.hypertable_schema::text,
+    j.hypertable_name::text,
+    j.config::text,
+    s.job_status,
+    s.last_run_status,
+    CASE WHEN isfinite(s.last_run_started_at) THEN s.last_run_started_at END
+        AS last_run_started_at,
+    CASE WHEN isfinite(s.last_successful_finish) THEN s.last_successful_finish END
+        AS last_successful_finish,
+    (EXTRACT(EPOCH FROM s.last_run_duration) * 1000)::float8 AS last_run_duration_ms,
+    CASE WHEN isfinite(s.next_start) THEN s.next_start END AS next_start,
+    s.total_runs,
+    s.total_successes,
+    s.total_failures,
+    {error_columns}
+FROM timescaledb_information.jobs j
+LEFT JOIN timescaledb_information.job_stats s ON s.job_id = j.job_id
+{error_join}
+ORDER BY j.job_id
+"#
+    )
+}
+
+#[derive(Debug, FromRow)]
+pub struct TimescaleJobRow {
+    pub job_id: i32,
+    pub application_name: Option<String>,
+    pub procedure: Option<String>,
+    pub schedule_interval: Option<String>,
+    pub scheduled: Option<bool>,
+    pub hypertable_schema: Option<String>,
+    pub hypertable_name: Option<String>,
+    pub config: Option<String>,
+    pub job_status: Option<String>,
+    pub last_run_status: Option<String>,
+    pub last_run_started_at: Option<DateTime<Utc>>,
+    pub last_successful_finish: Option<DateTime<Utc>>,
+    pub last_run_duration_ms: Option<f64>,
+    pub next_start: Option<DateTime<Utc>>,
+    pub total_runs: Option<i64>,
+    pub total_successes: Option<i64>,
+    pub total_failures: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+/// CockroachDB statement statistics, summed over the retained aggregation
+/// intervals. Latencies and row counts are kept as per-execution means (in
+/// seconds for latencies), so totals are `cnt * mean`. Statements run by
+/// CockroachDB itself (`$ internal-*` applications) are left out.
+pub const CRDB_STATEMENT_STATISTICS: &str = r#"
+SELECT
+    encode(fingerprint_id, 'hex') AS fingerprint_id,
+    max(metadata->>'query') AS query,
+    max(metadata->>'db') AS datname,
+    sum((statistics->'statistics'->>'cnt')::INT8)::INT8 AS calls,
+    sum(
+        (statistics->'statistics'->>'cnt')::FLOAT8
+        * (statistics->'statistics'->'svcLat'->>'mean')::FLOAT8
+    ) * 1000 AS total_time_ms,
+    sum(
+        (statistics->'statistics'->>'cnt')::FLOAT8
+        * (statistics->'statistics'->'numRows'->>'mean')::FLOAT8
+    )::INT8 AS rows_returned
+FROM crdb_internal.statement_statistics
+WHERE app_name NOT LIKE '$ internal%'
+GROUP BY fingerprint_id
+ORDER BY total_time_ms DESC
+LIMIT 100
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbStatementRow {
+    pub fingerprint_id: String,
+    pub query: Option<String>,
+    pub datname: Option<String>,
+    pub calls: Option<i64>,
+    pub total_time_ms: Option<f64>,
+    pub rows_returned: Option<i64>,
+}
+
+/// CockroachDB cluster settings. The column set grew over releases
+/// (`default_value` and `origin` are recent), so rows are read by name.
+pub const CRDB_CLUSTER_SETTINGS: &str = "SHOW CLUSTER SETTINGS";
+
+/// Range replicas, leaseholders and replicated bytes per node, with the
+/// cluster-wide range count on every row.
+pub const CRDB_RANGES_BY_NODE: &str = r#"
+WITH ranges AS (
+    SELECT replicas, lease_holder, range_size FROM crdb_internal.ranges
+),
+per_node AS (
+    SELECT
+        n AS node_id,
+        count(*)::INT8 AS replicas,
+        count(*) FILTER (WHERE r.lease_holder = n)::INT8 AS leaseholders,
+        sum(r.range_size)::INT8 AS size_bytes
+    FROM ranges r, unnest(r.replicas) AS n
+    GROUP BY n
+)
+SELECT
+    p.node_id::INT8 AS node_id,
+    g.address,
+    g.locality,
+    p.replicas,
+    p.leaseholders,
+    p.size_bytes,
+    (SELECT count(*) FROM ranges)::INT8 AS total_ranges
+FROM per_node p
+LEFT JOIN crdb_internal.gossip_nodes g ON g.node_id = p.node_id
+ORDER BY p.node_id
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbNodeRangesRow {
+    pub node_id: i64,
+    pub address: Option<String>,
+    pub locality: Option<String>,
+    pub replicas: Option<i64>,
+    pub leaseholders: Option<i64>,
+    pub size_bytes: Option<i64>,
+    pub total_ranges: Option<i64>,
+}
+
+/// Base tables of the current CockroachDB database with the row estimate
+/// from the latest table statistics.
+pub const CRDB_TABLES: &str = r#"
+SELECT
+    t.table_schema,
+    t.table_name,
+    s.estimated_row_count::INT8 AS row_estimate
+FROM information_schema.tables t
+LEFT JOIN crdb_internal.tables ct
+    ON ct.database_name = t.table_catalog
+    AND ct.schema_name = t.table_schema
+    AND ct.name = t.table_name
+    AND ct.drop_time IS NULL
+LEFT JOIN crdb_internal.table_row_statistics s ON s.table_id = ct.table_id
+WHERE t.table_type = 'BASE TABLE'
+    AND t.table_schema NOT IN ('pg_catalog', 'information_schema', 'crdb_internal', 'pg_extension')
+ORDER BY t.table_schema, t.table_name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbTableRow {
+    pub table_schema: String,
+    pub table_name: String,
+    pub row_estimate: Option<i64>,
+}
+
+/// Visible columns of tables and views in the current CockroachDB database.
+/// Hidden columns such as the implicit `rowid` key are left out.
+pub const CRDB_COLUMNS: &str = r#"
+SELECT
+    table_schema,
+    table_name,
+    column_name,
+    data_type,
+    crdb_sql_type AS native_type,
+    is_nullable = 'YES' AS nullable,
+    column_default,
+    ordinal_position::INT4 AS ordinal_position,
+    character_maximum_length::INT4 AS max_length,
+    numeric_precision::INT4 AS numeric_precision,
+    numeric_scale::INT4 AS numeric_scale,
+    NULLIF(generation_expression, '') AS generation_expression,
+    collation_name
+FROM information_schema.columns
+WHERE is_hidden = 'NO'
+    AND table_schema NOT IN ('pg_catalog', 'information_schema', 'crdb_internal', 'pg_extension')
+ORDER BY table_schema, table_name, ordinal_position
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbColumnRow {
     pub table_schema: String,
     pub table_name: String,
     pub column_name: String,
-    pub foreign_table_schema: String,
-    pub foreign_table_name: String,
-    pub foreign_column_name: String,
-    pub constraint_name: String,
+    pub data_type: String,
+    pub native_type: Option<String>,
+    pub nullable: bool,
+    pub column_default: Option<String>,
+    pub ordinal_position: i32,
+    pub max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+    pub generation_expression: Option<String>,
+    pub collation_name: Option<String>,
 }
+
+/// Indexes of the current CockroachDB database with their key columns in
+/// order. Stored (covering) columns and the implicit columns CockroachDB
+/// appends to secondary indexes are not key columns.
+pub const CRDB_INDEXES: &str = r#"
+SELECT
+    s.table_schema,
+    s.table_name,
+    s.index_name,
+    array_agg(s.column_name ORDER BY s.seq_in_index)
+        FILTER (WHERE s.storing = 'NO' AND s.implicit = 'NO') AS columns,
+    bool_and(s.non_unique = 'NO') AS is_unique,
+    EXISTS (
+        SELECT 1
+        FROM information_schema.table_constraints tc
+        WHERE tc.table_schema = s.table_schema
+            AND tc.table_name = s.table_name
+            AND tc.constraint_name = s.index_name
+            AND tc.constraint_type = 'PRIMARY KEY'
+    ) AS is_primary
+FROM information_schema.statistics s
+WHERE s.table_schema NOT IN ('pg_catalog', 'information_schema', 'crdb_internal', 'pg_extension')
+GROUP BY s.table_schema, s.table_name, s.index_name
+ORDER BY s.table_schema, s.table_name, s.index_name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbIndexRow {
+    pub table_schema: String,
+    pub table_name: String,
+    pub index_name: String,
+    pub columns: Option<Vec<String>>,
+    pub is_unique: bool,
+    pub is_primary: bool,
+}
+
+/// Views of the current CockroachDB database
+pub const CRDB_VIEWS: &str = r#"
+SELECT table_schema, table_name, view_definition
+FROM information_schema.views
+WHERE table_schema NOT IN ('pg_catalog', 'information_schema', 'crdb_internal', 'pg_extension')
+ORDER BY table_schema, table_name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbViewRow {
+    pub table_schema: String,
+    pub table_name: String,
+    pub view_definition: Option<String>,
+}
+
+/// Index reads recorded by CockroachDB since the last statistics reset, for
+/// the current database.
+pub const CRDB_INDEX_USAGE: &str = r#"
+SELECT
+    t.schema_name,
+    t.name AS table_name,
+    i.index_name,
+    u.total_reads::INT8 AS total_reads
+FROM crdb_internal.index_usage_statistics u
+JOIN crdb_internal.table_indexes i
+    ON i.descriptor_id = u.table_id AND i.index_id = u.index_id
+JOIN crdb_internal.tables t ON t.table_id = u.table_id
+WHERE t.database_name = current_database()
+    AND t.drop_time IS NULL
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct CrdbIndexUsageRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub index_name: String,
+    pub total_reads: Option<i64>,
+}
+
+/// YugabyteDB tablet servers as seen by the YSQL layer
+pub const YB_SERVERS: &str = r#"
+SELECT
+    host,
+    port::bigint AS port,
+    num_connections::bigint AS num_connections,
+    node_type,
+    cloud,
+    region,
+    zone
+FROM yb_servers()
+ORDER BY host, port
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct YbServerRow {
+    pub host: String,
+    pub port: Option<i64>,
+    pub num_connections: Option<i64>,
+    pub node_type: Option<String>,
+    pub cloud: Option<String>,
+    pub region: Option<String>,
+    pub zone: Option<String>,
+}
+
+/// Whether the `yb_local_tablets` view exists (YugabyteDB 2.18+)
+pub const YB_HAS_LOCAL_TABLETS: &str =
+    "SELECT to_regclass('pg_catalog.yb_local_tablets') IS NOT NULL";
+
+/// YSQL tablets with a replica on the connected tablet server, per table
+pub const YB_LOCAL_TABLETS: &str = r#"
+SELECT
+    namespace_name,
+    ysql_schema_name,
+    table_name,
+    count(*)::bigint AS tablets
+FROM yb_local_tablets
+WHERE table_type = 'YSQL'
+GROUP BY namespace_name, ysql_schema_name, table_name
+ORDER BY namespace_name, ysql_schema_name, table_name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct YbLocalTabletsRow {
+    pub namespace_name: Option<String>,
+    pub ysql_schema_name: Option<String>,
+    pub table_name: String,
+    pub tablets: i64,
+}
+
+/// Redshift query statistics from `STL_QUERY`, grouped by query text, with
+/// returned rows and disk spills from `SVL_QUERY_SUMMARY`. Only completed
+/// queries of regular users are counted (user 1 is `rdsdb`). The STL views
+/// keep a few days of history.
+pub const REDSHIFT_QUERY_STATS: &str = r#"
+SELECT
+    md5(trim(q.querytxt)) AS query_hash,
+    max(trim(q.querytxt)) AS query_text,
+    max(trim(q.database)) AS datname,
+    count(*)::bigint AS calls,
+    sum(datediff(microsecond, q.starttime, q.endtime))::float8 / 1000 AS total_time_ms,
+    sum(s.rows_returned)::bigint AS rows_returned,
+    sum(s.spilled)::bigint AS disk_spills
+FROM stl_query q
+LEFT JOIN (
+    SELECT
+        query,
+        sum(CASE WHEN label LIKE 'return%' THEN rows ELSE 0 END) AS rows_returned,
+        max(CASE WHEN is_diskbased = 't' THEN 1 ELSE 0 END) AS spilled
+    FROM svl_query_summary
+    GROUP BY query
+) s ON s.query = q.query
+WHERE q.userid > 1
+    AND q.aborted = 0
+GROUP BY md5(trim(q.querytxt))
+ORDER BY total_time_ms DESC
+LIMIT 100
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftQueryRow {
+    pub query_hash: String,
+    pub query_text: Option<String>,
+    pub datname: Option<String>,
+    pub calls: Option<i64>,
+    pub total_time_ms: Option<f64>,
+    pub rows_returned: Option<i64>,
+    pub disk_spills: Option<i64>,
+}
+
+/// Redshift tables with size, skew, sort and statistics health from
+/// `SVV_TABLE_INFO`. That view leaves out empty tables, so tables come from
+/// `pg_class` and the distribution style falls back to `reldiststyle`.
+pub const REDSHIFT_TABLE_INFO: &str = r#"
+SELECT
+    trim(n.nspname) AS schema_name,
+    trim(c.relname) AS table_name,
+    COALESCE(trim(t.diststyle), CASE c.reldiststyle
+        WHEN 0 THEN 'EVEN'
+        WHEN 1 THEN 'KEY'
+        WHEN 8 THEN 'ALL'
+        WHEN 10 THEN 'AUTO(ALL)'
+        WHEN 11 THEN 'AUTO(EVEN)'
+        WHEN 12 THEN 'AUTO(KEY)'
+    END) AS diststyle,
+    t.size::bigint * 1048576 AS size_bytes,
+    t.tbl_rows::bigint AS tbl_rows,
+    t.estimated_visible_rows::bigint AS estimated_visible_rows,
+    t.skew_rows::float8 AS skew_rows,
+    t.unsorted::float8 AS unsorted,
+    t.stats_off::float8 AS stats_off
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+LEFT JOIN svv_table_info t ON t.table_id = c.oid
+WHERE c.relkind = 'r'
+    AND n.nspname NOT IN ('pg_catalog', 'information_schema', 'pg_internal', 'pg_automv')
+    AND n.nspname NOT LIKE 'pg_temp%'
+ORDER BY n.nspname, c.relname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftTableRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub diststyle: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub tbl_rows: Option<i64>,
+    pub estimated_visible_rows: Option<i64>,
+    pub skew_rows: Option<f64>,
+    pub unsorted: Option<f64>,
+    pub stats_off: Option<f64>,
+}
+
+/// Distribution key and sort key columns. `attsortkeyord` is the position
+/// in the sort key, negative for interleaved sort keys.
+pub const REDSHIFT_KEY_COLUMNS: &str = r#"
+SELECT
+    trim(n.nspname) AS schema_name,
+    trim(c.relname) AS table_name,
+    trim(a.attname) AS column_name,
+    a.attisdistkey AS is_distkey,
+    a.attsortkeyord::int AS sortkey_ord
+FROM pg_attribute a
+JOIN pg_class c ON c.oid = a.attrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relkind = 'r'
+    AND a.attnum > 0
+    AND NOT a.attisdropped
+    AND (a.attisdistkey OR a.attsortkeyord <> 0)
+ORDER BY n.nspname, c.relname, abs(a.attsortkeyord)
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftKeyColumnRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub is_distkey: bool,
+    pub sortkey_ord: i32,
+}
+
+/// Columns of local tables and views, including late-binding views
+pub const REDSHIFT_COLUMNS: &str = r#"
+SELECT
+    table_schema,
+    table_name,
+    column_name,
+    data_type,
+    is_nullable = 'YES' AS nullable,
+    column_default,
+    ordinal_position::int AS ordinal_position,
+    character_maximum_length::int AS max_length,
+    numeric_precision::int AS numeric_precision,
+    numeric_scale::int AS numeric_scale
+FROM svv_columns
+WHERE table_schema NOT IN ('pg_catalog', 'information_schema', 'pg_internal', 'pg_automv')
+ORDER BY table_schema, table_name, ordinal_position
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftColumnRow {
+    pub table_schema: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub column_default: Option<String>,
+    pub ordinal_position: i32,
+    pub max_length: Option<i32>,
+    pub numeric_precision: Option<i32>,
+    pub numeric_scale: Option<i32>,
+}
+
+/// Redshift views
+pub const REDSHIFT_VIEWS: &str = r#"
+SELECT
+    trim(schemaname) AS schema_name,
+    trim(viewname) AS view_name,
+    definition
+FROM pg_views
+WHERE schemaname NOT IN ('pg_catalog', 'information_schema', 'pg_internal', 'pg_automv')
+ORDER BY schemaname, viewname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftViewRow {
+    pub schema_name: String,
+    pub view_name: String,
+    pub definition: Option<String>,
+}
+
+/// Redshift settings. `pg_settings` here predates `unit` and the other
+/// columns [`PG_SETTINGS`] reads.
+pub const REDSHIFT_SETTINGS: &str = r#"
+SELECT name, setting, category, vartype, source
+FROM pg_settings
+ORDER BY name
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftSettingRow {
+    pub name: String,
+    pub setting: String,
+    pub category: Option<String>,
+    pub vartype: Option<String>,
+    pub source: Option<String>,
+}
+
+/// WLM queue configuration and current load. Service classes 1-4 are used
+/// by the system; 5 is the superuser queue, 6-13 manual WLM queues and 100+
+/// automatic WLM queues. `queue_time` is in microseconds.
+pub const REDSHIFT_WLM_QUEUES: &str = r#"
+SELECT
+    c.service_class::int AS service_class,
+    trim(c.name) AS name,
+    c.num_query_tasks::int AS slots,
+    c.query_working_mem::int AS working_mem_mb,
+    c.max_execution_time::bigint AS max_execution_time_ms,
+    trim(c.concurrency_scaling) AS concurrency_scaling,
+    trim(c.query_priority) AS priority,
+    s.num_queued_queries::bigint AS queued,
+    s.num_executing_queries::bigint AS executing,
+    s.num_executed_queries::bigint AS executed_total,
+    q.max_queue_time::float8 / 1000 AS max_queue_time_ms
+FROM stv_wlm_service_class_config c
+LEFT JOIN stv_wlm_service_class_state s ON s.service_class = c.service_class
+LEFT JOIN (
+    SELECT service_class, max(queue_time) AS max_queue_time
+    FROM stv_wlm_query_state
+    WHERE trim(state) LIKE 'Queued%'
+    GROUP BY service_class
+) q ON q.service_class = c.service_class
+WHERE c.service_class >= 5
+ORDER BY c.service_class
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct RedshiftWlmQueueRow {
+    pub service_class: i32,
+    pub name: Option<String>,
+    pub slots: Option<i32>,
+    pub working_mem_mb: Option<i32>,
+    pub max_execution_time_ms: Option<i64>,
+    pub concurrency_scaling: Option<String>,
+    pub priority: Option<String>,
+    pub queued: Option<i64>,
+    pub executing: Option<i64>,
+    pub executed_total: Option<i64>,
+    pub max_queue_time_ms: Option<f64>,
+}
+
+/// Installed pgvector version and its schema, already quoted for use as a
+/// qualifier.
+pub const PGVECTOR_EXTENSION: &str = r#"
+SELECT e.extversion, quote_ident(n.nspname) AS schema
+FROM pg_extension e
+JOIN pg_namespace n ON n.oid = e.extnamespace
+WHERE e.extname = 'vector'
+"#;
+
+/// Columns whose type belongs to the pgvector extension (`vector`,
+/// `halfvec`, `sparsevec`). The type modifier is the declared dimension
+/// count. Partitions are left out; their parent carries the same columns.
+///
+/// `$1` is the namespace OIDs to include.
+pub const PGVECTOR_COLUMNS: &str = r#"
+SELECT
+    n.nspname AS schema_name,
+    c.relname AS table_name,
+    a.attname AS column_name,
+    t.typname::text AS vector_type,
+    CASE WHEN a.atttypmod > 0 THEN a.atttypmod END AS dimensions,
+    a.attnotnull AS not_null
+FROM pg_attribute a
+JOIN pg_class c ON c.oid = a.attrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_type t ON t.oid = a.atttypid
+JOIN pg_depend d
+    ON d.classid = 'pg_type'::regclass AND d.objid = t.oid AND d.deptype = 'e'
+JOIN pg_extension e ON e.oid = d.refobjid AND e.extname = 'vector'
+WHERE c.relkind IN ('r', 'p', 'm')
+    AND NOT c.relispartition
+    AND a.attnum > 0
+    AND NOT a.attisdropped
+    AND c.relnamespace = ANY($1)
+ORDER BY n.nspname, c.relname, a.attnum
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct PgvectorColumnRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub column_name: String,
+    pub vector_type: String,
+    pub dimensions: Option<i32>,
+    pub not_null: bool,
+}
+
+/// HNSW and IVFFlat indexes with their build options, operator class, size
+/// and scan count.
+///
+/// `$1` is the namespace OIDs to include.
+pub const PGVECTOR_INDEXES: &str = r#"
+SELECT
+    n.nspname AS schema_name,
+    t.relname AS table_name,
+    i.relname AS index_name,
+    am.amname::text AS method,
+    pg_get_indexdef(x.indexrelid, 1, true) AS key,
+    opc.opcname::text AS operator_class,
+    i.reloptions,
+    pg_relation_size(i.oid) AS size_bytes,
+    s.idx_scan,
+    t.reltuples::bigint AS table_rows
+FROM pg_index x
+JOIN pg_class i ON i.oid = x.indexrelid
+JOIN pg_class t ON t.oid = x.indrelid
+JOIN pg_namespace n ON n.oid = t.relnamespace
+JOIN pg_am am ON am.oid = i.relam
+LEFT JOIN pg_opclass opc ON opc.oid = x.indclass[0]
+LEFT JOIN pg_stat_user_indexes s ON s.indexrelid = x.indexrelid
+WHERE am.amname IN ('hnsw', 'ivfflat')
+    AND t.relnamespace = ANY($1)
+ORDER BY n.nspname, t.relname, i.relname
+"#;
+
+#[derive(Debug, FromRow)]
+pub struct PgvectorIndexRow {
+    pub schema_name: String,
+    pub table_name: String,
+    pub index_name: String,
+    pub method: String,
+    pub key: Option<String>,
+    pub operator_class: Option<String>,
+    pub reloptions: Option<Vec<String>>,
+    pub size_bytes: Option<i64>,
+    pub idx_scan: Option<i64>,
+    pub table_rows: Option<i64>,
+}
+
+/// Settings that shape ANN index builds and scans. pgvector registers its
+/// own (`hnsw.*`, `ivfflat.*`) when its library is loaded, so the caller
+/// loads it on the same connection first. Same columns as [`PG_SETTINGS`].
+pub const PGVECTOR_SETTINGS: &str = r#"
+SELECT
+    name,
+    setting,
+    unit,
+    category,
+    vartype,
+    source,
+    sourcefile,
+    boot_val,
+    reset_val,
+    pending_restart
+FROM pg_settings
+WHERE name LIKE 'hnsw.%'
+    OR name LIKE 'ivfflat.%'
+    OR name IN ('maintenance_work_mem', 'max_parallel_maintenance_workers')
+ORDER BY name
+"#;
@@ -0,0 +1,282 @@
+//! Amazon Redshift support.
+//!
+//! Redshift accepts Postgres connections but is a columnar MPP warehouse
+//! forked from PostgreSQL 8.0: there is no `pg_stat_statements`, no
+//! `pg_stat_user_tables` counters, no indexes and no relation size
+//! functions. Its own system views are read instead:
+//!
+//! - query statistics from `STL_QUERY` and `SVL_QUERY_SUMMARY`
+//! - table size, row skew across slices, unsorted rows and statistics
+//!   staleness from `SVV_TABLE_INFO`
+//! - distribution style, distribution key and sort key per table
+//! - columns from `SVV_COLUMNS`, views from `pg_views`
+//! - WLM queue configuration and load from the `STV_WLM_*` tables
+
+use super::queries;
+use super::DatabaseSlice;
+use crate::collector::CollectorError;
+use crate::config::SchemasConfig;
+use crate::payload::{
+    ColumnMetadata, QueryStats, RedshiftMetadata, SchemaMetadata, Setting, TableMetadata,
+    TableStats, ViewMetadata, WlmQueue,
+};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tracing::debug;
+
+type TableKey = (String, String);
+
+/// Distribution and sort keys of one table
+#[derive(Debug, Default, PartialEq)]
+struct Keys {
+    distribution_key: Option<String>,
+    sort_key: Vec<String>,
+    sort_key_style: Option<String>,
+}
+
+/// Top queries by total elapsed time.
+pub(super) async fn query_stats(pool: &PgPool) -> Result<Vec<QueryStats>, CollectorError> {
+    debug!("Collecting query statistics from STL_QUERY");
+
+    let rows = sqlx::query_as::<_, queries::RedshiftQueryRow>(queries::REDSHIFT_QUERY_STATS)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| QueryStats {
+            query_hash: Some(row.query_hash),
+            query: row.query_text,
+            mean_time_ms: match (row.total_time_ms, row.calls) {
+                (Some(total), Some(calls)) if calls > 0 => Some(total / calls as f64),
+                _ => None,
+            },
+            calls: row.calls,
+            total_time_ms: row.total_time_ms,
+            rows: row.rows_returned,
+            disk_spills: row.disk_spills,
+            database: row.datname,
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Settings as a flat map and as structured [`Setting`]s.
+pub(super) async fn settings(
+    pool: &PgPool,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting Redshift settings");
+
+    let rows = sqlx::query_as::<_, queries::RedshiftSettingRow>(queries::REDSHIFT_SETTINGS)
+        .fetch_all(pool)
+        .await?;
+
+    let details: Vec<Setting> = rows
+        .into_iter()
+        .map(|row| Setting {
+            is_default: row.source.as_deref() == Some("default"),
+            name: row.name,
+            value: row.setting,
+            category: row.category,
+            value_type: row.vartype,
+            source: row.source,
+            ..Default::default()
+        })
+        .collect();
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+/// WLM queues with their current load.
+pub(super) async fn wlm(pool: &PgPool) -> Result<RedshiftMetadata, CollectorError> {
+    debug!("Collecting WLM queue state");
+
+    let rows = sqlx::query_as::<_, queries::RedshiftWlmQueueRow>(queries::REDSHIFT_WLM_QUEUES)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(RedshiftMetadata {
+        wlm_queues: rows
+            .into_iter()
+            .map(|row| WlmQueue {
+                service_class: row.service_class,
+                name: row.name,
+                slots: row.slots,
+                working_mem_mb: row.working_mem_mb,
+                max_execution_time_ms: row.max_execution_time_ms,
+                concurrency_scaling: row.concurrency_scaling,
+                priority: row.priority,
+                queued: row.queued,
+                executing: row.executing,
+                executed_total: row.executed_total,
+                max_queue_time_ms: row.max_queue_time_ms,
+            })
+            .collect(),
+    })
+}
+
+/// Tables, views and table health of the database `pool` is connected to.
+pub(super) async fn collect_database(
+    pool: &PgPool,
+    filter: &SchemasConfig,
+) -> Result<DatabaseSlice, CollectorError> {
+    debug!("Collecting Redshift schema metadata");
+
+    let (table_rows, key_rows, column_rows, view_rows) = tokio::try_join!(
+        sqlx::query_as::<_, queries::RedshiftTableRow>(queries::REDSHIFT_TABLE_INFO)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::RedshiftKeyColumnRow>(queries::REDSHIFT_KEY_COLUMNS)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::RedshiftColumnRow>(queries::REDSHIFT_COLUMNS).fetch_all(pool),
+        sqlx::query_as::<_, queries::RedshiftViewRow>(queries::REDSHIFT_VIEWS).fetch_all(pool),
+    )?;
+
+    let mut keys = table_keys(key_rows);
+    let mut columns: HashMap<TableKey, Vec<ColumnMetadata>> = HashMap::new();
+    for row in column_rows {
+        if !filter.matches(&row.table_schema) {
+            continue;
+        }
+        columns
+            .entry((row.table_schema, row.table_name))
+            .or_default()
+            .push(ColumnMetadata {
+                name: row.column_name,
+                data_type: row.data_type,
+                nullable: row.nullable,
+                default: row.column_default,
+                position: row.ordinal_position,
+                max_length: row.max_length,
+                numeric_precision: row.numeric_precision,
+                numeric_scale: row.numeric_scale,
+                ..Default::default()
+            });
+    }
+
+    let mut tables = Vec::new();
+    let mut table_stats = Vec::new();
+    for row in table_rows {
+        if !filter.matches(&row.schema_name) {
+            continue;
+        }
+        let key = (row.schema_name.clone(), row.table_name.clone());
+        let table_keys = keys.remove(&key).unwrap_or_default();
+        table_stats.push(TableStats {
+            schema: row.schema_name.clone(),
+            table: row.table_name.clone(),
+            n_live_tup: row.estimated_visible_rows,
+            n_dead_tup: match (row.tbl_rows, row.estimated_visible_rows) {
+                (Some(total), Some(visible)) => Some((total - visible).max(0)),
+                _ => None,
+            },
+            skew_rows: row.skew_rows,
+            unsorted_pct: row.unsorted,
+            stats_off_pct: row.stats_off,
+            ..Default::default()
+        });
+        tables.push(TableMetadata {
+            columns: columns.remove(&key).unwrap_or_default(),
+            schema: row.schema_name,
+            name: row.table_name,
+            row_count_estimate: row.estimated_visible_rows,
+            size_bytes: row.size_bytes,
+            distribution_style: row.diststyle,
+            distribution_key: table_keys.distribution_key,
+            sort_key: table_keys.sort_key,
+            sort_key_style: table_keys.sort_key_style,
+            ..Default::default()
+        });
+    }
+
+    let views = view_rows
+        .into_iter()
+        .filter(|row| filter.matches(&row.schema_name))
+        .map(|row| ViewMetadata {
+            columns: columns
+                .remove(&(row.schema_name.clone(), row.view_name.clone()))
+                .unwrap_or_default(),
+            schema: row.schema_name,
+            name: row.view_name,
+            definition: row.definition,
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(DatabaseSlice {
+        table_stats,
+        index_stats: Vec::new(),
+        schema: SchemaMetadata {
+            tables,
+            views,
+            ..Default::default()
+        },
+        timescaledb: None,
+        pgvector: None,
+    })
+}
+
+/// Group key columns by table. Rows arrive in sort key order.
+fn table_keys(rows: Vec<queries::RedshiftKeyColumnRow>) -> HashMap<TableKey, Keys> {
+    let mut keys: HashMap<TableKey, Keys> = HashMap::new();
+    for row in rows {
+        let entry = keys.entry((row.schema_name, row.table_name)).or_default();
+        if row.is_distkey {
+            entry.distribution_key = Some(row.column_name.clone());
+        }
+        if row.sortkey_ord != 0 {
+            entry.sort_key_style = Some(
+                if row.sortkey_ord < 0 {
+                    "interleaved"
+                } else {
+                    "compound"
+                }
+                .to_string(),
+            );
+            entry.sort_key.push(row.column_name);
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use queries::RedshiftKeyColumnRow;
+
+    fn key(table: &str, column: &str, is_distkey: bool, sortkey_ord: i32) -> RedshiftKeyColumnRow {
+        RedshiftKeyColumnRow {
+            schema_name: "public".to_string(),
+            table_name: table.to_string(),
+            column_name: column.to_string(),
+            is_distkey,
+            sortkey_ord,
+        }
+    }
+
+    #[test]
+    fn test_table_keys() {
+        let keys = table_keys(vec![
+            key("sales", "sold_at", false, 1),
+            key("sales", "customer_id", true, 2),
+            key("events", "user_id", true, 0),
+            key("clicks", "page", false, -1),
+            key("clicks", "ts", false, -2),
+        ]);
+
+        let sales = &keys[&("public".to_string(), "sales".to_string())];
+        assert_eq!(sales.distribution_key.as_deref(), Some("customer_id"));
+        assert_eq!(sales.sort_key, vec!["sold_at", "customer_id"]);
+        assert_eq!(sales.sort_key_style.as_deref(), Some("compound"));
+
+        let events = &keys[&("public".to_string(), "events".to_string())];
+        assert!(events.sort_key.is_empty());
+        assert_eq!(events.sort_key_style, None);
+
+        let clicks = &keys[&("public".to_string(), "clicks".to_string())];
+        assert_eq!(clicks.sort_key, vec!["page", "ts"]);
+        assert_eq!(clicks.sort_key_style.as_deref(), Some("interleaved"));
+    }
+}
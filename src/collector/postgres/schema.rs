@@ -0,0 +1,538 @@
+//! Schema metadata collection from `pg_catalog`.
+//!
+//! Every relation is addressed by OID, so tables, indexes and columns that
+//! share a name across schemas are never mixed up. Tables are read in pages
+//! of [`PAGE_SIZE`] keyed on OID, and the columns, constraints and indexes of
+//! each page are fetched for exactly those OIDs, which keeps memory and
+//! statement time bounded on catalogs with tens of thousands of relations.
+
+use super::queries;
+use crate::collector::CollectorError;
+use crate::config::{PostgresCollectionConfig, SchemasConfig};
+use crate::payload::{
+    ColumnMetadata, ConstraintMetadata, FunctionMetadata, IndexMetadata, SchemaMetadata,
+    SequenceMetadata, TableMetadata, TriggerMetadata, ViewMetadata,
+};
+use sqlx::postgres::types::Oid;
+use sqlx::PgPool;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use tracing::debug;
+
+/// Number of tables (or views) whose details are fetched per round trip.
+const PAGE_SIZE: i64 = 1000;
+
+type ColumnsByRelation = HashMap<(String, String), Vec<ColumnMetadata>>;
+
+/// OIDs of the schemas that pass `filter`. System, TOAST and temporary
+/// schemas are excluded by the query itself.
+pub(super) async fn namespaces(
+    pool: &PgPool,
+    filter: &SchemasConfig,
+) -> Result<Vec<Oid>, CollectorError> {
+    let rows: Vec<(Oid, String)> = sqlx::query_as(queries::NAMESPACE_LIST)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|(_, name)| filter.matches(name))
+        .map(|(oid, _)| oid)
+        .collect())
+}
+
+/// Collect schema metadata for the given namespaces.
+pub(super) async fn collect(
+    pool: &PgPool,
+    server_version_num: i32,
+    options: &PostgresCollectionConfig,
+    namespaces: &[Oid],
+) -> Result<SchemaMetadata, CollectorError> {
+    debug!(schemas = namespaces.len(), "Collecting schema metadata");
+
+    let column_sql = queries::column_info(server_version_num);
+    let mcv = options.column_stats.most_common_values;
+
+    let mut tables = Vec::new();
+    let mut indexes = Vec::new();
+    let mut after = Oid(0);
+    loop {
+        let table_rows = sqlx::query_as::<_, queries::TableInfoRow>(queries::TABLE_INFO)
+            .bind(namespaces)
+            .bind(after)
+            .bind(PAGE_SIZE)
+            .fetch_all(pool)
+            .await?;
+        let Some(last) = table_rows.last() else {
+            break;
+        };
+        after = last.oid;
+        let page_full = table_rows.len() as i64 == PAGE_SIZE;
+
+        let oids: Vec<Oid> = table_rows.iter().map(|row| row.oid).collect();
+        let (mut columns, mut constraints, page_indexes) = tokio::try_join!(
+            columns(pool, &column_sql, &oids, mcv),
+            constraints(pool, &oids),
+            indexes_of(pool, &oids),
+        )?;
+
+        tables.extend(table_rows.into_iter().map(|row| {
+            let key = (row.table_schema.clone(), row.table_name.clone());
+            TableMetadata {
+                columns: columns.remove(&key).unwrap_or_default(),
+                constraints: constraints.remove(&key).unwrap_or_default(),
+                schema: row.table_schema,
+                name: row.table_name,
+                row_count_estimate: row.row_estimate,
+                size_bytes: row.total_bytes,
+                partition_strategy: row.partition_strategy,
+                partition_key: row.partition_key,
+                parent_schema: row.parent_schema,
+                parent_table: row.parent_table,
+                partition_bound: row.partition_bound,
+                ..Default::default()
+            }
+        }));
+        indexes.extend(page_indexes);
+
+        if !page_full {
+            break;
+        }
+    }
+
+    // Largest relations first, as consumers have always received them
+    tables.sort_by_key(|t| Reverse(t.size_bytes));
+    indexes.sort_by_key(|i| Reverse(i.size_bytes));
+
+    let function_sql = queries::function_info(server_version_num);
+    let (view_rows, function_rows, trigger_rows, sequence_rows) = tokio::try_join!(
+        sqlx::query_as::<_, queries::ViewInfoRow>(queries::VIEW_INFO)
+            .bind(namespaces)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::FunctionInfoRow>(&function_sql)
+            .bind(options.function_bodies)
+            .bind(namespaces)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::TriggerInfoRow>(queries::TRIGGER_INFO)
+            .bind(namespaces)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::SequenceInfoRow>(queries::SEQUENCE_INFO)
+            .bind(namespaces)
+            .fetch_all(pool),
+    )?;
+
+    let mut view_columns = ColumnsByRelation::new();
+    let view_oids: Vec<Oid> = view_rows.iter().map(|row| row.oid).collect();
+    for page in view_oids.chunks(PAGE_SIZE as usize) {
+        view_columns.extend(columns(pool, &column_sql, page, mcv).await?);
+    }
+
+    let views = view_rows
+        .into_iter()
+        .map(|row| ViewMetadata {
+            columns: view_columns
+                .remove(&(row.schema_name.clone(), row.view_name.clone()))
+                .unwrap_or_default(),
+            schema: row.schema_name,
+            name: row.view_name,
+            materialized: row.materialized,
+            definition: row.definition,
+            size_bytes: row.size_bytes,
+            row_count_estimate: row.row_estimate,
+            is_populated: row.is_populated,
+            ..Default::default()
+        })
+        .collect();
+
+    let functions = function_rows
+        .into_iter()
+        .map(|row| FunctionMetadata {
+            schema: row.schema_name,
+            name: row.function_name,
+            kind: row.kind,
+            language: row.language,
+            arguments: row.arguments,
+            return_type: row.return_type,
+            volatility: row.volatility,
+            security_definer: row.security_definer,
+            body: row.body,
+            ..Default::default()
+        })
+        .collect();
+
+    let triggers = trigger_rows
+        .into_iter()
+        .map(|row| TriggerMetadata {
+            schema: row.schema_name,
+            table: row.table_name,
+            name: row.trigger_name,
+            timing: row.timing,
+            events: row.events,
+            level: row.level,
+            function: row.function_name,
+            enabled: row.enabled,
+            definition: row.definition,
+            ..Default::default()
+        })
+        .collect();
+
+    let sequences = sequence_rows
+        .into_iter()
+        .map(|row| SequenceMetadata {
+            exhaustion_pct: sequence_exhaustion_pct(
+                row.last_value,
+                row.min_value,
+                row.max_value,
+                row.increment_by,
+            ),
+            schema: row.schemaname,
+            name: row.sequencename,
+            data_type: row.data_type,
+            last_value: row.last_value,
+            min_value: row.min_value,
+            max_value: row.max_value,
+            increment: row.increment_by,
+            cycle: row.cycle,
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(SchemaMetadata {
+        tables,
+        indexes,
+        views,
+        functions,
+        triggers,
+        sequences,
+    })
+}
+
+/// Columns of the relations in `oids`, grouped by `(schema, relation)` in
+/// column order.
+async fn columns(
+    pool: &PgPool,
+    sql: &str,
+    oids: &[Oid],
+    most_common_values: bool,
+) -> Result<ColumnsByRelation, CollectorError> {
+    let rows = sqlx::query_as::<_, queries::ColumnInfoRow>(sql)
+        .bind(oids)
+        .bind(most_common_values)
+        .fetch_all(pool)
+        .await?;
+
+    let mut by_relation = ColumnsByRelation::new();
+    for row in rows {
+        by_relation
+            .entry((row.table_schema.clone(), row.table_name.clone()))
+            .or_default()
+            .push(column_metadata(row, most_common_values));
+    }
+    Ok(by_relation)
+}
+
+/// Column of a `column_info` row. Most-common values are dropped unless
+/// `most_common_values` is set, should the query return them anyway.
+fn column_metadata(row: queries::ColumnInfoRow, most_common_values: bool) -> ColumnMetadata {
+    let (most_common_vals, most_common_freqs) = if most_common_values {
+        (row.most_common_vals, row.most_common_freqs)
+    } else {
+        (None, None)
+    };
+    ColumnMetadata {
+        name: row.column_name,
+        data_type: row.data_type,
+        nullable: row.nullable,
+        default: row.column_default,
+        position: row.ordinal_position,
+        null_rate: row.null_frac,
+        distinct_count: distinct_count(row.n_distinct, row.reltuples),
+        avg_width: row.avg_width,
+        correlation: row.correlation,
+        most_common_values: most_common_vals,
+        most_common_freqs,
+        native_type: row.native_type,
+        max_length: row.max_length,
+        numeric_precision: row.numeric_precision,
+        numeric_scale: row.numeric_scale,
+        element_type: row.element_type,
+        domain: row.domain,
+        enum_labels: row.enum_labels,
+        identity: row.identity,
+        generation_expression: row.generation_expression,
+        collation: row.collation,
+        comment: row.comment,
+        ..Default::default()
+    }
+}
+
+/// Estimated distinct values from `pg_stats.n_distinct`, which is a count
+/// when positive and minus a fraction of the rows when negative. The
+/// fraction is scaled by `reltuples`, and unknown while the table has never
+/// been analyzed (`-1`) or is estimated empty.
+fn distinct_count(n_distinct: Option<f64>, reltuples: Option<f64>) -> Option<i64> {
+    let n_distinct = n_distinct?;
+    if n_distinct >= 0.0 {
+        return Some(n_distinct.round() as i64);
+    }
+    match reltuples {
+        Some(rows) if rows > 0.0 => Some((-n_distinct * rows).round() as i64),
+        _ => None,
+    }
+}
+
+/// Indexes on the tables in `oids`.
+async fn indexes_of(pool: &PgPool, oids: &[Oid]) -> Result<Vec<IndexMetadata>, CollectorError> {
+    let rows = sqlx::query_as::<_, queries::IndexInfoRow>(queries::INDEX_INFO)
+        .bind(oids)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| IndexMetadata {
+            schema: row.schemaname,
+            table: row.tablename,
+            name: row.indexname,
+            columns: row
+                .columns
+                .map(|c| c.split(", ").map(String::from).collect())
+                .unwrap_or_default(),
+            is_unique: row.is_unique.unwrap_or(false),
+            is_primary: row.is_primary.unwrap_or(false),
+            size_bytes: row.index_size,
+            parent_index: row.parent_index,
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Constraints of the tables in `oids`, grouped by `(schema, table)`.
+async fn constraints(
+    pool: &PgPool,
+    oids: &[Oid],
+) -> Result<HashMap<(String, String), Vec<ConstraintMetadata>>, CollectorError> {
+    let rows = sqlx::query_as::<_, queries::ConstraintInfoRow>(queries::CONSTRAINT_INFO)
+        .bind(oids)
+        .fetch_all(pool)
+        .await?;
+
+    let mut by_table: HashMap<(String, String), Vec<ConstraintMetadata>> = HashMap::new();
+    for row in rows {
+        let key = (row.schema_name.clone(), row.table_name.clone());
+        if let Some(constraint) = constraint_metadata(row) {
+            by_table.entry(key).or_default().push(constraint);
+        }
+    }
+    Ok(by_table)
+}
+
+/// Kind of a `pg_constraint.contype`. Constraint triggers and NOT NULL
+/// constraints (PostgreSQL 18) are left out.
+fn constraint_kind(contype: &str) -> Option<&'static str> {
+    match contype {
+        "p" => Some("primary_key"),
+        "u" => Some("unique"),
+        "c" => Some("check"),
+        "x" => Some("exclusion"),
+        "f" => Some("foreign_key"),
+        _ => None,
+    }
+}
+
+fn constraint_metadata(row: queries::ConstraintInfoRow) -> Option<ConstraintMetadata> {
+    Some(ConstraintMetadata {
+        kind: constraint_kind(&row.contype)?.to_string(),
+        name: row.constraint_name,
+        columns: row.columns,
+        definition: row.definition,
+        validated: row.validated,
+        deferrable: row.deferrable,
+    })
+}
+
+/// Share of a sequence's range already consumed, in percent.
+///
+/// Descending sequences (negative increment) count down from `max_value`.
+fn sequence_exhaustion_pct(
+    last_value: Option<i64>,
+    min: i64,
+    max: i64,
+    increment: i64,
+) -> Option<f64> {
+    let last = last_value?;
+    let range = max as f64 - min as f64;
+    if range <= 0.0 {
+        return None;
+    }
+    let used = if increment >= 0 {
+        last as f64 - min as f64
+    } else {
+        max as f64 - last as f64
+    };
+    Some((used / range * 100.0).clamp(0.0, 100.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column_row(name: &str, data_type: &str, native_type: &str) -> queries::ColumnInfoRow {
+        queries::ColumnInfoRow {
+            table_schema: "public".to_string(),
+            table_name: "orders".to_string(),
+            column_name: name.to_string(),
+            ordinal_position: 1,
+            nullable: true,
+            data_type: data_type.to_string(),
+            column_default: None,
+            native_type: Some(native_type.to_string()),
+            max_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            element_type: None,
+            domain: None,
+            enum_labels: None,
+            identity: None,
+            generation_expression: None,
+            collation: None,
+            comment: None,
+            null_frac: None,
+            n_distinct: None,
+            reltuples: None,
+            avg_width: None,
+            correlation: None,
+            most_common_vals: None,
+            most_common_freqs: None,
+        }
+    }
+
+    fn constraint_row(name: &str, contype: &str, columns: &[&str]) -> queries::ConstraintInfoRow {
+        queries::ConstraintInfoRow {
+            schema_name: "public".to_string(),
+            table_name: "orders".to_string(),
+            constraint_name: name.to_string(),
+            contype: contype.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            definition: String::new(),
+            validated: true,
+            deferrable: false,
+        }
+    }
+
+    #[test]
+    fn test_constraint_metadata_kinds() {
+        let kind = |contype, columns: &[&str]| {
+            constraint_metadata(constraint_row("c", contype, columns)).map(|c| c.kind)
+        };
+        assert_eq!(kind("p", &["id"]).as_deref(), Some("primary_key"));
+        assert_eq!(kind("u", &["email"]).as_deref(), Some("unique"));
+        assert_eq!(kind("c", &[]).as_deref(), Some("check"));
+        assert_eq!(kind("x", &["room", "during"]).as_deref(), Some("exclusion"));
+        assert_eq!(kind("f", &["customer_id"]).as_deref(), Some("foreign_key"));
+        // Constraint triggers and NOT NULL constraints
+        assert_eq!(kind("t", &[]), None);
+        assert_eq!(kind("n", &["id"]), None);
+
+        let mut row = constraint_row("orders_amount_check", "c", &["amount"]);
+        row.definition = "CHECK ((amount > 0)) NOT VALID".to_string();
+        row.validated = false;
+        row.deferrable = true;
+        let check = constraint_metadata(row).unwrap();
+        assert_eq!(check.name, "orders_amount_check");
+        assert_eq!(check.columns, ["amount"]);
+        assert_eq!(check.definition, "CHECK ((amount > 0)) NOT VALID");
+        assert!(!check.validated);
+        assert!(check.deferrable);
+    }
+
+    #[test]
+    fn test_column_metadata_type_details() {
+        let mut row = column_row("tags", "ARRAY", "text[]");
+        row.element_type = Some("text".to_string());
+        let array = column_metadata(row, false);
+        assert_eq!(array.data_type, "ARRAY");
+        assert_eq!(array.native_type.as_deref(), Some("text[]"));
+        assert_eq!(array.element_type.as_deref(), Some("text"));
+        assert!(array.domain.is_none());
+
+        // Domains carry their base type as `data_type`
+        let mut row = column_row("email", "character varying", "public.email_address");
+        row.domain = Some("public.email_address".to_string());
+        row.max_length = Some(320);
+        let domain = column_metadata(row, false);
+        assert_eq!(domain.data_type, "character varying");
+        assert_eq!(domain.domain.as_deref(), Some("public.email_address"));
+        assert_eq!(domain.max_length, Some(320));
+
+        let mut row = column_row("status", "USER-DEFINED", "order_status");
+        row.enum_labels = Some(vec!["pending".to_string(), "shipped".to_string()]);
+        let status = column_metadata(row, false);
+        assert_eq!(status.data_type, "USER-DEFINED");
+        assert_eq!(
+            status.enum_labels,
+            Some(vec!["pending".to_string(), "shipped".to_string()])
+        );
+
+        let mut row = column_row("amount", "numeric", "numeric(12,2)");
+        row.numeric_precision = Some(12);
+        row.numeric_scale = Some(2);
+        row.identity = None;
+        row.generation_expression = Some("(price * quantity)".to_string());
+        let amount = column_metadata(row, false);
+        assert_eq!(
+            (amount.numeric_precision, amount.numeric_scale),
+            (Some(12), Some(2))
+        );
+        assert_eq!(
+            amount.generation_expression.as_deref(),
+            Some("(price * quantity)")
+        );
+    }
+
+    #[test]
+    fn test_distinct_count() {
+        assert_eq!(distinct_count(None, Some(1000.0)), None);
+        assert_eq!(distinct_count(Some(42.0), None), Some(42));
+        assert_eq!(distinct_count(Some(42.0), Some(-1.0)), Some(42));
+        // A fraction of the rows: -1 is unique, -0.25 a quarter of the rows
+        assert_eq!(distinct_count(Some(-1.0), Some(1000.0)), Some(1000));
+        assert_eq!(distinct_count(Some(-0.25), Some(10_000.0)), Some(2500));
+        // Never analyzed, or estimated empty
+        assert_eq!(distinct_count(Some(-0.5), Some(-1.0)), None);
+        assert_eq!(distinct_count(Some(-0.5), Some(0.0)), None);
+        assert_eq!(distinct_count(Some(-0.5), None), None);
+    }
+
+    #[test]
+    fn test_most_common_values_are_opt_in() {
+        let row = || {
+            let mut row = column_row("status", "text", "text");
+            row.n_distinct = Some(-0.5);
+            row.reltuples = Some(200.0);
+            row.most_common_vals = Some(vec!["pending".to_string()]);
+            row.most_common_freqs = Some(vec![0.9]);
+            row
+        };
+
+        let column = column_metadata(row(), false);
+        assert_eq!(column.distinct_count, Some(100));
+        assert!(column.most_common_values.is_none());
+        assert!(column.most_common_freqs.is_none());
+
+        let column = column_metadata(row(), true);
+        assert_eq!(column.most_common_values, Some(vec!["pending".to_string()]));
+        assert_eq!(column.most_common_freqs, Some(vec![0.9]));
+    }
+
+    #[test]
+    fn test_sequence_exhaustion_pct() {
+        assert_eq!(sequence_exhaustion_pct(None, 1, 100, 1), None);
+        assert_eq!(sequence_exhaustion_pct(Some(51), 1, 101, 1), Some(50.0));
+        assert_eq!(sequence_exhaustion_pct(Some(-76), -101, -1, -1), Some(75.0));
+
+        // An `integer` serial close to its limit
+        let pct = sequence_exhaustion_pct(Some(2_000_000_000), 1, i32::MAX as i64, 1).unwrap();
+        assert!(pct > 93.0 && pct < 94.0);
+    }
+}
@@ -0,0 +1,134 @@
+//! `pg_settings` normalization.
+//!
+//! Postgres reports memory and time settings as integers in a per-setting
+//! unit (`shared_buffers = 16384` in `8kB` pages, `statement_timeout` in
+//! `ms`). These helpers turn a raw row into a [`Setting`] with the value also
+//! expressed in bytes or milliseconds.
+
+use super::queries::PgSettingsRow;
+use crate::payload::Setting;
+
+/// Build a [`Setting`] from a `pg_settings` row.
+pub fn from_row(row: PgSettingsRow) -> Setting {
+    let (value_bytes, value_ms) = match row.unit.as_deref() {
+        Some(unit) => normalize(&row.setting, unit),
+        None => (None, None),
+    };
+    let is_default = row.source.as_deref() == Some("default");
+    Setting {
+        name: row.name,
+        value: row.setting,
+        unit: row.unit,
+        value_bytes,
+        value_ms,
+        category: row.category,
+        value_type: row.vartype,
+        source: row.source,
+        source_file: row.sourcefile,
+        boot_value: row.boot_val,
+        reset_value: row.reset_val,
+        pending_restart: row.pending_restart,
+        is_default,
+    }
+}
+
+/// Convert `value` in `unit` to `(bytes, milliseconds)`; at most one is set.
+///
+/// Memory units may carry a multiplier (`8kB`, `16MB`). Values of `-1`
+/// conventionally mean "disabled" or "use another setting" and are left
+/// unconverted.
+fn normalize(value: &str, unit: &str) -> (Option<i64>, Option<f64>) {
+    let Ok(n) = value.parse::<f64>() else {
+        return (None, None);
+    };
+    if n < 0.0 {
+        return (None, None);
+    }
+
+    let digits_end = unit
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(unit.len());
+    let (multiplier, base) = unit.split_at(digits_end);
+    let multiplier = multiplier.parse::<f64>().unwrap_or(1.0);
+
+    let bytes_per = match base {
+        "B" => Some(1.0),
+        "kB" => Some(1024.0),
+        "MB" => Some(1024.0 * 1024.0),
+        "GB" => Some(1024.0 * 1024.0 * 1024.0),
+        "TB" => Some(1024.0 * 1024.0 * 1024.0 * 1024.0),
+        _ => None,
+    };
+    if let Some(bytes_per) = bytes_per {
+        return (Some((n * multiplier * bytes_per) as i64), None);
+    }
+
+    let ms_per = match base {
+        "us" => Some(0.001),
+        "ms" => Some(1.0),
+        "s" => Some(1_000.0),
+        "min" => Some(60_000.0),
+        "h" => Some(3_600_000.0),
+        "d" => Some(86_400_000.0),
+        _ => None,
+    };
+    (None, ms_per.map(|ms| n * multiplier * ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, setting: &str, unit: Option<&str>, source: &str) -> PgSettingsRow {
+        PgSettingsRow {
+            name: name.to_string(),
+            setting: setting.to_string(),
+            unit: unit.map(String::from),
+            category: None,
+            vartype: Some("integer".to_string()),
+            source: Some(source.to_string()),
+            sourcefile: None,
+            boot_val: None,
+            reset_val: None,
+            pending_restart: Some(false),
+        }
+    }
+
+    #[test]
+    fn memory_units_normalize_to_bytes() {
+        assert_eq!(normalize("16384", "8kB"), (Some(134_217_728), None));
+        assert_eq!(normalize("4096", "kB"), (Some(4_194_304), None));
+        assert_eq!(normalize("1024", "MB"), (Some(1_073_741_824), None));
+        assert_eq!(normalize("16777216", "B"), (Some(16_777_216), None));
+    }
+
+    #[test]
+    fn time_units_normalize_to_ms() {
+        assert_eq!(normalize("30000", "ms"), (None, Some(30_000.0)));
+        assert_eq!(normalize("60", "s"), (None, Some(60_000.0)));
+        assert_eq!(normalize("1", "min"), (None, Some(60_000.0)));
+    }
+
+    #[test]
+    fn sentinel_and_unitless_values_are_not_normalized() {
+        assert_eq!(normalize("-1", "kB"), (None, None));
+        assert_eq!(normalize("on", "ms"), (None, None));
+        assert_eq!(normalize("100", "percent"), (None, None));
+    }
+
+    #[test]
+    fn from_row_flags_non_default_values() {
+        let shared_buffers = from_row(row(
+            "shared_buffers",
+            "16384",
+            Some("8kB"),
+            "configuration file",
+        ));
+        assert!(!shared_buffers.is_default);
+        assert_eq!(shared_buffers.value_bytes, Some(134_217_728));
+
+        let timeout = from_row(row("statement_timeout", "0", Some("ms"), "default"));
+        assert!(timeout.is_default);
+        assert_eq!(timeout.value_ms, Some(0.0));
+    }
+}
@@ -0,0 +1,561 @@
+//! TimescaleDB support.
+//!
+//! A hypertable is an empty parent table whose rows live in chunks: plain
+//! tables, usually in `_timescaledb_internal`, created per time range (and
+//! space partition). Left alone, the generic Postgres collection reports
+//! every chunk, its indexes and its stats as separate tables. When the
+//! extension is installed in a database, this module:
+//!
+//! - collects hypertables with their dimensions, chunk counts, sizes and
+//!   compression statistics, continuous aggregates, and background jobs, in
+//!   the schemas passing `collection.postgres.schemas`
+//! - folds chunks into their hypertable: chunk tables, indexes and triggers
+//!   are removed from the schema, chunk sizes are added to the hypertable's
+//!   size, and chunk table and index counters are summed into the
+//!   hypertable's stats
+//! - drops the extension's own catalog tables from the schema and stats
+//!
+//! Compressed chunk data lives in chunks of an internal "compressed"
+//! hypertable; those are folded into the user-facing hypertable as well.
+
+use super::partitions::{add, merge_index_stats, merge_table_stats};
+use super::queries;
+use crate::collector::CollectorError;
+use crate::config::SchemasConfig;
+use crate::payload::{
+    ContinuousAggregate, Hypertable, HypertableDimension, IndexStats, SchemaMetadata, TableStats,
+    TimescaleJob, TimescaleMetadata,
+};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tracing::debug;
+
+type TableKey = (String, String);
+type IndexKey = (String, String, String);
+
+/// Schemas holding the extension's own catalog and configuration tables.
+/// Chunks live in `_timescaledb_internal` (or a per-hypertable schema) and
+/// are handled through the [`ChunkMap`] instead.
+const CATALOG_SCHEMAS: &[&str] = &[
+    "_timescaledb_catalog",
+    "_timescaledb_config",
+    "_timescaledb_cache",
+    "_timescaledb_functions",
+    "_timescaledb_debug",
+    "timescaledb_information",
+    "timescaledb_experimental",
+];
+
+/// Chunk relations and chunk indexes, mapped onto their hypertables.
+#[derive(Debug, Default)]
+pub(super) struct ChunkMap {
+    tables: HashMap<TableKey, TableKey>,
+    indexes: HashMap<IndexKey, IndexKey>,
+    /// Uncompressed chunk count per hypertable
+    chunk_counts: HashMap<TableKey, i64>,
+    /// `approximate_row_count` per hypertable, which unlike summed chunk
+    /// estimates also counts compressed rows
+    row_estimates: HashMap<TableKey, i64>,
+}
+
+/// Collect TimescaleDB metadata, or `None` if the extension is not installed
+/// in this database.
+pub(super) async fn collect(
+    pool: &PgPool,
+    schemas: &SchemasConfig,
+) -> Result<Option<(TimescaleMetadata, ChunkMap)>, CollectorError> {
+    let extension: Option<(String, String)> = sqlx::query_as(queries::TIMESCALEDB_EXTENSION)
+        .fetch_optional(pool)
+        .await?;
+    let Some((version, ext_schema)) = extension else {
+        return Ok(None);
+    };
+    debug!(version = %version, "Collecting TimescaleDB metadata");
+
+    let hypertables_sql = queries::timescaledb_hypertables(&ext_schema);
+    let jobs_sql = queries::timescaledb_jobs(version_at_least(&version, 2, 15));
+    let (hypertable_rows, dimension_rows, chunk_rows, cagg_rows, job_rows) = tokio::try_join!(
+        sqlx::query_as::<_, queries::HypertableRow>(&hypertables_sql).fetch_all(pool),
+        sqlx::query_as::<_, queries::HypertableDimensionRow>(queries::TIMESCALEDB_DIMENSIONS)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::TimescaleChunkRow>(queries::TIMESCALEDB_CHUNKS)
+            .fetch_all(pool),
+        sqlx::query_as::<_, queries::ContinuousAggregateRow>(
+            queries::TIMESCALEDB_CONTINUOUS_AGGREGATES
+        )
+        .fetch_all(pool),
+        sqlx::query_as::<_, queries::TimescaleJobRow>(&jobs_sql).fetch_all(pool),
+    )?;
+
+    let (has_chunk_index,): (bool,) =
+        sqlx::query_as("SELECT to_regclass('_timescaledb_catalog.chunk_index') IS NOT NULL")
+            .fetch_one(pool)
+            .await?;
+    let chunk_index_rows = if has_chunk_index {
+        sqlx::query_as::<_, queries::TimescaleChunkIndexRow>(queries::TIMESCALEDB_CHUNK_INDEXES)
+            .fetch_all(pool)
+            .await?
+    } else {
+        Vec::new()
+    };
+
+    let mut chunks = ChunkMap::default();
+    for row in chunk_rows {
+        let hypertable = (row.hypertable_schema, row.hypertable_name);
+        if !row.compressed {
+            *chunks.chunk_counts.entry(hypertable.clone()).or_default() += 1;
+        }
+        chunks
+            .tables
+            .insert((row.relation_schema, row.relation_name), hypertable);
+    }
+    for row in chunk_index_rows {
+        chunks.indexes.insert(
+            (row.chunk_schema, row.chunk_name, row.chunk_index),
+            (
+                row.hypertable_schema,
+                row.hypertable_name,
+                row.hypertable_index,
+            ),
+        );
+    }
+
+    let mut dimensions: HashMap<TableKey, Vec<HypertableDimension>> = HashMap::new();
+    for row in dimension_rows {
+        dimensions
+            .entry((row.hypertable_schema, row.hypertable_name))
+            .or_default()
+            .push(HypertableDimension {
+                column: row.column_name,
+                column_type: row.column_type,
+                dimension_type: row.dimension_type,
+                time_interval: row.time_interval,
+                integer_interval: row.integer_interval,
+                num_partitions: row.num_partitions,
+            });
+    }
+
+    let hypertables = hypertable_rows
+        .into_iter()
+        .filter(|row| schemas.matches(&row.hypertable_schema))
+        .map(|row| {
+            let key = (row.hypertable_schema.clone(), row.hypertable_name.clone());
+            if let Some(rows) = row.row_estimate {
+                chunks.row_estimates.insert(key.clone(), rows);
+            }
+            Hypertable {
+                dimensions: dimensions.remove(&key).unwrap_or_default(),
+                schema: row.hypertable_schema,
+                name: row.hypertable_name,
+                num_chunks: row.num_chunks,
+                compressed_chunks: row.compressed_chunks,
+                compression_enabled: row.compression_enabled.unwrap_or(false),
+                table_bytes: row.table_bytes,
+                index_bytes: row.index_bytes,
+                toast_bytes: row.toast_bytes,
+                total_bytes: row.total_bytes,
+                before_compression_bytes: row.before_compression_total_bytes,
+                after_compression_bytes: row.after_compression_total_bytes,
+                compression_ratio: compression_ratio(
+                    row.before_compression_total_bytes,
+                    row.after_compression_total_bytes,
+                ),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    // Refresh policies run on the materialization hypertable of a continuous
+    // aggregate, which lives in an internal schema
+    let cagg_schemas: HashMap<TableKey, String> = cagg_rows
+        .iter()
+        .filter_map(|row| {
+            let schema = row.materialization_hypertable_schema.clone()?;
+            let name = row.materialization_hypertable_name.clone()?;
+            Some(((schema, name), row.view_schema.clone()))
+        })
+        .collect();
+
+    let continuous_aggregates = cagg_rows
+        .into_iter()
+        .filter(|row| schemas.matches(&row.view_schema))
+        .map(|row| ContinuousAggregate {
+            schema: row.view_schema,
+            name: row.view_name,
+            hypertable_schema: row.hypertable_schema,
+            hypertable_name: row.hypertable_name,
+            materialized_only: row.materialized_only.unwrap_or(false),
+            compression_enabled: row.compression_enabled.unwrap_or(false),
+            materialization_hypertable: row
+                .materialization_hypertable_schema
+                .zip(row.materialization_hypertable_name)
+                .map(|(schema, name)| format!("{schema}.{name}")),
+            definition: row.view_definition,
+            ..Default::default()
+        })
+        .collect();
+
+    let jobs = job_rows
+        .into_iter()
+        .filter(|row| {
+            job_in_scope(
+                row.hypertable_schema.as_deref(),
+                row.hypertable_name.as_deref(),
+                &cagg_schemas,
+                schemas,
+            )
+        })
+        .map(|row| TimescaleJob {
+            id: row.job_id,
+            application_name: row.application_name,
+            procedure: row.procedure,
+            schedule_interval: row.schedule_interval,
+            scheduled: row.scheduled.unwrap_or(false),
+            hypertable_schema: row.hypertable_schema,
+            hypertable_name: row.hypertable_name,
+            config: row.config.and_then(|c| serde_json::from_str(&c).ok()),
+            job_status: row.job_status,
+            last_run_status: row.last_run_status,
+            last_run_started_at: row.last_run_started_at,
+            last_successful_finish: row.last_successful_finish,
+            last_run_duration_ms: row.last_run_duration_ms,
+            next_start: row.next_start,
+            total_runs: row.total_runs,
+            total_successes: row.total_successes,
+            total_failures: row.total_failures,
+            last_error: row.last_error,
+            last_error_at: row.last_error_at,
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(Some((
+        TimescaleMetadata {
+            version,
+            hypertables,
+            continuous_aggregates,
+            jobs,
+        },
+        chunks,
+    )))
+}
+
+/// Whether a job's hypertable is in a collected schema, taking a
+/// continuous aggregate's materialization hypertable to be in the schema of
+/// the aggregate. Jobs without a hypertable (telemetry, user-defined
+/// actions) are always kept.
+fn job_in_scope(
+    hypertable_schema: Option<&str>,
+    hypertable_name: Option<&str>,
+    cagg_schemas: &HashMap<TableKey, String>,
+    schemas: &SchemasConfig,
+) -> bool {
+    let (Some(schema), Some(name)) = (hypertable_schema, hypertable_name) else {
+        return true;
+    };
+    let schema = cagg_schemas
+        .get(&(schema.to_string(), name.to_string()))
+        .map_or(schema, String::as_str);
+    schemas.matches(schema)
+}
+
+/// Replace chunks in the collected schema and stats with their hypertables.
+pub(super) fn fold_chunks(
+    schema: &mut SchemaMetadata,
+    table_stats: &mut Vec<TableStats>,
+    index_stats: &mut Vec<IndexStats>,
+    chunks: &ChunkMap,
+) {
+    let internal = |name: &str| CATALOG_SCHEMAS.contains(&name);
+    schema.tables.retain(|t| !internal(&t.schema));
+    schema.indexes.retain(|i| !internal(&i.schema));
+    schema.sequences.retain(|s| !internal(&s.schema));
+    table_stats.retain(|t| !internal(&t.schema));
+    index_stats.retain(|i| !internal(&i.schema));
+
+    let hypertable_of =
+        |schema: &str, table: &str| chunks.tables.get(&(schema.to_string(), table.to_string()));
+
+    let mut sizes: HashMap<TableKey, Option<i64>> = HashMap::new();
+    schema
+        .tables
+        .retain(|t| match hypertable_of(&t.schema, &t.name) {
+            Some(hypertable) => {
+                add(sizes.entry(hypertable.clone()).or_default(), t.size_bytes);
+                false
+            }
+            None => true,
+        });
+    for table in schema.tables.iter_mut() {
+        let key = (table.schema.clone(), table.name.clone());
+        if let Some(size) = sizes.remove(&key) {
+            add(&mut table.size_bytes, size);
+        }
+        if let Some(count) = chunks.chunk_counts.get(&key) {
+            table.partition_count = Some(*count);
+        }
+        if let Some(rows) = chunks.row_estimates.get(&key) {
+            table.row_count_estimate = Some(*rows);
+        }
+    }
+    schema
+        .indexes
+        .retain(|i| hypertable_of(&i.schema, &i.table).is_none());
+    schema
+        .triggers
+        .retain(|t| hypertable_of(&t.schema, &t.table).is_none());
+
+    let mut rolled: HashMap<TableKey, TableStats> = HashMap::new();
+    table_stats.retain(|stats| match hypertable_of(&stats.schema, &stats.table) {
+        Some(hypertable) => {
+            let target = rolled
+                .entry(hypertable.clone())
+                .or_insert_with(|| TableStats {
+                    schema: hypertable.0.clone(),
+                    table: hypertable.1.clone(),
+                    ..Default::default()
+                });
+            merge_table_stats(target, stats);
+            false
+        }
+        None => true,
+    });
+    for stats in table_stats.iter_mut() {
+        if let Some(child) = rolled.remove(&(stats.schema.clone(), stats.table.clone())) {
+            merge_table_stats(stats, &child);
+        }
+    }
+    table_stats.extend(rolled.into_values());
+
+    let mut rolled: HashMap<IndexKey, IndexStats> = HashMap::new();
+    index_stats.retain(|stats| {
+        if hypertable_of(&stats.schema, &stats.table).is_none() {
+            return true;
+        }
+        let key = (
+            stats.schema.clone(),
+            stats.table.clone(),
+            stats.index.clone(),
+        );
+        if let Some(parent) = chunks.indexes.get(&key) {
+            let target = rolled.entry(parent.clone()).or_insert_with(|| IndexStats {
+                schema: parent.0.clone(),
+                table: parent.1.clone(),
+                index: parent.2.clone(),
+                ..Default::default()
+            });
+            merge_index_stats(target, stats);
+        }
+        false
+    });
+    for stats in index_stats.iter_mut() {
+        let key = (
+            stats.schema.clone(),
+            stats.table.clone(),
+            stats.index.clone(),
+        );
+        if let Some(child) = rolled.remove(&key) {
+            merge_index_stats(stats, &child);
+        }
+    }
+    index_stats.extend(rolled.into_values());
+}
+
+fn compression_ratio(before: Option<i64>, after: Option<i64>) -> Option<f64> {
+    match (before, after) {
+        (Some(before), Some(after)) if after > 0 => Some(before as f64 / after as f64),
+        _ => None,
+    }
+}
+
+/// Whether an extension version such as `2.15.1` is at least `major.minor`.
+fn version_at_least(version: &str, major: u32, minor: u32) -> bool {
+    let mut parts = version
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|p| p.parse::<u32>().unwrap_or(0));
+    let found = (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+    found >= (major, minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::{IndexMetadata, TableMetadata};
+
+    const INTERNAL: &str = "_timescaledb_internal";
+
+    fn key(schema: &str, name: &str) -> TableKey {
+        (schema.to_string(), name.to_string())
+    }
+
+    fn table(schema: &str, name: &str, size: i64) -> TableMetadata {
+        TableMetadata {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            size_bytes: Some(size),
+            ..Default::default()
+        }
+    }
+
+    fn table_stats(schema: &str, name: &str, n_tup_ins: i64) -> TableStats {
+        TableStats {
+            schema: schema.to_string(),
+            table: name.to_string(),
+            n_tup_ins: Some(n_tup_ins),
+            ..Default::default()
+        }
+    }
+
+    fn index_stats(schema: &str, table: &str, index: &str, idx_scan: i64) -> IndexStats {
+        IndexStats {
+            schema: schema.to_string(),
+            table: table.to_string(),
+            index: index.to_string(),
+            idx_scan: Some(idx_scan),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_job_in_scope() {
+        let schemas = SchemasConfig {
+            include: vec!["public".to_string()],
+            exclude: Vec::new(),
+        };
+        let mut cagg_schemas = HashMap::new();
+        cagg_schemas.insert(
+            key(INTERNAL, "_materialized_hypertable_3"),
+            "public".to_string(),
+        );
+        cagg_schemas.insert(
+            key(INTERNAL, "_materialized_hypertable_4"),
+            "archive".to_string(),
+        );
+
+        let in_scope =
+            |schema, name| job_in_scope(Some(schema), Some(name), &cagg_schemas, &schemas);
+        assert!(in_scope("public", "metrics"));
+        assert!(!in_scope("archive", "metrics"));
+        assert!(in_scope(INTERNAL, "_materialized_hypertable_3"));
+        assert!(!in_scope(INTERNAL, "_materialized_hypertable_4"));
+        assert!(job_in_scope(None, None, &cagg_schemas, &schemas));
+    }
+
+    /// `public.metrics` with two chunks and one compressed chunk, plus an
+    /// unrelated `public.devices`
+    fn fixture() -> (SchemaMetadata, Vec<TableStats>, Vec<IndexStats>, ChunkMap) {
+        let metrics = key("public", "metrics");
+        let mut chunks = ChunkMap::default();
+        for chunk in ["_hyper_1_1_chunk", "_hyper_1_2_chunk"] {
+            chunks.tables.insert(key(INTERNAL, chunk), metrics.clone());
+        }
+        chunks
+            .tables
+            .insert(key(INTERNAL, "compress_hyper_2_3_chunk"), metrics.clone());
+        chunks.chunk_counts.insert(metrics.clone(), 2);
+        chunks.row_estimates.insert(metrics.clone(), 5000);
+        for chunk in ["_hyper_1_1_chunk", "_hyper_1_2_chunk"] {
+            chunks.indexes.insert(
+                (
+                    INTERNAL.to_string(),
+                    chunk.to_string(),
+                    format!("{chunk}_metrics_time_idx"),
+                ),
+                (
+                    "public".to_string(),
+                    "metrics".to_string(),
+                    "metrics_time_idx".to_string(),
+                ),
+            );
+        }
+
+        let schema = SchemaMetadata {
+            tables: vec![
+                table("public", "metrics", 8192),
+                table(INTERNAL, "_hyper_1_1_chunk", 1000),
+                table(INTERNAL, "_hyper_1_2_chunk", 2000),
+                table(INTERNAL, "compress_hyper_2_3_chunk", 300),
+                table("public", "devices", 50),
+                table("_timescaledb_catalog", "hypertable", 16384),
+            ],
+            indexes: vec![
+                IndexMetadata {
+                    schema: "public".to_string(),
+                    table: "metrics".to_string(),
+                    name: "metrics_time_idx".to_string(),
+                    ..Default::default()
+                },
+                IndexMetadata {
+                    schema: INTERNAL.to_string(),
+                    table: "_hyper_1_1_chunk".to_string(),
+                    name: "_hyper_1_1_chunk_metrics_time_idx".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let table_stats = vec![
+            table_stats("public", "metrics", 0),
+            table_stats(INTERNAL, "_hyper_1_1_chunk", 10),
+            table_stats(INTERNAL, "_hyper_1_2_chunk", 20),
+            table_stats("public", "devices", 5),
+            table_stats("_timescaledb_config", "bgw_job", 1),
+        ];
+        let index_stats = vec![
+            index_stats(
+                INTERNAL,
+                "_hyper_1_1_chunk",
+                "_hyper_1_1_chunk_metrics_time_idx",
+                3,
+            ),
+            index_stats(
+                INTERNAL,
+                "_hyper_1_2_chunk",
+                "_hyper_1_2_chunk_metrics_time_idx",
+                4,
+            ),
+        ];
+        (schema, table_stats, index_stats, chunks)
+    }
+
+    #[test]
+    fn test_fold_chunks_into_hypertable() {
+        let (mut schema, mut table_stats, mut index_stats, chunks) = fixture();
+        fold_chunks(&mut schema, &mut table_stats, &mut index_stats, &chunks);
+
+        let names: Vec<&str> = schema.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["metrics", "devices"]);
+        let metrics = &schema.tables[0];
+        assert_eq!(metrics.size_bytes, Some(8192 + 1000 + 2000 + 300));
+        assert_eq!(metrics.partition_count, Some(2));
+        assert_eq!(metrics.row_count_estimate, Some(5000));
+        assert_eq!(schema.indexes.len(), 1);
+
+        assert_eq!(table_stats.len(), 2);
+        assert_eq!(table_stats[0].table, "metrics");
+        assert_eq!(table_stats[0].n_tup_ins, Some(30));
+        assert_eq!(table_stats[1].n_tup_ins, Some(5));
+
+        // Chunk index counters land on the hypertable index, which has no
+        // stats row of its own
+        assert_eq!(index_stats.len(), 1);
+        assert_eq!(index_stats[0].table, "metrics");
+        assert_eq!(index_stats[0].index, "metrics_time_idx");
+        assert_eq!(index_stats[0].idx_scan, Some(7));
+    }
+
+    #[test]
+    fn test_version_at_least() {
+        assert!(version_at_least("2.15.0", 2, 15));
+        assert!(version_at_least("2.17.2", 2, 15));
+        assert!(version_at_least("3.0.0-dev", 2, 15));
+        assert!(!version_at_least("2.9.3", 2, 15));
+    }
+
+    #[test]
+    fn test_compression_ratio() {
+        assert_eq!(compression_ratio(Some(1000), Some(100)), Some(10.0));
+        assert_eq!(compression_ratio(Some(1000), Some(0)), None);
+        assert_eq!(compression_ratio(None, Some(100)), None);
+    }
+}
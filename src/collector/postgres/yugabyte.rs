@@ -0,0 +1,100 @@
+//! YugabyteDB support.
+//!
+//! YSQL keeps the Postgres catalogs and `pg_stat_statements`, so the
+//! generic collection applies as is. What it adds is tablet placement: the
+//! tablet servers of the cluster from `yb_servers()` and, on releases that
+//! have it, the tablets hosted by the connected server from
+//! `yb_local_tablets`.
+
+use super::queries;
+use crate::collector::CollectorError;
+use crate::config::SchemasConfig;
+use crate::payload::{Distribution, DistributionNode, TableDistribution};
+use sqlx::PgPool;
+use tracing::debug;
+
+/// Tablet servers and per-table tablet counts.
+pub(super) async fn tablets(
+    pool: &PgPool,
+    filter: &SchemasConfig,
+) -> Result<Distribution, CollectorError> {
+    debug!("Collecting tablet distribution");
+
+    let servers = sqlx::query_as::<_, queries::YbServerRow>(queries::YB_SERVERS)
+        .fetch_all(pool)
+        .await?;
+
+    let (has_local_tablets,): (bool,) = sqlx::query_as(queries::YB_HAS_LOCAL_TABLETS)
+        .fetch_one(pool)
+        .await?;
+    let tables = if has_local_tablets {
+        sqlx::query_as::<_, queries::YbLocalTabletsRow>(queries::YB_LOCAL_TABLETS)
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .filter_map(|row| {
+                let schema = row.ysql_schema_name?;
+                filter.matches(&schema).then_some(TableDistribution {
+                    database: row.namespace_name,
+                    schema,
+                    table: row.table_name,
+                    units: row.tablets,
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(Distribution {
+        unit: "tablet".to_string(),
+        total_units: None,
+        nodes: servers
+            .into_iter()
+            .map(|row| DistributionNode {
+                node: match row.port {
+                    Some(port) => format!("{}:{}", row.host, port),
+                    None => row.host.clone(),
+                },
+                address: Some(row.host),
+                locality: locality(&row.cloud, &row.region, &row.zone),
+                node_type: row.node_type,
+                connections: row.num_connections,
+                ..Default::default()
+            })
+            .collect(),
+        tables,
+    })
+}
+
+/// `cloud=aws,region=us-east-1,zone=us-east-1a`, in the key=value form
+/// CockroachDB uses for localities
+fn locality(
+    cloud: &Option<String>,
+    region: &Option<String>,
+    zone: &Option<String>,
+) -> Option<String> {
+    let parts: Vec<String> = [("cloud", cloud), ("region", region), ("zone", zone)]
+        .into_iter()
+        .filter_map(|(key, value)| value.as_ref().map(|v| format!("{key}={v}")))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locality() {
+        assert_eq!(
+            locality(
+                &Some("aws".to_string()),
+                &Some("us-east-1".to_string()),
+                &Some("us-east-1a".to_string())
+            ),
+            Some("cloud=aws,region=us-east-1,zone=us-east-1a".to_string())
+        );
+        assert_eq!(locality(&None, &None, &None), None);
+    }
+}
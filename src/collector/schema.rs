@@ -0,0 +1,421 @@
+//! Schema inference for schemaless documents via sampling.
+//!
+//! `SchemaWalker` accumulates per-path statistics across a sample of
+//! documents and emits one [`ColumnMetadata`] per unique path. Paths are
+//! flattened with dot notation for nested objects (`address.street`) and
+//! `[]` segments for array elements (`photos[]`, `photos[].url`).
+//!
+//! The walker is not tied to an engine: any value type implementing
+//! [`DocumentValue`] can be walked. BSON is implemented in the MongoDB
+//! collector; `serde_json::Value` is implemented here and used for
+//! Couchbase documents and Postgres `jsonb` columns.
+//!
+//! All observation work runs in `O(paths × document_size)` time. Memory is
+//! bounded by the per-path distinct-value cap and the global byte ceiling
+//! (see module-level constants) so even pathologically polymorphic
+//! collections cannot exhaust process memory.
+
+use crate::payload::ColumnMetadata;
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// Default minimum sample size — collections smaller than this are sampled in full.
+pub const MIN_SAMPLES: i64 = 100;
+/// Default maximum sample size, regardless of collection cardinality.
+pub const MAX_SAMPLES: i64 = 1_000;
+/// Target sample ratio when collection is between MIN and MAX bounds.
+pub const TARGET_RATIO: f64 = 0.01;
+
+/// Maximum recursion depth before paths are truncated.
+pub const MAX_DEPTH: u8 = 16;
+/// Maximum unique paths tracked per collection.
+pub const MAX_PATHS_PER_COLLECTION: usize = 5_000;
+/// Maximum array elements walked per document at any single `[]` level.
+pub const MAX_ARRAY_ELEMENTS_PER_DOC: usize = 100;
+
+/// Per-path distinct value cap. Once exceeded, `distinct_capped = true`.
+pub const DISTINCT_VALUE_CAP: usize = 1_000;
+/// Reservoir size for sample values per path.
+pub const SAMPLE_VALUES_PER_PATH: usize = 5;
+/// Global kill-switch for distinct-tracking memory. When exceeded, all paths
+/// switch to capped mode (no more distinct accumulation).
+pub const MAX_DISTINCT_TRACKED_BYTES: usize = 256 * 1024 * 1024;
+
+/// Pick an appropriate sample size for a given collection cardinality.
+///
+/// - Tiny collections (`<= MIN_SAMPLES`): sample everything.
+/// - Mid-sized: sample 1 % (clamped to MIN..=MAX).
+/// - Huge: capped at MAX_SAMPLES.
+pub fn pick_sample_size(doc_count: i64) -> i64 {
+    if doc_count <= 0 {
+        return MIN_SAMPLES;
+    }
+    let target = (doc_count as f64 * TARGET_RATIO).ceil() as i64;
+    target.clamp(MIN_SAMPLES.min(doc_count), MAX_SAMPLES)
+}
+
+/// A value inside a document, as seen by [`SchemaWalker`].
+pub trait DocumentValue: Sized {
+    /// Stable lowercase type label, recorded per path and used as
+    /// `ColumnMetadata.data_type`
+    fn type_label(&self) -> &'static str;
+
+    fn is_null(&self) -> bool;
+
+    /// Fields of an embedded document, `None` for any other value
+    fn fields(&self) -> Option<impl Iterator<Item = (&str, &Self)>>;
+
+    /// Elements of an array, `None` for any other value
+    fn elements(&self) -> Option<&[Self]>;
+
+    /// Compact JSON rendering of a leaf, for distinct tracking and samples
+    fn to_json(&self) -> Value;
+}
+
+/// JSON values carry the type names of `jsonb_typeof` and N1QL `TYPE()`.
+impl DocumentValue for Value {
+    fn type_label(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(_) => "number",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    fn is_null(&self) -> bool {
+        self.is_null()
+    }
+
+    fn fields(&self) -> Option<impl Iterator<Item = (&str, &Self)>> {
+        Some(self.as_object()?.iter().map(|(k, v)| (k.as_str(), v)))
+    }
+
+    fn elements(&self) -> Option<&[Self]> {
+        self.as_array().map(Vec::as_slice)
+    }
+
+    fn to_json(&self) -> Value {
+        self.clone()
+    }
+}
+
+/// Accumulator for schema inference across a stream of documents.
+pub struct SchemaWalker {
+    paths: IndexMap<String, PathStats>,
+    docs_sampled: i64,
+    truncated_paths: u64,
+    distinct_bytes: usize,
+    distinct_globally_capped: bool,
+    next_position: i32,
+}
+
+struct PathStats {
+    seen_count: i64,
+    null_count: i64,
+    types: BTreeSet<&'static str>,
+    distinct: HashSet<String>, // serialized JSON repr → cheap, deterministic hashable
+    distinct_capped: bool,
+    samples: Vec<Value>, // reservoir, len <= SAMPLE_VALUES_PER_PATH
+    samples_seen: u64,
+    array_max_len: Option<i64>,
+    is_array_element: bool,
+    first_seen_position: i32,
+}
+
+impl SchemaWalker {
+    pub fn new() -> Self {
+        Self {
+            paths: IndexMap::new(),
+            docs_sampled: 0,
+            truncated_paths: 0,
+            distinct_bytes: 0,
+            distinct_globally_capped: false,
+            next_position: 1,
+        }
+    }
+
+    /// Number of documents observed so far (denominator for presence_rate).
+    pub fn docs_sampled(&self) -> i64 {
+        self.docs_sampled
+    }
+
+    /// Truncated-path counter, exposed for tracing/diagnostics.
+    pub fn truncated_paths(&self) -> u64 {
+        self.truncated_paths
+    }
+
+    /// Observe one top-level document, given as its `(name, value)` fields:
+    /// a BSON `&Document` or a `&serde_json::Map`.
+    pub fn observe_document<'a, V, I>(&mut self, doc: I)
+    where
+        V: DocumentValue + 'a,
+        I: IntoIterator<Item = (&'a String, &'a V)>,
+    {
+        self.docs_sampled += 1;
+        let mut visited_in_doc: HashSet<String> = HashSet::new();
+        for (key, value) in doc {
+            self.observe_path(key, value, 1, false, &mut visited_in_doc);
+        }
+    }
+
+    fn observe_path<V: DocumentValue>(
+        &mut self,
+        path: &str,
+        value: &V,
+        depth: u8,
+        in_array: bool,
+        visited_in_doc: &mut HashSet<String>,
+    ) {
+        if depth > MAX_DEPTH {
+            self.record_path(path, "<truncated>", value, in_array, visited_in_doc);
+            self.truncated_paths += 1;
+            return;
+        }
+
+        // Record the value at this path (counts toward presence even for containers).
+        self.record_path(path, value.type_label(), value, in_array, visited_in_doc);
+
+        // Recurse into structural children.
+        if let Some(fields) = value.fields() {
+            for (k, v) in fields {
+                let child_path = format!("{}.{}", path, k);
+                self.observe_path(&child_path, v, depth + 1, in_array, visited_in_doc);
+            }
+        } else if let Some(items) = value.elements() {
+            let array_path = format!("{}[]", path);
+            // Track max length even if elements are clipped.
+            if let Some(stats) = self.paths.get_mut(path) {
+                let len = items.len() as i64;
+                stats.array_max_len = Some(stats.array_max_len.map_or(len, |m| m.max(len)));
+            }
+            for item in items.iter().take(MAX_ARRAY_ELEMENTS_PER_DOC) {
+                self.observe_path(&array_path, item, depth + 1, true, visited_in_doc);
+            }
+        }
+    }
+
+    fn record_path<V: DocumentValue>(
+        &mut self,
+        path: &str,
+        type_label: &'static str,
+        value: &V,
+        in_array: bool,
+        visited_in_doc: &mut HashSet<String>,
+    ) {
+        let already_seen_this_doc = visited_in_doc.contains(path);
+
+        // Refuse new paths once the cap is hit.
+        if !self.paths.contains_key(path) && self.paths.len() >= MAX_PATHS_PER_COLLECTION {
+            self.truncated_paths += 1;
+            return;
+        }
+
+        let position = self.next_position;
+        let stats = self
+            .paths
+            .entry(path.to_string())
+            .or_insert_with(|| PathStats {
+                seen_count: 0,
+                null_count: 0,
+                types: BTreeSet::new(),
+                distinct: HashSet::new(),
+                distinct_capped: false,
+                samples: Vec::new(),
+                samples_seen: 0,
+                array_max_len: None,
+                is_array_element: in_array,
+                first_seen_position: position,
+            });
+        if stats.first_seen_position == position {
+            self.next_position += 1;
+        }
+
+        if !already_seen_this_doc {
+            stats.seen_count += 1;
+            visited_in_doc.insert(path.to_string());
+        }
+        if value.is_null() {
+            stats.null_count += 1;
+        }
+        stats.types.insert(type_label);
+        if in_array {
+            stats.is_array_element = true;
+        }
+
+        // Distinct tracking — leaves only.
+        let is_leaf = value.fields().is_none() && value.elements().is_none();
+        if is_leaf && !stats.distinct_capped && !self.distinct_globally_capped {
+            let key = serde_json::to_string(&value.to_json()).unwrap_or_default();
+            if stats.distinct.len() < DISTINCT_VALUE_CAP {
+                let added = stats.distinct.insert(key.clone());
+                if added {
+                    self.distinct_bytes += key.len();
+                    if self.distinct_bytes > MAX_DISTINCT_TRACKED_BYTES {
+                        self.distinct_globally_capped = true;
+                    }
+                }
+            } else {
+                stats.distinct_capped = true;
+            }
+        }
+
+        // Reservoir sampling (Algorithm R) — deterministic surrogate using
+        // a Knuth multiplicative hash of samples_seen as the index source.
+        if is_leaf {
+            stats.samples_seen += 1;
+            let json = value.to_json();
+            if stats.samples.len() < SAMPLE_VALUES_PER_PATH {
+                stats.samples.push(json);
+            } else {
+                let n = stats.samples_seen;
+                let h = n.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 32;
+                let j = (h as usize) % (n as usize);
+                if j < SAMPLE_VALUES_PER_PATH {
+                    stats.samples[j] = json;
+                }
+            }
+        }
+    }
+
+    /// Emit one [`ColumnMetadata`] per observed path, ordered by first-seen position.
+    pub fn into_columns(self) -> Vec<ColumnMetadata> {
+        let denom = self.docs_sampled.max(1) as f64;
+        let globally_capped = self.distinct_globally_capped;
+        let mut entries: Vec<(String, PathStats)> = self.paths.into_iter().collect();
+        entries.sort_by_key(|(_, s)| s.first_seen_position);
+
+        entries
+            .into_iter()
+            .map(|(path, s)| {
+                let presence_rate = s.seen_count as f64 / denom;
+                let null_rate = s.null_count as f64 / denom;
+                let nullable = s.null_count > 0 || presence_rate < 1.0;
+
+                let data_type = if s.types.len() == 1 {
+                    s.types
+                        .iter()
+                        .next()
+                        .copied()
+                        .unwrap_or("unknown")
+                        .to_string()
+                } else if s.types.is_empty() {
+                    "unknown".to_string()
+                } else {
+                    "mixed".to_string()
+                };
+
+                let bson_types: Vec<String> = s.types.iter().map(|t| (*t).to_string()).collect();
+                let distinct_capped = s.distinct_capped || globally_capped;
+                let distinct_count = if globally_capped && s.distinct.is_empty() {
+                    None
+                } else {
+                    Some(s.distinct.len() as i64)
+                };
+
+                ColumnMetadata {
+                    name: path,
+                    data_type,
+                    nullable,
+                    default: None,
+                    position: s.first_seen_position,
+                    presence_rate: Some(presence_rate),
+                    null_rate: Some(null_rate),
+                    bson_types: if bson_types.is_empty() {
+                        None
+                    } else {
+                        Some(bson_types)
+                    },
+                    distinct_count,
+                    distinct_capped: Some(distinct_capped),
+                    sample_values: if s.samples.is_empty() {
+                        None
+                    } else {
+                        Some(s.samples)
+                    },
+                    is_array_element: Some(s.is_array_element),
+                    array_max_len: s.array_max_len,
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+impl Default for SchemaWalker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn walk(docs: &[Value]) -> Vec<ColumnMetadata> {
+        let mut w = SchemaWalker::new();
+        for doc in docs {
+            w.observe_document(doc.as_object().unwrap());
+        }
+        w.into_columns()
+    }
+
+    fn col<'a>(cols: &'a [ColumnMetadata], name: &str) -> &'a ColumnMetadata {
+        cols.iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("path {name} missing"))
+    }
+
+    #[test]
+    fn json_type_labels() {
+        let cols = walk(&[json!({
+            "n": 1, "f": 1.5, "s": "x", "b": true, "z": null, "o": {}, "a": []
+        })]);
+        for (name, label) in [
+            ("n", "number"),
+            ("f", "number"),
+            ("s", "string"),
+            ("b", "boolean"),
+            ("z", "null"),
+            ("o", "object"),
+            ("a", "array"),
+        ] {
+            assert_eq!(col(&cols, name).data_type, label);
+        }
+    }
+
+    #[test]
+    fn json_paths_and_rates() {
+        let cols = walk(&[
+            json!({"user": {"id": 1, "roles": ["admin", "ops"]}, "v": 1}),
+            json!({"user": {"id": 2, "roles": []}, "v": "x"}),
+            json!({"user": null}),
+        ]);
+        assert_eq!(col(&cols, "user").data_type, "mixed");
+        assert!((col(&cols, "user").null_rate.unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert!((col(&cols, "user.id").presence_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(col(&cols, "user.roles").array_max_len, Some(2));
+        assert_eq!(col(&cols, "user.roles[]").data_type, "string");
+        assert_eq!(col(&cols, "user.roles[]").is_array_element, Some(true));
+        assert_eq!(col(&cols, "v").distinct_count, Some(2));
+        assert_eq!(
+            col(&cols, "v").sample_values,
+            Some(vec![json!(1), json!("x")])
+        );
+    }
+
+    #[test]
+    fn pick_sample_size_clamps() {
+        assert_eq!(pick_sample_size(0), MIN_SAMPLES);
+        assert_eq!(pick_sample_size(50), 50);
+        assert_eq!(pick_sample_size(MIN_SAMPLES), MIN_SAMPLES);
+        // 1 % of 50_000 is 500, below MAX
+        assert_eq!(pick_sample_size(50_000), 500);
+        // 1 % of 1_000_000 would be 10_000 — clamps to MAX_SAMPLES
+        assert_eq!(pick_sample_size(1_000_000), MAX_SAMPLES);
+    }
+}
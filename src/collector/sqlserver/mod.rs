@@ -0,0 +1,192 @@
+//! SQL Server collector, over TDS with `tiberius`.
+//!
+//! Collects from the database named in the connection URL (the login's
+//! default database otherwise):
+//! - Query statistics from the Query Store, or from the plan cache
+//!   (`sys.dm_exec_query_stats`) where the Query Store is off
+//! - Table row counts and sizes (`sys.dm_db_partition_stats`) with access and
+//!   modification counters
+//! - Index usage (`sys.dm_db_index_usage_stats`) and missing index
+//!   suggestions
+//! - Server options (`sys.configurations`), database scoped configurations
+//!   and database options
+//! - Schema metadata from the `sys.*` catalog views
+//!
+//! Azure SQL Database, Azure SQL Managed Instance, Amazon RDS and
+//! self-managed servers are told apart in [`providers`]. The login needs
+//! `VIEW SERVER STATE` (`VIEW DATABASE STATE` on Azure SQL Database) for the
+//! DMVs.
+//!
+//! TDS runs one request at a time per connection, so the collector holds a
+//! single connection and queries it sequentially. A connection that fails
+//! mid-cycle is dropped and reopened on the next one.
+
+mod connection;
+pub mod providers;
+mod queries;
+mod rows;
+mod schema;
+mod stats;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, Payload, SqlServerMetadata};
+use async_trait::async_trait;
+use connection::SqlClient;
+use providers::ServerInfo;
+use rows::{fetch_all, flag, int, text};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// SQL Server metrics collector
+pub struct SqlServerCollector {
+    config: tiberius::Config,
+    client: Mutex<Option<SqlClient>>,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+    server_info: ServerInfo,
+}
+
+impl SqlServerCollector {
+    /// Connect, read the server properties and detect the provider.
+    pub async fn new(database_url: &str, provider: Provider) -> Result<Self, CollectorError> {
+        info!("Connecting to SQL Server");
+
+        let config = connection::config_from_url(database_url)?;
+        let mut client = connection::connect(&config).await?;
+
+        let row = fetch_all(&mut client, queries::SERVER_INFO)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CollectorError::QueryError("No server properties returned".into()))?;
+        let version = text(&row, "product_version");
+        let server_info = ServerInfo {
+            engine_edition: int(&row, "engine_edition"),
+            edition: text(&row, "edition"),
+            has_rdsadmin: flag(&row, "has_rdsadmin"),
+            server_name: text(&row, "server_name"),
+        };
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            database = text(&row, "database_name").as_deref().unwrap_or("unknown"),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(&server_info, database_url).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            config,
+            client: Mutex::new(Some(client)),
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+            server_info,
+        })
+    }
+
+    async fn collect_with(&self, client: &mut SqlClient) -> Result<Payload, CollectorError> {
+        let query_store_state = stats::query_store_state(client).await?;
+        let query_stats = stats::query_stats(client, query_store_state.as_deref()).await?;
+        let table_stats = stats::table_stats(client).await?;
+        let index_stats = stats::index_stats(client).await?;
+        let (settings, setting_details) = stats::settings(client).await?;
+        let schema = schema::collect(client).await?;
+        // Optional like the other engine-specific sections
+        let missing_indexes = stats::missing_indexes(client).await.unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to collect missing index suggestions");
+            Vec::new()
+        });
+
+        let host = self.config.get_addr();
+        let host = host
+            .rsplit_once(':')
+            .map_or(host.as_str(), |(host, _)| host);
+        let database_info = DatabaseInfo {
+            database_type: DatabaseType::Sqlserver.to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                &self.detected_provider,
+                &self.server_info,
+                host,
+            ),
+        };
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_query_stats(query_stats)
+            .with_table_stats(table_stats)
+            .with_index_stats(index_stats)
+            .with_settings(settings)
+            .with_setting_details(setting_details)
+            .with_schema(schema)
+            .with_sqlserver(SqlServerMetadata {
+                query_store_state,
+                missing_indexes,
+            }))
+    }
+}
+
+#[async_trait]
+impl Collector for SqlServerCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let mut guard = self.client.lock().await;
+        if guard.is_none() {
+            *guard = Some(connection::connect(&self.config).await?);
+        }
+        let client = guard.as_mut().expect("connected above");
+
+        let result = self.collect_with(client).await;
+        if let Err(CollectorError::ConnectionError(_)) = &result {
+            *guard = None;
+        }
+        let payload = result?;
+
+        info!(
+            tables = payload.schema.as_ref().map(|s| s.tables.len()).unwrap_or(0),
+            indexes = payload
+                .schema
+                .as_ref()
+                .map(|s| s.indexes.len())
+                .unwrap_or(0),
+            queries = payload.query_stats.as_ref().map(|q| q.len()).unwrap_or(0),
+            "Metrics collection complete"
+        );
+
+        Ok(payload)
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        let mut guard = self.client.lock().await;
+        if guard.is_none() {
+            *guard = Some(connection::connect(&self.config).await?);
+        }
+        let client = guard.as_mut().expect("connected above");
+        if let Err(e) = fetch_all(client, "SELECT 1").await {
+            *guard = None;
+            return Err(CollectorError::ConnectionError(e.to_string()));
+        }
+        Ok(())
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Sqlserver
+    }
+}
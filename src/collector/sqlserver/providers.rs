@@ -0,0 +1,146 @@
+//! SQL Server provider detection.
+//!
+//! Returns one of:
+//! - `"azure_sql"` — Azure SQL Database
+//! - `"azure_sql_managed_instance"` — Azure SQL Managed Instance
+//! - `"rds"` — Amazon RDS for SQL Server
+//! - `"generic"` — self-managed (on-premises or on a VM)
+//!
+//! `SERVERPROPERTY('EngineEdition')` identifies both Azure offerings
+//! regardless of the hostname used to connect (private endpoints, failover
+//! group listeners). RDS instances carry an `rdsadmin` database.
+
+use std::collections::HashMap;
+
+pub const PROVIDER_AZURE_SQL: &str = "azure_sql";
+pub const PROVIDER_AZURE_SQL_MI: &str = "azure_sql_managed_instance";
+pub const PROVIDER_RDS: &str = "rds";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// `SERVERPROPERTY('EngineEdition')` of Azure SQL Database
+const ENGINE_EDITION_AZURE_SQL: i64 = 5;
+/// `SERVERPROPERTY('EngineEdition')` of Azure SQL Managed Instance
+const ENGINE_EDITION_AZURE_SQL_MI: i64 = 8;
+
+/// What the server says about itself, from `SERVER_INFO`
+#[derive(Debug, Default)]
+pub struct ServerInfo {
+    pub engine_edition: Option<i64>,
+    pub edition: Option<String>,
+    pub has_rdsadmin: bool,
+    pub server_name: Option<String>,
+}
+
+/// Detect the provider from the server properties, falling back to the URL.
+pub fn detect_provider(info: &ServerInfo, url: &str) -> &'static str {
+    match info.engine_edition {
+        Some(ENGINE_EDITION_AZURE_SQL) => return PROVIDER_AZURE_SQL,
+        Some(ENGINE_EDITION_AZURE_SQL_MI) => return PROVIDER_AZURE_SQL_MI,
+        _ => {}
+    }
+    if info.has_rdsadmin {
+        return PROVIDER_RDS;
+    }
+
+    let lower = url.to_lowercase();
+    if lower.contains(".rds.amazonaws.com") {
+        PROVIDER_RDS
+    } else if lower.contains(".database.windows.net") {
+        PROVIDER_AZURE_SQL
+    } else {
+        PROVIDER_GENERIC
+    }
+}
+
+/// Provider metadata derivable from the server properties and hostname.
+pub fn provider_metadata(provider: &str, info: &ServerInfo, host: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    if let Some(edition) = &info.edition {
+        out.insert("edition".to_string(), edition.clone());
+    }
+    match provider {
+        PROVIDER_AZURE_SQL | PROVIDER_AZURE_SQL_MI => {
+            // `myserver.database.windows.net`, or for a managed instance
+            // `myinstance.abc123def456.database.windows.net`
+            if let Some(server) = host.split('.').next().filter(|s| !s.is_empty()) {
+                out.insert("azure_server".to_string(), server.to_string());
+            }
+        }
+        PROVIDER_RDS => {
+            // `mydb.abc123xyz.us-east-1.rds.amazonaws.com`
+            let parts: Vec<&str> = host.split('.').collect();
+            if parts.len() >= 6 {
+                out.insert("instance_identifier".to_string(), parts[0].to_string());
+                out.insert("region".to_string(), parts[2].to_string());
+            }
+        }
+        _ => {
+            if let Some(server_name) = &info.server_name {
+                out.insert("server_name".to_string(), server_name.clone());
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_azure_from_engine_edition() {
+        let info = ServerInfo {
+            engine_edition: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&info, "sqlserver://u:p@10.0.0.4/db"),
+            PROVIDER_AZURE_SQL
+        );
+
+        let info = ServerInfo {
+            engine_edition: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&info, "sqlserver://u:p@mi.abc123.database.windows.net/db"),
+            PROVIDER_AZURE_SQL_MI
+        );
+    }
+
+    #[test]
+    fn detects_rds() {
+        let info = ServerInfo {
+            engine_edition: Some(3),
+            has_rdsadmin: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&info, "sqlserver://u:p@db.internal/db"),
+            PROVIDER_RDS
+        );
+    }
+
+    #[test]
+    fn falls_back_to_generic() {
+        let info = ServerInfo {
+            engine_edition: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            detect_provider(&info, "sqlserver://sa:p@localhost/master"),
+            PROVIDER_GENERIC
+        );
+    }
+
+    #[test]
+    fn rds_metadata_extracts_identifier_and_region() {
+        let meta = provider_metadata(
+            PROVIDER_RDS,
+            &ServerInfo::default(),
+            "orders.abc123xyz.eu-west-1.rds.amazonaws.com",
+        );
+        assert_eq!(meta.get("instance_identifier").unwrap(), "orders");
+        assert_eq!(meta.get("region").unwrap(), "eu-west-1");
+    }
+}
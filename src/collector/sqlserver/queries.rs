@@ -0,0 +1,354 @@
+//! SQL Server DMV and catalog queries.
+//!
+//! Columns are cast to `BIGINT`, `FLOAT`, `BIT` or `NVARCHAR` so the row
+//! readers in [`super::rows`] don't have to cope with `TINYINT`, `NUMERIC` or
+//! `sql_variant` results. Everything targets SQL Server 2016 and later and
+//! Azure SQL; nothing relies on `STRING_AGG`.
+
+/// Version, edition and engine edition of the server
+pub const SERVER_INFO: &str = r#"
+SELECT
+    CONVERT(NVARCHAR(4000), @@VERSION) AS version_string,
+    CONVERT(NVARCHAR(128), SERVERPROPERTY('ProductVersion')) AS product_version,
+    CONVERT(NVARCHAR(128), SERVERPROPERTY('Edition')) AS edition,
+    CAST(SERVERPROPERTY('EngineEdition') AS BIGINT) AS engine_edition,
+    CAST(CASE WHEN DB_ID('rdsadmin') IS NOT NULL THEN 1 ELSE 0 END AS BIT) AS has_rdsadmin,
+    CONVERT(NVARCHAR(128), DB_NAME()) AS database_name,
+    CONVERT(NVARCHAR(128), SERVERPROPERTY('ServerName')) AS server_name
+"#;
+
+/// Query Store state of the current database. Fails before SQL Server 2016.
+pub const QUERY_STORE_STATE: &str = r#"
+SELECT CONVERT(NVARCHAR(60), actual_state_desc) AS actual_state
+FROM sys.database_query_store_options
+"#;
+
+/// Top queries by total duration from the Query Store, summed over the
+/// retained runtime statistics intervals of all plans of each query hash.
+/// The agent's own statements (tagged with
+/// [`super::connection::STATEMENT_TAG`]) are left out. Durations are recorded
+/// in microseconds.
+pub const QUERY_STORE_STATS: &str = r#"
+SELECT TOP 100
+    CONVERT(NVARCHAR(20), q.query_hash, 1) AS query_hash,
+    MAX(qt.query_sql_text) AS query_text,
+    CAST(SUM(rs.count_executions) AS BIGINT) AS calls,
+    CAST(SUM(rs.avg_duration * rs.count_executions) / 1000.0 AS FLOAT) AS total_time_ms,
+    CAST(SUM(rs.avg_rowcount * rs.count_executions) AS BIGINT) AS rows_returned,
+    CAST(SUM(rs.avg_logical_io_reads * rs.count_executions) AS BIGINT) AS logical_reads,
+    CAST(SUM(rs.avg_physical_io_reads * rs.count_executions) AS BIGINT) AS physical_reads
+FROM sys.query_store_runtime_stats rs
+JOIN sys.query_store_plan p ON p.plan_id = rs.plan_id
+JOIN sys.query_store_query q ON q.query_id = p.query_id
+JOIN sys.query_store_query_text qt ON qt.query_text_id = q.query_text_id
+WHERE qt.query_sql_text NOT LIKE '%/* datapace-agent */%'
+GROUP BY q.query_hash
+ORDER BY total_time_ms DESC
+"#;
+
+/// Top statements by total elapsed time from the plan cache, grouped by
+/// query hash. The plan cache is shared by the whole server, so only plans
+/// compiled in the current database are kept, and the agent's own batches
+/// (tagged with [`super::connection::STATEMENT_TAG`]) are left out. Only
+/// covers plans still in cache. Times are in microseconds.
+pub const DM_EXEC_QUERY_STATS: &str = r#"
+SELECT TOP 100
+    CONVERT(NVARCHAR(20), qs.query_hash, 1) AS query_hash,
+    MAX(SUBSTRING(
+        st.text,
+        qs.statement_start_offset / 2 + 1,
+        (CASE qs.statement_end_offset
+            WHEN -1 THEN DATALENGTH(st.text)
+            ELSE qs.statement_end_offset
+         END - qs.statement_start_offset) / 2 + 1
+    )) AS query_text,
+    CAST(SUM(qs.execution_count) AS BIGINT) AS calls,
+    CAST(SUM(qs.total_elapsed_time) / 1000.0 AS FLOAT) AS total_time_ms,
+    CAST(SUM(qs.total_rows) AS BIGINT) AS rows_returned,
+    CAST(SUM(qs.total_logical_reads) AS BIGINT) AS logical_reads,
+    CAST(SUM(qs.total_physical_reads) AS BIGINT) AS physical_reads
+FROM sys.dm_exec_query_stats qs
+CROSS APPLY sys.dm_exec_sql_text(qs.sql_handle) st
+CROSS APPLY (
+    SELECT CONVERT(INT, value) AS dbid
+    FROM sys.dm_exec_plan_attributes(qs.plan_handle)
+    WHERE attribute = 'dbid'
+) pa
+WHERE pa.dbid = DB_ID()
+  AND st.text NOT LIKE '/* datapace-agent */%'
+GROUP BY qs.query_hash
+ORDER BY total_time_ms DESC
+"#;
+
+/// Per-table row counts, sizes, access counters and modification counters.
+/// Scans of the heap or clustered index count as sequential scans; seeks,
+/// lookups and scans of nonclustered indexes as index scans.
+pub const TABLE_STATS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    CAST(ps.row_count AS BIGINT) AS row_count,
+    CAST(ps.reserved_pages AS BIGINT) * 8192 AS size_bytes,
+    CAST(us.seq_scans AS BIGINT) AS seq_scan,
+    CAST(us.idx_scans AS BIGINT) AS idx_scan,
+    CAST(os.inserts AS BIGINT) AS n_tup_ins,
+    CAST(os.updates AS BIGINT) AS n_tup_upd,
+    CAST(os.deletes AS BIGINT) AS n_tup_del
+FROM sys.tables t
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+CROSS APPLY (
+    SELECT
+        SUM(CASE WHEN p.index_id IN (0, 1) THEN p.row_count ELSE 0 END) AS row_count,
+        SUM(p.reserved_page_count) AS reserved_pages
+    FROM sys.dm_db_partition_stats p
+    WHERE p.object_id = t.object_id
+) ps
+OUTER APPLY (
+    SELECT
+        SUM(CASE WHEN u.index_id IN (0, 1) THEN u.user_scans ELSE 0 END) AS seq_scans,
+        SUM(u.user_seeks + u.user_lookups
+            + CASE WHEN u.index_id > 1 THEN u.user_scans ELSE 0 END) AS idx_scans
+    FROM sys.dm_db_index_usage_stats u
+    WHERE u.database_id = DB_ID() AND u.object_id = t.object_id
+) us
+OUTER APPLY (
+    SELECT
+        SUM(o.leaf_insert_count) AS inserts,
+        SUM(o.leaf_update_count) AS updates,
+        SUM(o.leaf_delete_count) AS deletes
+    FROM sys.dm_db_index_operational_stats(DB_ID(), t.object_id, NULL, NULL) o
+    WHERE o.index_id IN (0, 1)
+) os
+WHERE t.is_ms_shipped = 0
+ORDER BY s.name, t.name
+"#;
+
+/// Seeks, scans and lookups per index since the last restart
+pub const INDEX_STATS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    i.name AS index_name,
+    CAST(ISNULL(u.user_seeks, 0) + ISNULL(u.user_scans, 0) + ISNULL(u.user_lookups, 0) AS BIGINT)
+        AS idx_scan
+FROM sys.indexes i
+JOIN sys.tables t ON t.object_id = i.object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+LEFT JOIN sys.dm_db_index_usage_stats u
+    ON u.database_id = DB_ID() AND u.object_id = i.object_id AND u.index_id = i.index_id
+WHERE t.is_ms_shipped = 0 AND i.index_id > 0 AND i.is_hypothetical = 0
+ORDER BY s.name, t.name, i.name
+"#;
+
+/// Indexes the optimizer asked for in the current database, most valuable
+/// first. The columns are bracket-quoted, comma-separated lists.
+pub const MISSING_INDEXES: &str = r#"
+SELECT TOP 50
+    CONVERT(NVARCHAR(128), OBJECT_SCHEMA_NAME(d.object_id, d.database_id)) AS schema_name,
+    CONVERT(NVARCHAR(128), OBJECT_NAME(d.object_id, d.database_id)) AS table_name,
+    d.equality_columns,
+    d.inequality_columns,
+    d.included_columns,
+    CAST(gs.user_seeks AS BIGINT) AS user_seeks,
+    CAST(gs.user_scans AS BIGINT) AS user_scans,
+    CAST(gs.avg_total_user_cost AS FLOAT) AS avg_total_user_cost,
+    CAST(gs.avg_user_impact AS FLOAT) AS avg_user_impact
+FROM sys.dm_db_missing_index_details d
+JOIN sys.dm_db_missing_index_groups g ON g.index_handle = d.index_handle
+JOIN sys.dm_db_missing_index_group_stats gs ON gs.group_handle = g.index_group_handle
+WHERE d.database_id = DB_ID()
+ORDER BY (gs.user_seeks + gs.user_scans) * gs.avg_total_user_cost * gs.avg_user_impact DESC
+"#;
+
+/// Server configuration options
+pub const CONFIGURATIONS: &str = r#"
+SELECT
+    name,
+    CONVERT(NVARCHAR(256), value) AS value,
+    CONVERT(NVARCHAR(256), value_in_use) AS value_in_use,
+    CONVERT(NVARCHAR(256), minimum) AS minimum,
+    CONVERT(NVARCHAR(256), maximum) AS maximum,
+    CAST(is_dynamic AS BIT) AS is_dynamic,
+    CAST(is_advanced AS BIT) AS is_advanced,
+    CONVERT(NVARCHAR(256), description) AS description
+FROM sys.configurations
+ORDER BY name
+"#;
+
+/// Database scoped configurations (SQL Server 2016+; `is_value_default`
+/// from 2017)
+pub const DATABASE_SCOPED_CONFIGURATIONS: &str = r#"
+SELECT
+    name,
+    CONVERT(NVARCHAR(4000), value) AS value,
+    CAST(is_value_default AS BIT) AS is_value_default
+FROM sys.database_scoped_configurations
+ORDER BY name
+"#;
+
+/// Options of the current database that shape query behaviour
+pub const DATABASE_OPTIONS: &str = r#"
+SELECT
+    CAST(compatibility_level AS BIGINT) AS compatibility_level,
+    CONVERT(NVARCHAR(60), recovery_model_desc) AS recovery_model,
+    CAST(is_read_committed_snapshot_on AS BIT) AS read_committed_snapshot,
+    CONVERT(NVARCHAR(60), snapshot_isolation_state_desc) AS snapshot_isolation,
+    CAST(is_auto_create_stats_on AS BIT) AS auto_create_stats,
+    CAST(is_auto_update_stats_on AS BIT) AS auto_update_stats,
+    CAST(is_auto_update_stats_async_on AS BIT) AS auto_update_stats_async,
+    CONVERT(NVARCHAR(60), page_verify_option_desc) AS page_verify,
+    CONVERT(NVARCHAR(128), collation_name) AS collation
+FROM sys.databases
+WHERE database_id = DB_ID()
+"#;
+
+/// User tables with row counts and reserved size
+pub const TABLES: &str = r#"
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    CAST(ps.row_count AS BIGINT) AS row_count,
+    CAST(ps.reserved_pages AS BIGINT) * 8192 AS size_bytes,
+    CAST(t.is_memory_optimized AS BIT) AS is_memory_optimized,
+    CONVERT(NVARCHAR(60), t.temporal_type_desc) AS temporal_type
+FROM sys.tables t
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+CROSS APPLY (
+    SELECT
+        SUM(CASE WHEN p.index_id IN (0, 1) THEN p.row_count ELSE 0 END) AS row_count,
+        SUM(p.reserved_page_count) AS reserved_pages
+    FROM sys.dm_db_partition_stats p
+    WHERE p.object_id = t.object_id
+) ps
+WHERE t.is_ms_shipped = 0
+ORDER BY s.name, t.name
+"#;
+
+/// Columns of user tables and views. `max_length` is in bytes and -1 for
+/// `(max)` types.
+pub const COLUMNS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    o.name AS table_name,
+    c.name AS column_name,
+    CAST(c.column_id AS BIGINT) AS position,
+    ty.name AS type_name,
+    CAST(ty.is_user_defined AS BIT) AS is_user_defined,
+    bt.name AS base_type_name,
+    CAST(c.max_length AS BIGINT) AS max_length,
+    CAST(c.precision AS BIGINT) AS numeric_precision,
+    CAST(c.scale AS BIGINT) AS numeric_scale,
+    CAST(c.is_nullable AS BIT) AS is_nullable,
+    CAST(c.is_identity AS BIT) AS is_identity,
+    dc.definition AS column_default,
+    cc.definition AS generation_expression,
+    c.collation_name,
+    CONVERT(NVARCHAR(4000), ep.value) AS comment
+FROM sys.columns c
+JOIN sys.objects o ON o.object_id = c.object_id
+JOIN sys.schemas s ON s.schema_id = o.schema_id
+JOIN sys.types ty ON ty.user_type_id = c.user_type_id
+LEFT JOIN sys.types bt ON bt.user_type_id = ty.system_type_id
+LEFT JOIN sys.default_constraints dc ON dc.object_id = c.default_object_id
+LEFT JOIN sys.computed_columns cc
+    ON cc.object_id = c.object_id AND cc.column_id = c.column_id
+LEFT JOIN sys.extended_properties ep
+    ON ep.class = 1 AND ep.major_id = c.object_id AND ep.minor_id = c.column_id
+    AND ep.name = 'MS_Description'
+WHERE o.type IN ('U', 'V') AND o.is_ms_shipped = 0
+ORDER BY s.name, o.name, c.column_id
+"#;
+
+/// One row per index column, key columns in key order followed by included
+/// columns
+pub const INDEX_COLUMNS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    i.name AS index_name,
+    CONVERT(NVARCHAR(60), i.type_desc) AS index_type,
+    CAST(i.is_unique AS BIT) AS is_unique,
+    CAST(i.is_primary_key AS BIT) AS is_primary,
+    CAST(sz.reserved_pages AS BIGINT) * 8192 AS size_bytes,
+    c.name AS column_name,
+    CAST(ic.is_included_column AS BIT) AS is_included
+FROM sys.indexes i
+JOIN sys.tables t ON t.object_id = i.object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+JOIN sys.index_columns ic ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+OUTER APPLY (
+    SELECT SUM(p.reserved_page_count) AS reserved_pages
+    FROM sys.dm_db_partition_stats p
+    WHERE p.object_id = i.object_id AND p.index_id = i.index_id
+) sz
+WHERE t.is_ms_shipped = 0 AND i.index_id > 0 AND i.is_hypothetical = 0
+ORDER BY s.name, t.name, i.name, ic.is_included_column, ic.key_ordinal, ic.index_column_id
+"#;
+
+/// Primary key, unique, check and foreign key constraints, one row per
+/// constrained column. `definition` is the check expression, or the
+/// referenced table and column for foreign keys.
+pub const CONSTRAINTS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    t.name AS table_name,
+    kc.name AS constraint_name,
+    CASE kc.type WHEN 'PK' THEN 'primary_key' ELSE 'unique' END AS kind,
+    c.name AS column_name,
+    CAST(NULL AS NVARCHAR(MAX)) AS definition,
+    CAST(NULL AS NVARCHAR(128)) AS referenced_schema,
+    CAST(NULL AS NVARCHAR(128)) AS referenced_table,
+    CAST(NULL AS NVARCHAR(128)) AS referenced_column,
+    CAST(1 AS BIT) AS is_trusted,
+    CAST(ic.key_ordinal AS BIGINT) AS ordinal
+FROM sys.key_constraints kc
+JOIN sys.tables t ON t.object_id = kc.parent_object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+JOIN sys.index_columns ic
+    ON ic.object_id = kc.parent_object_id AND ic.index_id = kc.unique_index_id
+    AND ic.is_included_column = 0
+JOIN sys.columns c ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+WHERE t.is_ms_shipped = 0
+UNION ALL
+SELECT
+    s.name, t.name, cc.name, 'check', c.name, cc.definition, NULL, NULL, NULL,
+    CAST(CASE WHEN cc.is_not_trusted = 1 OR cc.is_disabled = 1 THEN 0 ELSE 1 END AS BIT),
+    CAST(0 AS BIGINT)
+FROM sys.check_constraints cc
+JOIN sys.tables t ON t.object_id = cc.parent_object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+LEFT JOIN sys.columns c ON c.object_id = cc.parent_object_id AND c.column_id = cc.parent_column_id
+WHERE t.is_ms_shipped = 0
+UNION ALL
+SELECT
+    s.name, t.name, fk.name, 'foreign_key', c.name, NULL, rs.name, rt.name, rc.name,
+    CAST(CASE WHEN fk.is_not_trusted = 1 OR fk.is_disabled = 1 THEN 0 ELSE 1 END AS BIT),
+    CAST(fkc.constraint_column_id AS BIGINT)
+FROM sys.foreign_keys fk
+JOIN sys.foreign_key_columns fkc ON fkc.constraint_object_id = fk.object_id
+JOIN sys.tables t ON t.object_id = fk.parent_object_id
+JOIN sys.schemas s ON s.schema_id = t.schema_id
+JOIN sys.columns c ON c.object_id = fkc.parent_object_id AND c.column_id = fkc.parent_column_id
+JOIN sys.tables rt ON rt.object_id = fkc.referenced_object_id
+JOIN sys.schemas rs ON rs.schema_id = rt.schema_id
+JOIN sys.columns rc
+    ON rc.object_id = fkc.referenced_object_id AND rc.column_id = fkc.referenced_column_id
+WHERE t.is_ms_shipped = 0
+ORDER BY 1, 2, 3, 11
+"#;
+
+/// User views with their definitions
+pub const VIEWS: &str = r#"
+SELECT
+    s.name AS schema_name,
+    v.name AS view_name,
+    CONVERT(NVARCHAR(MAX), OBJECT_DEFINITION(v.object_id)) AS definition,
+    CAST(CASE WHEN EXISTS (
+        SELECT 1 FROM sys.indexes i WHERE i.object_id = v.object_id AND i.index_id = 1
+    ) THEN 1 ELSE 0 END AS BIT) AS is_indexed
+FROM sys.views v
+JOIN sys.schemas s ON s.schema_id = v.schema_id
+WHERE v.is_ms_shipped = 0
+ORDER BY s.name, v.name
+"#;
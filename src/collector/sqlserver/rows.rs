@@ -0,0 +1,55 @@
+//! Typed column access for [`tiberius::Row`].
+//!
+//! `Row::get` panics when the requested type doesn't match the column, so
+//! these readers go through `try_get` and treat a mismatch like `NULL`.
+//! Integer reads accept any integer width.
+
+use crate::collector::CollectorError;
+use tiberius::Row;
+
+use super::connection::{SqlClient, STATEMENT_TAG};
+
+/// Run a query, tagged with [`STATEMENT_TAG`], and return the rows of its
+/// first result set.
+pub(super) async fn fetch_all(
+    client: &mut SqlClient,
+    sql: &str,
+) -> Result<Vec<Row>, CollectorError> {
+    Ok(client
+        .simple_query(format!("{STATEMENT_TAG}{sql}"))
+        .await?
+        .into_first_result()
+        .await?)
+}
+
+pub(super) fn text(row: &Row, column: &str) -> Option<String> {
+    row.try_get::<&str, _>(column)
+        .ok()
+        .flatten()
+        .map(str::to_string)
+}
+
+pub(super) fn int(row: &Row, column: &str) -> Option<i64> {
+    if let Ok(value) = row.try_get::<i64, _>(column) {
+        return value;
+    }
+    if let Ok(value) = row.try_get::<i32, _>(column) {
+        return value.map(i64::from);
+    }
+    if let Ok(value) = row.try_get::<i16, _>(column) {
+        return value.map(i64::from);
+    }
+    row.try_get::<u8, _>(column).ok().flatten().map(i64::from)
+}
+
+pub(super) fn float(row: &Row, column: &str) -> Option<f64> {
+    row.try_get::<f64, _>(column).ok().flatten()
+}
+
+/// A `BIT` column, `false` when `NULL`
+pub(super) fn flag(row: &Row, column: &str) -> bool {
+    row.try_get::<bool, _>(column)
+        .ok()
+        .flatten()
+        .unwrap_or(false)
+}
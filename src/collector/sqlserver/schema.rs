@@ -0,0 +1,303 @@
+//! SQL Server schema metadata from the `sys.*` catalog views of the current
+//! database: tables with sizes, columns, indexes with included columns,
+//! primary key, unique, check and foreign key constraints, and views.
+
+use super::connection::SqlClient;
+use super::queries;
+use super::rows::{fetch_all, flag, int, text};
+use crate::collector::CollectorError;
+use crate::payload::{
+    ColumnMetadata, ConstraintMetadata, IndexMetadata, SchemaMetadata, TableMetadata, ViewMetadata,
+};
+use std::collections::HashMap;
+use tracing::debug;
+
+type TableKey = (String, String);
+
+pub(super) async fn collect(client: &mut SqlClient) -> Result<SchemaMetadata, CollectorError> {
+    debug!("Collecting SQL Server schema metadata");
+
+    let table_rows = fetch_all(client, queries::TABLES).await?;
+    let column_rows = fetch_all(client, queries::COLUMNS).await?;
+    let index_rows = fetch_all(client, queries::INDEX_COLUMNS).await?;
+    let constraint_rows = fetch_all(client, queries::CONSTRAINTS).await?;
+    let view_rows = fetch_all(client, queries::VIEWS).await?;
+
+    let mut columns: HashMap<TableKey, Vec<ColumnMetadata>> = HashMap::new();
+    for row in &column_rows {
+        let type_name = text(row, "type_name").unwrap_or_default();
+        // Alias types report their base type's length, precision and scale
+        let base_type = if flag(row, "is_user_defined") {
+            text(row, "base_type_name").unwrap_or_else(|| type_name.clone())
+        } else {
+            type_name.clone()
+        };
+        let max_length = int(row, "max_length");
+        let precision = int(row, "numeric_precision");
+        let scale = int(row, "numeric_scale");
+        let char_length = character_length(&base_type, max_length);
+        let has_precision = matches!(base_type.as_str(), "decimal" | "numeric");
+
+        columns.entry(key(row)).or_default().push(ColumnMetadata {
+            name: text(row, "column_name").unwrap_or_default(),
+            native_type: Some(native_type(&base_type, max_length, precision, scale)),
+            domain: (base_type != type_name).then_some(type_name),
+            data_type: base_type,
+            nullable: flag(row, "is_nullable"),
+            default: text(row, "column_default"),
+            position: int(row, "position").unwrap_or_default() as i32,
+            max_length: char_length.filter(|l| *l > 0).map(|l| l as i32),
+            numeric_precision: has_precision.then(|| precision.unwrap_or_default() as i32),
+            numeric_scale: has_precision.then(|| scale.unwrap_or_default() as i32),
+            // Explicit values need `SET IDENTITY_INSERT`, like Postgres'
+            // `GENERATED ALWAYS` needs `OVERRIDING SYSTEM VALUE`
+            identity: flag(row, "is_identity").then(|| "ALWAYS".to_string()),
+            generation_expression: text(row, "generation_expression"),
+            collation: text(row, "collation_name"),
+            comment: text(row, "comment"),
+            ..Default::default()
+        });
+    }
+
+    let mut constraints: HashMap<TableKey, Vec<ConstraintMetadata>> = HashMap::new();
+    for (table, constraint) in group_constraints(&constraint_rows) {
+        constraints.entry(table).or_default().push(constraint);
+    }
+
+    let tables = table_rows
+        .iter()
+        .map(|row| {
+            let table = key(row);
+            TableMetadata {
+                columns: columns.remove(&table).unwrap_or_default(),
+                constraints: constraints.remove(&table).unwrap_or_default(),
+                schema: table.0,
+                name: table.1,
+                row_count_estimate: int(row, "row_count"),
+                size_bytes: int(row, "size_bytes"),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let views = view_rows
+        .iter()
+        .map(|row| {
+            let schema = text(row, "schema_name").unwrap_or_default();
+            let name = text(row, "view_name").unwrap_or_default();
+            ViewMetadata {
+                columns: columns
+                    .remove(&(schema.clone(), name.clone()))
+                    .unwrap_or_default(),
+                schema,
+                name,
+                // Indexed views are SQL Server's materialized views
+                materialized: flag(row, "is_indexed"),
+                definition: text(row, "definition"),
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    Ok(SchemaMetadata {
+        tables,
+        indexes: group_indexes(&index_rows),
+        views,
+        ..Default::default()
+    })
+}
+
+fn key(row: &tiberius::Row) -> TableKey {
+    (
+        text(row, "schema_name").unwrap_or_default(),
+        text(row, "table_name").unwrap_or_default(),
+    )
+}
+
+/// Fold the per-column index rows (ordered by index, then key columns
+/// before included columns) into indexes.
+fn group_indexes(rows: &[tiberius::Row]) -> Vec<IndexMetadata> {
+    let mut indexes: Vec<IndexMetadata> = Vec::new();
+    for row in rows {
+        let (schema, table) = key(row);
+        let name = text(row, "index_name").unwrap_or_default();
+        let column = text(row, "column_name").unwrap_or_default();
+
+        let index = match indexes.last_mut() {
+            Some(last) if last.schema == schema && last.table == table && last.name == name => last,
+            _ => {
+                indexes.push(IndexMetadata {
+                    schema,
+                    table,
+                    name,
+                    is_unique: flag(row, "is_unique"),
+                    is_primary: flag(row, "is_primary"),
+                    size_bytes: int(row, "size_bytes"),
+                    index_type: text(row, "index_type").map(|t| t.replace('_', " ")),
+                    ..Default::default()
+                });
+                indexes.last_mut().expect("just pushed")
+            }
+        };
+        if flag(row, "is_included") {
+            index.included_columns.push(column);
+        } else {
+            index.columns.push(column);
+        }
+    }
+    indexes
+}
+
+/// Fold the per-column constraint rows into constraints with a
+/// definition in SQL Server syntax.
+fn group_constraints(rows: &[tiberius::Row]) -> Vec<(TableKey, ConstraintMetadata)> {
+    let mut out: Vec<(TableKey, ConstraintMetadata)> = Vec::new();
+    // Referenced table and columns of each foreign key, in column order
+    let mut references: Vec<(String, Vec<String>)> = Vec::new();
+
+    for row in rows {
+        let table = key(row);
+        let name = text(row, "constraint_name").unwrap_or_default();
+        let kind = text(row, "kind").unwrap_or_default();
+        let column = text(row, "column_name");
+
+        let same = matches!(out.last(), Some((t, c)) if *t == table && c.name == name);
+        if !same {
+            out.push((
+                table,
+                ConstraintMetadata {
+                    name,
+                    definition: text(row, "definition").unwrap_or_default(),
+                    validated: flag(row, "is_trusted"),
+                    deferrable: false,
+                    columns: Vec::new(),
+                    kind: kind.clone(),
+                },
+            ));
+            references.push((
+                format!(
+                    "{}.{}",
+                    quote(&text(row, "referenced_schema").unwrap_or_default()),
+                    quote(&text(row, "referenced_table").unwrap_or_default())
+                ),
+                Vec::new(),
+            ));
+        }
+        let (_, constraint) = out.last_mut().expect("pushed above");
+        if let Some(column) = column {
+            constraint.columns.push(column);
+        }
+        if kind == "foreign_key" {
+            if let Some(referenced) = text(row, "referenced_column") {
+                references
+                    .last_mut()
+                    .expect("pushed above")
+                    .1
+                    .push(referenced);
+            }
+        }
+    }
+
+    for ((_, constraint), (table, referenced)) in out.iter_mut().zip(references) {
+        let columns = || {
+            constraint
+                .columns
+                .iter()
+                .map(|c| quote(c))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        constraint.definition = match constraint.kind.as_str() {
+            "primary_key" => format!("PRIMARY KEY ({})", columns()),
+            "unique" => format!("UNIQUE ({})", columns()),
+            "check" => format!("CHECK {}", constraint.definition),
+            "foreign_key" => format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                columns(),
+                table,
+                referenced
+                    .iter()
+                    .map(|c| quote(c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            _ => std::mem::take(&mut constraint.definition),
+        };
+    }
+    out
+}
+
+fn quote(identifier: &str) -> String {
+    format!("[{}]", identifier.replace(']', "]]"))
+}
+
+/// Length in characters of a character or binary column, -1 for `(max)`
+fn character_length(base_type: &str, max_length: Option<i64>) -> Option<i64> {
+    let bytes = max_length?;
+    match base_type {
+        "nchar" | "nvarchar" if bytes > 0 => Some(bytes / 2),
+        "char" | "varchar" | "binary" | "varbinary" | "nchar" | "nvarchar" => Some(bytes),
+        _ => None,
+    }
+}
+
+/// Full type as written in DDL, e.g. `nvarchar(100)`, `varchar(max)`,
+/// `decimal(18,2)`, `datetime2(3)`
+fn native_type(
+    base_type: &str,
+    max_length: Option<i64>,
+    precision: Option<i64>,
+    scale: Option<i64>,
+) -> String {
+    match base_type {
+        "char" | "varchar" | "binary" | "varbinary" | "nchar" | "nvarchar" => {
+            match character_length(base_type, max_length) {
+                Some(-1) => format!("{base_type}(max)"),
+                Some(length) => format!("{base_type}({length})"),
+                None => base_type.to_string(),
+            }
+        }
+        "decimal" | "numeric" => format!(
+            "{base_type}({},{})",
+            precision.unwrap_or_default(),
+            scale.unwrap_or_default()
+        ),
+        "datetime2" | "datetimeoffset" | "time" => {
+            format!("{base_type}({})", scale.unwrap_or_default())
+        }
+        _ => base_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_native_type() {
+        assert_eq!(
+            native_type("nvarchar", Some(200), None, None),
+            "nvarchar(100)"
+        );
+        assert_eq!(native_type("varchar", Some(-1), None, None), "varchar(max)");
+        assert_eq!(
+            native_type("nvarchar", Some(-1), None, None),
+            "nvarchar(max)"
+        );
+        assert_eq!(
+            native_type("decimal", Some(9), Some(18), Some(2)),
+            "decimal(18,2)"
+        );
+        assert_eq!(
+            native_type("datetime2", Some(7), Some(23), Some(3)),
+            "datetime2(3)"
+        );
+        assert_eq!(native_type("int", Some(4), Some(10), Some(0)), "int");
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("order"), "[order]");
+        assert_eq!(quote("a]b"), "[a]]b]");
+    }
+}
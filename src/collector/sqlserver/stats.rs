@@ -0,0 +1,310 @@
+//! SQL Server statistics and configuration.
+//!
+//! - query statistics from the Query Store when it is enabled for the
+//!   database, otherwise from the plan cache (`sys.dm_exec_query_stats`)
+//! - table row counts and sizes from `sys.dm_db_partition_stats`, access
+//!   counters from `sys.dm_db_index_usage_stats` and modification counters
+//!   from `sys.dm_db_index_operational_stats`
+//! - index usage and the missing index DMVs
+//! - server options from `sys.configurations`, database scoped
+//!   configurations and the options of the database itself
+//!
+//! The usage and missing index DMVs are cleared on restart (and, on Azure
+//! SQL, on failover), so their counters cover the time since then.
+
+use super::connection::SqlClient;
+use super::queries;
+use super::rows::{fetch_all, flag, float, int, text};
+use crate::collector::CollectorError;
+use crate::payload::{IndexStats, MissingIndex, QueryStats, Setting, TableStats};
+use std::collections::HashMap;
+use tiberius::Row;
+use tracing::debug;
+
+/// Query Store states in which its runtime statistics can be read
+const QUERY_STORE_READABLE: &[&str] = &["READ_WRITE", "READ_ONLY"];
+
+/// Query Store state of the current database, `None` where the Query Store
+/// does not exist (before SQL Server 2016).
+pub(super) async fn query_store_state(
+    client: &mut SqlClient,
+) -> Result<Option<String>, CollectorError> {
+    match fetch_all(client, queries::QUERY_STORE_STATE).await {
+        Ok(rows) => Ok(rows.first().and_then(|row| text(row, "actual_state"))),
+        Err(CollectorError::QueryError(e)) => {
+            debug!(error = %e, "Query Store not available");
+            Ok(None)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Top queries by total elapsed time.
+pub(super) async fn query_stats(
+    client: &mut SqlClient,
+    query_store_state: Option<&str>,
+) -> Result<Vec<QueryStats>, CollectorError> {
+    let sql = if query_store_state.is_some_and(|s| QUERY_STORE_READABLE.contains(&s)) {
+        debug!("Collecting query statistics from the Query Store");
+        queries::QUERY_STORE_STATS
+    } else {
+        debug!("Collecting query statistics from sys.dm_exec_query_stats");
+        queries::DM_EXEC_QUERY_STATS
+    };
+
+    Ok(fetch_all(client, sql)
+        .await?
+        .iter()
+        .map(|row| {
+            let calls = int(row, "calls");
+            let total_time_ms = float(row, "total_time_ms");
+            let logical_reads = int(row, "logical_reads");
+            let physical_reads = int(row, "physical_reads");
+            QueryStats {
+                query_hash: text(row, "query_hash"),
+                query: text(row, "query_text").map(|q| q.trim().to_string()),
+                mean_time_ms: match (total_time_ms, calls) {
+                    (Some(total), Some(calls)) if calls > 0 => Some(total / calls as f64),
+                    _ => None,
+                },
+                calls,
+                total_time_ms,
+                rows: int(row, "rows_returned"),
+                // Logical reads are page reads through the buffer pool,
+                // physical reads the ones that missed it
+                shared_blks_hit: match (logical_reads, physical_reads) {
+                    (Some(logical), Some(physical)) => Some((logical - physical).max(0)),
+                    _ => None,
+                },
+                shared_blks_read: physical_reads,
+                ..Default::default()
+            }
+        })
+        .collect())
+}
+
+/// Per-table counters of the current database.
+pub(super) async fn table_stats(client: &mut SqlClient) -> Result<Vec<TableStats>, CollectorError> {
+    debug!("Collecting table statistics");
+
+    Ok(fetch_all(client, queries::TABLE_STATS)
+        .await?
+        .iter()
+        .map(|row| TableStats {
+            schema: text(row, "schema_name").unwrap_or_default(),
+            table: text(row, "table_name").unwrap_or_default(),
+            seq_scan: int(row, "seq_scan"),
+            idx_scan: int(row, "idx_scan"),
+            n_tup_ins: int(row, "n_tup_ins"),
+            n_tup_upd: int(row, "n_tup_upd"),
+            n_tup_del: int(row, "n_tup_del"),
+            n_live_tup: int(row, "row_count"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Per-index usage of the current database.
+pub(super) async fn index_stats(client: &mut SqlClient) -> Result<Vec<IndexStats>, CollectorError> {
+    debug!("Collecting index statistics");
+
+    Ok(fetch_all(client, queries::INDEX_STATS)
+        .await?
+        .iter()
+        .map(|row| IndexStats {
+            schema: text(row, "schema_name").unwrap_or_default(),
+            table: text(row, "table_name").unwrap_or_default(),
+            index: text(row, "index_name").unwrap_or_default(),
+            idx_scan: int(row, "idx_scan"),
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Missing index suggestions for the current database.
+pub(super) async fn missing_indexes(
+    client: &mut SqlClient,
+) -> Result<Vec<MissingIndex>, CollectorError> {
+    debug!("Collecting missing index suggestions");
+
+    Ok(fetch_all(client, queries::MISSING_INDEXES)
+        .await?
+        .iter()
+        .map(|row| MissingIndex {
+            schema: text(row, "schema_name").unwrap_or_default(),
+            table: text(row, "table_name").unwrap_or_default(),
+            equality_columns: column_list(text(row, "equality_columns").as_deref()),
+            inequality_columns: column_list(text(row, "inequality_columns").as_deref()),
+            included_columns: column_list(text(row, "included_columns").as_deref()),
+            user_seeks: int(row, "user_seeks"),
+            user_scans: int(row, "user_scans"),
+            avg_total_user_cost: float(row, "avg_total_user_cost"),
+            avg_user_impact: float(row, "avg_user_impact"),
+        })
+        .collect())
+}
+
+/// Split a missing index column list such as `[customer_id], [status]`.
+fn column_list(columns: Option<&str>) -> Vec<String> {
+    columns
+        .unwrap_or_default()
+        .split("], ")
+        .map(|c| c.trim().trim_start_matches('[').trim_end_matches(']'))
+        .filter(|c| !c.is_empty())
+        .map(|c| c.replace("]]", "]"))
+        .collect()
+}
+
+/// Settings as a flat map and as structured [`Setting`]s.
+///
+/// Server options come first, then database scoped configurations and the
+/// database's own options with categories `database scoped` and `database`.
+/// `sys.configurations` does not report defaults, so `is_default` is only
+/// meaningful for database scoped configurations.
+pub(super) async fn settings(
+    client: &mut SqlClient,
+) -> Result<(HashMap<String, String>, Vec<Setting>), CollectorError> {
+    debug!("Collecting SQL Server settings");
+
+    let mut details: Vec<Setting> = fetch_all(client, queries::CONFIGURATIONS)
+        .await?
+        .iter()
+        .filter_map(configuration)
+        .collect();
+
+    // Not on SQL Server 2016 (`is_value_default`) and older
+    match fetch_all(client, queries::DATABASE_SCOPED_CONFIGURATIONS).await {
+        Ok(rows) => details.extend(rows.iter().filter_map(|row| {
+            Some(Setting {
+                name: text(row, "name")?,
+                value: text(row, "value").unwrap_or_default(),
+                category: Some("database scoped".to_string()),
+                is_default: flag(row, "is_value_default"),
+                ..Default::default()
+            })
+        })),
+        Err(e) => debug!(error = %e, "Database scoped configurations not available"),
+    }
+
+    if let Some(row) = fetch_all(client, queries::DATABASE_OPTIONS).await?.first() {
+        details.extend(database_options(row));
+    }
+
+    let flat = details
+        .iter()
+        .map(|s| (s.name.clone(), s.value.clone()))
+        .collect();
+    Ok((flat, details))
+}
+
+fn configuration(row: &Row) -> Option<Setting> {
+    let name = text(row, "name")?;
+    let value = text(row, "value_in_use")?;
+    let configured = text(row, "value");
+    let unit = config_unit(&name);
+    let (value_bytes, value_ms) = match (unit, value.parse::<i64>()) {
+        (Some(unit), Ok(n)) => normalize(unit, n),
+        _ => (None, None),
+    };
+
+    Some(Setting {
+        unit: unit.map(str::to_string),
+        value_bytes,
+        value_ms,
+        category: Some(
+            if flag(row, "is_advanced") {
+                "advanced"
+            } else {
+                "basic"
+            }
+            .to_string(),
+        ),
+        value_type: Some("integer".to_string()),
+        // `value` is what was configured with RECONFIGURE; it differs from
+        // `value_in_use` until a restart for static options
+        pending_restart: Some(configured.as_deref().is_some_and(|c| c != value)),
+        name,
+        value,
+        ..Default::default()
+    })
+}
+
+/// Unit in a configuration option name, e.g. `max server memory (MB)` → `MB`
+fn config_unit(name: &str) -> Option<&str> {
+    let unit = name.strip_suffix(')')?.rsplit_once('(')?.1;
+    matches!(unit, "B" | "KB" | "MB" | "ms" | "s" | "min").then_some(unit)
+}
+
+/// Convert a value in `unit` to bytes or milliseconds
+fn normalize(unit: &str, n: i64) -> (Option<i64>, Option<f64>) {
+    match unit {
+        "B" => (Some(n), None),
+        "KB" => (Some(n * 1024), None),
+        "MB" => (Some(n * 1024 * 1024), None),
+        "ms" => (None, Some(n as f64)),
+        "s" => (None, Some(n as f64 * 1_000.0)),
+        "min" => (None, Some(n as f64 * 60_000.0)),
+        _ => (None, None),
+    }
+}
+
+fn database_options(row: &Row) -> Vec<Setting> {
+    let setting = |name: &str, value: Option<String>| {
+        value.map(|value| Setting {
+            name: name.to_string(),
+            value,
+            category: Some("database".to_string()),
+            ..Default::default()
+        })
+    };
+    let on_off = |column: &str| Some(if flag(row, column) { "on" } else { "off" }.to_string());
+
+    [
+        setting(
+            "compatibility_level",
+            int(row, "compatibility_level").map(|l| l.to_string()),
+        ),
+        setting("recovery_model", text(row, "recovery_model")),
+        setting("read_committed_snapshot", on_off("read_committed_snapshot")),
+        setting("snapshot_isolation", text(row, "snapshot_isolation")),
+        setting("auto_create_stats", on_off("auto_create_stats")),
+        setting("auto_update_stats", on_off("auto_update_stats")),
+        setting("auto_update_stats_async", on_off("auto_update_stats_async")),
+        setting("page_verify", text(row, "page_verify")),
+        setting("collation", text(row, "collation")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_list() {
+        assert_eq!(
+            column_list(Some("[customer_id], [status]")),
+            vec!["customer_id", "status"]
+        );
+        assert_eq!(column_list(Some("[odd]]name]")), vec!["odd]name"]);
+        assert!(column_list(None).is_empty());
+    }
+
+    #[test]
+    fn test_config_unit() {
+        assert_eq!(config_unit("max server memory (MB)"), Some("MB"));
+        assert_eq!(config_unit("remote login timeout (s)"), Some("s"));
+        assert_eq!(config_unit("max degree of parallelism"), None);
+        assert_eq!(normalize("MB", 2048), (Some(2 * 1024 * 1024 * 1024), None));
+        assert_eq!(normalize("min", 2), (None, Some(120_000.0)));
+    }
+
+    #[test]
+    fn test_query_stats_leave_out_agent_statements() {
+        let tag = super::super::connection::STATEMENT_TAG;
+        assert!(queries::QUERY_STORE_STATS.contains(&format!("'%{tag}%'")));
+        assert!(queries::DM_EXEC_QUERY_STATS.contains(&format!("'{tag}%'")));
+    }
+}
@@ -0,0 +1,217 @@
+//! Chroma adapter, over the REST API (port 8000).
+//!
+//! Speaks API v2 (Chroma 0.6+ and Chroma Cloud), or v1 on servers that do
+//! not have it. Collections of one tenant and database come from the
+//! collection list, with the dimension fixed by the first embedding added
+//! and the HNSW (or SPANN) settings from `configuration_json`, or the
+//! `hnsw:*` metadata keys older releases use; counts come from
+//! `collections/{id}/count`. Every collection has the same four fields: id,
+//! embedding, document and metadata.
+
+use super::client::{int, text, VectorClient};
+use super::collection::{distance, AnnIndex, Collection, Field, VectorField};
+use super::Adapter;
+use crate::collector::CollectorError;
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::debug;
+
+const DEFAULT_TENANT: &str = "default_tenant";
+const DEFAULT_DATABASE: &str = "default_database";
+
+/// Chroma's HNSW defaults, for collections that do not override them
+const DEFAULT_SPACE: &str = "l2";
+const DEFAULT_M: i64 = 16;
+const DEFAULT_EF_CONSTRUCTION: i64 = 100;
+
+pub(super) struct Chroma {
+    /// `api/v2` or `api/v1`
+    api: &'static str,
+    tenant: String,
+    database: String,
+}
+
+impl Chroma {
+    /// Pick the API version the server has. The tenant and database come
+    /// from `tenant` / `database` parameters or the URL path, as
+    /// `tenant/database` or `database`.
+    pub(super) async fn detect(client: &VectorClient) -> Result<Self, CollectorError> {
+        let api = match client.get_opt("api/v2/version").await? {
+            Some(_) => "api/v2",
+            None => "api/v1",
+        };
+        let (path_tenant, path_database) = match client.path().split_once('/') {
+            Some((tenant, database)) => (Some(tenant), Some(database)),
+            None => (None, Some(client.path()).filter(|p| !p.is_empty())),
+        };
+        Ok(Self {
+            api,
+            tenant: client
+                .param("tenant")
+                .or(path_tenant)
+                .unwrap_or(DEFAULT_TENANT)
+                .to_string(),
+            database: client
+                .param("database")
+                .or(path_database)
+                .unwrap_or(DEFAULT_DATABASE)
+                .to_string(),
+        })
+    }
+
+    fn collections_path(&self) -> String {
+        match self.api {
+            "api/v2" => format!(
+                "api/v2/tenants/{}/databases/{}/collections",
+                self.tenant, self.database
+            ),
+            _ => format!(
+                "api/v1/collections?tenant={}&database={}",
+                self.tenant, self.database
+            ),
+        }
+    }
+
+    fn count_path(&self, id: &str) -> String {
+        match self.api {
+            "api/v2" => format!("{}/{id}/count", self.collections_path()),
+            _ => format!("api/v1/collections/{id}/count"),
+        }
+    }
+}
+
+#[async_trait]
+impl Adapter for Chroma {
+    async fn version(&self, client: &VectorClient) -> Result<Option<String>, CollectorError> {
+        // The body is a bare JSON string
+        Ok(text(&client.get(&format!("{}/version", self.api)).await?))
+    }
+
+    async fn collections(&self, client: &VectorClient) -> Result<Vec<Collection>, CollectorError> {
+        let list = client.get(&self.collections_path()).await?;
+        let mut collections = Vec::new();
+        for item in list.as_array().into_iter().flatten() {
+            let Some(id) = item["id"].as_str() else {
+                continue;
+            };
+            debug!(collection = ?item["name"], "Counting Chroma collection");
+            let count = int(&client.get(&self.count_path(id)).await?);
+            collections.push(collection(&self.database, item, count));
+        }
+        Ok(collections)
+    }
+}
+
+fn collection(database: &str, item: &Value, count: Option<i64>) -> Collection {
+    let config = &item["configuration_json"];
+    let metadata = &item["metadata"];
+    // 1.x: `hnsw` / `spann`; 0.6: `hnsw_configuration`
+    let hnsw = match &config["hnsw"] {
+        Value::Null => &config["hnsw_configuration"],
+        hnsw => hnsw,
+    };
+    let spann = &config["spann"];
+
+    let (method, space, m, ef_construction) = if spann.is_object() {
+        (
+            "spann",
+            text(&spann["space"]),
+            // SPANN has no graph degree
+            None,
+            int(&spann["ef_construction"]),
+        )
+    } else {
+        (
+            "hnsw",
+            text(&hnsw["space"]).or_else(|| text(&metadata["hnsw:space"])),
+            int(&hnsw["max_neighbors"])
+                .or_else(|| int(&hnsw["M"]))
+                .or_else(|| int(&metadata["hnsw:M"]))
+                .or(Some(DEFAULT_M)),
+            int(&hnsw["ef_construction"])
+                .or_else(|| int(&metadata["hnsw:construction_ef"]))
+                .or(Some(DEFAULT_EF_CONSTRUCTION)),
+        )
+    };
+
+    let field = |name: &str, data_type: &str, nullable: bool| Field {
+        name: name.to_string(),
+        data_type: data_type.to_string(),
+        nullable,
+        ..Default::default()
+    };
+    let fields = vec![
+        Field {
+            primary_key: true,
+            ..field("id", "string", false)
+        },
+        Field {
+            vector: Some(VectorField {
+                vector_type: "vector",
+                dimensions: int(&item["dimension"]),
+                distance: Some(distance(space.as_deref().unwrap_or(DEFAULT_SPACE))),
+                index: Some(AnnIndex {
+                    method: method.to_string(),
+                    m,
+                    ef_construction,
+                    ..Default::default()
+                }),
+            }),
+            ..field("embedding", "float32[]", false)
+        },
+        field("document", "string", true),
+        field("metadata", "object", true),
+    ];
+
+    Collection {
+        namespace: database.to_string(),
+        name: text(&item["name"]).unwrap_or_default(),
+        fields,
+        point_count: count,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collection() {
+        let item = json!({
+            "id": "8f7c0e2a-0000-0000-0000-000000000000",
+            "name": "notes",
+            "dimension": 384,
+            "configuration_json": {"hnsw": {"space": "cosine", "ef_construction": 200, "max_neighbors": 32}},
+            "metadata": null
+        });
+        let collection = collection("default_database", &item, Some(42));
+        assert_eq!(collection.name, "notes");
+        assert_eq!(collection.point_count, Some(42));
+
+        let names: Vec<&str> = collection.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "embedding", "document", "metadata"]);
+
+        let vector = collection.fields[1].vector.as_ref().unwrap();
+        let index = vector.index.as_ref().unwrap();
+        assert_eq!(vector.dimensions, Some(384));
+        assert_eq!(vector.distance.as_deref(), Some("cosine"));
+        assert_eq!((index.m, index.ef_construction), (Some(32), Some(200)));
+    }
+
+    #[test]
+    fn test_legacy_metadata() {
+        let item = json!({
+            "id": "1",
+            "name": "legacy",
+            "metadata": {"hnsw:space": "ip", "hnsw:M": 8}
+        });
+        let collection = collection("default_database", &item, None);
+        let vector = collection.fields[1].vector.as_ref().unwrap();
+        let index = vector.index.as_ref().unwrap();
+        assert_eq!(vector.distance.as_deref(), Some("inner_product"));
+        assert_eq!(vector.dimensions, None);
+        assert_eq!((index.m, index.ef_construction), (Some(8), Some(100)));
+    }
+}
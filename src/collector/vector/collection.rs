@@ -0,0 +1,269 @@
+//! Collections as the adapters report them, and their mapping onto the
+//! payload.
+//!
+//! A collection becomes a table in a schema named after its namespace (the
+//! Milvus database, the Chroma database, or the product name where there is
+//! none), with its fields as columns. Vector fields are listed among them
+//! and again in [`VectorStoreMetadata`] with their dimensions; their ANN
+//! indexes become both an [`IndexMetadata`] and a [`VectorIndex`] carrying
+//! the build parameters. Point counts and index status go to
+//! [`TableStats`].
+
+use crate::payload::{
+    ColumnMetadata, IndexMetadata, SchemaMetadata, TableMetadata, TableStats, VectorColumn,
+    VectorIndex, VectorStoreMetadata,
+};
+
+/// One collection, class or index, whatever the product calls it
+#[derive(Debug, Default)]
+pub(super) struct Collection {
+    pub namespace: String,
+    pub name: String,
+    pub fields: Vec<Field>,
+    pub point_count: Option<i64>,
+    pub indexed_vectors: Option<i64>,
+    pub segment_count: Option<i64>,
+    pub status: Option<String>,
+    pub optimizer_status: Option<String>,
+}
+
+/// A stored field: a vector, or a scalar / payload field
+#[derive(Debug, Default)]
+pub(super) struct Field {
+    pub name: String,
+    /// Type as the product names it, e.g. `VarChar`, `keyword`, `text[]`
+    pub data_type: String,
+    pub nullable: bool,
+    pub primary_key: bool,
+    /// Type of the scalar (payload) index on the field, if it has one
+    pub index_type: Option<String>,
+    pub vector: Option<VectorField>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct VectorField {
+    /// `vector`, `halfvec`, `sparsevec`, `bit`, `uint8vec` or `int8vec`
+    pub vector_type: &'static str,
+    pub dimensions: Option<i64>,
+    /// Distance in pgvector's terms, see [`distance`]
+    pub distance: Option<String>,
+    /// `None` when vectors are searched without an index
+    pub index: Option<AnnIndex>,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct AnnIndex {
+    /// Index name where the product names indexes; the field name otherwise
+    pub name: Option<String>,
+    /// Lowercased index type, e.g. `hnsw`, `flat`, `ivf_flat`
+    pub method: String,
+    pub m: Option<i64>,
+    pub ef_construction: Option<i64>,
+    pub lists: Option<i64>,
+    pub quantization: Option<String>,
+}
+
+/// Distance metric in the terms pgvector uses, so that the same metric has
+/// one name across engines: `cosine`, `l2`, `inner_product`, `l1`,
+/// `hamming` or `jaccard`. Unknown metrics are passed on lowercased.
+pub(super) fn distance(metric: &str) -> String {
+    let metric = metric.to_ascii_lowercase();
+    match metric.as_str() {
+        "euclid" | "euclidean" | "l2" | "l2-squared" => "l2",
+        "dot" | "ip" | "inner_product" => "inner_product",
+        "manhattan" | "l1" => "l1",
+        _ => return metric,
+    }
+    .to_string()
+}
+
+/// Everything the collector reports about a set of collections
+#[derive(Debug, Default)]
+pub(super) struct Mapped {
+    pub schema: SchemaMetadata,
+    pub table_stats: Vec<TableStats>,
+    pub vector_store: VectorStoreMetadata,
+}
+
+pub(super) fn map(collections: Vec<Collection>) -> Mapped {
+    let mut out = Mapped::default();
+
+    for collection in collections {
+        let schema = collection.namespace;
+        let table = collection.name;
+        let mut columns = Vec::new();
+
+        for (i, field) in collection.fields.into_iter().enumerate() {
+            let index = |name: String, columns: Vec<String>| IndexMetadata {
+                schema: schema.clone(),
+                table: table.clone(),
+                name,
+                columns,
+                ..Default::default()
+            };
+            if field.primary_key {
+                out.schema.indexes.push(IndexMetadata {
+                    is_unique: true,
+                    is_primary: true,
+                    ..index("primary_key".to_string(), vec![field.name.clone()])
+                });
+            }
+            if let Some(index_type) = &field.index_type {
+                out.schema.indexes.push(IndexMetadata {
+                    index_type: Some(index_type.clone()),
+                    ..index(field.name.clone(), vec![field.name.clone()])
+                });
+            }
+
+            let mut native_type = None;
+            if let Some(vector) = field.vector {
+                native_type = vector
+                    .dimensions
+                    .map(|d| format!("{}({d})", vector.vector_type));
+                let mut index_names = Vec::new();
+                if let Some(ann) = vector.index {
+                    let name = ann.name.unwrap_or_else(|| field.name.clone());
+                    index_names.push(name.clone());
+                    out.schema.indexes.push(IndexMetadata {
+                        index_type: Some(ann.method.clone()),
+                        ..index(name.clone(), vec![field.name.clone()])
+                    });
+                    out.vector_store.indexes.push(VectorIndex {
+                        schema: schema.clone(),
+                        table: table.clone(),
+                        name,
+                        method: ann.method,
+                        column: Some(field.name.clone()),
+                        distance: vector.distance.clone(),
+                        m: ann.m.map(|v| v as i32),
+                        ef_construction: ann.ef_construction.map(|v| v as i32),
+                        lists: ann.lists.map(|v| v as i32),
+                        quantization: ann.quantization,
+                        table_rows: collection.point_count,
+                        ..Default::default()
+                    });
+                }
+                out.vector_store.columns.push(VectorColumn {
+                    database: None,
+                    schema: schema.clone(),
+                    table: table.clone(),
+                    column: field.name.clone(),
+                    vector_type: vector.vector_type.to_string(),
+                    dimensions: vector.dimensions.map(|d| d as i32),
+                    nullable: field.nullable,
+                    indexes: index_names,
+                });
+            }
+
+            columns.push(ColumnMetadata {
+                name: field.name,
+                data_type: field.data_type,
+                native_type,
+                nullable: field.nullable,
+                position: i as i32 + 1,
+                ..Default::default()
+            });
+        }
+
+        out.table_stats.push(TableStats {
+            schema: schema.clone(),
+            table: table.clone(),
+            n_live_tup: collection.point_count,
+            indexed_vectors: collection.indexed_vectors,
+            segment_count: collection.segment_count,
+            status: collection.status,
+            optimizer_status: collection.optimizer_status,
+            ..Default::default()
+        });
+        out.schema.tables.push(TableMetadata {
+            schema,
+            name: table,
+            columns,
+            row_count_estimate: collection.point_count,
+            ..Default::default()
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("Cosine"), "cosine");
+        assert_eq!(distance("Euclid"), "l2");
+        assert_eq!(distance("l2-squared"), "l2");
+        assert_eq!(distance("IP"), "inner_product");
+        assert_eq!(distance("Manhattan"), "l1");
+        assert_eq!(distance("BM25"), "bm25");
+    }
+
+    #[test]
+    fn test_map() {
+        let mapped = map(vec![Collection {
+            namespace: "default".to_string(),
+            name: "docs".to_string(),
+            fields: vec![
+                Field {
+                    name: "id".to_string(),
+                    data_type: "Int64".to_string(),
+                    primary_key: true,
+                    ..Default::default()
+                },
+                Field {
+                    name: "embedding".to_string(),
+                    data_type: "FloatVector".to_string(),
+                    vector: Some(VectorField {
+                        vector_type: "vector",
+                        dimensions: Some(768),
+                        distance: Some("cosine".to_string()),
+                        index: Some(AnnIndex {
+                            name: Some("embedding_hnsw".to_string()),
+                            method: "hnsw".to_string(),
+                            m: Some(16),
+                            ef_construction: Some(200),
+                            ..Default::default()
+                        }),
+                    }),
+                    ..Default::default()
+                },
+                Field {
+                    name: "tag".to_string(),
+                    data_type: "VarChar".to_string(),
+                    nullable: true,
+                    index_type: Some("inverted".to_string()),
+                    ..Default::default()
+                },
+            ],
+            point_count: Some(1_000),
+            indexed_vectors: Some(900),
+            status: Some("LoadStateLoaded".to_string()),
+            ..Default::default()
+        }]);
+
+        let table = &mapped.schema.tables[0];
+        assert_eq!(table.row_count_estimate, Some(1_000));
+        assert_eq!(table.columns[1].native_type.as_deref(), Some("vector(768)"));
+        assert_eq!(table.columns[2].position, 3);
+
+        let indexes = &mapped.schema.indexes;
+        assert_eq!(indexes.len(), 3);
+        assert!(indexes[0].is_primary);
+        assert_eq!(indexes[1].index_type.as_deref(), Some("hnsw"));
+        assert_eq!(indexes[2].index_type.as_deref(), Some("inverted"));
+
+        let vector_index = &mapped.vector_store.indexes[0];
+        assert_eq!(vector_index.m, Some(16));
+        assert_eq!(vector_index.table_rows, Some(1_000));
+        assert_eq!(
+            mapped.vector_store.columns[0].indexes,
+            vec!["embedding_hnsw"]
+        );
+
+        let stats = &mapped.table_stats[0];
+        assert_eq!(stats.n_live_tup, Some(1_000));
+        assert_eq!(stats.indexed_vectors, Some(900));
+    }
+}
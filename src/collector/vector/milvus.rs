@@ -0,0 +1,314 @@
+//! Milvus adapter, over the RESTful API v2 (port 19530, Milvus 2.4+ and
+//! Zilliz Cloud).
+//!
+//! Every call is a `POST` under `/v2/vectordb/` that answers HTTP 200 with a
+//! `code` (non-zero on failure). Collections of one database come from
+//! `collections/list` and `collections/describe` (fields, indexes, load
+//! state), row counts from `collections/get_stats`, and index types, build
+//! parameters and indexed row counts from `indexes/describe`. The API has
+//! no version endpoint.
+
+use super::client::{int, text, VectorClient};
+use super::collection::{distance, AnnIndex, Collection, Field, VectorField};
+use super::Adapter;
+use crate::collector::CollectorError;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tracing::debug;
+
+const DEFAULT_DATABASE: &str = "default";
+
+pub(super) struct Milvus {
+    database: String,
+}
+
+impl Milvus {
+    /// The database comes from the URL path or a `database` / `db_name`
+    /// parameter.
+    pub(super) fn new(client: &VectorClient) -> Self {
+        let database = Some(client.path())
+            .filter(|path| !path.is_empty())
+            .or_else(|| client.param("database"))
+            .or_else(|| client.param("db_name"))
+            .unwrap_or(DEFAULT_DATABASE);
+        Self {
+            database: database.to_string(),
+        }
+    }
+
+    async fn call(
+        &self,
+        client: &VectorClient,
+        path: &str,
+        mut body: Value,
+    ) -> Result<Value, CollectorError> {
+        body["dbName"] = json!(self.database);
+        let response = client.post(&format!("v2/vectordb/{path}"), &body).await?;
+        check(path, response)
+    }
+}
+
+#[async_trait]
+impl Adapter for Milvus {
+    /// Lists the collections to check access, since there is no version to
+    /// read.
+    async fn version(&self, client: &VectorClient) -> Result<Option<String>, CollectorError> {
+        self.call(client, "collections/list", json!({})).await?;
+        Ok(None)
+    }
+
+    async fn collections(&self, client: &VectorClient) -> Result<Vec<Collection>, CollectorError> {
+        let names = self.call(client, "collections/list", json!({})).await?;
+        let mut collections = Vec::new();
+        for name in names
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            debug!(collection = name, "Describing Milvus collection");
+            let body = json!({ "collectionName": name });
+            let description = self
+                .call(client, "collections/describe", body.clone())
+                .await?;
+            let stats = self.call(client, "collections/get_stats", body).await?;
+
+            let mut indexes = Vec::new();
+            for index_name in description["indexes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|index| index["indexName"].as_str())
+            {
+                let body = json!({ "collectionName": name, "indexName": index_name });
+                let described = self.call(client, "indexes/describe", body).await?;
+                indexes.extend(described.as_array().into_iter().flatten().cloned());
+            }
+
+            collections.push(collection(
+                &self.database,
+                name,
+                &description,
+                &stats,
+                &indexes,
+            ));
+        }
+        Ok(collections)
+    }
+}
+
+/// The `data` of a response, or its `message` as an error
+fn check(path: &str, response: Value) -> Result<Value, CollectorError> {
+    match int(&response["code"]) {
+        None | Some(0) | Some(200) => Ok(response["data"].clone()),
+        Some(code) => {
+            let message = text(&response["message"]).unwrap_or_else(|| format!("code {code}"));
+            let lower = message.to_lowercase();
+            // 1800: not authenticated; 65535 with a privilege message
+            if code == 1800
+                || lower.contains("authenticat")
+                || lower.contains("permission denied")
+                || lower.contains("privilege")
+            {
+                Err(CollectorError::PermissionError(message))
+            } else {
+                Err(CollectorError::QueryError(format!("{path}: {message}")))
+            }
+        }
+    }
+}
+
+fn collection(
+    database: &str,
+    name: &str,
+    description: &Value,
+    stats: &Value,
+    indexes: &[Value],
+) -> Collection {
+    let mut indexed_vectors: Option<i64> = None;
+    let mut fields = Vec::new();
+
+    for field in description["fields"].as_array().into_iter().flatten() {
+        let field_name = text(&field["name"]).unwrap_or_default();
+        let data_type = text(&field["type"]).unwrap_or_default();
+        let index = indexes
+            .iter()
+            .find(|index| index["fieldName"].as_str() == Some(field_name.as_str()));
+        let vector_type = match data_type.as_str() {
+            "FloatVector" => Some("vector"),
+            "Float16Vector" | "BFloat16Vector" => Some("halfvec"),
+            "BinaryVector" => Some("bit"),
+            "SparseFloatVector" => Some("sparsevec"),
+            "Int8Vector" => Some("int8vec"),
+            _ => None,
+        };
+
+        let vector = vector_type.map(|vector_type| {
+            if let Some(rows) = index.and_then(|index| int(&index["indexedRows"])) {
+                indexed_vectors = Some(indexed_vectors.map_or(rows, |min| min.min(rows)));
+            }
+            VectorField {
+                vector_type,
+                dimensions: param(field, "dim"),
+                distance: index.and_then(|index| index["metricType"].as_str().map(distance)),
+                index: index.map(ann_index),
+            }
+        });
+        let primary_key = field["primaryKey"].as_bool().unwrap_or(false);
+        fields.push(Field {
+            name: field_name,
+            nullable: field["nullable"].as_bool().unwrap_or(false),
+            primary_key,
+            index_type: match (&vector, index) {
+                (None, Some(index)) => text(&index["indexType"]).map(|t| t.to_lowercase()),
+                _ => None,
+            },
+            data_type,
+            vector,
+        });
+    }
+
+    Collection {
+        namespace: database.to_string(),
+        name: name.to_string(),
+        fields,
+        point_count: int(&stats["rowCount"]),
+        indexed_vectors,
+        status: text(&description["load"]),
+        ..Default::default()
+    }
+}
+
+fn ann_index(index: &Value) -> AnnIndex {
+    let method = text(&index["indexType"]).unwrap_or_default().to_lowercase();
+    // Build parameters sit at the top level or under `params`, as an object
+    // or as `[{key, value}]`, depending on the release
+    let build = |key: &str| int(&index[key]).or_else(|| param(index, key));
+    let quantization = if method.ends_with("_sq8") || method.ends_with("_sq") {
+        Some(format!(
+            "scalar_{}",
+            text(&index["params"]["sq_type"])
+                .unwrap_or_else(|| "int8".to_string())
+                .to_lowercase()
+        ))
+    } else if method.ends_with("_prq") {
+        Some("product_residual".to_string())
+    } else if method.ends_with("_pq") {
+        Some("product".to_string())
+    } else if method.contains("rabitq") {
+        Some("rabitq".to_string())
+    } else {
+        None
+    };
+    AnnIndex {
+        name: text(&index["indexName"]),
+        m: build("M"),
+        ef_construction: build("efConstruction"),
+        lists: build("nlist"),
+        quantization,
+        method,
+    }
+}
+
+/// A parameter from `params`, either `{"dim": "768"}` or
+/// `[{"key": "dim", "value": "768"}]`
+fn param(value: &Value, key: &str) -> Option<i64> {
+    match &value["params"] {
+        Value::Array(params) => params
+            .iter()
+            .find(|p| p["key"].as_str() == Some(key))
+            .and_then(|p| int(&p["value"])),
+        params => int(&params[key]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection() {
+        let description = json!({
+            "collectionName": "docs",
+            "load": "LoadStateLoaded",
+            "fields": [
+                {"name": "id", "type": "Int64", "primaryKey": true, "autoId": true},
+                {"name": "embedding", "type": "FloatVector", "primaryKey": false,
+                 "params": [{"key": "dim", "value": "768"}]},
+                {"name": "category", "type": "VarChar", "nullable": true,
+                 "params": [{"key": "max_length", "value": "64"}]}
+            ],
+            "indexes": [{"fieldName": "embedding", "indexName": "embedding_idx", "metricType": "COSINE"}]
+        });
+        let indexes = vec![
+            json!({"fieldName": "embedding", "indexName": "embedding_idx", "indexType": "HNSW",
+                   "metricType": "COSINE", "indexedRows": 900, "totalRows": 1000,
+                   "params": {"M": "16", "efConstruction": "200"}}),
+            json!({"fieldName": "category", "indexName": "category_idx", "indexType": "INVERTED"}),
+        ];
+        let collection = collection(
+            "default",
+            "docs",
+            &description,
+            &json!({"rowCount": 1000}),
+            &indexes,
+        );
+
+        assert_eq!(collection.point_count, Some(1_000));
+        assert_eq!(collection.indexed_vectors, Some(900));
+        assert_eq!(collection.status.as_deref(), Some("LoadStateLoaded"));
+        assert!(collection.fields[0].primary_key);
+
+        let vector = collection.fields[1].vector.as_ref().unwrap();
+        let index = vector.index.as_ref().unwrap();
+        assert_eq!(vector.dimensions, Some(768));
+        assert_eq!(vector.distance.as_deref(), Some("cosine"));
+        assert_eq!(index.name.as_deref(), Some("embedding_idx"));
+        assert_eq!((index.m, index.ef_construction), (Some(16), Some(200)));
+
+        assert!(collection.fields[2].nullable);
+        assert_eq!(collection.fields[2].index_type.as_deref(), Some("inverted"));
+    }
+
+    #[test]
+    fn test_ann_index() {
+        let index = ann_index(
+            &json!({"indexType": "IVF_SQ8", "params": [{"key": "nlist", "value": "1024"}]}),
+        );
+        assert_eq!(index.method, "ivf_sq8");
+        assert_eq!(index.lists, Some(1024));
+        assert_eq!(index.quantization.as_deref(), Some("scalar_int8"));
+        assert_eq!(
+            ann_index(&json!({"indexType": "IVF_PQ"}))
+                .quantization
+                .as_deref(),
+            Some("product")
+        );
+        assert_eq!(
+            ann_index(&json!({"indexType": "DISKANN"})).quantization,
+            None
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        assert_eq!(
+            check("collections/list", json!({"code": 0, "data": ["a"]})).unwrap(),
+            json!(["a"])
+        );
+        assert!(matches!(
+            check(
+                "collections/list",
+                json!({"code": 1800, "message": "user hasn't authenticated"})
+            ),
+            Err(CollectorError::PermissionError(_))
+        ));
+        assert!(matches!(
+            check(
+                "collections/describe",
+                json!({"code": 100, "message": "collection not found"})
+            ),
+            Err(CollectorError::QueryError(_))
+        ));
+    }
+}
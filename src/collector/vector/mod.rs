@@ -0,0 +1,207 @@
+//! Vector store collector for Qdrant, Weaviate, Milvus and Chroma, over
+//! their HTTP APIs.
+//!
+//! Collects:
+//! - Collections (Weaviate classes) as tables, with their vector and
+//!   scalar / payload fields as columns
+//! - Vector parameters: dimensions, distance metric, ANN index type and its
+//!   build parameters (`m`, `ef_construction`, `nlist`) and quantization,
+//!   in the same shape pgvector reports them
+//! - Scalar / payload indexes
+//! - Point counts, indexed vector counts, segment counts and collection
+//!   and optimizer status
+//!
+//! The products differ only in their APIs; each has a small adapter
+//! returning [`collection::Collection`]s, which are mapped onto the payload
+//! in one place. Read-only credentials are enough everywhere: a read-only
+//! API key (Qdrant, Weaviate, Chroma) or a user with the `DescribeCollection`,
+//! `ListCollections`, `GetStatistics` and `IndexDetail` privileges (Milvus).
+
+mod chroma;
+mod client;
+mod collection;
+mod milvus;
+pub mod providers;
+mod qdrant;
+mod weaviate;
+
+use crate::collector::{Collector, CollectorError};
+use crate::config::{DatabaseType, Provider};
+use crate::payload::{DatabaseInfo, Payload};
+use async_trait::async_trait;
+use client::VectorClient;
+use collection::Collection;
+use std::fmt;
+use tracing::info;
+
+/// The vector stores this collector speaks to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Product {
+    Qdrant,
+    Weaviate,
+    Milvus,
+    Chroma,
+}
+
+impl Product {
+    /// The product for a database type, `None` for the ones this collector
+    /// does not handle
+    pub fn from_database_type(db_type: DatabaseType) -> Option<Self> {
+        match db_type {
+            DatabaseType::Qdrant => Some(Product::Qdrant),
+            DatabaseType::Weaviate => Some(Product::Weaviate),
+            DatabaseType::Milvus => Some(Product::Milvus),
+            DatabaseType::Chroma => Some(Product::Chroma),
+            _ => None,
+        }
+    }
+
+    pub fn database_type(&self) -> DatabaseType {
+        match self {
+            Product::Qdrant => DatabaseType::Qdrant,
+            Product::Weaviate => DatabaseType::Weaviate,
+            Product::Milvus => DatabaseType::Milvus,
+            Product::Chroma => DatabaseType::Chroma,
+        }
+    }
+
+    /// Port of the HTTP API
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Product::Qdrant => 6333,
+            Product::Weaviate => 8080,
+            Product::Milvus => 19530,
+            Product::Chroma => 8000,
+        }
+    }
+}
+
+impl fmt::Display for Product {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Product::Qdrant => write!(f, "Qdrant"),
+            Product::Weaviate => write!(f, "Weaviate"),
+            Product::Milvus => write!(f, "Milvus"),
+            Product::Chroma => write!(f, "Chroma"),
+        }
+    }
+}
+
+/// What differs between products: how to read the version and the
+/// collections
+#[async_trait]
+trait Adapter: Send + Sync {
+    /// Server version, where the API reports one
+    async fn version(&self, client: &VectorClient) -> Result<Option<String>, CollectorError>;
+
+    async fn collections(&self, client: &VectorClient) -> Result<Vec<Collection>, CollectorError>;
+}
+
+/// Qdrant / Weaviate / Milvus / Chroma metrics collector
+pub struct VectorCollector {
+    client: VectorClient,
+    adapter: Box<dyn Adapter>,
+    product: Product,
+    database_url: String,
+    detected_provider: String,
+    version: Option<String>,
+}
+
+impl VectorCollector {
+    /// Connect to the product's API and detect the provider.
+    pub async fn new(
+        database_url: &str,
+        db_type: DatabaseType,
+        provider: Provider,
+    ) -> Result<Self, CollectorError> {
+        let product = Product::from_database_type(db_type).ok_or_else(|| {
+            CollectorError::UnsupportedDatabase(format!(
+                "{db_type} is not a supported vector store"
+            ))
+        })?;
+        info!("Connecting to {product}");
+
+        let client = VectorClient::from_url(database_url, product)?;
+        let adapter: Box<dyn Adapter> = match product {
+            Product::Qdrant => Box::new(qdrant::Qdrant),
+            Product::Weaviate => Box::new(weaviate::Weaviate),
+            Product::Milvus => Box::new(milvus::Milvus::new(&client)),
+            Product::Chroma => Box::new(chroma::Chroma::detect(&client).await?),
+        };
+        let version = adapter.version(&client).await?;
+        info!(
+            version = version.as_deref().unwrap_or("unknown"),
+            "Connected to database"
+        );
+
+        let detected_provider = if provider == Provider::Auto {
+            providers::detect_provider(product, client.host()).to_string()
+        } else {
+            provider.to_string()
+        };
+        info!(provider = %detected_provider, "Database provider detected");
+
+        Ok(Self {
+            client,
+            adapter,
+            product,
+            database_url: database_url.to_string(),
+            detected_provider,
+            version,
+        })
+    }
+}
+
+#[async_trait]
+impl Collector for VectorCollector {
+    async fn collect(&self) -> Result<Payload, CollectorError> {
+        info!("Starting metrics collection");
+
+        let collections = self.adapter.collections(&self.client).await?;
+        let mapped = collection::map(collections);
+
+        let database_info = DatabaseInfo {
+            database_type: self.product.database_type().to_string(),
+            version: self.version.clone(),
+            provider: self.detected_provider.clone(),
+            provider_metadata: providers::provider_metadata(
+                self.product,
+                &self.detected_provider,
+                self.client.host(),
+            ),
+        };
+
+        info!(
+            collections = mapped.schema.tables.len(),
+            vector_columns = mapped.vector_store.columns.len(),
+            indexes = mapped.schema.indexes.len(),
+            "Metrics collection complete"
+        );
+
+        Ok(Payload::new(database_info)
+            .with_instance_id(&self.database_url)
+            .with_table_stats(mapped.table_stats)
+            .with_schema(mapped.schema)
+            .with_vector_store(mapped.vector_store))
+    }
+
+    async fn test_connection(&self) -> Result<(), CollectorError> {
+        self.adapter
+            .version(&self.client)
+            .await
+            .map(|_| ())
+            .map_err(|e| CollectorError::ConnectionError(e.to_string()))
+    }
+
+    fn provider(&self) -> &str {
+        &self.detected_provider
+    }
+
+    fn version(&self) -> Option<String> {
+        self.version.clone()
+    }
+
+    fn database_type(&self) -> DatabaseType {
+        self.product.database_type()
+    }
+}
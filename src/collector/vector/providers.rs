@@ -0,0 +1,108 @@
+//! Vector store provider detection.
+//!
+//! Returns one of:
+//! - `"qdrant_cloud"` — Qdrant Cloud
+//! - `"weaviate_cloud"` — Weaviate Cloud
+//! - `"zilliz_cloud"` — Zilliz Cloud (managed Milvus)
+//! - `"chroma_cloud"` — Chroma Cloud
+//! - `"generic"` — self-hosted
+//!
+//! The managed services are recognized by hostname.
+
+use super::Product;
+use std::collections::HashMap;
+
+pub const PROVIDER_QDRANT_CLOUD: &str = "qdrant_cloud";
+pub const PROVIDER_WEAVIATE_CLOUD: &str = "weaviate_cloud";
+pub const PROVIDER_ZILLIZ_CLOUD: &str = "zilliz_cloud";
+pub const PROVIDER_CHROMA_CLOUD: &str = "chroma_cloud";
+pub const PROVIDER_GENERIC: &str = "generic";
+
+/// Detect the provider from the hostname.
+pub fn detect_provider(product: Product, host: &str) -> &'static str {
+    let host = host.to_lowercase();
+    match product {
+        Product::Qdrant if host.ends_with(".cloud.qdrant.io") => PROVIDER_QDRANT_CLOUD,
+        Product::Weaviate
+            if host.ends_with(".weaviate.cloud") || host.ends_with(".weaviate.network") =>
+        {
+            PROVIDER_WEAVIATE_CLOUD
+        }
+        Product::Milvus if host.ends_with(".zillizcloud.com") || host.ends_with(".zilliz.com") => {
+            PROVIDER_ZILLIZ_CLOUD
+        }
+        Product::Chroma if host.ends_with(".trychroma.com") => PROVIDER_CHROMA_CLOUD,
+        _ => PROVIDER_GENERIC,
+    }
+}
+
+/// Provider metadata derivable from the hostname.
+pub fn provider_metadata(product: Product, provider: &str, host: &str) -> HashMap<String, String> {
+    let mut out = HashMap::new();
+    out.insert("product".to_string(), product.database_type().to_string());
+
+    let labels: Vec<&str> = host.split('.').collect();
+    match provider {
+        // `<cluster id>.<region>.<cloud>.cloud.qdrant.io`
+        PROVIDER_QDRANT_CLOUD if labels.len() >= 6 => {
+            out.insert("cluster_id".to_string(), labels[0].to_string());
+            out.insert("region".to_string(), labels[1].to_string());
+            out.insert("cloud".to_string(), labels[2].to_string());
+        }
+        // `<cluster>.c0.<region>.gcp.weaviate.cloud`, or
+        // `<cluster>.<region>.zillizcloud.com`-style endpoints
+        PROVIDER_WEAVIATE_CLOUD | PROVIDER_ZILLIZ_CLOUD => {
+            out.insert("cluster_id".to_string(), labels[0].to_string());
+        }
+        _ => {}
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_provider() {
+        assert_eq!(
+            detect_provider(
+                Product::Qdrant,
+                "xyz-example.eu-central.aws.cloud.qdrant.io"
+            ),
+            PROVIDER_QDRANT_CLOUD
+        );
+        assert_eq!(
+            detect_provider(Product::Weaviate, "abc.c0.europe-west3.gcp.weaviate.cloud"),
+            PROVIDER_WEAVIATE_CLOUD
+        );
+        assert_eq!(
+            detect_provider(
+                Product::Milvus,
+                "in03-abc.serverless.gcp-us-west1.cloud.zilliz.com"
+            ),
+            PROVIDER_ZILLIZ_CLOUD
+        );
+        assert_eq!(
+            detect_provider(Product::Chroma, "api.trychroma.com"),
+            PROVIDER_CHROMA_CLOUD
+        );
+        assert_eq!(
+            detect_provider(Product::Qdrant, "localhost"),
+            PROVIDER_GENERIC
+        );
+    }
+
+    #[test]
+    fn test_provider_metadata() {
+        let meta = provider_metadata(
+            Product::Qdrant,
+            PROVIDER_QDRANT_CLOUD,
+            "xyz-example.eu-central.aws.cloud.qdrant.io",
+        );
+        assert_eq!(meta.get("cluster_id").unwrap(), "xyz-example");
+        assert_eq!(meta.get("region").unwrap(), "eu-central");
+        assert_eq!(meta.get("cloud").unwrap(), "aws");
+        assert_eq!(meta.get("product").unwrap(), "qdrant");
+    }
+}
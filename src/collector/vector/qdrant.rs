@@ -0,0 +1,235 @@
+//! Qdrant adapter, over the REST API (port 6333).
+//!
+//! Collections come from `GET /collections` and `GET /collections/{name}`,
+//! which carries the vector parameters, the HNSW and quantization config
+//! (collection-wide, overridable per named vector), point and segment
+//! counts, and the optimizer status. Payload is schemaless; only fields
+//! with a payload index are known and reported.
+
+use super::client::{int, text, VectorClient};
+use super::collection::{distance, AnnIndex, Collection, Field, VectorField};
+use super::Adapter;
+use crate::collector::CollectorError;
+use async_trait::async_trait;
+use serde_json::Value;
+use tracing::debug;
+
+/// Namespace for all collections; Qdrant has no databases
+const NAMESPACE: &str = "qdrant";
+
+/// Name of the vector in collections with a single unnamed one
+const DEFAULT_VECTOR: &str = "vector";
+
+pub(super) struct Qdrant;
+
+#[async_trait]
+impl Adapter for Qdrant {
+    async fn version(&self, client: &VectorClient) -> Result<Option<String>, CollectorError> {
+        Ok(text(&client.get("").await?["version"]))
+    }
+
+    async fn collections(&self, client: &VectorClient) -> Result<Vec<Collection>, CollectorError> {
+        let list = client.get("collections").await?;
+        let mut collections = Vec::new();
+        for name in list["result"]["collections"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c["name"].as_str())
+        {
+            debug!(collection = name, "Describing Qdrant collection");
+            let info = client.get(&format!("collections/{name}")).await?;
+            collections.push(collection(name, &info["result"]));
+        }
+        Ok(collections)
+    }
+}
+
+fn collection(name: &str, info: &Value) -> Collection {
+    let config = &info["config"];
+    let params = &config["params"];
+    let mut fields = Vec::new();
+
+    match params["vectors"].as_object() {
+        // A single unnamed vector: `{"size": 384, "distance": "Cosine"}`
+        Some(vectors) if vectors.contains_key("size") => {
+            fields.push(dense_vector(DEFAULT_VECTOR, &params["vectors"], config))
+        }
+        Some(vectors) => fields.extend(
+            vectors
+                .iter()
+                .map(|(name, vector)| dense_vector(name, vector, config)),
+        ),
+        None => {}
+    }
+    for (name, sparse) in params["sparse_vectors"].as_object().into_iter().flatten() {
+        fields.push(Field {
+            name: name.clone(),
+            data_type: "sparse".to_string(),
+            nullable: true,
+            vector: Some(VectorField {
+                vector_type: "sparsevec",
+                distance: Some("inner_product".to_string()),
+                index: Some(AnnIndex {
+                    method: "sparse_inverted".to_string(),
+                    quantization: text(&sparse["index"]["datatype"]).filter(|t| t != "float32"),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+    }
+    for (name, schema) in info["payload_schema"].as_object().into_iter().flatten() {
+        let data_type = text(&schema["data_type"]).unwrap_or_else(|| "unknown".to_string());
+        fields.push(Field {
+            name: name.clone(),
+            index_type: Some(data_type.clone()),
+            data_type,
+            nullable: true,
+            ..Default::default()
+        });
+    }
+
+    Collection {
+        namespace: NAMESPACE.to_string(),
+        name: name.to_string(),
+        fields,
+        point_count: int(&info["points_count"]),
+        indexed_vectors: int(&info["indexed_vectors_count"]),
+        segment_count: int(&info["segments_count"]),
+        status: text(&info["status"]),
+        optimizer_status: match &info["optimizer_status"] {
+            Value::String(status) => Some(status.clone()),
+            // `{"error": "..."}`
+            other => text(&other["error"]),
+        },
+    }
+}
+
+/// A dense vector; its own `hnsw_config` and `quantization_config` override
+/// the collection's
+fn dense_vector(name: &str, vector: &Value, config: &Value) -> Field {
+    let hnsw =
+        |key: &str| int(&vector["hnsw_config"][key]).or_else(|| int(&config["hnsw_config"][key]));
+    let quantization = match &vector["quantization_config"] {
+        Value::Null => &config["quantization_config"],
+        own => own,
+    };
+    let m = hnsw("m");
+    Field {
+        name: name.to_string(),
+        data_type: "vector".to_string(),
+        // Points may lack any named vector
+        nullable: true,
+        vector: Some(VectorField {
+            vector_type: match vector["datatype"].as_str() {
+                Some("float16") => "halfvec",
+                Some("uint8") => "uint8vec",
+                _ => "vector",
+            },
+            dimensions: int(&vector["size"]),
+            distance: vector["distance"].as_str().map(distance),
+            index: Some(AnnIndex {
+                // `m = 0` turns off the HNSW graph
+                method: if m == Some(0) { "flat" } else { "hnsw" }.to_string(),
+                m,
+                ef_construction: hnsw("ef_construct"),
+                quantization: quantization_name(quantization),
+                ..Default::default()
+            }),
+        }),
+        ..Default::default()
+    }
+}
+
+/// `{"scalar": {"type": "int8"}}` to `scalar_int8`, `{"product":
+/// {"compression": "x16"}}` to `product_x16`, `{"binary": {}}` to `binary`
+fn quantization_name(config: &Value) -> Option<String> {
+    let (kind, params) = config.as_object()?.iter().next()?;
+    let detail = text(&params["type"]).or_else(|| text(&params["compression"]));
+    Some(match detail {
+        Some(detail) => format!("{kind}_{}", detail.to_lowercase()),
+        None => kind.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collection() {
+        let info = json!({
+            "status": "yellow",
+            "optimizer_status": {"error": "Service internal error: No space left on device"},
+            "indexed_vectors_count": 9000,
+            "points_count": 10000,
+            "segments_count": 4,
+            "config": {
+                "params": {
+                    "vectors": {
+                        "text": {"size": 384, "distance": "Cosine"},
+                        "image": {"size": 512, "distance": "Dot", "datatype": "float16",
+                                  "hnsw_config": {"m": 32},
+                                  "quantization_config": {"product": {"compression": "x16"}}}
+                    },
+                    "sparse_vectors": {"keywords": {"index": {"on_disk": true}}}
+                },
+                "hnsw_config": {"m": 16, "ef_construct": 100},
+                "quantization_config": {"scalar": {"type": "int8", "quantile": 0.99}}
+            },
+            "payload_schema": {"city": {"data_type": "keyword", "points": 10000}}
+        });
+        let collection = collection("products", &info);
+
+        assert_eq!(collection.point_count, Some(10_000));
+        assert_eq!(collection.segment_count, Some(4));
+        assert_eq!(
+            collection.optimizer_status.as_deref(),
+            Some("Service internal error: No space left on device")
+        );
+
+        let names: Vec<&str> = collection.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["text", "image", "keywords", "city"]);
+
+        let text = collection.fields[0].vector.as_ref().unwrap();
+        let index = text.index.as_ref().unwrap();
+        assert_eq!(text.dimensions, Some(384));
+        assert_eq!(text.distance.as_deref(), Some("cosine"));
+        assert_eq!((index.m, index.ef_construction), (Some(16), Some(100)));
+        assert_eq!(index.quantization.as_deref(), Some("scalar_int8"));
+
+        let image = collection.fields[1].vector.as_ref().unwrap();
+        let index = image.index.as_ref().unwrap();
+        assert_eq!(image.vector_type, "halfvec");
+        assert_eq!(image.distance.as_deref(), Some("inner_product"));
+        assert_eq!((index.m, index.ef_construction), (Some(32), Some(100)));
+        assert_eq!(index.quantization.as_deref(), Some("product_x16"));
+
+        assert_eq!(
+            collection.fields[2].vector.as_ref().unwrap().vector_type,
+            "sparsevec"
+        );
+        assert_eq!(collection.fields[3].index_type.as_deref(), Some("keyword"));
+    }
+
+    #[test]
+    fn test_single_unnamed_vector() {
+        let info = json!({
+            "status": "green",
+            "optimizer_status": "ok",
+            "points_count": 5,
+            "config": {"params": {"vectors": {"size": 4, "distance": "Euclid"}},
+                       "hnsw_config": {"m": 0, "ef_construct": 100}}
+        });
+        let collection = collection("tiny", &info);
+        assert_eq!(collection.optimizer_status.as_deref(), Some("ok"));
+        let field = &collection.fields[0];
+        assert_eq!(field.name, "vector");
+        let vector = field.vector.as_ref().unwrap();
+        assert_eq!(vector.distance.as_deref(), Some("l2"));
+        assert_eq!(vector.index.as_ref().unwrap().method, "flat");
+    }
+}
@@ -0,0 +1,283 @@
+//! Weaviate adapter, over the REST API (port 8080).
+//!
+//! Classes and their properties come from `GET /v1/schema`, including the
+//! vector index config of the class or of each named vector
+//! (`vectorConfig`). Object counts and indexing status come from
+//! `GET /v1/nodes?output=verbose`, per shard; replicas of a shard are only
+//! counted once. Dimensions are not part of the schema (the vectorizer
+//! decides them) and are not reported.
+
+use super::client::{int, text, VectorClient};
+use super::collection::{distance, AnnIndex, Collection, Field, VectorField};
+use super::Adapter;
+use crate::collector::CollectorError;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Namespace for all classes; Weaviate has no databases
+const NAMESPACE: &str = "weaviate";
+
+/// Name of the vector of classes without named vectors
+const DEFAULT_VECTOR: &str = "vector";
+
+/// Shard status of a vector index that is not building
+const READY: &str = "READY";
+
+pub(super) struct Weaviate;
+
+#[async_trait]
+impl Adapter for Weaviate {
+    async fn version(&self, client: &VectorClient) -> Result<Option<String>, CollectorError> {
+        Ok(text(&client.get("v1/meta").await?["version"]))
+    }
+
+    async fn collections(&self, client: &VectorClient) -> Result<Vec<Collection>, CollectorError> {
+        let schema = client.get("v1/schema").await?;
+        // Optional: needs the `read_nodes` permission where RBAC is on
+        let shards = match client.get("v1/nodes?output=verbose").await {
+            Ok(nodes) => shard_stats(&nodes),
+            Err(e) => {
+                warn!(error = %e, "Failed to collect Weaviate shard statistics");
+                HashMap::new()
+            }
+        };
+
+        Ok(schema["classes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|class| {
+                let mut collection = collection(class);
+                if let Some(stats) = shards.get(&collection.name) {
+                    collection.point_count = Some(stats.objects);
+                    collection.segment_count = Some(stats.shards);
+                    collection.status = Some(stats.status.clone());
+                }
+                collection
+            })
+            .collect())
+    }
+}
+
+fn collection(class: &Value) -> Collection {
+    let name = text(&class["class"]).unwrap_or_default();
+    let mut fields = vec![Field {
+        name: "id".to_string(),
+        data_type: "uuid".to_string(),
+        primary_key: true,
+        ..Default::default()
+    }];
+
+    match class["vectorConfig"].as_object() {
+        Some(named) if !named.is_empty() => fields.extend(named.iter().map(|(name, config)| {
+            vector(
+                name,
+                &config["vectorIndexType"],
+                &config["vectorIndexConfig"],
+            )
+        })),
+        _ => fields.push(vector(
+            DEFAULT_VECTOR,
+            &class["vectorIndexType"],
+            &class["vectorIndexConfig"],
+        )),
+    }
+
+    for property in class["properties"].as_array().into_iter().flatten() {
+        let data_type = property["dataType"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("|");
+        // Both default to true; `indexSearchable` only applies to text
+        let filterable = property["indexFilterable"].as_bool().unwrap_or(true);
+        let searchable =
+            property["indexSearchable"].as_bool().unwrap_or(true) && data_type.starts_with("text");
+        let index_type = match (filterable, searchable) {
+            (true, true) => Some("filterable,searchable"),
+            (true, false) => Some("filterable"),
+            (false, true) => Some("searchable"),
+            (false, false) => None,
+        };
+        fields.push(Field {
+            name: text(&property["name"]).unwrap_or_default(),
+            data_type,
+            nullable: true,
+            index_type: index_type.map(str::to_string),
+            ..Default::default()
+        });
+    }
+
+    Collection {
+        namespace: NAMESPACE.to_string(),
+        name,
+        fields,
+        ..Default::default()
+    }
+}
+
+fn vector(name: &str, index_type: &Value, config: &Value) -> Field {
+    let method = index_type.as_str().unwrap_or("hnsw").to_lowercase();
+    // `skip` stores vectors without indexing them
+    let index = (!config["skip"].as_bool().unwrap_or(false)).then(|| AnnIndex {
+        m: int(&config["maxConnections"]),
+        ef_construction: int(&config["efConstruction"]),
+        quantization: ["pq", "bq", "sq", "rq"]
+            .into_iter()
+            .find(|kind| config[*kind]["enabled"].as_bool() == Some(true))
+            .map(|kind| {
+                match kind {
+                    "pq" => "product",
+                    "bq" => "binary",
+                    "sq" => "scalar",
+                    _ => "rotational",
+                }
+                .to_string()
+            }),
+        method,
+        ..Default::default()
+    });
+    Field {
+        name: name.to_string(),
+        data_type: "vector".to_string(),
+        nullable: true,
+        vector: Some(VectorField {
+            vector_type: "vector",
+            dimensions: None,
+            distance: config["distance"].as_str().map(distance),
+            index,
+        }),
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ShardStats {
+    objects: i64,
+    shards: i64,
+    /// `READY`, or the first other indexing status of a shard, e.g. `INDEXING`
+    status: String,
+}
+
+/// Per class: objects and shards, each replicated shard counted once
+fn shard_stats(nodes: &Value) -> HashMap<String, ShardStats> {
+    // (class, shard) -> (objects, status), keeping the largest replica
+    let mut shards: HashMap<(String, String), (i64, String)> = HashMap::new();
+    for shard in nodes["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|node| node["shards"].as_array().into_iter().flatten())
+    {
+        let (Some(class), Some(name)) = (text(&shard["class"]), text(&shard["name"])) else {
+            continue;
+        };
+        let objects = int(&shard["objectCount"]).unwrap_or(0);
+        let status = text(&shard["vectorIndexingStatus"]).unwrap_or_else(|| READY.to_string());
+        let entry = shards
+            .entry((class, name))
+            .or_insert((objects, status.clone()));
+        entry.0 = entry.0.max(objects);
+        if status != READY {
+            entry.1 = status;
+        }
+    }
+
+    let mut out: HashMap<String, ShardStats> = HashMap::new();
+    for ((class, _), (objects, status)) in shards {
+        let stats = out.entry(class).or_insert_with(|| ShardStats {
+            status: READY.to_string(),
+            ..Default::default()
+        });
+        stats.objects += objects;
+        stats.shards += 1;
+        if stats.status == READY {
+            stats.status = status;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_collection() {
+        let class = json!({
+            "class": "Article",
+            "vectorIndexType": "hnsw",
+            "vectorIndexConfig": {"distance": "cosine", "efConstruction": 128,
+                                  "maxConnections": 32, "skip": false,
+                                  "pq": {"enabled": true}, "bq": {"enabled": false}},
+            "properties": [
+                {"name": "title", "dataType": ["text"], "indexFilterable": true, "indexSearchable": true},
+                {"name": "wordCount", "dataType": ["int"], "indexFilterable": false, "indexSearchable": false}
+            ]
+        });
+        let collection = collection(&class);
+        assert_eq!(collection.name, "Article");
+
+        let names: Vec<&str> = collection.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "vector", "title", "wordCount"]);
+        assert!(collection.fields[0].primary_key);
+
+        let vector = collection.fields[1].vector.as_ref().unwrap();
+        let index = vector.index.as_ref().unwrap();
+        assert_eq!(vector.distance.as_deref(), Some("cosine"));
+        assert_eq!((index.m, index.ef_construction), (Some(32), Some(128)));
+        assert_eq!(index.quantization.as_deref(), Some("product"));
+
+        assert_eq!(
+            collection.fields[2].index_type.as_deref(),
+            Some("filterable,searchable")
+        );
+        assert_eq!(collection.fields[3].index_type, None);
+    }
+
+    #[test]
+    fn test_named_vectors() {
+        let class = json!({
+            "class": "Product",
+            "vectorConfig": {
+                "title": {"vectorIndexType": "flat", "vectorIndexConfig": {"distance": "dot"}},
+                "image": {"vectorIndexType": "hnsw", "vectorIndexConfig": {"distance": "l2-squared", "skip": true}}
+            }
+        });
+        let collection = collection(&class);
+        let title = collection.fields[1].vector.as_ref().unwrap();
+        assert_eq!(collection.fields[1].name, "title");
+        assert_eq!(title.index.as_ref().unwrap().method, "flat");
+        assert_eq!(title.distance.as_deref(), Some("inner_product"));
+        let image = collection.fields[2].vector.as_ref().unwrap();
+        assert_eq!(image.distance.as_deref(), Some("l2"));
+        assert!(image.index.is_none());
+    }
+
+    #[test]
+    fn test_shard_stats() {
+        let nodes = json!({"nodes": [
+            {"name": "node1", "shards": [
+                {"class": "Article", "name": "s1", "objectCount": 100, "vectorIndexingStatus": "READY"},
+                {"class": "Article", "name": "s2", "objectCount": 50, "vectorIndexingStatus": "READY"}
+            ]},
+            {"name": "node2", "shards": [
+                {"class": "Article", "name": "s1", "objectCount": 98, "vectorIndexingStatus": "INDEXING"}
+            ]}
+        ]});
+        let stats = shard_stats(&nodes);
+        assert_eq!(
+            stats["Article"],
+            ShardStats {
+                objects: 150,
+                shards: 2,
+                status: "INDEXING".to_string(),
+            }
+        );
+    }
+}
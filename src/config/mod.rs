@@ -161,9 +161,13 @@ impl DatabaseType {
             return Ok(DatabaseType::Redis);
         }
 
-        // Elasticsearch
+        // Elasticsearch and OpenSearch
         if url_lower.starts_with("elasticsearch://")
-            || url_lower.starts_with("https://") && url_lower.contains("elastic")
+            || url_lower.starts_with("opensearch://")
+            || url_lower.starts_with("https://")
+                && (url_lower.contains("elastic")
+                    || url_lower.contains("opensearch")
+                    || url_lower.contains(".es.amazonaws.com"))
         {
             return Ok(DatabaseType::Elasticsearch);
         }
@@ -213,7 +217,7 @@ impl DatabaseType {
             return Ok(DatabaseType::Pinecone);
         }
 
-        if url_lower.contains("milvus") || url_lower.starts_with("milvus://") {
+        if url_lower.contains("milvus") || url_lower.contains("zilliz") {
             return Ok(DatabaseType::Milvus);
         }
 
@@ -229,11 +233,13 @@ impl DatabaseType {
             return Ok(DatabaseType::Chroma);
         }
 
-        // pgvector is detected via PostgreSQL URL + extension check at runtime
+        // pgvector has no URL of its own: the Postgres collector checks each
+        // database it collects for the `vector` extension
 
         // Graph databases
         if url_lower.starts_with("neo4j://")
             || url_lower.starts_with("bolt://")
+            || url_lower.starts_with("bolt+s")
             || url_lower.contains("neo4j")
         {
             return Ok(DatabaseType::Neo4j);
@@ -278,7 +284,7 @@ impl DatabaseType {
             DatabaseType::Oracle => &["oracle://"],
             DatabaseType::Db2 => &["db2://", "ibmdb://"],
             DatabaseType::Redis => &["redis://", "rediss://"],
-            DatabaseType::Elasticsearch => &["elasticsearch://", "https://"],
+            DatabaseType::Elasticsearch => &["elasticsearch://", "opensearch://", "https://"],
             DatabaseType::Clickhouse => &["clickhouse://"],
             DatabaseType::Cosmosdb => &["https://"],
             DatabaseType::Couchbase => &["couchbase://", "couchbases://"],
@@ -286,18 +292,18 @@ impl DatabaseType {
             DatabaseType::Bigquery => &["bigquery://"],
             DatabaseType::Redshift => &["postgres://"], // Redshift uses PostgreSQL protocol
             DatabaseType::Dynamodb => &["https://"],
-            DatabaseType::Influxdb => &["influxdb://", "https://"],
+            DatabaseType::Influxdb => &["influxdb://", "https://", "http://"],
             DatabaseType::Timescaledb => &["postgres://", "postgresql://"],
             DatabaseType::Cockroachdb => &["postgres://", "postgresql://"],
             DatabaseType::Yugabytedb => &["postgres://", "postgresql://"],
             DatabaseType::Tidb => &["mysql://"],
             DatabaseType::Pinecone => &["https://"],
             DatabaseType::Milvus => &["milvus://", "https://"],
-            DatabaseType::Weaviate => &["https://"],
-            DatabaseType::Qdrant => &["https://", "http://"],
-            DatabaseType::Chroma => &["https://", "http://"],
+            DatabaseType::Weaviate => &["weaviate://", "https://", "http://"],
+            DatabaseType::Qdrant => &["qdrant://", "https://", "http://"],
+            DatabaseType::Chroma => &["chroma://", "https://", "http://"],
             DatabaseType::Pgvector => &["postgres://", "postgresql://"],
-            DatabaseType::Neo4j => &["neo4j://", "bolt://", "neo4j+s://"],
+            DatabaseType::Neo4j => &["neo4j://", "bolt://", "neo4j+s://", "bolt+s://"],
             DatabaseType::Neptune => &["wss://", "https://"],
             DatabaseType::Arangodb => &["https://", "http://"],
             DatabaseType::Janusgraph => &["ws://", "wss://"],
@@ -309,7 +315,22 @@ impl DatabaseType {
 
     /// Check if this database type is currently supported
     pub fn is_implemented(&self) -> bool {
-        matches!(self, DatabaseType::Postgres | DatabaseType::Mongodb)
+        matches!(
+            self,
+            DatabaseType::Postgres
+                | DatabaseType::Mongodb
+                | DatabaseType::Couchbase
+                | DatabaseType::Sqlserver
+                | DatabaseType::Clickhouse
+                | DatabaseType::Elasticsearch
+                | DatabaseType::Influxdb
+                | DatabaseType::Neo4j
+                | DatabaseType::Memgraph
+                | DatabaseType::Qdrant
+                | DatabaseType::Weaviate
+                | DatabaseType::Milvus
+                | DatabaseType::Chroma
+        ) || (*self == DatabaseType::Oracle && cfg!(feature = "oracle"))
     }
 
     /// Get the category of this database
@@ -505,6 +526,14 @@ pub struct CollectionConfig {
 
     #[serde(default = "default_metrics")]
     pub metrics: Vec<MetricType>,
+
+    /// Schema change tracking
+    #[serde(default)]
+    pub schema_changes: SchemaChangesConfig,
+
+    /// PostgreSQL-specific collection options
+    #[serde(default)]
+    pub postgres: PostgresCollectionConfig,
 }
 
 impl Default for CollectionConfig {
@@ -512,6 +541,8 @@ impl Default for CollectionConfig {
         Self {
             interval_secs: default_interval_secs(),
             metrics: default_metrics(),
+            schema_changes: SchemaChangesConfig::default(),
+            postgres: PostgresCollectionConfig::default(),
         }
     }
 }
@@ -522,6 +553,218 @@ impl CollectionConfig {
     }
 }
 
+/// Schema change tracking.
+///
+/// When enabled, the full schema is uploaded the first time and then every
+/// `full_refresh_secs`. In between, uploads carry only the schema
+/// fingerprint and, when something changed, a list of change events against
+/// the last uploaded schema. Off by default: the full schema is sent every
+/// cycle, as backends that don't apply change events expect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaChangesConfig {
+    #[serde(default = "default_schema_changes_enabled")]
+    pub enabled: bool,
+
+    #[serde(default = "default_schema_full_refresh_secs")]
+    pub full_refresh_secs: u64,
+}
+
+impl Default for SchemaChangesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_schema_changes_enabled(),
+            full_refresh_secs: default_schema_full_refresh_secs(),
+        }
+    }
+}
+
+impl SchemaChangesConfig {
+    pub fn full_refresh(&self) -> Duration {
+        Duration::from_secs(self.full_refresh_secs)
+    }
+}
+
+/// PostgreSQL-specific collection options
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostgresCollectionConfig {
+    /// Declarative partitioning handling
+    #[serde(default)]
+    pub partitions: PartitionConfig,
+
+    /// Collection from other databases in the same cluster
+    #[serde(default)]
+    pub databases: DatabasesConfig,
+
+    /// Which `pg_settings` rows to capture
+    #[serde(default)]
+    pub settings: SettingsScope,
+
+    /// EXPLAIN plan capture for the most expensive statements
+    #[serde(default)]
+    pub plans: PlanCaptureConfig,
+
+    /// Column statistics from `pg_stats`
+    #[serde(default)]
+    pub column_stats: ColumnStatsConfig,
+
+    /// Structure inside `jsonb` columns, inferred from sampled rows
+    #[serde(default)]
+    pub jsonb_sampling: JsonbSamplingConfig,
+
+    /// Include full function and procedure definitions in schema metadata
+    #[serde(default)]
+    pub function_bodies: bool,
+
+    /// Which schemas to collect tables, indexes and schema metadata from
+    #[serde(default)]
+    pub schemas: SchemasConfig,
+}
+
+/// Column statistics from `pg_stats`.
+///
+/// Null fraction, distinct count, average width and correlation are always
+/// collected. Most-common-values lists hold real column data, so they are
+/// only sent when `most_common_values` is turned on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnStatsConfig {
+    #[serde(default)]
+    pub most_common_values: bool,
+}
+
+/// Structure inference for `jsonb` columns.
+///
+/// Disabled by default, as it reads table data. When enabled, every table
+/// with `jsonb` columns is sampled with `TABLESAMPLE SYSTEM`, sized from its
+/// row estimate as MongoDB collections are, and each path found inside a
+/// column is reported as an extra column named `column.path` with the same
+/// presence, type and sample-value profile as a MongoDB field. Partitions
+/// are covered by their parent's sample.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonbSamplingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// `statement_timeout` for each table's sample, in milliseconds
+    #[serde(default = "default_jsonb_statement_timeout_ms")]
+    pub statement_timeout_ms: u64,
+}
+
+impl Default for JsonbSamplingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            statement_timeout_ms: default_jsonb_statement_timeout_ms(),
+        }
+    }
+}
+
+/// EXPLAIN plan capture for the top statements in `pg_stat_statements`.
+///
+/// Disabled by default. When enabled, the `top_n` statements by total
+/// execution time get a JSON plan, taken from `auto_explain` log output when
+/// it is readable and otherwise from `EXPLAIN (GENERIC_PLAN)` (PostgreSQL
+/// 16+). Plans are cached per statement and only refreshed when its mean
+/// execution time changes materially.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanCaptureConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "default_plan_top_n")]
+    pub top_n: usize,
+
+    /// `statement_timeout` for each EXPLAIN, in milliseconds
+    #[serde(default = "default_plan_statement_timeout_ms")]
+    pub statement_timeout_ms: u64,
+}
+
+impl Default for PlanCaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            top_n: default_plan_top_n(),
+            statement_timeout_ms: default_plan_statement_timeout_ms(),
+        }
+    }
+}
+
+/// Which `pg_settings` rows are captured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsScope {
+    /// A curated list of performance-relevant settings
+    #[default]
+    Curated,
+    /// Every setting, including extension GUCs
+    All,
+}
+
+/// Which databases of the cluster to collect from.
+///
+/// When disabled (the default) only the database named in the connection URL
+/// is collected. When enabled, every connectable, non-template database in
+/// `pg_database` is collected, filtered by `include` and then `exclude`; the
+/// filters apply to the database in the URL too, which is still used for
+/// cluster-wide statistics.
+/// Patterns match the whole database name and support `*` wildcards; an empty
+/// `include` list matches everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatabasesConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl DatabasesConfig {
+    /// Whether `name` passes the include/exclude filters.
+    pub fn matches(&self, name: &str) -> bool {
+        include_exclude_matches(&self.include, &self.exclude, name)
+    }
+}
+
+/// Which schemas (namespaces) to collect from.
+///
+/// System schemas (`pg_catalog`, `information_schema`, TOAST and temporary
+/// schemas) are never collected. The rest are filtered by `include` and then
+/// `exclude`, with the same `*` patterns as [`DatabasesConfig`]. The filter
+/// applies to table and index statistics as well as schema metadata.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemasConfig {
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl SchemasConfig {
+    /// Whether `name` passes the include/exclude filters.
+    pub fn matches(&self, name: &str) -> bool {
+        include_exclude_matches(&self.include, &self.exclude, name)
+    }
+}
+
+/// How partitioned tables are reported.
+///
+/// By default every partition is reported as its own table, carrying a link
+/// to its parent. `rollup_stats` additionally sums partition sizes and
+/// counters into the partitioned parent. `collapse_threshold` removes the
+/// partitions of any partition tree with more leaves than the threshold and
+/// reports only the (rolled-up) parent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartitionConfig {
+    #[serde(default)]
+    pub rollup_stats: bool,
+
+    #[serde(default)]
+    pub collapse_threshold: Option<usize>,
+}
+
 /// Types of metrics that can be collected (database-agnostic)
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -676,6 +919,26 @@ fn default_metrics() -> Vec<MetricType> {
     MetricType::all()
 }
 
+fn default_schema_changes_enabled() -> bool {
+    false
+}
+
+fn default_schema_full_refresh_secs() -> u64 {
+    3600
+}
+
+fn default_plan_top_n() -> usize {
+    10
+}
+
+fn default_plan_statement_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_jsonb_statement_timeout_ms() -> u64 {
+    5000
+}
+
 fn default_log_level() -> LogLevel {
     LogLevel::Info
 }
@@ -786,6 +1049,8 @@ impl Config {
             collection: CollectionConfig {
                 interval_secs,
                 metrics: default_metrics(),
+                schema_changes: SchemaChangesConfig::default(),
+                postgres: PostgresCollectionConfig::default(),
             },
             logging: LoggingConfig {
                 level: log_level,
@@ -849,6 +1114,33 @@ fn expand_env_vars(input: &str) -> String {
     result
 }
 
+/// Whether `name` matches one of `include` (or `include` is empty) and none
+/// of `exclude`.
+fn include_exclude_matches(include: &[String], exclude: &[String], name: &str) -> bool {
+    let included = include.is_empty() || include.iter().any(|p| glob_matches(p, name));
+    included && !exclude.iter().any(|p| glob_matches(p, name))
+}
+
+/// Match `name` against a pattern where `*` stands for any run of characters.
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    // No `*`: an exact match
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let Some(last) = parts.next_back() else {
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
 /// Parse duration string like "60s", "5m", "1h" into seconds
 fn parse_duration_secs(s: &str) -> Option<u64> {
     let s = s.trim();
@@ -927,6 +1219,16 @@ mod tests {
             DatabaseType::Mongodb
         );
 
+        // Couchbase
+        assert_eq!(
+            DatabaseType::from_url("couchbase://cb.internal/travel-sample").unwrap(),
+            DatabaseType::Couchbase
+        );
+        assert_eq!(
+            DatabaseType::from_url("couchbases://cb.a1b2c3d4.cloud.couchbase.com").unwrap(),
+            DatabaseType::Couchbase
+        );
+
         // SQL Server
         assert_eq!(
             DatabaseType::from_url("sqlserver://localhost/db").unwrap(),
@@ -958,6 +1260,59 @@ mod tests {
             DatabaseType::from_url("elasticsearch://localhost:9200").unwrap(),
             DatabaseType::Elasticsearch
         );
+        assert_eq!(
+            DatabaseType::from_url("opensearch://localhost:9200").unwrap(),
+            DatabaseType::Elasticsearch
+        );
+        assert_eq!(
+            DatabaseType::from_url("https://search-logs-abc123.us-east-1.es.amazonaws.com")
+                .unwrap(),
+            DatabaseType::Elasticsearch
+        );
+
+        // Neo4j / Memgraph
+        assert_eq!(
+            DatabaseType::from_url("neo4j+s://abcd1234.databases.neo4j.io").unwrap(),
+            DatabaseType::Neo4j
+        );
+        assert_eq!(
+            DatabaseType::from_url("bolt+s://graph.internal:7687").unwrap(),
+            DatabaseType::Neo4j
+        );
+
+        // Time-series
+        assert_eq!(
+            DatabaseType::from_url("influxdb://localhost:8086/acme?token=t").unwrap(),
+            DatabaseType::Influxdb
+        );
+        assert_eq!(
+            DatabaseType::from_url("https://us-east-1-1.aws.cloud2.influxdata.com").unwrap(),
+            DatabaseType::Influxdb
+        );
+
+        // Vector stores
+        assert_eq!(
+            DatabaseType::from_url("qdrant://localhost:6333").unwrap(),
+            DatabaseType::Qdrant
+        );
+        assert_eq!(
+            DatabaseType::from_url("https://xyz.eu-central.aws.cloud.qdrant.io:6333").unwrap(),
+            DatabaseType::Qdrant
+        );
+        assert_eq!(
+            DatabaseType::from_url("https://abc.c0.europe-west3.gcp.weaviate.cloud").unwrap(),
+            DatabaseType::Weaviate
+        );
+        assert_eq!(
+            DatabaseType::from_url("https://in03-abc.serverless.gcp-us-west1.cloud.zilliz.com")
+                .unwrap(),
+            DatabaseType::Milvus
+        );
+        assert_eq!(
+            DatabaseType::from_url("chroma://localhost:8000/default_tenant/default_database")
+                .unwrap(),
+            DatabaseType::Chroma
+        );
 
         // ClickHouse
         assert_eq!(
@@ -1014,6 +1369,136 @@ mod tests {
         assert_eq!(DatabaseType::Cockroachdb.category(), "NewSQL");
     }
 
+    #[test]
+    fn test_partition_config_from_yaml() {
+        let yaml = r#"
+interval_secs: 60
+postgres:
+  partitions:
+    rollup_stats: true
+    collapse_threshold: 100
+"#;
+        let collection: CollectionConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(collection.postgres.partitions.rollup_stats);
+        assert_eq!(collection.postgres.partitions.collapse_threshold, Some(100));
+
+        let collection: CollectionConfig = serde_yaml::from_str("interval_secs: 60").unwrap();
+        assert!(!collection.postgres.partitions.rollup_stats);
+        assert_eq!(collection.postgres.partitions.collapse_threshold, None);
+        assert_eq!(collection.postgres.settings, SettingsScope::Curated);
+
+        let collection: CollectionConfig =
+            serde_yaml::from_str("interval_secs: 60\npostgres:\n  settings: all").unwrap();
+        assert_eq!(collection.postgres.settings, SettingsScope::All);
+    }
+
+    #[test]
+    fn test_plan_capture_config_defaults() {
+        let collection: CollectionConfig = serde_yaml::from_str("interval_secs: 60").unwrap();
+        assert!(!collection.postgres.plans.enabled);
+        assert_eq!(collection.postgres.plans.top_n, 10);
+
+        let yaml = r#"
+interval_secs: 60
+postgres:
+  plans:
+    enabled: true
+    top_n: 5
+"#;
+        let collection: CollectionConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(collection.postgres.plans.enabled);
+        assert_eq!(collection.postgres.plans.top_n, 5);
+        assert_eq!(collection.postgres.plans.statement_timeout_ms, 1000);
+        assert!(!collection.postgres.column_stats.most_common_values);
+    }
+
+    #[test]
+    fn test_jsonb_sampling_config_defaults() {
+        let collection: CollectionConfig = serde_yaml::from_str("interval_secs: 60").unwrap();
+        assert!(!collection.postgres.jsonb_sampling.enabled);
+        assert_eq!(
+            collection.postgres.jsonb_sampling.statement_timeout_ms,
+            5000
+        );
+
+        let yaml = r#"
+postgres:
+  jsonb_sampling:
+    enabled: true
+"#;
+        let collection: CollectionConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(collection.postgres.jsonb_sampling.enabled);
+        assert_eq!(
+            collection.postgres.jsonb_sampling.statement_timeout_ms,
+            5000
+        );
+    }
+
+    #[test]
+    fn test_schema_changes_config_defaults() {
+        let collection: CollectionConfig = serde_yaml::from_str("interval_secs: 60").unwrap();
+        assert!(!collection.schema_changes.enabled);
+        assert_eq!(
+            collection.schema_changes.full_refresh(),
+            Duration::from_secs(3600)
+        );
+
+        let collection: CollectionConfig =
+            serde_yaml::from_str("schema_changes:\n  enabled: true").unwrap();
+        assert!(collection.schema_changes.enabled);
+        assert_eq!(collection.schema_changes.full_refresh_secs, 3600);
+    }
+
+    #[test]
+    fn test_databases_config_filters() {
+        let all = DatabasesConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(all.matches("app"));
+
+        let filtered = DatabasesConfig {
+            enabled: true,
+            include: vec!["tenant_*".to_string(), "app".to_string()],
+            exclude: vec!["tenant_*_archive".to_string()],
+        };
+        assert!(filtered.matches("app"));
+        assert!(filtered.matches("tenant_42"));
+        assert!(!filtered.matches("tenant_42_archive"));
+        assert!(!filtered.matches("application"));
+        assert!(!filtered.matches("rdsadmin"));
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("app", "app"));
+        assert!(!glob_matches("app", "app2"));
+        assert!(glob_matches("*", ""));
+        assert!(glob_matches("tenant_*", "tenant_"));
+        assert!(glob_matches("*_archive", "tenant_1_archive"));
+        assert!(glob_matches("t*_*_a*", "tenant_1_archive"));
+        assert!(!glob_matches("a*a", "a"));
+        assert!(!glob_matches("tenant_*_archive", "tenant_archive"));
+        // Regex metacharacters are literal
+        assert!(glob_matches("db.prod*", "db.prod_eu"));
+        assert!(!glob_matches("db.prod*", "dbxprod_eu"));
+    }
+
+    #[test]
+    fn test_schemas_config_filters() {
+        assert!(SchemasConfig::default().matches("public"));
+
+        let yaml = r#"
+include: ["public", "tenant_*"]
+exclude: ["tenant_old"]
+"#;
+        let schemas: SchemasConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(schemas.matches("public"));
+        assert!(schemas.matches("tenant_a"));
+        assert!(!schemas.matches("tenant_old"));
+        assert!(!schemas.matches("audit"));
+    }
+
     #[test]
     fn test_metric_type_aliases() {
         // Test that old PostgreSQL-specific names still work via serde aliases
@@ -67,9 +67,13 @@ async fn main() -> Result<()> {
     );
 
     // Create collector
-    let collector = collector::create_collector(&config.database.url, config.database.provider)
-        .await
-        .context("Failed to create database collector")?;
+    let collector = collector::create_collector_with_config(
+        &config.database.url,
+        config.database.provider,
+        &config.collection,
+    )
+    .await
+    .context("Failed to create database collector")?;
 
     info!(
         provider = collector.provider(),
@@ -101,7 +105,8 @@ async fn main() -> Result<()> {
             config.collection.interval(),
             shutdown_rx,
             None,
-        );
+        )
+        .with_schema_changes(&config.collection.schema_changes);
         return scheduler.run_once().await.map_err(Into::into);
     }
 
@@ -148,7 +153,8 @@ async fn main() -> Result<()> {
         config.collection.interval(),
         shutdown_rx,
         Some(health_state),
-    );
+    )
+    .with_schema_changes(&config.collection.schema_changes);
 
     info!(
         interval_secs = config.collection.interval().as_secs(),
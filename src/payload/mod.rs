@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
+pub mod schema_changes;
+
 /// The main payload sent to Datapace Cloud
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payload {
@@ -39,9 +41,84 @@ pub struct Payload {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub settings: Option<HashMap<String, String>>,
 
-    /// Schema metadata
+    /// Structured settings with units, source and defaults, where the engine
+    /// exposes them. `settings` keeps the flat name → value view.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub setting_details: Option<Vec<Setting>>,
+
+    /// Schema metadata. Only sent when the schema is first seen and on the
+    /// periodic full refresh while change tracking is on; see
+    /// `schema_changes`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<SchemaMetadata>,
+
+    /// Fingerprint of the collected schema. Sent every cycle, whether or not
+    /// `schema` itself is included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version_hash: Option<String>,
+
+    /// Fingerprint of the last schema this agent uploaded, which
+    /// `schema_changes` applies to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_base_hash: Option<String>,
+
+    /// Changes since the schema identified by `schema_base_hash`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_changes: Option<Vec<SchemaChange>>,
+
+    /// Database-level statistics (transaction ID age, etc.)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database_stats: Option<DatabaseStats>,
+
+    /// Extensions, roles and privileges
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inventory: Option<Inventory>,
+
+    /// TimescaleDB hypertables, continuous aggregates and jobs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timescaledb: Option<TimescaleMetadata>,
+
+    /// pgvector columns, ANN indexes and their settings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pgvector: Option<PgvectorMetadata>,
+
+    /// Placement of data across the nodes of a distributed SQL cluster
+    /// (CockroachDB ranges, YugabyteDB tablets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution: Option<Distribution>,
+
+    /// Redshift workload management queues
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redshift: Option<RedshiftMetadata>,
+
+    /// SQL Server Query Store state and missing index suggestions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sqlserver: Option<SqlServerMetadata>,
+
+    /// ClickHouse part counts, merge pressure and replication state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clickhouse: Option<ClickHouseMetadata>,
+
+    /// Elasticsearch / OpenSearch cluster health, shards and nodes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elasticsearch: Option<ElasticsearchMetadata>,
+
+    /// Vector fields and ANN indexes of a vector store (Qdrant, Weaviate,
+    /// Milvus, Chroma)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_store: Option<VectorStoreMetadata>,
+
+    /// InfluxDB buckets, series cardinality and runtime metrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub influxdb: Option<InfluxdbMetadata>,
+
+    /// Couchbase buckets and GSI index state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub couchbase: Option<CouchbaseMetadata>,
+
+    /// Oracle wait events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oracle: Option<OracleMetadata>,
 }
 
 impl Payload {
@@ -56,7 +133,24 @@ impl Payload {
             table_stats: None,
             index_stats: None,
             settings: None,
+            setting_details: None,
             schema: None,
+            schema_version_hash: None,
+            schema_base_hash: None,
+            schema_changes: None,
+            database_stats: None,
+            inventory: None,
+            timescaledb: None,
+            pgvector: None,
+            distribution: None,
+            redshift: None,
+            sqlserver: None,
+            clickhouse: None,
+            elasticsearch: None,
+            vector_store: None,
+            influxdb: None,
+            couchbase: None,
+            oracle: None,
         }
     }
 
@@ -90,12 +184,96 @@ impl Payload {
         self
     }
 
+    /// Add structured setting details
+    pub fn with_setting_details(mut self, details: Vec<Setting>) -> Self {
+        self.setting_details = Some(details);
+        self
+    }
+
     /// Add schema metadata
     pub fn with_schema(mut self, schema: SchemaMetadata) -> Self {
         self.schema = Some(schema);
         self
     }
 
+    /// Add database-level statistics
+    pub fn with_database_stats(mut self, stats: DatabaseStats) -> Self {
+        self.database_stats = Some(stats);
+        self
+    }
+
+    /// Add the extension, role and privilege inventory
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    /// Add TimescaleDB metadata
+    pub fn with_timescaledb(mut self, timescaledb: TimescaleMetadata) -> Self {
+        self.timescaledb = Some(timescaledb);
+        self
+    }
+
+    /// Add pgvector metadata
+    pub fn with_pgvector(mut self, pgvector: PgvectorMetadata) -> Self {
+        self.pgvector = Some(pgvector);
+        self
+    }
+
+    /// Add cluster data distribution
+    pub fn with_distribution(mut self, distribution: Distribution) -> Self {
+        self.distribution = Some(distribution);
+        self
+    }
+
+    /// Add Redshift workload management state
+    pub fn with_redshift(mut self, redshift: RedshiftMetadata) -> Self {
+        self.redshift = Some(redshift);
+        self
+    }
+
+    /// Add SQL Server metadata
+    pub fn with_sqlserver(mut self, sqlserver: SqlServerMetadata) -> Self {
+        self.sqlserver = Some(sqlserver);
+        self
+    }
+
+    /// Add ClickHouse metadata
+    pub fn with_clickhouse(mut self, clickhouse: ClickHouseMetadata) -> Self {
+        self.clickhouse = Some(clickhouse);
+        self
+    }
+
+    /// Add Elasticsearch / OpenSearch metadata
+    pub fn with_elasticsearch(mut self, elasticsearch: ElasticsearchMetadata) -> Self {
+        self.elasticsearch = Some(elasticsearch);
+        self
+    }
+
+    /// Add vector store metadata
+    pub fn with_vector_store(mut self, vector_store: VectorStoreMetadata) -> Self {
+        self.vector_store = Some(vector_store);
+        self
+    }
+
+    /// Add InfluxDB metadata
+    pub fn with_influxdb(mut self, influxdb: InfluxdbMetadata) -> Self {
+        self.influxdb = Some(influxdb);
+        self
+    }
+
+    /// Add Couchbase metadata
+    pub fn with_couchbase(mut self, couchbase: CouchbaseMetadata) -> Self {
+        self.couchbase = Some(couchbase);
+        self
+    }
+
+    /// Add Oracle metadata
+    pub fn with_oracle(mut self, oracle: OracleMetadata) -> Self {
+        self.oracle = Some(oracle);
+        self
+    }
+
     /// Serialize the payload to JSON
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -126,7 +304,7 @@ pub struct DatabaseInfo {
 }
 
 /// Query statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueryStats {
     /// Query hash/ID
     pub query_hash: Option<String>,
@@ -151,10 +329,36 @@ pub struct QueryStats {
 
     /// Shared blocks read from disk
     pub shared_blks_read: Option<i64>,
+
+    /// Executions in which a step ran out of memory and spilled to disk
+    /// (Redshift)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_spills: Option<i64>,
+
+    /// Rows read from storage, across all executions (ClickHouse)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_rows: Option<i64>,
+
+    /// Bytes read from storage, across all executions (ClickHouse)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_bytes: Option<i64>,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Execution plan in the engine's JSON format, when plan capture is on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<serde_json::Value>,
+
+    /// Where `plan` came from (`generic_plan`, `auto_explain`, `db_stats`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_source: Option<String>,
 }
 
 /// Table statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TableStats {
     /// Schema name
     pub schema: String,
@@ -162,6 +366,11 @@ pub struct TableStats {
     /// Table name
     pub table: String,
 
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
     /// Number of sequential scans
     pub seq_scan: Option<i64>,
 
@@ -200,10 +409,158 @@ pub struct TableStats {
 
     /// Last auto analyze
     pub last_autoanalyze: Option<DateTime<Utc>>,
+
+    // ---------- Vacuum health (Postgres-only; None elsewhere) ----------
+    /// Rows modified since the last analyze
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_mod_since_analyze: Option<i64>,
+
+    /// Rows inserted since the last vacuum (PostgreSQL 13+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_ins_since_vacuum: Option<i64>,
+
+    /// Number of manual vacuums
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vacuum_count: Option<i64>,
+
+    /// Number of autovacuum runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autovacuum_count: Option<i64>,
+
+    /// Number of manual analyzes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analyze_count: Option<i64>,
+
+    /// Number of autoanalyze runs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoanalyze_count: Option<i64>,
+
+    /// Transaction ID age of `relfrozenxid` — wraparound distance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frozen_xid_age: Option<i64>,
+
+    /// Multixact ID age of `relminmxid`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_mxid_age: Option<i64>,
+
+    /// Estimated fraction of heap pages that are bloat, in 0.0..=1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bloat_ratio: Option<f64>,
+
+    /// Size of the table's TOAST relation (including its index) in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_size_bytes: Option<i64>,
+
+    /// Per-table storage parameter overrides (e.g. `autovacuum_vacuum_scale_factor=0.01`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reloptions: Option<Vec<String>>,
+
+    /// Heap blocks read from disk (buffer cache misses)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heap_blks_read: Option<i64>,
+
+    /// Heap blocks found in the buffer cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heap_blks_hit: Option<i64>,
+
+    /// Index blocks read from disk, over all of the table's indexes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idx_blks_read: Option<i64>,
+
+    /// Index blocks found in the buffer cache, over all of the table's indexes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idx_blks_hit: Option<i64>,
+
+    /// TOAST blocks read from disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_blks_read: Option<i64>,
+
+    /// TOAST blocks found in the buffer cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_blks_hit: Option<i64>,
+
+    /// Rows on the slice with the most rows divided by rows on the slice
+    /// with the fewest; 1.0 is an even spread (Redshift)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skew_rows: Option<f64>,
+
+    /// Percentage of rows not in sort key order (Redshift)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsorted_pct: Option<f64>,
+
+    /// How stale the planner statistics are, in percent; 0 is current
+    /// (Redshift)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats_off_pct: Option<f64>,
+
+    // ---------- Elasticsearch / OpenSearch ----------
+    /// Search queries executed against the index's shards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_count: Option<i64>,
+
+    /// Time spent executing those queries
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_time_ms: Option<f64>,
+
+    /// Search queries per second since the previous collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_rate: Option<f64>,
+
+    /// Mean query time since the previous collection, or over the index's
+    /// lifetime on the first one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_latency_ms: Option<f64>,
+
+    /// Documents indexed into the index's shards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_count: Option<i64>,
+
+    /// Time spent indexing those documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_time_ms: Option<f64>,
+
+    /// Documents indexed per second since the previous collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_rate: Option<f64>,
+
+    /// Mean indexing time per document since the previous collection, or
+    /// over the index's lifetime on the first one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_latency_ms: Option<f64>,
+
+    // ---------- Vector stores ----------
+    /// Vectors already in the ANN index; the rest are searched by brute
+    /// force until the optimizer (Qdrant) or index build (Milvus) catches up
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexed_vectors: Option<i64>,
+
+    /// Segments the collection is stored in (Qdrant)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segment_count: Option<i64>,
+
+    /// Collection state as the store reports it, e.g. `green` / `yellow`
+    /// (Qdrant), `READY` / `INDEXING` (Weaviate), `LoadStateLoaded` (Milvus)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// `ok`, or the error the optimizer stopped with (Qdrant)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimizer_status: Option<String>,
+
+    // ---------- InfluxDB ----------
+    /// Series (distinct tag sets) in the measurement; each one costs index
+    /// memory whether or not it is still written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_cardinality: Option<i64>,
+
+    // ---------- Couchbase ----------
+    /// Memory the data service holds for the collection's documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_used_bytes: Option<i64>,
 }
 
 /// Index statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct IndexStats {
     /// Schema name
     pub schema: String,
@@ -214,6 +571,11 @@ pub struct IndexStats {
     /// Index name
     pub index: String,
 
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
     /// Number of index scans
     pub idx_scan: Option<i64>,
 
@@ -222,149 +584,1815 @@ pub struct IndexStats {
 
     /// Live table rows fetched by index
     pub idx_tup_fetch: Option<i64>,
-}
 
-/// Schema metadata
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SchemaMetadata {
-    /// Tables in the database
-    pub tables: Vec<TableMetadata>,
+    /// Index blocks read from disk (buffer cache misses)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idx_blks_read: Option<i64>,
 
-    /// Indexes in the database
-    pub indexes: Vec<IndexMetadata>,
+    /// Index blocks found in the buffer cache
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idx_blks_hit: Option<i64>,
 }
 
-/// Table metadata
+/// A configuration setting with its unit and provenance.
 ///
-/// `schema` and `name` form a fully-qualified identifier. For MongoDB the
-/// mapping is `(schema = database_name, name = collection_name)`.
+/// `value` is the raw value as the engine reports it, in `unit`. For memory
+/// and time settings the value is also normalized to bytes or milliseconds so
+/// consumers don't need to know that e.g. `shared_buffers` counts 8kB pages.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct TableMetadata {
-    /// Schema name (or MongoDB database name)
-    pub schema: String,
-
-    /// Table name (or MongoDB collection name)
+pub struct Setting {
+    /// Setting name
     pub name: String,
 
-    /// Column definitions (or per-field profiles for MongoDB)
-    pub columns: Vec<ColumnMetadata>,
+    /// Current value, in `unit`
+    pub value: String,
 
-    /// Estimated row count (Postgres) or document count (Mongo `collStats.count`)
+    /// Unit of `value` (e.g. `8kB`, `ms`)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub row_count_estimate: Option<i64>,
+    pub unit: Option<String>,
 
-    /// Total size in bytes
+    /// Value converted to bytes, for memory settings
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub size_bytes: Option<i64>,
+    pub value_bytes: Option<i64>,
 
-    // ---------- MongoDB-specific (None for relational sources) ----------
-    /// Number of documents actually sampled — denominator for ColumnMetadata.presence_rate
+    /// Value converted to milliseconds, for time settings
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub document_count_sampled: Option<i64>,
+    pub value_ms: Option<f64>,
 
-    /// Average document size in bytes (`collStats.avgObjSize`)
+    /// Setting category
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub avg_document_size_bytes: Option<i64>,
+    pub category: Option<String>,
 
-    /// Compressed on-disk storage size (`collStats.storageSize`)
+    /// Value type (`bool`, `integer`, `real`, `string`, `enum`)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub storage_size_bytes: Option<i64>,
+    pub value_type: Option<String>,
 
-    /// True if this is a capped collection (MongoDB)
+    /// Where the current value came from (`default`, `configuration file`, ...)
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_capped: Option<bool>,
+    pub source: Option<String>,
 
-    /// True if this is a view rather than a base collection
+    /// Configuration file the value was read from, if any
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_view: Option<bool>,
+    pub source_file: Option<String>,
 
-    /// True if this is a MongoDB time-series collection
+    /// Value assumed at server start if not otherwise set
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_timeseries: Option<bool>,
+    pub boot_value: Option<String>,
+
+    /// Value a session `RESET` would return to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_value: Option<String>,
+
+    /// True if the configuration file has changed but a restart is needed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_restart: Option<bool>,
+
+    /// True if the value is the built-in default
+    pub is_default: bool,
 }
 
-/// Column / field metadata.
+/// Database-level statistics
 ///
-/// Carries both relational column attributes and MongoDB-specific schema
-/// inference results. For Postgres, the MongoDB-only fields are `None`.
-/// For MongoDB, `name` is a dot/bracket path (e.g. `address.street`,
-/// `photos[]`, `photos[].url`) and `default` is `None`.
+/// Covers counters that belong to the database as a whole rather than to any
+/// single table. Fields are optional so each engine fills what it exposes.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct ColumnMetadata {
-    /// Column name (or dot/bracket path for MongoDB nested fields)
-    pub name: String,
+pub struct DatabaseStats {
+    /// Transaction ID age of `pg_database.datfrozenxid`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frozen_xid_age: Option<i64>,
 
-    /// Data type — single label if homogeneous, `"mixed"` for polymorphic Mongo fields
-    pub data_type: String,
+    /// Multixact ID age of `pg_database.datminmxid`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_mxid_age: Option<i64>,
 
-    /// Whether the column is nullable. For MongoDB: true if any null observed
-    /// or presence_rate < 1.0.
-    pub nullable: bool,
+    /// Committed transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xact_commit: Option<i64>,
 
-    /// Default value expression (relational only; always None for MongoDB)
+    /// Rolled back transactions
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default: Option<String>,
+    pub xact_rollback: Option<i64>,
 
-    /// Ordinal position (stable first-seen order for MongoDB)
-    pub position: i32,
+    /// Blocks read from disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blks_read: Option<i64>,
 
-    // ---------- MongoDB-specific (None for relational sources) ----------
-    /// Fraction of sampled documents containing this path, in 0.0..=1.0
+    /// Blocks found in the buffer cache
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub presence_rate: Option<f64>,
+    pub blks_hit: Option<i64>,
 
-    /// Fraction of sampled documents where this path holds an explicit BSON null
+    /// Temporary files created by queries
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub null_rate: Option<f64>,
+    pub temp_files: Option<i64>,
 
-    /// Distinct BSON type labels observed at this path
+    /// Bytes written to temporary files
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub bson_types: Option<Vec<String>>,
+    pub temp_bytes: Option<i64>,
 
-    /// Distinct value count, exact up to the per-path cap
+    /// Deadlocks detected
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub distinct_count: Option<i64>,
+    pub deadlocks: Option<i64>,
 
-    /// True if the per-path distinct cap was exceeded ("many")
+    /// Queries cancelled due to recovery conflicts on a standby
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub distinct_capped: Option<bool>,
+    pub conflicts: Option<i64>,
 
-    /// Up to N reservoir-sampled values, BSON-coerced to JSON for transport
+    /// Buffer cache hit ratio for table heap blocks, over user tables
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sample_values: Option<Vec<serde_json::Value>>,
+    pub heap_hit_ratio: Option<f64>,
 
-    /// True if this path is under a `[]` array element segment
+    /// Buffer cache hit ratio for index blocks, over user tables
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub is_array_element: Option<bool>,
+    pub index_hit_ratio: Option<f64>,
 
-    /// Maximum array length observed at any `[]` ancestor of this path
+    /// Buffer cache hit ratio for TOAST blocks, over user tables
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub array_max_len: Option<i64>,
+    pub toast_hit_ratio: Option<f64>,
+
+    /// Checkpointer and background writer activity (instance-wide)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpointer: Option<CheckpointerStats>,
+
+    /// I/O by backend type, object and context (instance-wide)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub io: Option<Vec<IoStats>>,
 }
 
-/// Index metadata
+/// Checkpointer and background writer counters
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct IndexMetadata {
-    /// Schema name
-    pub schema: String,
+pub struct CheckpointerStats {
+    /// Checkpoints started because `checkpoint_timeout` elapsed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoints_timed: Option<i64>,
+
+    /// Checkpoints requested (WAL volume, `CHECKPOINT`, shutdown, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoints_requested: Option<i64>,
+
+    /// Time spent writing checkpoint buffers, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_write_time_ms: Option<f64>,
+
+    /// Time spent syncing checkpoint files, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoint_sync_time_ms: Option<f64>,
+
+    /// Buffers written by the checkpointer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffers_checkpoint: Option<i64>,
+
+    /// Buffers written by the background writer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffers_clean: Option<i64>,
+
+    /// Times the background writer stopped because it wrote too many buffers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxwritten_clean: Option<i64>,
+
+    /// Buffers written directly by client backends
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffers_backend: Option<i64>,
+
+    /// Times a client backend had to run its own fsync
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffers_backend_fsync: Option<i64>,
+
+    /// Buffers allocated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffers_alloc: Option<i64>,
+
+    /// When these counters were last reset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats_reset: Option<DateTime<Utc>>,
+}
+
+/// I/O counters for one backend type, object and context
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IoStats {
+    /// Backend type (`client backend`, `autovacuum worker`, `checkpointer`, ...)
+    pub backend_type: String,
+
+    /// Target object (`relation`, `temp relation`)
+    pub object: String,
+
+    /// I/O context (`normal`, `vacuum`, `bulkread`, `bulkwrite`)
+    pub context: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reads: Option<i64>,
+
+    /// Read time in milliseconds (requires `track_io_timing`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_time_ms: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub writes: Option<i64>,
+
+    /// Write time in milliseconds (requires `track_io_timing`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_time_ms: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hits: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evictions: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reuses: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fsyncs: Option<i64>,
+}
+
+/// Installed extensions, roles and privileges.
+///
+/// Describes what is installed and who can do what, never credentials:
+/// password hashes and other secrets are not collected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Inventory {
+    /// Installed extensions
+    pub extensions: Vec<Extension>,
+
+    /// Roles and their attributes
+    pub roles: Vec<Role>,
+
+    /// Database-level grants
+    pub database_privileges: Vec<DatabasePrivilege>,
+}
+
+/// Installed extension
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Extension {
+    /// Extension name
+    pub name: String,
+
+    /// Installed version
+    pub version: String,
+
+    /// Schema the extension's objects live in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Version a fresh `CREATE EXTENSION` would install
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_version: Option<String>,
+
+    /// All versions available on the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_versions: Option<Vec<String>>,
+
+    /// True if `ALTER EXTENSION ... UPDATE` leads from the installed version
+    /// to `default_version`
+    pub update_available: bool,
+}
+
+/// Database role
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Role {
+    /// Role name
+    pub name: String,
+
+    /// Bypasses all permission checks
+    pub superuser: bool,
+
+    /// Inherits privileges of roles it is a member of
+    pub inherit: bool,
+
+    /// Can create and alter roles
+    pub create_role: bool,
+
+    /// Can create databases
+    pub create_db: bool,
+
+    /// Can log in
+    pub login: bool,
+
+    /// Can start streaming replication
+    pub replication: bool,
+
+    /// Bypasses row-level security
+    pub bypass_rls: bool,
+
+    /// Connection limit, -1 for unlimited
+    pub connection_limit: i32,
+
+    /// Password expiry time, if one is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+
+    /// Roles this role is a member of
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub member_of: Vec<String>,
+}
+
+/// Privilege granted on a database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabasePrivilege {
+    /// Database name
+    pub database: String,
+
+    /// Role the privilege is granted to (`PUBLIC` for everyone)
+    pub grantee: String,
+
+    /// Privilege (`CONNECT`, `CREATE`, `TEMPORARY`)
+    pub privilege: String,
+
+    /// Grantee may grant the privilege to others
+    pub is_grantable: bool,
+}
+
+/// Schema metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaMetadata {
+    /// Tables in the database
+    pub tables: Vec<TableMetadata>,
+
+    /// Indexes in the database
+    pub indexes: Vec<IndexMetadata>,
+
+    /// Views and materialized views
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<ViewMetadata>,
+
+    /// Functions and procedures
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<FunctionMetadata>,
+
+    /// User-defined triggers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<TriggerMetadata>,
+
+    /// Sequences
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequences: Vec<SequenceMetadata>,
+}
+
+/// Table metadata
+///
+/// `schema` and `name` form a fully-qualified identifier. For MongoDB the
+/// mapping is `(schema = database_name, name = collection_name)`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableMetadata {
+    /// Schema name (or MongoDB database name)
+    pub schema: String,
+
+    /// Table name (or MongoDB collection name)
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Column definitions (or per-field profiles for MongoDB)
+    pub columns: Vec<ColumnMetadata>,
+
+    /// Table constraints (primary key, unique, check, exclusion, foreign key)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<ConstraintMetadata>,
+
+    /// Estimated row count (Postgres) or document count (Mongo `collStats.count`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_count_estimate: Option<i64>,
+
+    /// Total size in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
+
+    // ---------- MongoDB-specific (None for relational sources) ----------
+    /// Number of documents actually sampled — denominator for ColumnMetadata.presence_rate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_count_sampled: Option<i64>,
+
+    /// Average document size in bytes (`collStats.avgObjSize`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_document_size_bytes: Option<i64>,
+
+    /// Compressed on-disk storage size (`collStats.storageSize`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_size_bytes: Option<i64>,
+
+    /// True if this is a capped collection (MongoDB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_capped: Option<bool>,
+
+    /// True if this is a view rather than a base collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_view: Option<bool>,
+
+    /// True if this is a MongoDB time-series collection
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_timeseries: Option<bool>,
+
+    // ---------- Postgres declarative partitioning ----------
+    /// Partitioning strategy of a partitioned table (`range`, `list`, `hash`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_strategy: Option<String>,
+
+    /// Partition key definition, e.g. `RANGE (created_at)`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_key: Option<String>,
+
+    /// Number of leaf partitions beneath a partitioned table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_count: Option<i64>,
+
+    /// True if this table's partitions were collapsed into it and are not
+    /// reported individually
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partitions_collapsed: Option<bool>,
+
+    /// Schema of the parent table, for partitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_schema: Option<String>,
+
+    /// Name of the parent table, for partitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_table: Option<String>,
+
+    /// Partition bound, e.g. `FOR VALUES FROM ('2024-01-01') TO ('2024-02-01')`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition_bound: Option<String>,
+
+    // ---------- Redshift ----------
+    /// Distribution style: `KEY`, `EVEN`, `ALL`, or `AUTO(...)` with the
+    /// style Redshift currently chose
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution_style: Option<String>,
+
+    /// Distribution key column, for `KEY` distribution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distribution_key: Option<String>,
+
+    /// Sort key columns in key order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sort_key: Vec<String>,
+
+    /// `compound` or `interleaved`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_key_style: Option<String>,
+
+    // ---------- ClickHouse ----------
+    /// Table engine, e.g. `MergeTree`, `ReplicatedReplacingMergeTree`,
+    /// `Distributed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+
+    /// Fingerprint of the table with its columns and constraints, changing
+    /// only with DDL; see `Payload::schema_version_hash`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+/// Column / field metadata.
+///
+/// Carries both relational column attributes and MongoDB-specific schema
+/// inference results. `null_rate` and `distinct_count` are shared: Postgres
+/// fills them from planner statistics (`pg_stats`), MongoDB from sampling.
+/// For Postgres, the MongoDB-only fields are `None`.
+/// For MongoDB, `name` is a dot/bracket path (e.g. `address.street`,
+/// `photos[]`, `photos[].url`) and `default` is `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ColumnMetadata {
+    /// Column name (or dot/bracket path for MongoDB nested fields)
+    pub name: String,
+
+    /// Data type — single label if homogeneous, `"mixed"` for polymorphic Mongo fields
+    pub data_type: String,
+
+    /// Whether the column is nullable. For MongoDB: true if any null observed
+    /// or presence_rate < 1.0.
+    pub nullable: bool,
+
+    /// Default value expression (relational only; always None for MongoDB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+
+    /// Ordinal position (stable first-seen order for MongoDB)
+    pub position: i32,
+
+    // ---------- MongoDB-specific (None for relational sources) ----------
+    /// Fraction of sampled documents containing this path, in 0.0..=1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presence_rate: Option<f64>,
+
+    /// Fraction of sampled documents where this path holds an explicit BSON null
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_rate: Option<f64>,
+
+    /// Distinct BSON type labels observed at this path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bson_types: Option<Vec<String>>,
+
+    /// Distinct value count, exact up to the per-path cap
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<i64>,
+
+    /// True if the per-path distinct cap was exceeded ("many")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distinct_capped: Option<bool>,
+
+    /// Up to N reservoir-sampled values, BSON-coerced to JSON for transport
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_values: Option<Vec<serde_json::Value>>,
+
+    /// True if this path is under a `[]` array element segment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_array_element: Option<bool>,
+
+    /// Maximum array length observed at any `[]` ancestor of this path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub array_max_len: Option<i64>,
+
+    // ---------- Postgres planner statistics (None elsewhere) ----------
+    /// Average stored width in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_width: Option<i32>,
+
+    /// Correlation between physical row order and column order, -1.0..=1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correlation: Option<f64>,
+
+    /// Most common values, as text. Only collected when enabled
+    /// (`collection.postgres.column_stats.most_common_values`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_common_values: Option<Vec<String>>,
+
+    /// Frequencies of `most_common_values`, as fractions of all rows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub most_common_freqs: Option<Vec<f64>>,
+
+    // ---------- Relational type details (None for MongoDB) ----------
+    /// Full engine type including modifiers, e.g. `character varying(255)`,
+    /// `numeric(10,2)`, `public.mood[]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub native_type: Option<String>,
+
+    /// Maximum length for character types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<i32>,
+
+    /// Precision for numeric types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numeric_precision: Option<i32>,
+
+    /// Scale for exact numeric types
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub numeric_scale: Option<i32>,
+
+    /// Element type for array columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element_type: Option<String>,
+
+    /// Domain the column is declared with, schema-qualified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+
+    /// Labels of the column's enum type (or array element enum type), in order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enum_labels: Option<Vec<String>>,
+
+    /// Identity generation (`ALWAYS`, `BY DEFAULT`) for identity columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+
+    /// Expression for generated columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_expression: Option<String>,
+
+    /// Collation, when it differs from the type's default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collation: Option<String>,
+
+    /// Column comment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Table constraint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConstraintMetadata {
+    /// Constraint name
+    pub name: String,
+
+    /// `primary_key`, `unique`, `check`, `exclusion` or `foreign_key`; graph
+    /// databases add `not_null` (property existence) and `property_type`
+    pub kind: String,
+
+    /// Constrained columns (empty for table-level checks on expressions)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+
+    /// Constraint clause, e.g. `CHECK ((amount > 0))`
+    pub definition: String,
+
+    /// False for constraints added `NOT VALID` and not yet validated
+    pub validated: bool,
+
+    /// Whether checking can be deferred to the end of the transaction
+    pub deferrable: bool,
+}
+
+/// Index metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexMetadata {
+    /// Schema name
+    pub schema: String,
+
+    /// Table name
+    pub table: String,
+
+    /// Index name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Columns in the index
+    pub columns: Vec<String>,
+
+    /// Whether the index enforces uniqueness
+    pub is_unique: bool,
+
+    /// Whether this is the primary key
+    pub is_primary: bool,
+
+    /// Index size in bytes
+    pub size_bytes: Option<i64>,
+
+    /// Partitioned index this index is attached to, for indexes on partitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_index: Option<String>,
+
+    /// Index type, e.g. `CLUSTERED`, `NONCLUSTERED`, `CLUSTERED COLUMNSTORE`
+    /// (SQL Server), or the data skipping index type such as `minmax` or
+    /// `bloom_filter` (ClickHouse)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_type: Option<String>,
+
+    /// Non-key columns stored in the index leaf level (`INCLUDE`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub included_columns: Vec<String>,
+
+    /// Fingerprint of the index definition, changing only with DDL; see
+    /// `Payload::schema_version_hash`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+/// View or materialized view metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ViewMetadata {
+    /// Schema name
+    pub schema: String,
+
+    /// View name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// True for a materialized view
+    pub materialized: bool,
+
+    /// View query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+
+    /// Columns, where the engine reports them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnMetadata>,
+
+    /// Storage size including indexes (materialized views only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
+
+    /// Estimated row count (materialized views only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_count_estimate: Option<i64>,
+
+    /// False if the materialized view was created `WITH NO DATA` and never
+    /// refreshed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_populated: Option<bool>,
+}
+
+/// Function or procedure metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FunctionMetadata {
+    /// Schema name
+    pub schema: String,
+
+    /// Function name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// `function`, `procedure`, `aggregate` or `window`
+    pub kind: String,
+
+    /// Implementation language (`plpgsql`, `sql`, `c`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Argument list, e.g. `customer_id integer, since date`
+    pub arguments: String,
+
+    /// Result type (functions only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+
+    /// `immutable`, `stable` or `volatile`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volatility: Option<String>,
+
+    /// Runs with the privileges of its owner
+    pub security_definer: bool,
+
+    /// Full definition, only collected when enabled
+    /// (`collection.postgres.function_bodies`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+/// Trigger metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriggerMetadata {
+    /// Schema name
+    pub schema: String,
+
+    /// Table the trigger is on
+    pub table: String,
+
+    /// Trigger name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// `BEFORE`, `AFTER` or `INSTEAD OF`
+    pub timing: String,
+
+    /// Events that fire the trigger (`INSERT`, `UPDATE`, `DELETE`, `TRUNCATE`)
+    pub events: Vec<String>,
+
+    /// `ROW` or `STATEMENT`
+    pub level: String,
+
+    /// Trigger function, schema-qualified
+    pub function: String,
+
+    /// False if the trigger is disabled
+    pub enabled: bool,
+
+    /// `CREATE TRIGGER` statement
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+/// Sequence metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SequenceMetadata {
+    /// Schema name
+    pub schema: String,
+
+    /// Sequence name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Value type (`smallint`, `integer`, `bigint`)
+    pub data_type: String,
+
+    /// Last value handed out; `None` if never used or not readable
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_value: Option<i64>,
+
+    pub min_value: i64,
+
+    pub max_value: i64,
+
+    pub increment: i64,
+
+    /// Whether the sequence wraps around at its limit
+    pub cycle: bool,
+
+    /// Share of the value range already used, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exhaustion_pct: Option<f64>,
+}
+
+/// TimescaleDB metadata.
+///
+/// Chunks are not reported as tables: their sizes and counters are folded
+/// into the hypertable's `TableMetadata`, `TableStats` and `IndexStats`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimescaleMetadata {
+    /// Extension version
+    pub version: String,
+
+    pub hypertables: Vec<Hypertable>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub continuous_aggregates: Vec<ContinuousAggregate>,
+
+    /// Background jobs: policies and user-defined actions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<TimescaleJob>,
+}
+
+/// TimescaleDB hypertable
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Hypertable {
+    /// Schema name
+    pub schema: String,
+
+    /// Hypertable name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Partitioning dimensions, time dimension first
+    pub dimensions: Vec<HypertableDimension>,
+
+    /// Number of chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_chunks: Option<i64>,
+
+    /// Number of compressed chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compressed_chunks: Option<i64>,
+
+    pub compression_enabled: bool,
+
+    /// Heap size across all chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_bytes: Option<i64>,
+
+    /// Index size across all chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_bytes: Option<i64>,
+
+    /// TOAST size across all chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_bytes: Option<i64>,
+
+    /// Total size, including compressed data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_bytes: Option<i64>,
+
+    /// Size of the compressed chunks before compression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_compression_bytes: Option<i64>,
+
+    /// Size of the compressed chunks after compression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_compression_bytes: Option<i64>,
+
+    /// `before_compression_bytes / after_compression_bytes`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compression_ratio: Option<f64>,
+}
+
+/// Hypertable partitioning dimension
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HypertableDimension {
+    /// Partitioning column
+    pub column: String,
+
+    /// Column type
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column_type: Option<String>,
+
+    /// `time` or `space`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimension_type: Option<String>,
+
+    /// Chunk interval of a time dimension on a timestamp column, e.g. `7 days`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_interval: Option<String>,
+
+    /// Chunk interval of a time dimension on an integer column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub integer_interval: Option<i64>,
+
+    /// Number of hash partitions of a space dimension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_partitions: Option<i32>,
+}
+
+/// TimescaleDB continuous aggregate
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContinuousAggregate {
+    /// Schema name
+    pub schema: String,
+
+    /// View name
+    pub name: String,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Schema of the source hypertable
+    pub hypertable_schema: String,
+
+    /// Source hypertable
+    pub hypertable_name: String,
+
+    /// True if queries only return materialized data (no real-time
+    /// aggregation)
+    pub materialized_only: bool,
+
+    pub compression_enabled: bool,
+
+    /// Internal hypertable holding the materialized data, schema-qualified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub materialization_hypertable: Option<String>,
+
+    /// View query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub definition: Option<String>,
+}
+
+/// TimescaleDB background job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimescaleJob {
+    pub id: i32,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// e.g. `Compression Policy [1002]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_name: Option<String>,
+
+    /// Procedure the job runs, schema-qualified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub procedure: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule_interval: Option<String>,
+
+    /// False if the job is paused
+    pub scheduled: bool,
+
+    /// Schema of the hypertable the job works on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hypertable_schema: Option<String>,
+
+    /// Hypertable the job works on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hypertable_name: Option<String>,
+
+    /// Job configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config: Option<serde_json::Value>,
+
+    /// `Scheduled`, `Running` or `Paused`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_status: Option<String>,
+
+    /// `Success` or `Failed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_status: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_started_at: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_successful_finish: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run_duration_ms: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_start: Option<DateTime<Utc>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_runs: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_successes: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_failures: Option<i64>,
+
+    /// Message of the most recent failure (TimescaleDB 2.15+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+/// pgvector metadata, for databases with the `vector` extension.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PgvectorMetadata {
+    /// Extension version
+    pub version: String,
+
+    pub columns: Vec<VectorColumn>,
+
+    /// HNSW and IVFFlat indexes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<VectorIndex>,
+
+    /// `hnsw.*` and `ivfflat.*` settings, plus the server settings that
+    /// bound index build memory and parallelism
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub settings: Vec<Setting>,
+}
+
+/// Column of a pgvector type
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VectorColumn {
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    pub schema: String,
+
+    pub table: String,
+
+    pub column: String,
+
+    /// `vector`, `halfvec` or `sparsevec`; vector stores also report `bit`
+    /// (binary) and `uint8vec` / `int8vec` (byte vectors)
+    pub vector_type: String,
+
+    /// Declared dimensions; `None` if the column accepts any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<i32>,
+
+    pub nullable: bool,
+
+    /// Names of the HNSW/IVFFlat indexes on the column
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<String>,
+}
+
+/// Approximate nearest neighbour index
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VectorIndex {
+    /// Database name, set when several databases are collected from one
+    /// cluster (Postgres `collection.postgres.databases`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    pub schema: String,
+
+    pub table: String,
+
+    pub name: String,
+
+    /// `hnsw` or `ivfflat`; vector stores report their own index types,
+    /// lowercased, e.g. `flat`, `ivf_pq` or `diskann`
+    pub method: String,
+
+    /// Indexed column or expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+
+    /// e.g. `vector_cosine_ops`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator_class: Option<String>,
+
+    /// Distance the index serves: `l2`, `inner_product`, `cosine`, `l1`,
+    /// `hamming` or `jaccard`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<String>,
+
+    /// HNSW connections per layer, including pgvector's default when the
+    /// index does not set it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub m: Option<i32>,
+
+    /// HNSW candidate list size during build, with pgvector's default
+    /// applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ef_construction: Option<i32>,
+
+    /// IVFFlat inverted lists, with pgvector's default applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lists: Option<i32>,
+
+    /// Vector compression applied to the index, e.g. `scalar_int8`,
+    /// `product_x16` or `binary` (vector stores)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quantization: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
+
+    /// Index scans since statistics were last reset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub idx_scan: Option<i64>,
+
+    /// Estimated rows in the table, for sizing `lists` and `m`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_rows: Option<i64>,
+}
+
+/// How a distributed SQL cluster splits its data and where the pieces live.
+///
+/// CockroachDB splits tables into ranges, each replicated to several nodes
+/// with one leaseholder serving reads; YugabyteDB does the same with tablets
+/// and tablet leaders.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    /// Unit of placement: `range` or `tablet`
+    pub unit: String,
+
+    /// Number of units in the cluster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_units: Option<i64>,
+
+    pub nodes: Vec<DistributionNode>,
+
+    /// Units per table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<TableDistribution>,
+}
+
+/// A node of a distributed SQL cluster
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DistributionNode {
+    /// Node ID (CockroachDB) or `host:port` (YugabyteDB)
+    pub node: String,
+
+    /// Address clients and peers reach the node on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// Where the node runs, e.g. `region=us-east1,zone=us-east1-b`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locality: Option<String>,
+
+    /// `primary` or `read_replica` (YugabyteDB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<String>,
+
+    /// Replicas of units stored on the node
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<i64>,
+
+    /// Units the node holds the lease (leadership) for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leaders: Option<i64>,
+
+    /// Data stored on the node across its replicas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<i64>,
+
+    /// Open client connections (YugabyteDB)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connections: Option<i64>,
+}
+
+/// Units of a single table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableDistribution {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    pub schema: String,
+
+    pub table: String,
+
+    /// Number of units. For YugabyteDB this counts the tablets with a
+    /// replica on the node the agent is connected to.
+    pub units: i64,
+}
+
+/// Redshift metadata that has no Postgres counterpart.
+///
+/// Table sizes, skew and sort state are reported through `TableMetadata`
+/// and `TableStats`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RedshiftMetadata {
+    /// User-visible WLM queues (service classes 5 and up)
+    pub wlm_queues: Vec<WlmQueue>,
+}
+
+/// Redshift workload management queue (service class) with its current load
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WlmQueue {
+    pub service_class: i32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Concurrency slots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slots: Option<i32>,
+
+    /// Working memory per slot, in MB
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_mem_mb: Option<i32>,
+
+    /// Queries are cancelled after this long; 0 means no limit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_execution_time_ms: Option<i64>,
+
+    /// `auto` or `off`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency_scaling: Option<String>,
+
+    /// Queue priority under automatic WLM, e.g. `normal`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+
+    /// Queries waiting for a slot right now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued: Option<i64>,
+
+    /// Queries running right now
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executing: Option<i64>,
+
+    /// Queries executed since the cluster started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executed_total: Option<i64>,
+
+    /// Longest wait of a currently queued query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_queue_time_ms: Option<f64>,
+}
+
+/// SQL Server metadata that has no Postgres counterpart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SqlServerMetadata {
+    /// Query Store state of the collected database (`READ_WRITE`,
+    /// `READ_ONLY`, `OFF`, ...); `None` before SQL Server 2016. Query stats
+    /// come from the Query Store when it is readable and from
+    /// `sys.dm_exec_query_stats` otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query_store_state: Option<String>,
+
+    /// Indexes the optimizer reported missing since the last restart
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_indexes: Vec<MissingIndex>,
+}
+
+/// Index suggested by the SQL Server missing index DMVs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MissingIndex {
+    pub schema: String,
 
-    /// Table name
     pub table: String,
 
-    /// Index name
+    /// Columns used in equality predicates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equality_columns: Vec<String>,
+
+    /// Columns used in range and other non-equality predicates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inequality_columns: Vec<String>,
+
+    /// Columns the queries read that would cover them as included columns
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub included_columns: Vec<String>,
+
+    /// Seeks the index would have served
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_seeks: Option<i64>,
+
+    /// Scans the index would have served
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_scans: Option<i64>,
+
+    /// Average cost of the queries that would benefit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_total_user_cost: Option<f64>,
+
+    /// Estimated reduction of that cost, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_user_impact: Option<f64>,
+}
+
+/// ClickHouse metadata that has no Postgres counterpart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClickHouseMetadata {
+    /// Active data parts per MergeTree table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parts: Vec<TableParts>,
+
+    /// State of each replicated table on the collected server
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replicas: Vec<ReplicaStatus>,
+}
+
+/// Active parts of a MergeTree table, from `system.parts`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableParts {
+    pub database: String,
+
+    pub table: String,
+
+    pub active_parts: i64,
+
+    pub partitions: i64,
+
+    /// Active parts in the partition that has the most
+    pub max_parts_in_partition: i64,
+
+    pub rows: i64,
+
+    pub bytes_on_disk: i64,
+
+    pub data_compressed_bytes: i64,
+
+    pub data_uncompressed_bytes: i64,
+
+    /// Merges running at collection time
+    pub merges_in_progress: i64,
+
+    /// `max_parts_in_partition` relative to `parts_to_throw_insert`. Inserts
+    /// are slowed down well before 1.0 (`parts_to_delay_insert`) and fail
+    /// with `TOO_MANY_PARTS` at 1.0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_pressure: Option<f64>,
+}
+
+/// Replication state of a table, from `system.replicas`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicaStatus {
+    pub database: String,
+
+    pub table: String,
+
+    pub is_leader: bool,
+
+    /// Read-only, typically after losing the (Zoo)Keeper session
+    pub is_readonly: bool,
+
+    /// Seconds the replica is behind the freshest part it knows of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absolute_delay_s: Option<i64>,
+
+    /// Replication log entries not yet fetched into the queue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_lag: Option<i64>,
+
+    /// Fetches, merges and mutations waiting in the replication queue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_size: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inserts_in_queue: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merges_in_queue: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_replicas: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_replicas: Option<i64>,
+}
+
+/// Elasticsearch / OpenSearch metadata that has no Postgres counterpart
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElasticsearchMetadata {
+    /// Cluster health from `_cluster/health`
+    pub health: ClusterHealth,
+
+    /// Health and shard layout of each index
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indices: Vec<IndexHealth>,
+
+    /// Shards that are not allocated to any node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unassigned_shards: Vec<UnassignedShard>,
+
+    /// Resource usage of each node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<NodeStats>,
+}
+
+/// Cluster health, from `_cluster/health`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClusterHealth {
+    pub cluster_name: String,
+
+    /// `green`, `yellow` or `red`
+    pub status: String,
+
+    pub number_of_nodes: i64,
+
+    pub number_of_data_nodes: i64,
+
+    pub active_primary_shards: i64,
+
+    pub active_shards: i64,
+
+    pub relocating_shards: i64,
+
+    pub initializing_shards: i64,
+
+    pub unassigned_shards: i64,
+
+    /// Unassigned shards whose allocation waits for a node to return
+    pub delayed_unassigned_shards: i64,
+
+    pub number_of_pending_tasks: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_shards_percent: Option<f64>,
+}
+
+/// Health and shard layout of an index, from `_cat/indices` and `_cat/shards`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexHealth {
+    pub index: String,
+
+    /// `green`, `yellow` or `red`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<String>,
+
+    /// `open` or `close`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_shards: Option<i64>,
+
+    /// Replicas per primary shard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<i64>,
+
+    /// Deleted documents not yet merged away
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_docs: Option<i64>,
+
+    /// Size of all copies, primaries and replicas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store_size_bytes: Option<i64>,
+
+    /// Size of the largest primary shard
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_shard_size_bytes: Option<i64>,
+
+    pub unassigned_shards: i64,
+}
+
+/// A shard copy that is not allocated, from `_cat/shards`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnassignedShard {
+    pub index: String,
+
+    pub shard: i64,
+
+    pub primary: bool,
+
+    /// Why the shard became unassigned, e.g. `NODE_LEFT`, `ALLOCATION_FAILED`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Resource usage of a node, from `_nodes/stats`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeStats {
     pub name: String,
 
-    /// Columns in the index
-    pub columns: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
 
-    /// Whether the index enforces uniqueness
-    pub is_unique: bool,
+    /// Node roles, e.g. `master`, `data_hot`, `ingest`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
 
-    /// Whether this is the primary key
-    pub is_primary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heap_used_percent: Option<f64>,
 
-    /// Index size in bytes
-    pub size_bytes: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heap_max_bytes: Option<i64>,
+
+    /// Old generation garbage collections since the node started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gc_old_count: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gc_old_time_ms: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_percent: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average_1m: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_total_bytes: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_available_bytes: Option<i64>,
+
+    /// Search requests rejected because the thread pool queue was full
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_rejected: Option<i64>,
+
+    /// Write requests rejected because the thread pool queue was full
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_rejected: Option<i64>,
+}
+
+/// Vector store metadata. Collections themselves are reported as tables,
+/// with their vector fields among the columns; this section carries what
+/// the table shape has no room for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VectorStoreMetadata {
+    /// Vector fields, with their dimensions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<VectorColumn>,
+
+    /// ANN indexes with their build parameters and quantization
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<VectorIndex>,
+}
+
+/// InfluxDB metadata that has no Postgres counterpart.
+///
+/// Buckets (InfluxDB 1.x databases) are reported as schemas and their
+/// measurements as tables, with tags and fields as columns.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InfluxdbMetadata {
+    /// API the agent read the server through: `v2`, or `v1` (InfluxQL)
+    pub api: String,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<InfluxBucket>,
+
+    /// Storage, query and runtime metrics from the `/metrics` endpoint,
+    /// summed per bucket where the metric has one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub metrics: Vec<InfluxMetric>,
+}
+
+/// An InfluxDB 2.x bucket, or an InfluxDB 1.x database with its default
+/// retention policy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InfluxBucket {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+
+    /// `user` or `system` (`_monitoring`, `_tasks`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_type: Option<String>,
+
+    /// Retention policy name (InfluxDB 1.x)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention_policy: Option<String>,
+
+    /// How long data is kept; `None` is forever
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention_seconds: Option<i64>,
+
+    /// Time range covered by each shard group
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_group_duration_seconds: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub measurement_count: Option<i64>,
+
+    /// Series across all measurements of the bucket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series_cardinality: Option<i64>,
+}
+
+/// One metric from the Prometheus `/metrics` endpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InfluxMetric {
+    /// Metric name, e.g. `storage_bucket_series_num`
+    pub name: String,
+
+    /// Bucket ID the value belongs to; `None` for server-wide metrics
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket: Option<String>,
+
+    pub value: f64,
+}
+
+/// Couchbase metadata that has no Postgres counterpart.
+///
+/// Collections are reported as tables, with the bucket as `database` and
+/// the scope as `schema`; GSI index definitions are in the schema's
+/// indexes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CouchbaseMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub buckets: Vec<CouchbaseBucket>,
+
+    /// State and last use of each index, from `system:indexes`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<CouchbaseIndex>,
+}
+
+/// A Couchbase bucket with its quota and usage, from
+/// `/pools/default/buckets`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CouchbaseBucket {
+    pub name: String,
+
+    /// `couchbase`, `ephemeral` or `memcached`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_type: Option<String>,
+
+    /// `couchstore` or `magma`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_backend: Option<String>,
+
+    /// `valueOnly` or `fullEviction` (`noEviction` / `nruEviction` for
+    /// ephemeral buckets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eviction_policy: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<i64>,
+
+    /// Memory quota across all nodes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ram_quota_bytes: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_count: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mem_used_bytes: Option<i64>,
+
+    /// Share of the memory quota in use, in percent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota_percent_used: Option<f64>,
+
+    /// Data and index files on disk, including fragmentation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_used_bytes: Option<i64>,
+
+    /// Data files on disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_used_bytes: Option<i64>,
+
+    /// Share of active items held in memory, in percent; reads of the rest
+    /// go to disk
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resident_ratio: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ops_per_sec: Option<f64>,
+}
+
+/// A GSI or search index, from `system:indexes`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CouchbaseIndex {
+    pub bucket: String,
+
+    pub scope: String,
+
+    pub collection: String,
+
+    pub name: String,
+
+    /// `online`, `deferred`, `building`, `pending` or `offline`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    /// Partial index predicate (`WHERE` clause)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /// Partition key expression, for partitioned indexes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partition: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<i64>,
+
+    /// Last time a query scanned the index (Couchbase 7.1+)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_scan_time: Option<DateTime<Utc>>,
+}
+
+/// Oracle-specific metadata
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OracleMetadata {
+    /// Non-idle wait events since instance startup, by total time waited
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wait_events: Vec<OracleWaitEvent>,
+}
+
+/// A wait event from `V$SYSTEM_EVENT`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OracleWaitEvent {
+    /// Event name, e.g. `db file sequential read`
+    pub event: String,
+
+    /// `User I/O`, `Concurrency`, `Commit`, ...
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wait_class: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_waits: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_timeouts: Option<i64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_waited_ms: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub average_wait_ms: Option<f64>,
+}
+
+/// Kind of schema object a [`SchemaChange`] refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaObjectKind {
+    Table,
+    Column,
+    Constraint,
+    Index,
+    View,
+    Function,
+    Trigger,
+    Sequence,
+}
+
+/// What happened to a schema object between two uploads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaChangeAction {
+    Added,
+    Dropped,
+    Modified,
+}
+
+/// A single schema change event, e.g. a dropped index or a column whose
+/// type changed.
+///
+/// `before` and `after` hold the object as it appears in [`SchemaMetadata`],
+/// without volatile statistics (sizes, row estimates, column statistics).
+/// Table events do not include columns or constraints, which get their own
+/// events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaChange {
+    pub object: SchemaObjectKind,
+
+    pub action: SchemaChangeAction,
+
+    /// Database name, set when several databases are collected from one
+    /// cluster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub database: Option<String>,
+
+    /// Schema name
+    pub schema: String,
+
+    /// Table (or view) the object belongs to, for columns, constraints,
+    /// indexes and triggers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+
+    /// Object name. Functions include their argument list, e.g.
+    /// `refresh(since date)`, to tell overloads apart.
+    pub name: String,
+
+    /// Top-level fields that differ, for `modified` events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_fields: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<serde_json::Value>,
 }
 
 /// Generate a stable instance ID from connection info
@@ -421,4 +2449,25 @@ mod tests {
         assert!(payload.settings.is_some());
         assert!(payload.query_stats.is_none());
     }
+
+    #[test]
+    fn test_vacuum_health_fields_skipped_when_absent() {
+        let stats = TableStats {
+            schema: "public".to_string(),
+            table: "orders".to_string(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(!json.contains("frozen_xid_age"));
+        assert!(!json.contains("bloat_ratio"));
+
+        let stats = TableStats {
+            frozen_xid_age: Some(150_000_000),
+            reloptions: Some(vec!["autovacuum_enabled=false".to_string()]),
+            ..stats
+        };
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.contains("\"frozen_xid_age\":150000000"));
+        assert!(json.contains("autovacuum_enabled=false"));
+    }
 }
@@ -0,0 +1,490 @@
+//! Schema fingerprinting and change detection.
+//!
+//! A [`SchemaFingerprint`] is a normalized, per-object view of a
+//! [`SchemaMetadata`]: every table, column, constraint, index, view, function,
+//! trigger and sequence keyed by its qualified name, with volatile statistics
+//! (sizes, row estimates, column statistics, sequence positions) removed.
+//! Two fingerprints of an unchanged schema hash the same regardless of how
+//! the data in it has moved, and [`SchemaFingerprint::diff`] turns two
+//! fingerprints into [`SchemaChange`] events.
+//!
+//! Columns inferred from sampled documents (MongoDB, Couchbase, paths inside
+//! Postgres `jsonb` columns) are left out: which paths a sample sees, and
+//! their types, positions and nullability, vary from one sample to the next
+//! without any DDL.
+
+use super::{ColumnMetadata, SchemaChange, SchemaChangeAction, SchemaMetadata, SchemaObjectKind};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// Table fields that change without DDL. Columns and constraints are
+/// fingerprinted as objects of their own.
+const TABLE_VOLATILE: &[&str] = &[
+    "fingerprint",
+    "columns",
+    "constraints",
+    "row_count_estimate",
+    "size_bytes",
+    "document_count_sampled",
+    "avg_document_size_bytes",
+    "storage_size_bytes",
+    "partition_count",
+];
+
+/// Column statistics, from the planner or from document sampling
+const COLUMN_VOLATILE: &[&str] = &[
+    "presence_rate",
+    "null_rate",
+    "distinct_count",
+    "distinct_capped",
+    "sample_values",
+    "array_max_len",
+    "avg_width",
+    "correlation",
+    "most_common_values",
+    "most_common_freqs",
+];
+
+const INDEX_VOLATILE: &[&str] = &["fingerprint", "size_bytes"];
+
+const VIEW_VOLATILE: &[&str] = &["size_bytes", "row_count_estimate", "is_populated"];
+
+const SEQUENCE_VOLATILE: &[&str] = &["last_value", "exhaustion_pct"];
+
+/// Identity of a schema object across collections
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct ObjectKey {
+    database: Option<String>,
+    schema: String,
+    table: Option<String>,
+    kind: SchemaObjectKind,
+    name: String,
+}
+
+/// Database, schema and name of a table
+type TableKey<'a> = (&'a Option<String>, &'a str, &'a str);
+
+/// Normalized snapshot of a schema, see the module docs.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaFingerprint {
+    hash: String,
+    objects: BTreeMap<ObjectKey, Value>,
+}
+
+impl SchemaFingerprint {
+    pub fn new(schema: &SchemaMetadata) -> Self {
+        let mut objects = BTreeMap::new();
+        let mut add = |kind, database: &Option<String>, schema: &str, table, name, value| {
+            let key = ObjectKey {
+                database: database.clone(),
+                schema: schema.to_string(),
+                table,
+                kind,
+                name,
+            };
+            objects.insert(key, value);
+        };
+
+        for t in &schema.tables {
+            add(
+                SchemaObjectKind::Table,
+                &t.database,
+                &t.schema,
+                None,
+                t.name.clone(),
+                normalized(t, TABLE_VOLATILE),
+            );
+            for c in t.columns.iter().filter(|c| !is_sampled(c)) {
+                add(
+                    SchemaObjectKind::Column,
+                    &t.database,
+                    &t.schema,
+                    Some(t.name.clone()),
+                    c.name.clone(),
+                    normalized(c, COLUMN_VOLATILE),
+                );
+            }
+            for c in &t.constraints {
+                add(
+                    SchemaObjectKind::Constraint,
+                    &t.database,
+                    &t.schema,
+                    Some(t.name.clone()),
+                    c.name.clone(),
+                    normalized(c, &[]),
+                );
+            }
+        }
+        for i in &schema.indexes {
+            add(
+                SchemaObjectKind::Index,
+                &i.database,
+                &i.schema,
+                Some(i.table.clone()),
+                i.name.clone(),
+                normalized(i, INDEX_VOLATILE),
+            );
+        }
+        for v in &schema.views {
+            let mut value = normalized(v, VIEW_VOLATILE);
+            if let Some(Value::Array(columns)) = value.get_mut("columns") {
+                for column in columns {
+                    strip(column, COLUMN_VOLATILE);
+                }
+            }
+            add(
+                SchemaObjectKind::View,
+                &v.database,
+                &v.schema,
+                None,
+                v.name.clone(),
+                value,
+            );
+        }
+        for f in &schema.functions {
+            add(
+                SchemaObjectKind::Function,
+                &f.database,
+                &f.schema,
+                None,
+                format!("{}({})", f.name, f.arguments),
+                normalized(f, &[]),
+            );
+        }
+        for t in &schema.triggers {
+            add(
+                SchemaObjectKind::Trigger,
+                &t.database,
+                &t.schema,
+                Some(t.table.clone()),
+                t.name.clone(),
+                normalized(t, &[]),
+            );
+        }
+        for s in &schema.sequences {
+            add(
+                SchemaObjectKind::Sequence,
+                &s.database,
+                &s.schema,
+                None,
+                s.name.clone(),
+                normalized(s, SEQUENCE_VOLATILE),
+            );
+        }
+
+        let hash = digest(&objects);
+        Self { hash, objects }
+    }
+
+    /// Hex digest identifying this schema version
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Set the per-object fingerprint of every table (with its columns and
+    /// constraints) and index of `schema`, which this fingerprint was taken
+    /// of.
+    pub fn stamp(&self, schema: &mut SchemaMetadata) {
+        // Objects iterate in key order, so each table's are hashed in a
+        // stable order
+        let mut tables: BTreeMap<TableKey, Vec<(&ObjectKey, &Value)>> = BTreeMap::new();
+        for (key, value) in &self.objects {
+            let table = match key.kind {
+                SchemaObjectKind::Table => &key.name,
+                SchemaObjectKind::Column | SchemaObjectKind::Constraint => match &key.table {
+                    Some(table) => table,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            tables
+                .entry((&key.database, &key.schema, table))
+                .or_default()
+                .push((key, value));
+        }
+
+        for t in &mut schema.tables {
+            t.fingerprint = tables
+                .get(&(&t.database, t.schema.as_str(), t.name.as_str()))
+                .map(|objects| digest(objects.iter().copied()));
+        }
+        for i in &mut schema.indexes {
+            let key = ObjectKey {
+                database: i.database.clone(),
+                schema: i.schema.clone(),
+                table: Some(i.table.clone()),
+                kind: SchemaObjectKind::Index,
+                name: i.name.clone(),
+            };
+            i.fingerprint = self
+                .objects
+                .get_key_value(&key)
+                .map(|(key, value)| digest([(key, value)]));
+        }
+    }
+
+    /// Change events that turn `self` into `newer`, ordered by database and
+    /// schema, with schema-level objects (tables, views, functions,
+    /// sequences) ahead of the columns, constraints, indexes and triggers of
+    /// each table.
+    pub fn diff(&self, newer: &SchemaFingerprint) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+
+        for (key, before) in &self.objects {
+            match newer.objects.get(key) {
+                None => changes.push((
+                    key,
+                    change(key, SchemaChangeAction::Dropped, Some(before), None),
+                )),
+                Some(after) if after != before => {
+                    let mut event =
+                        change(key, SchemaChangeAction::Modified, Some(before), Some(after));
+                    event.changed_fields = changed_fields(before, after);
+                    changes.push((key, event));
+                }
+                Some(_) => {}
+            }
+        }
+        for (key, after) in &newer.objects {
+            if !self.objects.contains_key(key) {
+                changes.push((
+                    key,
+                    change(key, SchemaChangeAction::Added, None, Some(after)),
+                ));
+            }
+        }
+
+        changes.sort_by_key(|(key, _)| *key);
+        changes.into_iter().map(|(_, event)| event).collect()
+    }
+}
+
+/// Hex digest of normalized objects, in key order
+fn digest<'a>(objects: impl IntoIterator<Item = (&'a ObjectKey, &'a Value)>) -> String {
+    let mut hasher = Sha256::new();
+    for (key, value) in objects {
+        hasher.update(format!("{key:?}").as_bytes());
+        hasher.update(value.to_string().as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(&hasher.finalize()[..16])
+}
+
+fn change(
+    key: &ObjectKey,
+    action: SchemaChangeAction,
+    before: Option<&Value>,
+    after: Option<&Value>,
+) -> SchemaChange {
+    SchemaChange {
+        object: key.kind,
+        action,
+        database: key.database.clone(),
+        schema: key.schema.clone(),
+        table: key.table.clone(),
+        name: key.name.clone(),
+        changed_fields: Vec::new(),
+        before: before.cloned(),
+        after: after.cloned(),
+    }
+}
+
+/// Top-level fields whose values differ between two objects
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    let (Value::Object(before), Value::Object(after)) = (before, after) else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = before
+        .keys()
+        .chain(after.keys().filter(|k| !before.contains_key(*k)))
+        .filter(|k| before.get(*k) != after.get(*k))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields
+}
+
+/// True for columns inferred from sampled documents rather than declared,
+/// which are the ones carrying a presence rate
+fn is_sampled(column: &ColumnMetadata) -> bool {
+    column.presence_rate.is_some()
+}
+
+fn normalized<T: Serialize>(item: &T, volatile: &[&str]) -> Value {
+    let mut value = serde_json::to_value(item).unwrap_or(Value::Null);
+    strip(&mut value, volatile);
+    value
+}
+
+fn strip(value: &mut Value, fields: &[&str]) {
+    if let Value::Object(map) = value {
+        for field in fields {
+            map.remove(*field);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::{IndexMetadata, SequenceMetadata, TableMetadata};
+
+    fn schema() -> SchemaMetadata {
+        SchemaMetadata {
+            tables: vec![TableMetadata {
+                schema: "public".to_string(),
+                name: "orders".to_string(),
+                columns: vec![ColumnMetadata {
+                    name: "amount".to_string(),
+                    data_type: "integer".to_string(),
+                    position: 1,
+                    null_rate: Some(0.1),
+                    ..Default::default()
+                }],
+                row_count_estimate: Some(100),
+                size_bytes: Some(8192),
+                ..Default::default()
+            }],
+            indexes: vec![IndexMetadata {
+                schema: "public".to_string(),
+                table: "orders".to_string(),
+                name: "orders_amount_idx".to_string(),
+                columns: vec!["amount".to_string()],
+                size_bytes: Some(8192),
+                ..Default::default()
+            }],
+            sequences: vec![SequenceMetadata {
+                schema: "public".to_string(),
+                name: "orders_id_seq".to_string(),
+                data_type: "bigint".to_string(),
+                last_value: Some(10),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_volatile_fields_do_not_change_hash() {
+        let before = SchemaFingerprint::new(&schema());
+
+        let mut grown = schema();
+        grown.tables[0].row_count_estimate = Some(1_000_000);
+        grown.tables[0].size_bytes = Some(1 << 30);
+        grown.tables[0].columns[0].null_rate = Some(0.5);
+        grown.indexes[0].size_bytes = Some(1 << 20);
+        grown.sequences[0].last_value = Some(1_000_000);
+        let after = SchemaFingerprint::new(&grown);
+
+        assert_eq!(before.hash(), after.hash());
+        assert!(before.diff(&after).is_empty());
+    }
+
+    #[test]
+    fn test_diff_reports_structural_changes() {
+        let before = SchemaFingerprint::new(&schema());
+
+        let mut changed = schema();
+        changed.tables[0].columns[0].data_type = "bigint".to_string();
+        changed.indexes.clear();
+        changed.tables.push(TableMetadata {
+            schema: "public".to_string(),
+            name: "refunds".to_string(),
+            ..Default::default()
+        });
+        let after = SchemaFingerprint::new(&changed);
+        assert_ne!(before.hash(), after.hash());
+
+        let changes = before.diff(&after);
+        assert_eq!(changes.len(), 3);
+
+        // Schema-level objects sort ahead of the objects that belong to a table
+        let table = &changes[0];
+        assert_eq!(table.object, SchemaObjectKind::Table);
+        assert_eq!(table.action, SchemaChangeAction::Added);
+        assert_eq!(table.name, "refunds");
+        assert!(table.after.as_ref().unwrap().get("columns").is_none());
+
+        let column = &changes[1];
+        assert_eq!(column.object, SchemaObjectKind::Column);
+        assert_eq!(column.action, SchemaChangeAction::Modified);
+        assert_eq!(column.table.as_deref(), Some("orders"));
+        assert_eq!(column.changed_fields, vec!["data_type"]);
+        assert_eq!(column.before.as_ref().unwrap()["data_type"], "integer");
+        assert_eq!(column.after.as_ref().unwrap()["data_type"], "bigint");
+        assert!(column.after.as_ref().unwrap().get("null_rate").is_none());
+
+        let index = &changes[2];
+        assert_eq!(index.object, SchemaObjectKind::Index);
+        assert_eq!(index.action, SchemaChangeAction::Dropped);
+        assert!(index.after.is_none());
+    }
+
+    #[test]
+    fn test_sampled_columns_do_not_change_hash() {
+        let sample = |columns: &[(&str, &str, bool)]| SchemaMetadata {
+            tables: vec![TableMetadata {
+                schema: "shop".to_string(),
+                name: "users".to_string(),
+                columns: columns
+                    .iter()
+                    .zip(1..)
+                    .map(|((name, data_type, nullable), position)| ColumnMetadata {
+                        name: name.to_string(),
+                        data_type: data_type.to_string(),
+                        nullable: *nullable,
+                        position,
+                        presence_rate: Some(if *nullable { 0.5 } else { 1.0 }),
+                        bson_types: Some(vec![data_type.to_string()]),
+                        ..Default::default()
+                    })
+                    .collect(),
+                document_count_sampled: Some(100),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        // Two samples of the same collection: another field seen, another
+        // order, another type mix
+        let first = SchemaFingerprint::new(&sample(&[
+            ("_id", "objectId", false),
+            ("age", "int", false),
+            ("email", "string", true),
+        ]));
+        let second = SchemaFingerprint::new(&sample(&[
+            ("_id", "objectId", false),
+            ("email", "string", false),
+            ("age", "mixed", true),
+            ("photos[]", "object", true),
+        ]));
+
+        assert_eq!(first.hash(), second.hash());
+        assert!(first.diff(&second).is_empty());
+    }
+
+    #[test]
+    fn test_stamp_per_object_fingerprints() {
+        let mut before = schema();
+        SchemaFingerprint::new(&before).stamp(&mut before);
+        let table = before.tables[0].fingerprint.clone().unwrap();
+        let index = before.indexes[0].fingerprint.clone().unwrap();
+        assert_ne!(table, index);
+
+        // A column change moves its table's fingerprint only
+        let mut changed = schema();
+        changed.tables[0].columns[0].data_type = "bigint".to_string();
+        changed.tables[0].size_bytes = Some(1 << 30);
+        SchemaFingerprint::new(&changed).stamp(&mut changed);
+        assert_ne!(changed.tables[0].fingerprint.as_ref(), Some(&table));
+        assert_eq!(changed.indexes[0].fingerprint.as_ref(), Some(&index));
+
+        // Stamped fingerprints don't feed back into the schema hash
+        assert_eq!(
+            SchemaFingerprint::new(&before).hash(),
+            SchemaFingerprint::new(&schema()).hash()
+        );
+    }
+}
@@ -2,9 +2,13 @@
 //!
 //! Manages periodic collection and upload of database metrics.
 
+mod schema_tracker;
+
 use crate::collector::{Collector, CollectorError};
+use crate::config::SchemaChangesConfig;
 use crate::health::SharedHealthState;
 use crate::uploader::{Upload, UploaderError};
+use schema_tracker::SchemaTracker;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
@@ -40,6 +44,7 @@ pub struct Scheduler {
     shutdown_rx: watch::Receiver<bool>,
     health_state: Option<SharedHealthState>,
     start_time: std::time::Instant,
+    schema_tracker: SchemaTracker,
 }
 
 impl Scheduler {
@@ -58,9 +63,16 @@ impl Scheduler {
             shutdown_rx,
             health_state,
             start_time: std::time::Instant::now(),
+            schema_tracker: SchemaTracker::new(&SchemaChangesConfig::default()),
         }
     }
 
+    /// Configure schema change tracking (off by default; see `SchemaChangesConfig`)
+    pub fn with_schema_changes(mut self, config: &SchemaChangesConfig) -> Self {
+        self.schema_tracker = SchemaTracker::new(config);
+        self
+    }
+
     /// Run the scheduler loop
     ///
     /// This will collect and upload metrics at the configured interval
@@ -154,15 +166,20 @@ impl Scheduler {
         let start = std::time::Instant::now();
 
         match self.collector.collect().await {
-            Ok(payload) => {
+            Ok(mut payload) => {
                 let collection_time = start.elapsed();
                 debug!(
                     duration_ms = collection_time.as_millis(),
                     "Metrics collection completed"
                 );
 
+                let pending_schema = self.schema_tracker.prepare(&mut payload);
+
                 match self.uploader.upload(&payload).await {
                     Ok(()) => {
+                        if let Some(pending) = pending_schema {
+                            self.schema_tracker.commit(pending);
+                        }
                         let total_time = start.elapsed();
                         info!(
                             collection_ms = collection_time.as_millis(),
@@ -200,7 +217,8 @@ impl Scheduler {
     pub async fn run_once(&self) -> Result<(), SchedulerError> {
         info!("Running single metrics collection (dry-run mode)");
 
-        let payload = self.collector.collect().await?;
+        let mut payload = self.collector.collect().await?;
+        self.schema_tracker.prepare(&mut payload);
 
         // In dry-run mode, just print the payload
         match payload.to_json_pretty() {
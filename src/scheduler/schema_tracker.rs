@@ -0,0 +1,167 @@
+//! Decides, per cycle, whether the full schema is uploaded or only the
+//! changes since the last upload.
+
+use crate::config::SchemaChangesConfig;
+use crate::payload::schema_changes::SchemaFingerprint;
+use crate::payload::Payload;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Schema state as of the last successful upload
+struct Uploaded {
+    fingerprint: SchemaFingerprint,
+    full_at: Instant,
+}
+
+/// Schema of a payload that is about to be uploaded. Handed back to
+/// [`SchemaTracker::commit`] once the upload succeeded, so a failed upload
+/// never moves the base that later change events are computed against.
+pub struct PendingSchema {
+    fingerprint: SchemaFingerprint,
+    full: bool,
+}
+
+pub struct SchemaTracker {
+    enabled: bool,
+    full_refresh: Duration,
+    last: Mutex<Option<Uploaded>>,
+}
+
+impl SchemaTracker {
+    pub fn new(config: &SchemaChangesConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            full_refresh: config.full_refresh(),
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Stamp `payload` and its tables and indexes with their fingerprints
+    /// and, unless a full upload is due, replace the schema with the changes
+    /// since the last upload.
+    pub fn prepare(&self, payload: &mut Payload) -> Option<PendingSchema> {
+        let schema = payload.schema.as_mut()?;
+        let fingerprint = SchemaFingerprint::new(schema);
+        fingerprint.stamp(schema);
+        payload.schema_version_hash = Some(fingerprint.hash().to_string());
+        if !self.enabled {
+            return None;
+        }
+
+        let last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let full = match last.as_ref() {
+            Some(last) if last.full_at.elapsed() < self.full_refresh => {
+                payload.schema = None;
+                if last.fingerprint.hash() != fingerprint.hash() {
+                    payload.schema_base_hash = Some(last.fingerprint.hash().to_string());
+                    payload.schema_changes = Some(last.fingerprint.diff(&fingerprint));
+                }
+                false
+            }
+            _ => true,
+        };
+
+        Some(PendingSchema { fingerprint, full })
+    }
+
+    /// Record `pending` as the schema the backend now has.
+    pub fn commit(&self, pending: PendingSchema) {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let full_at = match last.as_ref() {
+            Some(last) if !pending.full => last.full_at,
+            _ => Instant::now(),
+        };
+        *last = Some(Uploaded {
+            fingerprint: pending.fingerprint,
+            full_at,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::payload::{
+        DatabaseInfo, IndexMetadata, SchemaChangeAction, SchemaMetadata, SchemaObjectKind,
+    };
+    use std::collections::HashMap;
+
+    fn payload(indexes: &[&str]) -> Payload {
+        Payload::new(DatabaseInfo {
+            database_type: "postgres".to_string(),
+            version: None,
+            provider: "generic".to_string(),
+            provider_metadata: HashMap::new(),
+        })
+        .with_schema(SchemaMetadata {
+            indexes: indexes
+                .iter()
+                .map(|name| IndexMetadata {
+                    schema: "public".to_string(),
+                    table: "orders".to_string(),
+                    name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_full_schema_then_changes_only() {
+        let tracker = SchemaTracker::new(&SchemaChangesConfig {
+            enabled: true,
+            ..Default::default()
+        });
+
+        let mut first = payload(&["a"]);
+        let pending = tracker.prepare(&mut first).unwrap();
+        assert!(first.schema.as_ref().unwrap().indexes[0]
+            .fingerprint
+            .is_some());
+        let base = first.schema_version_hash.clone().unwrap();
+        tracker.commit(pending);
+
+        // Unchanged: only the hash goes out
+        let mut same = payload(&["a"]);
+        let pending = tracker.prepare(&mut same).unwrap();
+        assert!(same.schema.is_none());
+        assert!(same.schema_changes.is_none());
+        assert_eq!(same.schema_version_hash.as_ref(), Some(&base));
+        tracker.commit(pending);
+
+        // An index was added, but the upload fails: the next cycle still
+        // diffs against the last uploaded schema
+        let mut changed = payload(&["a", "b"]);
+        let _failed = tracker.prepare(&mut changed).unwrap();
+        let mut retried = payload(&["a", "b"]);
+        tracker.prepare(&mut retried).unwrap();
+        assert!(retried.schema.is_none());
+        assert_eq!(retried.schema_base_hash.as_ref(), Some(&base));
+        let changes = retried.schema_changes.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].object, SchemaObjectKind::Index);
+        assert_eq!(changes[0].action, SchemaChangeAction::Added);
+        assert_eq!(changes[0].name, "b");
+    }
+
+    #[test]
+    fn test_full_refresh_and_disabled() {
+        let tracker = SchemaTracker::new(&SchemaChangesConfig {
+            enabled: true,
+            full_refresh_secs: 0,
+        });
+        for _ in 0..2 {
+            let mut p = payload(&["a"]);
+            let pending = tracker.prepare(&mut p).unwrap();
+            assert!(p.schema.is_some());
+            tracker.commit(pending);
+        }
+
+        let tracker = SchemaTracker::new(&SchemaChangesConfig::default());
+        let mut p = payload(&["a"]);
+        assert!(tracker.prepare(&mut p).is_none());
+        assert!(p.schema.is_some());
+        assert!(p.schema_version_hash.is_some());
+    }
+}
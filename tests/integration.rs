@@ -5,17 +5,45 @@
 //! cargo test --test integration
 //! ```
 
+#[path = "integration/clickhouse_test.rs"]
+mod clickhouse_test;
+
+#[path = "integration/couchbase_test.rs"]
+mod couchbase_test;
+
 #[path = "integration/e2e_test.rs"]
 mod e2e_test;
 
+#[path = "integration/elasticsearch_test.rs"]
+mod elasticsearch_test;
+
+#[path = "integration/influxdb_test.rs"]
+mod influxdb_test;
+
 #[path = "integration/mongodb_container_test.rs"]
 mod mongodb_container_test;
 
+#[path = "integration/neo4j_container_test.rs"]
+mod neo4j_container_test;
+
+#[cfg(feature = "oracle")]
+#[path = "integration/oracle_container_test.rs"]
+mod oracle_container_test;
+
 #[path = "integration/postgres_container_test.rs"]
 mod postgres_container_test;
 
 #[path = "integration/postgres_test.rs"]
 mod postgres_test;
 
+#[path = "integration/sqlserver_container_test.rs"]
+mod sqlserver_container_test;
+
 #[path = "integration/uploader_test.rs"]
 mod uploader_test;
+
+#[path = "integration/vector_container_test.rs"]
+mod vector_container_test;
+
+#[path = "integration/vector_test.rs"]
+mod vector_test;
@@ -0,0 +1,276 @@
+//! ClickHouse collector tests against a mock HTTP interface.
+//!
+//! Each system table query is answered with a canned `FORMAT JSON` result,
+//! matched on the table it reads.
+
+use datapace_agent::collector::clickhouse::ClickHouseCollector;
+use datapace_agent::collector::{Collector, CollectorError};
+use datapace_agent::config::Provider;
+use serde_json::{json, Value};
+use wiremock::matchers::{body_string_contains, header, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount(server: &MockServer, table: &str, data: Value) {
+    Mock::given(method("POST"))
+        .and(body_string_contains(table))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "meta": [],
+            "data": data,
+            "rows": data.as_array().map_or(0, |a| a.len()),
+        })))
+        .mount(server)
+        .await;
+}
+
+async fn mock_server() -> MockServer {
+    let server = MockServer::start().await;
+
+    mount(
+        &server,
+        "version()",
+        json!([{"version": "24.3.2.23", "database_name": "analytics", "host_name": "ch-0"}]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.query_log",
+        json!([{
+            "query_hash": "1234567890",
+            "query_text": "SELECT count() FROM events WHERE tenant_id = ?",
+            "database_name": "analytics",
+            "calls": "40",
+            "total_time_ms": "2000",
+            "result_rows": "40",
+            "read_rows": "800000000",
+            "read_bytes": "6400000000"
+        }]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.settings",
+        json!([
+            {"name": "max_execution_time", "value": "30", "changed": 1, "type": "Seconds"},
+            {"name": "max_threads", "value": "'auto(8)'", "changed": 0, "type": "MaxThreads"}
+        ]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.merge_tree_settings",
+        json!([{"name": "parts_to_throw_insert", "value": "300", "changed": 1, "type": "UInt64"}]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.server_settings",
+        json!([{"name": "max_concurrent_queries", "value": "100", "changed": 0, "type": "UInt64"}]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.merges",
+        json!([{"database": "analytics", "table": "events", "merges": "2"}]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.parts",
+        json!([{
+            "database": "analytics",
+            "table": "events",
+            "active_parts": "180",
+            "partitions": "3",
+            "max_parts_in_partition": "150",
+            "rows": "1000000",
+            "bytes_on_disk": "52428800",
+            "data_compressed_bytes": "50000000",
+            "data_uncompressed_bytes": "400000000"
+        }]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.tables",
+        json!([
+            {
+                "database": "analytics",
+                "name": "events",
+                "engine": "ReplicatedMergeTree",
+                "sorting_key": "tenant_id, toStartOfHour(ts)",
+                "primary_key": "tenant_id",
+                "partition_key": "toYYYYMM(ts)",
+                "total_rows": "1000000",
+                "total_bytes": "52428800",
+                "as_select": "",
+                "comment": ""
+            },
+            {
+                "database": "analytics",
+                "name": "events_hourly_mv",
+                "engine": "MaterializedView",
+                "sorting_key": "",
+                "primary_key": "",
+                "partition_key": "",
+                "total_rows": null,
+                "total_bytes": null,
+                "as_select": "SELECT tenant_id, count() FROM analytics.events GROUP BY tenant_id",
+                "comment": ""
+            }
+        ]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.columns",
+        json!([
+            {"database": "analytics", "table": "events", "name": "tenant_id", "type": "UInt32",
+             "position": "1", "default_kind": "", "default_expression": "", "comment": ""},
+            {"database": "analytics", "table": "events", "name": "referrer",
+             "type": "LowCardinality(Nullable(String))", "position": "2", "default_kind": "",
+             "default_expression": "", "comment": ""},
+            {"database": "analytics", "table": "events", "name": "day", "type": "Date",
+             "position": "3", "default_kind": "MATERIALIZED", "default_expression": "toDate(ts)",
+             "comment": ""}
+        ]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.data_skipping_indices",
+        json!([{"database": "analytics", "table": "events", "name": "idx_referrer",
+                "type": "bloom_filter", "expr": "referrer"}]),
+    )
+    .await;
+    mount(
+        &server,
+        "system.replicas",
+        json!([{
+            "database": "analytics",
+            "table": "events",
+            "is_leader": 1,
+            "is_readonly": 0,
+            "absolute_delay": "12",
+            "log_lag": "4",
+            "queue_size": 7,
+            "inserts_in_queue": 5,
+            "merges_in_queue": 2,
+            "total_replicas": 3,
+            "active_replicas": 3
+        }]),
+    )
+    .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_clickhouse_collect() {
+    let server = mock_server().await;
+    let collector = ClickHouseCollector::new(&server.uri(), Provider::Auto)
+        .await
+        .expect("collector should connect");
+    assert_eq!(collector.version().as_deref(), Some("24.3.2.23"));
+    assert_eq!(collector.provider(), "generic");
+
+    let payload = collector
+        .collect()
+        .await
+        .expect("collection should succeed");
+
+    let queries = payload.query_stats.unwrap();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(queries[0].calls, Some(40));
+    assert_eq!(queries[0].mean_time_ms, Some(50.0));
+    assert_eq!(queries[0].read_bytes, Some(6_400_000_000));
+
+    let settings = payload.settings.unwrap();
+    assert_eq!(settings.get("max_execution_time").unwrap(), "30");
+    assert_eq!(
+        settings.get("merge_tree.parts_to_throw_insert").unwrap(),
+        "300"
+    );
+    assert_eq!(
+        settings.get("server.max_concurrent_queries").unwrap(),
+        "100"
+    );
+    let details = payload.setting_details.unwrap();
+    let timeout = details
+        .iter()
+        .find(|s| s.name == "max_execution_time")
+        .unwrap();
+    assert_eq!(timeout.value_ms, Some(30_000.0));
+    assert!(!timeout.is_default);
+
+    let clickhouse = payload.clickhouse.unwrap();
+    let parts = &clickhouse.parts[0];
+    assert_eq!(parts.active_parts, 180);
+    assert_eq!(parts.merges_in_progress, 2);
+    // 150 parts in a partition against parts_to_throw_insert = 300
+    assert_eq!(parts.merge_pressure, Some(0.5));
+    let replica = &clickhouse.replicas[0];
+    assert!(replica.is_leader);
+    assert_eq!(replica.absolute_delay_s, Some(12));
+    assert_eq!(replica.log_lag, Some(4));
+
+    let table_stats = payload.table_stats.unwrap();
+    assert_eq!(table_stats[0].schema, "analytics");
+    assert_eq!(table_stats[0].n_live_tup, Some(1_000_000));
+
+    let schema = payload.schema.unwrap();
+    assert_eq!(schema.tables.len(), 1);
+    let events = &schema.tables[0];
+    assert_eq!(events.engine.as_deref(), Some("ReplicatedMergeTree"));
+    assert_eq!(events.sort_key, vec!["tenant_id", "toStartOfHour(ts)"]);
+    assert_eq!(events.partition_key.as_deref(), Some("toYYYYMM(ts)"));
+    assert!(events.columns[1].nullable);
+    assert_eq!(
+        events.columns[2].generation_expression.as_deref(),
+        Some("toDate(ts)")
+    );
+    assert_eq!(schema.views.len(), 1);
+    assert!(schema.views[0].materialized);
+
+    let primary = schema.indexes.iter().find(|i| i.is_primary).unwrap();
+    assert_eq!(primary.columns, vec!["tenant_id"]);
+    let skipping = schema
+        .indexes
+        .iter()
+        .find(|i| i.name == "idx_referrer")
+        .unwrap();
+    assert_eq!(skipping.index_type.as_deref(), Some("bloom_filter"));
+}
+
+#[tokio::test]
+async fn test_clickhouse_sends_credentials_and_database() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(header("X-ClickHouse-User", "agent"))
+        .and(header("X-ClickHouse-Key", "secret"))
+        .and(header("X-ClickHouse-Database", "analytics"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": [{"version": "24.3.2.23", "database_name": "analytics", "host_name": "ch-0"}]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = server.uri().replace("http://", "http://agent:secret@") + "/analytics";
+    ClickHouseCollector::new(&url, Provider::Auto)
+        .await
+        .expect("collector should connect");
+}
+
+#[tokio::test]
+async fn test_clickhouse_access_denied() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(516).set_body_string(
+            "Code: 516. DB::Exception: agent: Authentication failed. (AUTHENTICATION_FAILED)",
+        ))
+        .mount(&server)
+        .await;
+
+    let result = ClickHouseCollector::new(&server.uri(), Provider::Auto).await;
+    assert!(matches!(result, Err(CollectorError::PermissionError(_))));
+}
@@ -0,0 +1,205 @@
+//! Elasticsearch / OpenSearch collector tests against a mock REST API.
+
+use datapace_agent::collector::elasticsearch::ElasticsearchCollector;
+use datapace_agent::collector::{Collector, CollectorError};
+use datapace_agent::config::Provider;
+use serde_json::{json, Value};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount(server: &MockServer, api: &str, body: Value) {
+    Mock::given(method("GET"))
+        .and(path(api))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+async fn mock_server(distribution: Option<&str>) -> MockServer {
+    let server = MockServer::start().await;
+
+    let mut version = json!({"number": "2.13.0", "build_type": "tar"});
+    if let Some(distribution) = distribution {
+        version["distribution"] = json!(distribution);
+    }
+    mount(
+        &server,
+        "/",
+        json!({"name": "node-1", "cluster_name": "search", "cluster_uuid": "u-1", "version": version}),
+    )
+    .await;
+    mount(
+        &server,
+        "/_cluster/health",
+        json!({
+            "cluster_name": "search",
+            "status": "yellow",
+            "number_of_nodes": 2,
+            "number_of_data_nodes": 2,
+            "active_primary_shards": 4,
+            "active_shards": 7,
+            "relocating_shards": 0,
+            "initializing_shards": 0,
+            "unassigned_shards": 1,
+            "delayed_unassigned_shards": 0,
+            "number_of_pending_tasks": 0,
+            "active_shards_percent_as_number": 87.5
+        }),
+    )
+    .await;
+    mount(
+        &server,
+        "/_cat/indices",
+        json!([
+            {"health": "yellow", "status": "open", "index": "orders", "pri": "3", "rep": "1",
+             "docs.count": "120000", "docs.deleted": "3000", "store.size": "96000000",
+             "pri.store.size": "48000000", "search.query_total": "5000",
+             "search.query_time": "25000", "indexing.index_total": "120000",
+             "indexing.index_time": "60000", "indexing.delete_total": "10"},
+            {"health": "green", "status": "open", "index": ".kibana_1", "pri": "1", "rep": "1",
+             "docs.count": "10"}
+        ]),
+    )
+    .await;
+    mount(
+        &server,
+        "/_cat/shards",
+        json!([
+            {"index": "orders", "shard": "0", "prirep": "p", "state": "STARTED", "store": "20000000"},
+            {"index": "orders", "shard": "1", "prirep": "p", "state": "STARTED", "store": "16000000"},
+            {"index": "orders", "shard": "1", "prirep": "r", "state": "UNASSIGNED",
+             "store": null, "unassigned.reason": "NODE_LEFT"}
+        ]),
+    )
+    .await;
+    mount(
+        &server,
+        "/_nodes/stats/jvm,os,fs,thread_pool",
+        json!({"nodes": {"abc": {
+            "name": "node-1",
+            "host": "10.0.0.1",
+            "roles": ["data", "master"],
+            "jvm": {"mem": {"heap_used_percent": 71, "heap_max_in_bytes": 4294967296_i64},
+                    "gc": {"collectors": {"old": {"collection_count": 3,
+                                                  "collection_time_in_millis": 420}}}},
+            "os": {"cpu": {"percent": 35, "load_average": {"1m": 1.5}}},
+            "fs": {"total": {"total_in_bytes": 107374182400_i64,
+                             "available_in_bytes": 53687091200_i64}},
+            "thread_pool": {"search": {"rejected": 0}, "write": {"rejected": 12}}
+        }}}),
+    )
+    .await;
+    mount(
+        &server,
+        "/_cluster/settings",
+        json!({
+            "persistent": {"cluster.routing.allocation.disk.watermark.high": "85%"},
+            "transient": {},
+            "defaults": {
+                "cluster.routing.allocation.disk.watermark.high": "90%",
+                "indices.recovery.max_bytes_per_sec": "40mb",
+                "discovery.seed_hosts": ["10.0.0.1", "10.0.0.2"]
+            }
+        }),
+    )
+    .await;
+    mount(
+        &server,
+        "/_mapping",
+        json!({"orders": {"mappings": {"properties": {
+            "customer": {"properties": {"email": {"type": "keyword"}}},
+            "placed_at": {"type": "date", "format": "strict_date_optional_time"}
+        }}}}),
+    )
+    .await;
+
+    server
+}
+
+#[tokio::test]
+async fn test_elasticsearch_collect() {
+    let server = mock_server(None).await;
+    let collector = ElasticsearchCollector::new(&server.uri(), Provider::Auto)
+        .await
+        .expect("collector should connect");
+    assert_eq!(collector.provider(), "generic");
+
+    let payload = collector
+        .collect()
+        .await
+        .expect("collection should succeed");
+
+    let es = payload.elasticsearch.unwrap();
+    assert_eq!(es.health.status, "yellow");
+    assert_eq!(es.indices.len(), 1, "system indices are left out");
+    assert_eq!(es.indices[0].max_shard_size_bytes, Some(20_000_000));
+    assert_eq!(es.indices[0].unassigned_shards, 1);
+    assert_eq!(es.unassigned_shards[0].reason.as_deref(), Some("NODE_LEFT"));
+    assert_eq!(es.nodes[0].write_rejected, Some(12));
+    assert_eq!(es.nodes[0].heap_used_percent, Some(71.0));
+
+    let stats = &payload.table_stats.unwrap()[0];
+    assert_eq!(stats.schema, "search");
+    assert_eq!(stats.n_live_tup, Some(120_000));
+    assert_eq!(stats.n_dead_tup, Some(3_000));
+    assert_eq!(stats.search_latency_ms, Some(5.0));
+    assert_eq!(stats.search_rate, None);
+
+    let settings = payload.settings.unwrap();
+    assert_eq!(
+        settings
+            .get("cluster.routing.allocation.disk.watermark.high")
+            .unwrap(),
+        "85%"
+    );
+    assert_eq!(
+        settings.get("discovery.seed_hosts").unwrap(),
+        "10.0.0.1,10.0.0.2"
+    );
+    let details = payload.setting_details.unwrap();
+    let recovery = details
+        .iter()
+        .find(|s| s.name == "indices.recovery.max_bytes_per_sec")
+        .unwrap();
+    assert!(recovery.is_default);
+    assert_eq!(recovery.value_bytes, Some(40 * 1024 * 1024));
+
+    let schema = payload.schema.unwrap();
+    let orders = &schema.tables[0];
+    assert_eq!(orders.row_count_estimate, Some(120_000));
+    assert_eq!(orders.size_bytes, Some(48_000_000));
+    let names: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["customer", "customer.email", "placed_at"]);
+
+    // Second collection has a previous snapshot to compute rates from
+    let payload = collector.collect().await.unwrap();
+    assert!(payload.table_stats.unwrap()[0].search_rate.is_some());
+}
+
+#[tokio::test]
+async fn test_opensearch_detected() {
+    let server = mock_server(Some("opensearch")).await;
+    let collector = ElasticsearchCollector::new(&server.uri(), Provider::Auto)
+        .await
+        .unwrap();
+    assert_eq!(collector.provider(), "opensearch");
+}
+
+#[tokio::test]
+async fn test_elasticsearch_api_key_and_permission_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(header("Authorization", "ApiKey aWQ6a2V5"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "error": {"type": "security_exception", "reason": "action [cluster:monitor/main] is unauthorized"},
+            "status": 403
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let url = format!("{}?api_key=aWQ6a2V5", server.uri());
+    let result = ElasticsearchCollector::new(&url, Provider::Auto).await;
+    assert!(matches!(result, Err(CollectorError::PermissionError(_))));
+}
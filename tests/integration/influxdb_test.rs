@@ -0,0 +1,307 @@
+//! InfluxDB collector tests against mock v2 and v1 HTTP APIs.
+
+use datapace_agent::collector::influxdb::InfluxdbCollector;
+use datapace_agent::collector::{Collector, CollectorError};
+use datapace_agent::config::Provider;
+use serde_json::{json, Value};
+use wiremock::matchers::{body_string_contains, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount_ping(server: &MockServer, version: &str) {
+    Mock::given(method("GET"))
+        .and(path("/ping"))
+        .respond_with(
+            ResponseTemplate::new(204)
+                .insert_header("X-Influxdb-Version", version)
+                .insert_header("X-Influxdb-Build", "OSS"),
+        )
+        .mount(server)
+        .await;
+}
+
+async fn mount(server: &MockServer, api: &str, body: Value) {
+    Mock::given(method("GET"))
+        .and(path(api))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+/// A Flux query whose text contains `needle`, answered with `csv`
+async fn mount_flux(server: &MockServer, needle: &str, csv: &str, priority: u8) {
+    Mock::given(method("POST"))
+        .and(path("/api/v2/query"))
+        .and(body_string_contains(needle))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/csv")
+                .set_body_string(csv),
+        )
+        .with_priority(priority)
+        .mount(server)
+        .await;
+}
+
+/// An InfluxQL statement, answered with one result holding `series`
+async fn mount_influxql(server: &MockServer, statement: &str, series: Value) {
+    Mock::given(method("GET"))
+        .and(path("/query"))
+        .and(query_param("q", statement))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_json(json!({"results": [{"statement_id": 0, "series": series}]})),
+        )
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_influxdb_v2_collect() {
+    let server = MockServer::start().await;
+    mount_ping(&server, "v2.7.10").await;
+    mount(
+        &server,
+        "/api/v2/buckets",
+        json!({"buckets": [
+            {
+                "id": "0a1b2c3d4e5f6071",
+                "orgID": "9f8e7d6c5b4a3921",
+                "name": "telegraf",
+                "type": "user",
+                "retentionRules": [
+                    {"type": "expire", "everySeconds": 604800, "shardGroupDurationSeconds": 86400}
+                ]
+            },
+            {
+                "id": "1a1b2c3d4e5f6071",
+                "orgID": "9f8e7d6c5b4a3921",
+                "name": "_monitoring",
+                "type": "system",
+                "retentionRules": [{"type": "expire", "everySeconds": 604800}]
+            }
+        ]}),
+    )
+    .await;
+    mount_flux(
+        &server,
+        "schema.measurements(",
+        ",result,table,_value\r\n,_result,0,cpu\r\n,_result,0,mem\r\n",
+        5,
+    )
+    .await;
+    mount_flux(
+        &server,
+        "measurementTagKeys",
+        ",result,table,_value\r\n,_result,0,_field\r\n,_result,0,_measurement\r\n,_result,0,host\r\n",
+        5,
+    )
+    .await;
+    mount_flux(
+        &server,
+        "measurementFieldKeys",
+        ",result,table,_value\r\n,_result,0,usage_idle\r\n",
+        5,
+    )
+    .await;
+    // Per measurement before per bucket: both mention `influxdb.cardinality`
+    mount_flux(
+        &server,
+        r#"r._measurement == \"cpu\""#,
+        ",result,table,_value\r\n,_result,0,1200\r\n",
+        1,
+    )
+    .await;
+    mount_flux(
+        &server,
+        "influxdb.cardinality",
+        ",result,table,_value\r\n,_result,0,1500\r\n",
+        5,
+    )
+    .await;
+    mount(
+        &server,
+        "/api/v2/config",
+        json!({"config": {
+            "storage-cache-max-memory-size": 1073741824,
+            "http-read-timeout": "30s",
+            "log-level": "info"
+        }}),
+    )
+    .await;
+    Mock::given(method("GET"))
+        .and(path("/metrics"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(
+            "# TYPE storage_bucket_series_num gauge\n\
+             storage_bucket_series_num{bucket=\"0a1b2c3d4e5f6071\"} 1500\n\
+             storage_bucket_series_num{bucket=\"1a1b2c3d4e5f6071\"} 80\n\
+             go_goroutines 42\n",
+        ))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}/acme?token=secret", server.uri());
+    let collector = InfluxdbCollector::new(&url, Provider::Auto)
+        .await
+        .expect("collector");
+    assert_eq!(collector.version().as_deref(), Some("2.7.10"));
+    assert_eq!(collector.provider(), "generic");
+    collector.test_connection().await.expect("test_connection");
+
+    let payload = collector.collect().await.expect("collect");
+    assert_eq!(payload.database.database_type, "influxdb");
+    assert_eq!(
+        payload
+            .database
+            .provider_metadata
+            .get("org")
+            .map(String::as_str),
+        Some("acme")
+    );
+
+    let schema = payload.schema.expect("schema");
+    assert_eq!(schema.tables.len(), 2);
+    let cpu = &schema.tables[0];
+    assert_eq!(
+        (cpu.schema.as_str(), cpu.name.as_str()),
+        ("telegraf", "cpu")
+    );
+    let columns: Vec<_> = cpu.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, ["time", "host", "usage_idle"]);
+    assert_eq!(cpu.columns[1].native_type.as_deref(), Some("tag"));
+    assert_eq!(cpu.columns[2].native_type.as_deref(), Some("field"));
+
+    let stats = payload.table_stats.expect("table_stats");
+    assert_eq!(stats[0].series_cardinality, Some(1200));
+
+    let influxdb = payload.influxdb.expect("influxdb");
+    assert_eq!(influxdb.api, "v2");
+    let telegraf = &influxdb.buckets[0];
+    assert_eq!(telegraf.retention_seconds, Some(604_800));
+    assert_eq!(telegraf.shard_group_duration_seconds, Some(86_400));
+    assert_eq!(telegraf.measurement_count, Some(2));
+    assert_eq!(telegraf.series_cardinality, Some(1500));
+    // System buckets are not described; their series come from /metrics
+    let monitoring = &influxdb.buckets[1];
+    assert_eq!(monitoring.measurement_count, None);
+    assert_eq!(monitoring.series_cardinality, Some(80));
+    assert!(influxdb.metrics.iter().any(|m| m.name == "go_goroutines"));
+
+    let details = payload.setting_details.expect("setting_details");
+    let cache = details
+        .iter()
+        .find(|s| s.name == "storage-cache-max-memory-size")
+        .unwrap();
+    assert_eq!(cache.value_bytes, Some(1_073_741_824));
+    let timeout = details
+        .iter()
+        .find(|s| s.name == "http-read-timeout")
+        .unwrap();
+    assert_eq!(timeout.value_ms, Some(30_000.0));
+}
+
+#[tokio::test]
+async fn test_influxdb_v1_collect() {
+    let server = MockServer::start().await;
+    mount_ping(&server, "1.8.10").await;
+    mount_influxql(
+        &server,
+        "SHOW DATABASES",
+        json!([{"name": "databases", "columns": ["name"], "values": [["_internal"], ["telegraf"]]}]),
+    )
+    .await;
+    mount_influxql(
+        &server,
+        r#"SHOW RETENTION POLICIES ON "telegraf""#,
+        json!([{
+            "columns": ["name", "duration", "shardGroupDuration", "replicaN", "default"],
+            "values": [
+                ["autogen", "0s", "168h0m0s", 1, false],
+                ["two_weeks", "336h0m0s", "24h0m0s", 1, true]
+            ]
+        }]),
+    )
+    .await;
+    mount_influxql(
+        &server,
+        r#"SHOW MEASUREMENTS ON "telegraf""#,
+        json!([{"name": "measurements", "columns": ["name"], "values": [["cpu"], ["disk"]]}]),
+    )
+    .await;
+    mount_influxql(
+        &server,
+        r#"SHOW TAG KEYS ON "telegraf""#,
+        json!([
+            {"name": "cpu", "columns": ["tagKey"], "values": [["cpu"], ["host"]]},
+            {"name": "disk", "columns": ["tagKey"], "values": [["path"]]}
+        ]),
+    )
+    .await;
+    mount_influxql(
+        &server,
+        r#"SHOW FIELD KEYS ON "telegraf""#,
+        json!([
+            {"name": "cpu", "columns": ["fieldKey", "fieldType"], "values": [["usage_idle", "float"]]},
+            {"name": "disk", "columns": ["fieldKey", "fieldType"], "values": [["free", "integer"]]}
+        ]),
+    )
+    .await;
+    mount_influxql(
+        &server,
+        r#"SHOW SERIES EXACT CARDINALITY ON "telegraf""#,
+        json!([
+            {"name": "cpu", "columns": ["count"], "values": [[40]]},
+            {"name": "disk", "columns": ["count"], "values": [[2]]}
+        ]),
+    )
+    .await;
+
+    let url = format!("{}?api=v1", server.uri());
+    let collector = InfluxdbCollector::new(&url, Provider::Auto)
+        .await
+        .expect("collector");
+    assert_eq!(collector.version().as_deref(), Some("1.8.10"));
+    collector.test_connection().await.expect("test_connection");
+
+    let payload = collector.collect().await.expect("collect");
+    let schema = payload.schema.expect("schema");
+    let disk = schema.tables.iter().find(|t| t.name == "disk").unwrap();
+    assert_eq!(disk.schema, "telegraf");
+    assert_eq!(disk.columns[2].name, "free");
+    assert_eq!(disk.columns[2].data_type, "integer");
+
+    let influxdb = payload.influxdb.expect("influxdb");
+    assert_eq!(influxdb.api, "v1");
+    assert_eq!(influxdb.buckets.len(), 1);
+    let telegraf = &influxdb.buckets[0];
+    assert_eq!(telegraf.retention_policy.as_deref(), Some("two_weeks"));
+    assert_eq!(telegraf.retention_seconds, Some(1_209_600));
+    assert_eq!(telegraf.series_cardinality, Some(42));
+    assert!(payload.setting_details.unwrap_or_default().is_empty());
+}
+
+#[tokio::test]
+async fn test_influxdb_permission_error() {
+    let server = MockServer::start().await;
+    mount_ping(&server, "2.7.10").await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/buckets"))
+        .respond_with(
+            ResponseTemplate::new(401)
+                .set_body_json(json!({"code": "unauthorized", "message": "unauthorized access"})),
+        )
+        .mount(&server)
+        .await;
+
+    let collector = InfluxdbCollector::new(&server.uri(), Provider::Auto)
+        .await
+        .expect("collector");
+    let err = collector.collect().await.expect_err("should fail");
+    assert!(
+        matches!(err, CollectorError::PermissionError(ref message) if message.contains("unauthorized access")),
+        "{err:?}"
+    );
+    assert!(matches!(
+        collector.test_connection().await,
+        Err(CollectorError::ConnectionError(_))
+    ));
+}
@@ -0,0 +1,245 @@
+//! Neo4j and Memgraph integration tests using testcontainers.
+//!
+//! These tests spin up a Neo4j 5 and a Memgraph container, create two labels
+//! joined by a relationship, an index and a uniqueness constraint, and verify
+//! the collector against each, including telling the two engines apart.
+//!
+//! # Requirements
+//!
+//! - Docker installed and running
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test --test integration neo4j_container
+//! ```
+
+use datapace_agent::collector;
+use datapace_agent::payload::SchemaMetadata;
+use neo4rs::{query, Graph};
+use std::time::Duration;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+const BOLT_PORT: u16 = 7687;
+const PASSWORD: &str = "datapace-test";
+
+/// Connect over Bolt, retrying while the server finishes starting up.
+async fn connect(uri: &str, user: &str, password: &str) -> Graph {
+    for _ in 0..60 {
+        if let Ok(graph) = Graph::new(uri, user, password).await {
+            if graph.run(query("RETURN 1")).await.is_ok() {
+                return graph;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    panic!("{uri} did not accept Bolt connections");
+}
+
+/// Run each statement in its own transaction; schema changes can't share one
+/// with writes.
+async fn seed(graph: &Graph, statements: &[&str]) {
+    for statement in statements {
+        graph
+            .run(query(statement))
+            .await
+            .unwrap_or_else(|e| panic!("Failed to run `{statement}`: {e}"));
+    }
+}
+
+const DATA: &str = "CREATE (:Customer {email: 'ada@example.com', name: 'Ada'})\
+    -[:PLACED {at: 1}]->(:Order {total: 10.5}), \
+    (:Customer {email: 'grace@example.com', name: 'Grace'})";
+
+/// Start a container and return (container_handle, host, bolt_port).
+///
+/// If Docker is not available, returns None so callers can skip the test.
+async fn start(
+    image: GenericImage,
+    env: &[(&str, &str)],
+) -> Option<(ContainerAsync<GenericImage>, String, u16)> {
+    let mut image = image
+        .with_exposed_port(BOLT_PORT.tcp())
+        .with_startup_timeout(Duration::from_secs(180));
+    for (key, value) in env {
+        image = image.with_env_var(*key, *value);
+    }
+    let container = match image.start().await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Skipping test (Docker not available): {e}");
+            return None;
+        }
+    };
+    let host = container.get_host().await.unwrap().to_string();
+    let port = container.get_host_port_ipv4(BOLT_PORT).await.unwrap();
+    Some((container, host, port))
+}
+
+/// Assertions shared by both engines: labels and relationship types become
+/// tables with their properties as columns, and the index and constraint
+/// land on the `Customer` table.
+fn assert_graph_schema(schema: &SchemaMetadata, index_name: &str) {
+    let customer = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "nodes" && t.name == "Customer")
+        .expect("Customer label should be collected");
+    let mut columns: Vec<&str> = customer.columns.iter().map(|c| c.name.as_str()).collect();
+    columns.sort_unstable();
+    assert_eq!(columns, ["email", "name"]);
+
+    assert!(schema
+        .tables
+        .iter()
+        .any(|t| t.schema == "nodes" && t.name == "Order"));
+    let placed = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "relationships" && t.name == "PLACED")
+        .expect("PLACED relationship type should be collected");
+    assert_eq!(placed.columns.len(), 1);
+    assert_eq!(placed.columns[0].name, "at");
+
+    let index = schema
+        .indexes
+        .iter()
+        .find(|i| i.name == index_name)
+        .unwrap_or_else(|| panic!("{index_name} should be collected"));
+    assert_eq!(
+        (index.schema.as_str(), index.table.as_str()),
+        ("nodes", "Customer")
+    );
+    assert_eq!(index.columns, ["name"]);
+
+    let unique = customer
+        .constraints
+        .iter()
+        .find(|c| c.kind == "unique")
+        .expect("uniqueness constraint should be collected");
+    assert_eq!(unique.columns, ["email"]);
+}
+
+#[tokio::test]
+async fn test_neo4j_collector_with_container() {
+    let Some((_container, host, port)) = start(
+        GenericImage::new("neo4j", "5").with_wait_for(WaitFor::message_on_stdout("Started.")),
+        &[("NEO4J_AUTH", &format!("neo4j/{PASSWORD}"))],
+    )
+    .await
+    else {
+        return;
+    };
+
+    let graph = connect(&format!("bolt://{host}:{port}"), "neo4j", PASSWORD).await;
+    seed(
+        &graph,
+        &[
+            "CREATE INDEX customer_name_idx FOR (c:Customer) ON (c.name)",
+            "CREATE CONSTRAINT customer_email_unique FOR (c:Customer) REQUIRE c.email IS UNIQUE",
+            DATA,
+        ],
+    )
+    .await;
+
+    let url = format!("bolt://neo4j:{PASSWORD}@{host}:{port}");
+    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector");
+    collector
+        .test_connection()
+        .await
+        .expect("Connection test failed");
+    assert_eq!(collector.provider(), "generic");
+    assert!(collector.version().is_some_and(|v| v.starts_with("5.")));
+
+    let payload = collector.collect().await.expect("Collection failed");
+    assert_eq!(payload.database.database_type, "neo4j");
+
+    let schema = payload.schema.expect("schema should be present");
+    assert_graph_schema(&schema, "customer_name_idx");
+    let customer = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "nodes" && t.name == "Customer")
+        .unwrap();
+    let email = customer.columns.iter().find(|c| c.name == "email").unwrap();
+    assert_eq!(email.data_type, "String");
+    // Every Customer node has both properties
+    assert!(customer.columns.iter().all(|c| !c.nullable));
+    assert!(customer
+        .constraints
+        .iter()
+        .any(|c| c.name == "customer_email_unique" && c.definition.contains("IS UNIQUE")));
+    // The uniqueness constraint is backed by an index of the same name
+    assert!(schema
+        .indexes
+        .iter()
+        .any(|i| i.name == "customer_email_unique" && i.is_unique));
+
+    let stats = payload.table_stats.expect("table_stats should be present");
+    let customers = stats
+        .iter()
+        .find(|t| t.schema == "nodes" && t.table == "Customer")
+        .expect("Customer counts should be collected");
+    assert_eq!(customers.n_live_tup, Some(2));
+    assert!(payload
+        .index_stats
+        .expect("index_stats should be present")
+        .iter()
+        .any(|i| i.index == "customer_name_idx"));
+
+    let settings = payload.settings.expect("settings should be present");
+    assert!(settings.contains_key("db.transaction.timeout"));
+}
+
+#[tokio::test]
+async fn test_memgraph_collector_with_container() {
+    let Some((_container, host, port)) =
+        start(GenericImage::new("memgraph/memgraph", "2.21.0"), &[]).await
+    else {
+        return;
+    };
+
+    // Memgraph runs without authentication by default
+    let graph = connect(&format!("bolt://{host}:{port}"), "", "").await;
+    seed(
+        &graph,
+        &[
+            "CREATE INDEX ON :Customer(name)",
+            "CREATE CONSTRAINT ON (c:Customer) ASSERT c.email IS UNIQUE",
+            DATA,
+        ],
+    )
+    .await;
+
+    let url = format!("bolt://{host}:{port}");
+    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector");
+    assert_eq!(collector.provider(), "memgraph");
+    assert!(collector.version().is_some());
+
+    let payload = collector.collect().await.expect("Collection failed");
+    assert_eq!(payload.database.database_type, "memgraph");
+
+    let schema = payload.schema.expect("schema should be present");
+    assert_graph_schema(&schema, "Customer(name)");
+    let customer = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "nodes" && t.name == "Customer")
+        .unwrap();
+    assert!(customer
+        .constraints
+        .iter()
+        .any(|c| c.definition == "CREATE CONSTRAINT ON (n:Customer) ASSERT n.email IS UNIQUE"));
+
+    let settings = payload.settings.expect("settings should be present");
+    assert!(settings.contains_key("bolt_port"));
+    // Memgraph keeps no query statistics or count store
+    assert!(payload.query_stats.unwrap_or_default().is_empty());
+    assert!(payload.table_stats.unwrap_or_default().is_empty());
+}
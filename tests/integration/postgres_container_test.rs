@@ -15,14 +15,24 @@
 //! ```
 
 use datapace_agent::collector;
+use datapace_agent::collector::postgres::PostgresCollector;
+use datapace_agent::collector::Collector;
 use testcontainers::runners::AsyncRunner;
+use testcontainers::ImageExt;
 use testcontainers_modules::postgres::Postgres;
 
 /// Start a PG container and return (container_handle, connection_url).
 ///
 /// If Docker is not available, returns None so callers can skip the test.
 async fn start_postgres() -> Option<(testcontainers::ContainerAsync<Postgres>, String)> {
-    let container = match Postgres::default().start().await {
+    start_postgres_tag("11-alpine").await
+}
+
+/// Start a PG container of the given image tag.
+async fn start_postgres_tag(
+    tag: &str,
+) -> Option<(testcontainers::ContainerAsync<Postgres>, String)> {
+    let container = match Postgres::default().with_tag(tag).start().await {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Skipping test (Docker not available): {e}");
@@ -39,40 +49,63 @@ async fn start_postgres() -> Option<(testcontainers::ContainerAsync<Postgres>, S
 
 #[tokio::test]
 async fn test_collector_with_container() {
-    let Some((_container, url)) = start_postgres().await else {
-        return;
-    };
-
-    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
-        .await
-        .expect("Failed to create collector");
-
-    let payload = collector.collect().await.expect("Collection failed");
-
-    // Verify payload structure
-    assert!(
-        !payload.agent_version.is_empty(),
-        "agent_version must be set"
-    );
-    assert_eq!(
-        payload.database.database_type, "postgres",
-        "database_type should be postgres"
-    );
-
-    // Table stats should be present (may be empty on a fresh DB)
-    assert!(
-        payload.table_stats.is_some(),
-        "table_stats should be present"
-    );
-
-    // Index stats should be present
-    assert!(
-        payload.index_stats.is_some(),
-        "index_stats should be present"
-    );
-
-    // Settings should be present
-    assert!(payload.settings.is_some(), "settings should be present");
+    // pg_stat_checkpointer and pg_stat_io are read differently before 16,
+    // on 16 and from 17 on
+    for tag in ["11-alpine", "16-alpine", "17-alpine"] {
+        let Some((_container, url)) = start_postgres_tag(tag).await else {
+            return;
+        };
+
+        let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+            .await
+            .expect("Failed to create collector");
+
+        let payload = collector.collect().await.expect("Collection failed");
+
+        // Verify payload structure
+        assert!(
+            !payload.agent_version.is_empty(),
+            "agent_version must be set"
+        );
+        assert_eq!(
+            payload.database.database_type, "postgres",
+            "database_type should be postgres"
+        );
+
+        // Table stats should be present (may be empty on a fresh DB)
+        assert!(
+            payload.table_stats.is_some(),
+            "table_stats should be present"
+        );
+
+        // Index stats should be present
+        assert!(
+            payload.index_stats.is_some(),
+            "index_stats should be present"
+        );
+
+        // Settings should be present
+        assert!(payload.settings.is_some(), "settings should be present");
+
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        let (server_version_num,): (i32,) =
+            sqlx::query_as("SELECT current_setting('server_version_num')::int")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let database_stats = payload
+            .database_stats
+            .expect("database_stats should be present");
+        assert!(
+            database_stats.checkpointer.is_some(),
+            "checkpointer should be present on {tag}"
+        );
+        assert_eq!(
+            database_stats.io.is_some(),
+            server_version_num >= 160000,
+            "pg_stat_io should be read from 16 on ({tag})"
+        );
+    }
 }
 
 #[tokio::test]
@@ -130,3 +163,93 @@ async fn test_collector_version_detection() {
         version_str
     );
 }
+
+#[tokio::test]
+async fn test_collector_jsonb_sampling() {
+    let Some((_container, url)) = start_postgres().await else {
+        return;
+    };
+
+    let pool = sqlx::PgPool::connect(&url).await.unwrap();
+    for statement in [
+        "CREATE TABLE events (id bigint PRIMARY KEY, payload jsonb)",
+        r#"INSERT INTO events VALUES
+            (1, '{"customer": {"id": 7}, "items": [{"sku": "A1"}]}'),
+            (2, '{"customer": null, "items": []}'),
+            (3, NULL)"#,
+        "ANALYZE events",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let mut options = datapace_agent::config::PostgresCollectionConfig::default();
+    options.jsonb_sampling.enabled = true;
+    let collector = PostgresCollector::new(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector")
+        .with_options(options);
+    let payload = collector.collect().await.expect("Collection failed");
+
+    let schema = payload.schema.expect("schema should be present");
+    let events = schema
+        .tables
+        .iter()
+        .find(|t| t.name == "events")
+        .expect("events table");
+    assert_eq!(events.document_count_sampled, Some(3));
+    let column = |name: &str| {
+        events
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("{name} missing"))
+    };
+    assert_eq!(column("payload").data_type, "jsonb");
+    assert_eq!(column("payload.customer").data_type, "mixed");
+    assert_eq!(column("payload.customer.id").data_type, "number");
+    assert_eq!(column("payload.items[].sku").is_array_element, Some(true));
+    let presence = column("payload.items").presence_rate.unwrap();
+    assert!((presence - 2.0 / 3.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_collector_database_filters_apply_to_url_database() {
+    let Some((_container, url)) = start_postgres().await else {
+        return;
+    };
+
+    let pool = sqlx::PgPool::connect(&url).await.unwrap();
+    sqlx::query("CREATE TABLE primary_only (id int)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("CREATE DATABASE tenant_a")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let current: (String,) = sqlx::query_as("SELECT current_database()")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+    let mut options = datapace_agent::config::PostgresCollectionConfig::default();
+    options.databases.enabled = true;
+    options.databases.exclude = vec![current.0.clone()];
+    let collector = PostgresCollector::new(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector")
+        .with_options(options);
+    let payload = collector.collect().await.expect("Collection failed");
+
+    let schema = payload.schema.expect("schema should be present");
+    assert!(schema.tables.iter().all(|t| t.name != "primary_only"));
+    assert!(schema
+        .tables
+        .iter()
+        .all(|t| t.database.as_deref() == Some("tenant_a")));
+    assert!(payload
+        .table_stats
+        .unwrap_or_default()
+        .iter()
+        .all(|t| t.database.as_deref() != Some(current.0.as_str())));
+}
@@ -102,6 +102,28 @@ async fn test_postgres_collect() {
 
     // Schema should be present
     assert!(payload.schema.is_some());
+
+    // Database-level wraparound ages should be present
+    let database_stats = payload.database_stats.expect("database_stats missing");
+    assert!(database_stats.frozen_xid_age.is_some());
+    assert!(database_stats.xact_commit.is_some());
+    assert!(database_stats.checkpointer.is_some());
+    for table in payload.table_stats.unwrap() {
+        assert!(
+            table.frozen_xid_age.is_some(),
+            "{}.{} missing frozen_xid_age",
+            table.schema,
+            table.table
+        );
+    }
+
+    // Inventory lists at least the connecting role and the CONNECT grants
+    let inventory = payload.inventory.expect("inventory missing");
+    assert!(!inventory.roles.is_empty());
+    assert!(inventory
+        .database_privileges
+        .iter()
+        .any(|p| p.privilege == "CONNECT"));
 }
 
 #[tokio::test]
@@ -160,6 +182,141 @@ async fn test_postgres_database_type() {
     assert_eq!(db_type, datapace_agent::config::DatabaseType::Postgres);
 }
 
+#[tokio::test]
+async fn test_postgres_column_types_and_constraints() {
+    let database_url = require_database!();
+
+    let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+    for statement in [
+        "DROP SCHEMA IF EXISTS datapace_test_types CASCADE",
+        "CREATE SCHEMA datapace_test_types",
+        "CREATE TYPE datapace_test_types.order_status AS ENUM ('pending', 'shipped')",
+        "CREATE DOMAIN datapace_test_types.email AS varchar(320) CHECK (VALUE LIKE '%@%')",
+        "CREATE TABLE datapace_test_types.customers (id bigint PRIMARY KEY)",
+        r#"CREATE TABLE datapace_test_types.orders (
+            id bigint GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+            customer_id bigint REFERENCES datapace_test_types.customers (id),
+            contact datapace_test_types.email,
+            status datapace_test_types.order_status NOT NULL,
+            tags text[],
+            amount numeric(12, 2) CHECK (amount > 0),
+            during tsrange,
+            EXCLUDE USING gist (during WITH &&)
+        )"#,
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let collector = datapace_agent::collector::postgres::PostgresCollector::new(
+        &database_url,
+        datapace_agent::config::Provider::Auto,
+    )
+    .await
+    .expect("Failed to create collector");
+    let payload = collector.collect().await.expect("Collection failed");
+    sqlx::query("DROP SCHEMA datapace_test_types CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let schema = payload.schema.expect("schema missing");
+    let orders = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "datapace_test_types" && t.name == "orders")
+        .expect("orders table missing");
+    let column = |name: &str| {
+        orders
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("{name} missing"))
+    };
+
+    assert_eq!(column("id").identity.as_deref(), Some("ALWAYS"));
+    assert_eq!(column("tags").data_type, "ARRAY");
+    assert_eq!(column("tags").element_type.as_deref(), Some("text"));
+    assert_eq!(column("contact").data_type, "character varying");
+    assert_eq!(
+        column("contact").domain.as_deref(),
+        Some("datapace_test_types.email")
+    );
+    assert_eq!(column("contact").max_length, Some(320));
+    assert_eq!(column("status").data_type, "USER-DEFINED");
+    assert_eq!(
+        column("status").enum_labels,
+        Some(vec!["pending".to_string(), "shipped".to_string()])
+    );
+    assert_eq!(column("amount").numeric_precision, Some(12));
+    assert_eq!(column("amount").numeric_scale, Some(2));
+
+    let kinds: Vec<&str> = orders.constraints.iter().map(|c| c.kind.as_str()).collect();
+    for kind in ["primary_key", "foreign_key", "check", "exclusion"] {
+        assert!(
+            kinds.contains(&kind),
+            "{kind} constraint missing: {kinds:?}"
+        );
+    }
+    let foreign_key = orders
+        .constraints
+        .iter()
+        .find(|c| c.kind == "foreign_key")
+        .unwrap();
+    assert_eq!(foreign_key.columns, ["customer_id"]);
+    assert!(foreign_key
+        .definition
+        .contains("REFERENCES datapace_test_types.customers(id)"));
+    let check = orders
+        .constraints
+        .iter()
+        .find(|c| c.kind == "check")
+        .unwrap();
+    assert_eq!(check.definition, "CHECK ((amount > (0)::numeric))");
+}
+
+#[tokio::test]
+async fn test_postgres_bloat_ratio_with_inheritance_children() {
+    let database_url = require_database!();
+
+    let pool = sqlx::PgPool::connect(&database_url).await.unwrap();
+    for statement in [
+        "DROP TABLE IF EXISTS datapace_test_bloat_parent CASCADE",
+        "CREATE TABLE datapace_test_bloat_parent (id bigint, payload text)",
+        "CREATE TABLE datapace_test_bloat_child () INHERITS (datapace_test_bloat_parent)",
+        "INSERT INTO datapace_test_bloat_parent SELECT g, repeat('x', 200) FROM generate_series(1, 5000) g",
+        "INSERT INTO datapace_test_bloat_child SELECT g, repeat('x', 200) FROM generate_series(1, 5000) g",
+        // Half of the parent's own pages are now dead space
+        "DELETE FROM ONLY datapace_test_bloat_parent WHERE id % 2 = 0",
+        "VACUUM ANALYZE datapace_test_bloat_parent",
+        "ANALYZE datapace_test_bloat_child",
+    ] {
+        sqlx::query(statement).execute(&pool).await.unwrap();
+    }
+
+    let collector = datapace_agent::collector::postgres::PostgresCollector::new(
+        &database_url,
+        datapace_agent::config::Provider::Auto,
+    )
+    .await
+    .expect("Failed to create collector");
+    let payload = collector.collect().await.expect("Collection failed");
+    sqlx::query("DROP TABLE datapace_test_bloat_parent CASCADE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Counting the inherited statistics too would double the row width and
+    // hide the dead space
+    let parent = payload
+        .table_stats
+        .expect("table_stats missing")
+        .into_iter()
+        .find(|t| t.table == "datapace_test_bloat_parent")
+        .expect("parent table stats missing");
+    let bloat = parent.bloat_ratio.expect("bloat_ratio missing");
+    assert!(bloat > 0.3 && bloat < 0.7, "bloat_ratio {bloat}");
+}
+
 // ============================================================================
 // Test Utilities
 // ============================================================================
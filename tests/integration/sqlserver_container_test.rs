@@ -0,0 +1,263 @@
+//! SQL Server integration tests using testcontainers.
+//!
+//! These tests spin up a SQL Server 2022 container, create a database with
+//! two tables, an index and a foreign key, and verify the collector against
+//! it as `sa`, with query statistics from the Query Store and from the plan
+//! cache.
+//!
+//! # Requirements
+//!
+//! - Docker installed and running
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test --test integration sqlserver_container
+//! ```
+
+use datapace_agent::collector::{self, CollectorError};
+use std::time::Duration;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use tiberius::{Client, Config};
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+
+const SQLSERVER_PORT: u16 = 1433;
+const PASSWORD: &str = "Datapace_2024";
+
+type SqlClient = Client<Compat<TcpStream>>;
+
+/// Connect as `sa`, retrying while the server finishes starting up.
+async fn connect(host: &str, port: u16) -> SqlClient {
+    let config = Config::from_ado_string(&format!(
+        "Server=tcp:{host},{port};User ID=sa;Password={PASSWORD};TrustServerCertificate=true"
+    ))
+    .unwrap();
+    for _ in 0..60 {
+        if let Ok(tcp) = TcpStream::connect(config.get_addr()).await {
+            if let Ok(client) = Client::connect(config.clone(), tcp.compat_write()).await {
+                return client;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    panic!("SQL Server did not accept connections");
+}
+
+async fn execute(client: &mut SqlClient, sql: &str) {
+    client
+        .simple_query(sql)
+        .await
+        .unwrap()
+        .into_results()
+        .await
+        .unwrap_or_else(|e| panic!("Failed to run `{sql}`: {e}"));
+}
+
+/// Start a SQL Server container, seed the `orders_db` database and return
+/// (container_handle, seed_client, connection_url).
+///
+/// If Docker is not available, returns None so callers can skip the test.
+async fn start_sqlserver() -> Option<(ContainerAsync<GenericImage>, SqlClient, String)> {
+    let image = GenericImage::new("mcr.microsoft.com/mssql/server", "2022-latest")
+        .with_exposed_port(SQLSERVER_PORT.tcp())
+        .with_wait_for(WaitFor::message_on_stdout(
+            "SQL Server is now ready for client connections",
+        ))
+        .with_env_var("ACCEPT_EULA", "Y")
+        .with_env_var("MSSQL_SA_PASSWORD", PASSWORD)
+        .with_startup_timeout(Duration::from_secs(180));
+    let container = match image.start().await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Skipping test (Docker not available): {e}");
+            return None;
+        }
+    };
+
+    let host = container.get_host().await.unwrap().to_string();
+    let host_port = container.get_host_port_ipv4(SQLSERVER_PORT).await.unwrap();
+    let mut client = connect(&host, host_port).await;
+
+    for sql in [
+        // Compiled in master, so it must not show up in orders_db's stats
+        "SELECT COUNT(*) FROM sys.objects AS master_only_marker",
+        "CREATE DATABASE orders_db",
+        "ALTER DATABASE orders_db SET QUERY_STORE = ON (OPERATION_MODE = READ_WRITE, QUERY_CAPTURE_MODE = ALL)",
+        "USE orders_db",
+        "CREATE TABLE dbo.customers (id INT PRIMARY KEY, name NVARCHAR(100) NOT NULL)",
+        "CREATE TABLE dbo.orders (
+            id INT IDENTITY PRIMARY KEY,
+            customer_id INT NOT NULL
+                CONSTRAINT orders_customer_fk REFERENCES dbo.customers (id),
+            status VARCHAR(20) NOT NULL,
+            total DECIMAL(10, 2)
+        )",
+        "CREATE INDEX orders_status_idx ON dbo.orders (status) INCLUDE (total)",
+        "INSERT INTO dbo.customers VALUES (1, N'Ada'), (2, N'Grace')",
+        "INSERT INTO dbo.orders (customer_id, status, total)
+            SELECT TOP 100 1 + ABS(CHECKSUM(NEWID())) % 2,
+                CASE WHEN ROW_NUMBER() OVER (ORDER BY (SELECT NULL)) % 3 = 0 THEN 'shipped' ELSE 'open' END,
+                10.5
+            FROM sys.all_objects",
+        "SELECT COUNT(*) FROM dbo.orders AS shipped_orders WHERE status = 'shipped'",
+    ] {
+        execute(&mut client, sql).await;
+    }
+
+    let url = format!(
+        "sqlserver://sa:{PASSWORD}@{host}:{host_port}/orders_db?trustServerCertificate=true"
+    );
+    Some((container, client, url))
+}
+
+#[tokio::test]
+async fn test_sqlserver_collector_with_container() {
+    let Some((_container, mut client, url)) = start_sqlserver().await else {
+        return;
+    };
+
+    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector");
+    collector
+        .test_connection()
+        .await
+        .expect("Connection test failed");
+    assert_eq!(collector.provider(), "generic");
+    assert!(collector.version().is_some_and(|v| v.starts_with("16.")));
+
+    let payload = collector.collect().await.expect("Collection failed");
+    assert_eq!(payload.database.database_type, "sqlserver");
+
+    let schema = payload.schema.expect("schema should be present");
+    let orders = schema
+        .tables
+        .iter()
+        .find(|t| t.schema == "dbo" && t.name == "orders")
+        .expect("orders table should be collected");
+    let columns: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(columns, ["id", "customer_id", "status", "total"]);
+    let total = &orders.columns[3];
+    assert_eq!(total.data_type, "decimal");
+    assert_eq!(
+        (total.numeric_precision, total.numeric_scale),
+        (Some(10), Some(2))
+    );
+    assert_eq!(orders.columns[2].max_length, Some(20));
+    assert_eq!(orders.columns[0].identity.as_deref(), Some("ALWAYS"));
+
+    let foreign_key = orders
+        .constraints
+        .iter()
+        .find(|c| c.name == "orders_customer_fk")
+        .expect("foreign key should be collected");
+    assert_eq!(foreign_key.kind, "foreign_key");
+    assert_eq!(foreign_key.columns, ["customer_id"]);
+    assert_eq!(
+        foreign_key.definition,
+        "FOREIGN KEY ([customer_id]) REFERENCES [dbo].[customers] ([id])"
+    );
+    assert!(orders.constraints.iter().any(|c| c.kind == "primary_key"));
+
+    let index = schema
+        .indexes
+        .iter()
+        .find(|i| i.name == "orders_status_idx")
+        .expect("orders_status_idx should be collected");
+    assert_eq!(index.columns, ["status"]);
+    assert_eq!(index.included_columns, ["total"]);
+    assert!(!index.is_unique);
+
+    let stats = payload.table_stats.expect("table_stats should be present");
+    let orders = stats
+        .iter()
+        .find(|t| t.schema == "dbo" && t.table == "orders")
+        .expect("orders table stats should be collected");
+    assert_eq!(orders.n_live_tup, Some(100));
+
+    let settings = payload.settings.expect("settings should be present");
+    assert!(settings.contains_key("max degree of parallelism"));
+    assert!(settings.contains_key("compatibility_level"));
+
+    let sqlserver = payload.sqlserver.expect("sqlserver should be present");
+    assert_eq!(sqlserver.query_store_state.as_deref(), Some("READ_WRITE"));
+    let queries = payload.query_stats.expect("query_stats should be present");
+    assert!(queries.iter().any(|q| q
+        .query
+        .as_deref()
+        .is_some_and(|q| q.contains("shipped_orders"))));
+    assert!(queries.iter().all(|q| !q
+        .query
+        .as_deref()
+        .unwrap_or_default()
+        .contains("datapace-agent")));
+
+    // Without the Query Store, statistics come from the plan cache, limited
+    // to plans compiled in orders_db
+    execute(
+        &mut client,
+        "ALTER DATABASE orders_db SET QUERY_STORE = OFF",
+    )
+    .await;
+    let payload = collector.collect().await.expect("Collection failed");
+    assert_eq!(
+        payload.sqlserver.unwrap().query_store_state.as_deref(),
+        Some("OFF")
+    );
+    let queries = payload.query_stats.expect("query_stats should be present");
+    assert!(queries.iter().any(|q| q
+        .query
+        .as_deref()
+        .is_some_and(|q| q.contains("shipped_orders"))));
+    for query in queries.iter().filter_map(|q| q.query.as_deref()) {
+        assert!(!query.contains("master_only_marker"), "{query}");
+        assert!(!query.contains("datapace-agent"), "{query}");
+    }
+}
+
+#[tokio::test]
+async fn test_sqlserver_collector_reconnects_after_connection_error() {
+    let Some((_container, mut client, url)) = start_sqlserver().await else {
+        return;
+    };
+
+    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector");
+    collector.collect().await.expect("Collection failed");
+
+    // Kill the collector's session so its connection breaks mid-cycle
+    let sessions = client
+        .simple_query(
+            "SELECT session_id FROM sys.dm_exec_sessions WHERE program_name = 'datapace-agent'",
+        )
+        .await
+        .unwrap()
+        .into_first_result()
+        .await
+        .unwrap();
+    assert!(!sessions.is_empty(), "collector session should be open");
+    for session in sessions {
+        let id: i16 = session.get("session_id").unwrap();
+        execute(&mut client, &format!("KILL {id}")).await;
+    }
+
+    let err = collector
+        .collect()
+        .await
+        .expect_err("collection on a killed session should fail");
+    assert!(
+        matches!(err, CollectorError::ConnectionError(_)),
+        "unexpected error: {err}"
+    );
+
+    // The broken connection was dropped and the next cycle reconnects
+    let payload = collector
+        .collect()
+        .await
+        .expect("Collection after reconnect failed");
+    assert!(payload.schema.is_some());
+}
@@ -0,0 +1,109 @@
+//! Vector store integration tests using testcontainers.
+//!
+//! These tests spin up a Qdrant container, create a collection over the REST
+//! API and verify the collector against it.
+//!
+//! # Requirements
+//!
+//! - Docker installed and running
+//!
+//! # Running
+//!
+//! ```bash
+//! cargo test --test integration vector_container
+//! ```
+
+use datapace_agent::collector;
+use serde_json::json;
+use std::time::Duration;
+use testcontainers::core::IntoContainerPort;
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage};
+
+const QDRANT_PORT: u16 = 6333;
+
+/// Start a Qdrant container, wait for its API and return (container_handle,
+/// connection_url).
+///
+/// If Docker is not available, returns None so callers can skip the test.
+async fn start_qdrant() -> Option<(ContainerAsync<GenericImage>, String)> {
+    let image = GenericImage::new("qdrant/qdrant", "v1.12.4").with_exposed_port(QDRANT_PORT.tcp());
+    let container = match image.start().await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Skipping test (Docker not available): {e}");
+            return None;
+        }
+    };
+
+    let host = container.get_host().await.unwrap();
+    let host_port = container.get_host_port_ipv4(QDRANT_PORT).await.unwrap();
+    let base = format!("http://{host}:{host_port}");
+
+    let http = reqwest::Client::new();
+    for _ in 0..60 {
+        if http
+            .get(format!("{base}/readyz"))
+            .send()
+            .await
+            .is_ok_and(|r| r.status().is_success())
+        {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    http.put(format!("{base}/collections/docs"))
+        .json(&json!({
+            "vectors": {"size": 4, "distance": "Dot"},
+            "hnsw_config": {"m": 8}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .expect("Failed to create collection");
+    http.put(format!("{base}/collections/docs/points?wait=true"))
+        .json(&json!({"points": [
+            {"id": 1, "vector": [0.1, 0.2, 0.3, 0.4], "payload": {"lang": "en"}},
+            {"id": 2, "vector": [0.4, 0.3, 0.2, 0.1], "payload": {"lang": "de"}}
+        ]}))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .expect("Failed to insert points");
+
+    Some((container, format!("qdrant://{host}:{host_port}")))
+}
+
+#[tokio::test]
+async fn test_qdrant_collector_with_container() {
+    let Some((_container, url)) = start_qdrant().await else {
+        return;
+    };
+
+    let collector = collector::create_collector(&url, datapace_agent::config::Provider::Auto)
+        .await
+        .expect("Failed to create collector");
+    collector
+        .test_connection()
+        .await
+        .expect("Connection test failed");
+
+    let payload = collector.collect().await.expect("Collection failed");
+    assert_eq!(payload.database.database_type, "qdrant");
+    assert_eq!(payload.database.version.as_deref(), Some("1.12.4"));
+
+    let vector_store = payload
+        .vector_store
+        .expect("vector_store should be present");
+    let index = &vector_store.indexes[0];
+    assert_eq!(index.table, "docs");
+    assert_eq!(index.m, Some(8));
+    assert_eq!(index.distance.as_deref(), Some("inner_product"));
+    assert_eq!(vector_store.columns[0].dimensions, Some(4));
+
+    let stats = payload.table_stats.expect("table_stats should be present");
+    assert_eq!(stats[0].n_live_tup, Some(2));
+}
@@ -0,0 +1,274 @@
+//! Vector store collector tests against mock Qdrant, Weaviate, Milvus and
+//! Chroma APIs.
+
+use datapace_agent::collector::vector::VectorCollector;
+use datapace_agent::collector::{Collector, CollectorError};
+use datapace_agent::config::{DatabaseType, Provider};
+use serde_json::{json, Value};
+use wiremock::matchers::{body_partial_json, header, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+async fn mount(server: &MockServer, api: &str, body: Value) {
+    Mock::given(method("GET"))
+        .and(path(api))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+async fn mount_post(server: &MockServer, api: &str, request: Value, body: Value) {
+    Mock::given(method("POST"))
+        .and(path(api))
+        .and(body_partial_json(request))
+        .respond_with(ResponseTemplate::new(200).set_body_json(body))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_qdrant_collect() {
+    let server = MockServer::start().await;
+    mount(
+        &server,
+        "/",
+        json!({"title": "qdrant", "version": "1.12.4"}),
+    )
+    .await;
+    mount(
+        &server,
+        "/collections",
+        json!({"result": {"collections": [{"name": "products"}]}, "status": "ok"}),
+    )
+    .await;
+    mount(
+        &server,
+        "/collections/products",
+        json!({"result": {
+            "status": "green",
+            "optimizer_status": "ok",
+            "points_count": 2500,
+            "indexed_vectors_count": 2500,
+            "segments_count": 2,
+            "config": {
+                "params": {"vectors": {"size": 768, "distance": "Cosine"}},
+                "hnsw_config": {"m": 16, "ef_construct": 100},
+                "quantization_config": {"scalar": {"type": "int8"}}
+            },
+            "payload_schema": {"brand": {"data_type": "keyword", "points": 2500}}
+        }, "status": "ok"}),
+    )
+    .await;
+
+    let collector = VectorCollector::new(&server.uri(), DatabaseType::Qdrant, Provider::Auto)
+        .await
+        .expect("collector");
+    assert_eq!(collector.version().as_deref(), Some("1.12.4"));
+    assert_eq!(collector.provider(), "generic");
+
+    let payload = collector.collect().await.expect("collect");
+    assert_eq!(payload.database.database_type, "qdrant");
+
+    let schema = payload.schema.expect("schema");
+    let table = &schema.tables[0];
+    assert_eq!(
+        (table.schema.as_str(), table.name.as_str()),
+        ("qdrant", "products")
+    );
+    assert_eq!(table.columns[0].native_type.as_deref(), Some("vector(768)"));
+    assert_eq!(schema.indexes.len(), 2);
+
+    let vector_store = payload.vector_store.expect("vector_store");
+    let index = &vector_store.indexes[0];
+    assert_eq!(index.method, "hnsw");
+    assert_eq!(index.distance.as_deref(), Some("cosine"));
+    assert_eq!(index.quantization.as_deref(), Some("scalar_int8"));
+    assert_eq!(vector_store.columns[0].dimensions, Some(768));
+
+    let stats = &payload.table_stats.expect("table_stats")[0];
+    assert_eq!(stats.n_live_tup, Some(2500));
+    assert_eq!(stats.segment_count, Some(2));
+    assert_eq!(stats.optimizer_status.as_deref(), Some("ok"));
+}
+
+#[tokio::test]
+async fn test_qdrant_api_key_and_permission_error() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(header("api-key", "secret"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .set_body_json(json!({"status": {"error": "Forbidden: read access denied"}})),
+        )
+        .mount(&server)
+        .await;
+
+    let url = format!("{}?api_key=secret", server.uri());
+    let result = VectorCollector::new(&url, DatabaseType::Qdrant, Provider::Auto).await;
+    assert!(matches!(result, Err(CollectorError::PermissionError(m)) if m.contains("read access")));
+}
+
+#[tokio::test]
+async fn test_weaviate_collect() {
+    let server = MockServer::start().await;
+    mount(&server, "/v1/meta", json!({"version": "1.27.2"})).await;
+    mount(
+        &server,
+        "/v1/schema",
+        json!({"classes": [{
+            "class": "Article",
+            "vectorIndexType": "hnsw",
+            "vectorIndexConfig": {"distance": "cosine", "efConstruction": 128, "maxConnections": 32},
+            "properties": [{"name": "title", "dataType": ["text"]}]
+        }]}),
+    )
+    .await;
+    mount(
+        &server,
+        "/v1/nodes",
+        json!({"nodes": [{"name": "node1", "shards": [
+            {"class": "Article", "name": "abc", "objectCount": 42, "vectorIndexingStatus": "READY"}
+        ]}]}),
+    )
+    .await;
+
+    let collector = VectorCollector::new(&server.uri(), DatabaseType::Weaviate, Provider::Auto)
+        .await
+        .expect("collector");
+    assert_eq!(collector.version().as_deref(), Some("1.27.2"));
+
+    let payload = collector.collect().await.expect("collect");
+    let schema = payload.schema.expect("schema");
+    let columns: Vec<&str> = schema.tables[0]
+        .columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(columns, vec!["id", "vector", "title"]);
+
+    let index = &payload.vector_store.expect("vector_store").indexes[0];
+    assert_eq!((index.m, index.ef_construction), (Some(32), Some(128)));
+
+    let stats = &payload.table_stats.expect("table_stats")[0];
+    assert_eq!(stats.n_live_tup, Some(42));
+    assert_eq!(stats.status.as_deref(), Some("READY"));
+}
+
+#[tokio::test]
+async fn test_milvus_collect() {
+    let server = MockServer::start().await;
+    let db = json!({"dbName": "search"});
+    mount_post(
+        &server,
+        "/v2/vectordb/collections/list",
+        db.clone(),
+        json!({"code": 0, "data": ["docs"]}),
+    )
+    .await;
+    mount_post(
+        &server,
+        "/v2/vectordb/collections/describe",
+        json!({"dbName": "search", "collectionName": "docs"}),
+        json!({"code": 0, "data": {
+            "collectionName": "docs",
+            "load": "LoadStateLoaded",
+            "fields": [
+                {"name": "id", "type": "Int64", "primaryKey": true},
+                {"name": "embedding", "type": "FloatVector", "params": [{"key": "dim", "value": "1024"}]}
+            ],
+            "indexes": [{"fieldName": "embedding", "indexName": "embedding", "metricType": "IP"}]
+        }}),
+    )
+    .await;
+    mount_post(
+        &server,
+        "/v2/vectordb/collections/get_stats",
+        db.clone(),
+        json!({"code": 0, "data": {"rowCount": 10000}}),
+    )
+    .await;
+    mount_post(
+        &server,
+        "/v2/vectordb/indexes/describe",
+        db,
+        json!({"code": 0, "data": [{
+            "fieldName": "embedding", "indexName": "embedding", "indexType": "IVF_FLAT",
+            "metricType": "IP", "indexedRows": 9500, "totalRows": 10000,
+            "params": {"nlist": "1024"}
+        }]}),
+    )
+    .await;
+
+    let url = format!("{}/search", server.uri());
+    let collector = VectorCollector::new(&url, DatabaseType::Milvus, Provider::Auto)
+        .await
+        .expect("collector");
+    let payload = collector.collect().await.expect("collect");
+
+    let schema = payload.schema.expect("schema");
+    assert_eq!(schema.tables[0].schema, "search");
+    assert!(schema.indexes[0].is_primary);
+
+    let index = &payload.vector_store.expect("vector_store").indexes[0];
+    assert_eq!(index.method, "ivf_flat");
+    assert_eq!(index.lists, Some(1024));
+    assert_eq!(index.distance.as_deref(), Some("inner_product"));
+
+    let stats = &payload.table_stats.expect("table_stats")[0];
+    assert_eq!(stats.n_live_tup, Some(10_000));
+    assert_eq!(stats.indexed_vectors, Some(9_500));
+}
+
+#[tokio::test]
+async fn test_milvus_permission_error() {
+    let server = MockServer::start().await;
+    mount_post(
+        &server,
+        "/v2/vectordb/collections/list",
+        json!({}),
+        json!({"code": 1800, "message": "user hasn't authenticated"}),
+    )
+    .await;
+
+    let result = VectorCollector::new(&server.uri(), DatabaseType::Milvus, Provider::Auto).await;
+    assert!(matches!(result, Err(CollectorError::PermissionError(_))));
+}
+
+#[tokio::test]
+async fn test_chroma_collect() {
+    let server = MockServer::start().await;
+    mount(&server, "/api/v2/version", json!("1.0.4")).await;
+    mount(
+        &server,
+        "/api/v2/tenants/acme/databases/prod/collections",
+        json!([{
+            "id": "c0ffee00-0000-0000-0000-000000000000",
+            "name": "notes",
+            "dimension": 384,
+            "configuration_json": {"hnsw": {"space": "cosine", "ef_construction": 100, "max_neighbors": 16}},
+            "metadata": null
+        }]),
+    )
+    .await;
+    mount(
+        &server,
+        "/api/v2/tenants/acme/databases/prod/collections/c0ffee00-0000-0000-0000-000000000000/count",
+        json!(17),
+    )
+    .await;
+
+    let url = format!("{}/acme/prod", server.uri());
+    let collector = VectorCollector::new(&url, DatabaseType::Chroma, Provider::Auto)
+        .await
+        .expect("collector");
+    assert_eq!(collector.version().as_deref(), Some("1.0.4"));
+
+    let payload = collector.collect().await.expect("collect");
+    let schema = payload.schema.expect("schema");
+    assert_eq!(schema.tables[0].schema, "prod");
+    assert_eq!(schema.tables[0].row_count_estimate, Some(17));
+
+    let vector_store = payload.vector_store.expect("vector_store");
+    assert_eq!(vector_store.columns[0].dimensions, Some(384));
+    assert_eq!(vector_store.indexes[0].distance.as_deref(), Some("cosine"));
+}